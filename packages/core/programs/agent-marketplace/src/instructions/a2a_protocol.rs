@@ -7,6 +7,8 @@
 
 use anchor_lang::prelude::*;
 use crate::{*, PodAIMarketplaceError};
+use crate::state::a2a_protocol::*;
+use crate::security::ProtocolSubsystem;
 
 // =====================================================
 // A2A PROTOCOL INSTRUCTIONS
@@ -14,16 +16,18 @@ use crate::{*, PodAIMarketplaceError};
 
 /// Creates a new A2A communication session between agents
 /// 
-/// Establishes secure communication channels between 2+ agents with session
-/// management, message routing, and automatic cleanup after inactivity.
+/// Opens a pending session from the initiator to a responder, who must
+/// accept it before any messages are exchanged.
 /// 
 /// # Arguments
 /// 
 /// * `ctx` - The context containing session accounts
 /// * `session_data` - Session configuration including:
-///   - `session_id` - Unique identifier for the session
-///   - `participants` - Array of agent public keys
+///   - `session_id` - Identifier, unique per initiator
+///   - `initiator` - The signer opening the session
+///   - `responder` - The agent invited to the session
 ///   - `session_type` - Direct, group, or swarm communication
+///   - `expires_at` - When the session stops accepting messages
 /// 
 /// # Returns
 /// 
@@ -31,8 +35,9 @@ use crate::{*, PodAIMarketplaceError};
 /// 
 /// # Errors
 /// 
-/// * `InvalidParticipants` - If participants array is empty or too large
-/// * `SessionAlreadyExists` - If session ID is already in use
+/// * `UnauthorizedAccess` - If signer is not the initiator
+/// * `InvalidConfiguration` - If the responder is missing or is the initiator
+/// * `InvalidDuration` - If `expires_at` is past or beyond `MAX_A2A_SESSION_DURATION`
 /// 
 /// # Example
 /// 
@@ -50,9 +55,10 @@ use crate::{*, PodAIMarketplaceError};
 /// 
 /// # Security
 /// 
-/// - Only the session creator can initialize the session
-/// - All participants must be valid agent accounts
-/// - Session IDs must be unique to prevent replay attacks
+/// - Only the initiator can create the session, and it stays pending until
+///   the responder accepts it with `accept_a2a_session`
+/// - Sessions expire at `expires_at`, at most `MAX_A2A_SESSION_DURATION` out
+/// - Session IDs are part of the address, so an initiator can't reuse one
 /// 
 /// # A2A Protocol Standards
 /// 
@@ -64,38 +70,64 @@ pub fn create_a2a_session(
     ctx: Context<CreateA2ASession>,
     session_data: A2ASessionData,
 ) -> Result<()> {
-    // SECURITY: Verify signer authorization
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    // SECURITY: Sessions are only opened on the initiator's own behalf
     require!(
-        ctx.accounts.creator.is_signer,
+        ctx.accounts.creator.key() == session_data.initiator,
         PodAIMarketplaceError::UnauthorizedAccess
     );
-    // SECURITY: Input validation
-    const MAX_SESSION_ID_LENGTH: usize = 64;
-    const MAX_PARTICIPANTS: usize = 10;
-    const MAX_CONTEXT_LENGTH: usize = 2048;
     
     // Validate session_id is non-zero (since it's u64)
     require!(
         session_data.session_id > 0,
         PodAIMarketplaceError::InputTooLong
     );
-    // Validate session has both initiator and responder
+    // Validate session has a distinct responder
     require!(
-        session_data.initiator != Pubkey::default() && session_data.responder != Pubkey::default(),
+        session_data.responder != Pubkey::default() && session_data.responder != session_data.initiator,
         PodAIMarketplaceError::InvalidConfiguration
     );
-    let session = &mut ctx.accounts.session;
+    require!(
+        session_data.session_type.len() <= MAX_GENERAL_STRING_LENGTH
+            && session_data.metadata.len() <= MAX_GENERAL_STRING_LENGTH,
+        PodAIMarketplaceError::InputTooLong
+    );
     let clock = Clock::get()?;
-    // Set session fields from the stub structure
+    require!(
+        session_data.expires_at > clock.unix_timestamp
+            && session_data.expires_at <= clock.unix_timestamp + MAX_A2A_SESSION_DURATION,
+        PodAIMarketplaceError::InvalidDuration
+    );
+
+    let session = &mut ctx.accounts.session;
     session.session_id = session_data.session_id;
     session.initiator = session_data.initiator;
     session.responder = session_data.responder;
     session.session_type = session_data.session_type;
     session.metadata = session_data.metadata;
-    session.is_active = true;
+    session.status = A2ASessionStatus::Pending;
     session.created_at = clock.unix_timestamp;
     session.expires_at = session_data.expires_at;
+    session.accepted_at = None;
+    session.closed_at = None;
+    session.message_count = 0;
+    session.open_messages = 0;
     session.bump = ctx.bumps.session;
+
+    ctx.accounts.initiator_status.initialize(
+        session.key(),
+        session.initiator,
+        clock.unix_timestamp,
+        ctx.bumps.initiator_status,
+    );
+
+    let context = &mut ctx.accounts.context;
+    context.session = session.key();
+    context.entries = Vec::new();
+    context.head = 0;
+    context.total_messages = 0;
+    context.bump = ctx.bumps.context;
+
     emit!(crate::A2ASessionCreatedEvent {
         session_id: session_data.session_id,
         initiator: session_data.initiator,
@@ -105,19 +137,53 @@ pub fn create_a2a_session(
     Ok(())
 }
 
-/// Sends a message in an A2A session with multi-modal content support
+/// Accepts a pending A2A session as its responder
 /// 
-/// Transmits structured messages between agents including text, code, data,
-/// and streaming content with automatic context management.
+/// Messages can only be sent once the responder has accepted.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the session's responder
+/// * `InvalidStatusTransition` - If the session is not pending
+/// * `A2ASessionExpired` - If the session expired before acceptance
+pub fn accept_a2a_session(ctx: Context<AcceptA2ASession>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+
+    let clock = Clock::get()?;
+    let session = &mut ctx.accounts.session;
+    session.accept(clock.unix_timestamp)?;
+
+    ctx.accounts.responder_status.initialize(
+        session.key(),
+        session.responder,
+        clock.unix_timestamp,
+        ctx.bumps.responder_status,
+    );
+
+    emit!(crate::A2ASessionAcceptedEvent {
+        session: session.key(),
+        session_id: session.session_id,
+        responder: session.responder,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Sends one chunk of a message in an A2A session
+/// 
+/// Messages longer than `MAX_A2A_CHUNK_LENGTH` are streamed as several
+/// chunks, each stored in its own account, which clients reassemble by
+/// `message_id` and `chunk_index`.
 /// 
 /// # Arguments
 /// 
-/// * `ctx` - The context containing message and session accounts
-/// * `message_content` - Message data including:
-///   - `content_type` - Type of content (text, code, data, stream)
-///   - `payload` - The actual message content
-///   - `metadata` - Optional message metadata
-///   - `requires_response` - Whether a response is expected
+/// * `ctx` - The context containing message, session, context and sender status accounts
+/// * `message_data` - Chunk data including:
+///   - `message_id` - Message the chunk belongs to
+///   - `content` - The chunk's content
+///   - `message_type` - Type of content (text, code, data, stream)
+///   - `chunk_index` - Position of the chunk, starting at 0
+///   - `is_final` - Whether the chunk completes the message
 /// 
 /// # Returns
 /// 
@@ -125,75 +191,90 @@ pub fn create_a2a_session(
 /// 
 /// # Errors
 /// 
-/// * `SessionInactive` - If session has expired
-/// * `UnauthorizedSender` - If sender is not a participant
-/// * `MessageTooLarge` - If content exceeds size limits
+/// * `InvalidStatusTransition` - If the session is not active
+/// * `A2ASessionExpired` - If the session has expired
+/// * `UnauthorizedAccess` - If sender is not a participant
+/// * `InputTooLong` - If the chunk exceeds `MAX_A2A_CHUNK_LENGTH`
+/// * `InvalidA2AProtocolMessage` - If the chunk doesn't continue the
+///   sender's current message in order
 /// 
 /// # Example
 /// 
 /// ```ignore
 /// let message_data = A2AMessageData {
-///     role: MessageRole::Agent,
-///     parts: vec![MessagePart {
-///         part_type: PartType::Text,
-///         content: "Processing request...".to_string(),
-///         metadata: None,
-///     }],
+///     message_id: 42,
+///     session_id: 123,
+///     sender: agent1.key(),
+///     content: "Processing request...".to_string(),
+///     message_type: "text".to_string(),
+///     timestamp: clock.unix_timestamp,
+///     chunk_index: 0,
+///     is_final: true,
 /// };
 /// send_a2a_message(ctx, message_data)?;
 /// ```
 /// 
-/// # Multi-Modal Support
+/// # Streaming
 /// 
-/// Supports various content types:
-/// - Text messages
-/// - Code snippets with syntax highlighting
-/// - Structured data (JSON, binary)
-/// - Streaming content with chunk management
+/// Chunk 0 starts a new message. Each later chunk must follow the sender's
+/// previous chunk of the same message; sending chunk 0 again abandons an
+/// unfinished message.
 /// 
 /// # Context Management
 /// 
-/// Messages are automatically added to session context
-/// with FIFO eviction when window size is exceeded
+/// When the final chunk arrives, the message's chained chunk hash is added
+/// to the session's context window, evicting the oldest entry once
+/// `A2A_CONTEXT_WINDOW` messages are held
 pub fn send_a2a_message(
     ctx: Context<SendA2AMessage>,
     message_data: A2AMessageData,
 ) -> Result<()> {
-    // SECURITY: Verify signer authorization
-    require!(
-        ctx.accounts.sender.is_signer,
-        PodAIMarketplaceError::UnauthorizedAccess
-    );
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
     
-    let session = &ctx.accounts.session;
+    let session = &mut ctx.accounts.session;
     
     // SECURITY: Verify sender is a participant in the session
     require!(
-        session.initiator == ctx.accounts.sender.key() || 
-        session.responder == ctx.accounts.sender.key(),
+        session.is_participant(&ctx.accounts.sender.key()),
         PodAIMarketplaceError::UnauthorizedAccess
     );
     
-    // SECURITY: Verify session is still active
+    // SECURITY: Input validation
     require!(
-        session.is_active,
-        PodAIMarketplaceError::InvalidStatusTransition
+        !message_data.content.is_empty() && message_data.content.len() <= MAX_A2A_CHUNK_LENGTH,
+        PodAIMarketplaceError::InputTooLong
     );
-    
-    // SECURITY: Input validation
-    const MAX_CONTENT_LENGTH: usize = 4096;
-    
     require!(
-        !message_data.content.is_empty() && message_data.content.len() <= MAX_CONTENT_LENGTH,
+        message_data.message_type.len() <= MAX_GENERAL_STRING_LENGTH,
         PodAIMarketplaceError::InputTooLong
     );
-    let message = &mut ctx.accounts.message;
+
     let clock = Clock::get()?;
-    
-    // Set message fields from the stub structure
+    // SECURITY: Only accepted, unexpired sessions carry messages
+    let sequence = session.record_message(clock.unix_timestamp)?;
+
+    let sender = ctx.accounts.sender.key();
+    let completed = ctx.accounts.sender_status.record_chunk(
+        message_data.message_id,
+        message_data.chunk_index,
+        message_data.is_final,
+        message_data.content.as_bytes(),
+    )?;
+    if let Some(hash) = completed {
+        ctx.accounts.context.push(A2AContextEntry {
+            message_id: message_data.message_id,
+            sender,
+            hash,
+        });
+    }
+
+    let message = &mut ctx.accounts.message;
     message.message_id = message_data.message_id;
-    message.session = ctx.accounts.session.key();
+    message.session = session.key();
     message.sender = ctx.accounts.sender.key();
+    message.sequence = sequence;
+    message.chunk_index = message_data.chunk_index;
+    message.is_final = message_data.is_final;
     message.content = message_data.content;
     message.message_type = message_data.message_type;
     message.sent_at = clock.unix_timestamp;
@@ -202,7 +283,10 @@ pub fn send_a2a_message(
     emit!(crate::A2AMessageSentEvent {
         message_id: message_data.message_id,
         session_id: session.session_id,
-        sender: ctx.accounts.sender.key(),
+        sender,
+        chunk_index: message_data.chunk_index,
+        is_final: message_data.is_final,
+        message_hash: completed,
         timestamp: clock.unix_timestamp,
     });
     Ok(())
@@ -227,19 +311,24 @@ pub fn send_a2a_message(
 /// 
 /// # Errors
 /// 
-/// * `UnauthorizedAccess` - If updater is not a participant
-/// * `SessionClosed` - If session is already closed
+/// * `UnauthorizedAccess` - If updater is not a participant or posts for another agent
+/// * `InvalidStatusTransition` - If session is already closed
+/// * `A2ASessionExpired` - If the session has expired
 /// 
 /// # Example
 /// 
 /// ```ignore
 /// let status_data = A2AStatusData {
-///     session_id: "session_123".to_string(),
-///     message_id: "msg_456".to_string(),
-///     state: MessageStatus::Working,
+///     status_id: 1,
+///     agent: agent1.key(),
+///     status: "working".to_string(),
+///     capabilities: vec!["code-review".to_string()],
+///     availability: true,
+///     last_updated: clock.unix_timestamp,
+///     message_id: 456,
 ///     progress: 50,
 ///     partial_content: "Processing...".to_string(),
-///     final: false,
+///     is_final: false,
 /// };
 /// update_a2a_status(ctx, status_data)?;
 /// ```
@@ -252,50 +341,136 @@ pub fn send_a2a_message(
 /// - State transitions
 /// - Final result notification
 /// 
-/// # Automatic Cleanup
+/// # Cleanup
 /// 
-/// Sessions are automatically closed after 30 minutes
-/// of inactivity to free resources
+/// Status accounts are closed together with the session by
+/// `close_a2a_session`
 pub fn update_a2a_status(
     ctx: Context<UpdateA2AStatus>,
     status_data: A2AStatusData,
 ) -> Result<()> {
-    // SECURITY: Verify signer authorization
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let session = &ctx.accounts.session;
+    let updater = ctx.accounts.updater.key();
+
+    // SECURITY: Participants can only publish their own status
     require!(
-        ctx.accounts.updater.is_signer,
+        session.is_participant(&updater) && status_data.agent == updater,
         PodAIMarketplaceError::UnauthorizedAccess
     );
+    let clock = Clock::get()?;
+    require!(
+        session.status != A2ASessionStatus::Closed,
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+    require!(!session.is_expired(clock.unix_timestamp), PodAIMarketplaceError::A2ASessionExpired);
+
     // SECURITY: Input validation
-    const MAX_STATUS_LENGTH: usize = 256;
-    const MAX_CAPABILITIES: usize = 20;
-    const MAX_CAPABILITY_LENGTH: usize = 64;
-    
     require!(
-        !status_data.status.is_empty() && status_data.status.len() <= MAX_STATUS_LENGTH,
+        !status_data.status.is_empty() && status_data.status.len() <= MAX_GENERAL_STRING_LENGTH,
         PodAIMarketplaceError::InputTooLong
     );
     require!(
-        status_data.capabilities.len() <= MAX_CAPABILITIES,
+        status_data.capabilities.len() <= MAX_A2A_CAPABILITIES,
         PodAIMarketplaceError::InputTooLong
     );
     for capability in &status_data.capabilities {
         require!(
-            !capability.is_empty() && capability.len() <= MAX_CAPABILITY_LENGTH,
+            !capability.is_empty() && capability.len() <= MAX_A2A_CAPABILITY_LENGTH,
             PodAIMarketplaceError::InputTooLong
         );
     }
     let status = &mut ctx.accounts.status;
-    let clock = Clock::get()?;
-    status.agent = status_data.agent;
+    status.record_progress(
+        status_data.message_id,
+        status_data.progress,
+        status_data.partial_content,
+        status_data.is_final,
+    )?;
     status.status = status_data.status.clone();
     status.capabilities = status_data.capabilities.clone();
     status.availability = status_data.availability;
     status.last_updated = clock.unix_timestamp;
-    status.bump = ctx.bumps.status;
     emit!(crate::A2AStatusUpdatedEvent {
         agent: status_data.agent,
         status: status_data.status,
         availability: status_data.availability,
+        message_id: status.message_id,
+        progress: status.progress,
+        is_final: status.is_final,
+        timestamp: clock.unix_timestamp,
+    });
+    Ok(())
+}
+
+/// Closes an A2A session and reclaims the rent of its accounts
+/// 
+/// Either participant may close a session at any time; once it has expired
+/// anyone may, since all rent only ever returns to whoever paid it. Message
+/// accounts are passed as writable remaining accounts in `(message, sender)`
+/// pairs and refunded to their sender. The session, context and status
+/// accounts are closed to their participants once no messages remain, so sessions with
+/// many messages can be cleaned up over several transactions.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If a non-participant closes an unexpired session,
+///   or a refund account is not the message's sender
+/// * `InvalidConfiguration` - If remaining accounts are not writable pairs of
+///   this session's messages and their senders
+pub fn close_a2a_session<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CloseA2ASession<'info>>,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let clock = Clock::get()?;
+    let session_key = ctx.accounts.session.key();
+
+    // SECURITY: Only participants can end a live session early
+    require!(
+        ctx.accounts.session.can_close(&ctx.accounts.closer.key(), clock.unix_timestamp),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    let mut closed_messages: u64 = 0;
+    for pair in ctx.remaining_accounts.chunks(2) {
+        require!(pair.len() == 2, PodAIMarketplaceError::InvalidConfiguration);
+        let (message_info, sender_info) = (&pair[0], &pair[1]);
+        require!(
+            message_info.is_writable && sender_info.is_writable,
+            PodAIMarketplaceError::InvalidConfiguration
+        );
+        let message = Account::<A2AMessage>::try_from(message_info)?;
+        require!(message.session == session_key, PodAIMarketplaceError::InvalidConfiguration);
+        // SECURITY: Message rent goes back to the sender who paid it
+        require!(message.sender == sender_info.key(), PodAIMarketplaceError::UnauthorizedAccess);
+        message.close(sender_info.clone())?;
+        closed_messages += 1;
+    }
+
+    let session = &mut ctx.accounts.session;
+    session.mark_closed(clock.unix_timestamp);
+    session.open_messages = session.open_messages
+        .checked_sub(closed_messages)
+        .ok_or(PodAIMarketplaceError::ArithmeticUnderflow)?;
+    let session_closed = session.open_messages == 0;
+
+    if session_closed {
+        require!(
+            session.accepted_at.is_none() || ctx.accounts.responder_status.is_some(),
+            PodAIMarketplaceError::InvalidConfiguration
+        );
+        if let Some(responder_status) = &ctx.accounts.responder_status {
+            responder_status.close(ctx.accounts.responder.to_account_info())?;
+        }
+        ctx.accounts.initiator_status.close(ctx.accounts.initiator.to_account_info())?;
+        ctx.accounts.context.close(ctx.accounts.initiator.to_account_info())?;
+        ctx.accounts.session.close(ctx.accounts.initiator.to_account_info())?;
+    }
+
+    emit!(crate::A2ASessionClosedEvent {
+        session: session_key,
+        closed_by: ctx.accounts.closer.key(),
+        messages_closed: closed_messages,
+        session_closed,
         timestamp: clock.unix_timestamp,
     });
     Ok(())
@@ -306,16 +481,37 @@ pub fn update_a2a_status(
 // =====================================================
 
 #[derive(Accounts)]
+#[instruction(session_data: A2ASessionData)]
 pub struct CreateA2ASession<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
         space = A2ASession::LEN,
-        seeds = [b"a2a_session", creator.key().as_ref()],
+        seeds = [A2A_SESSION_SEED, creator.key().as_ref(), &session_data.session_id.to_le_bytes()],
         bump
     )]
     pub session: Account<'info, A2ASession>,
     
+    #[account(
+        init,
+        payer = creator,
+        space = A2AStatus::LEN,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub initiator_status: Account<'info, A2AStatus>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = A2AContext::LEN,
+        seeds = [A2A_CONTEXT_SEED, session.key().as_ref()],
+        bump
+    )]
+    pub context: Account<'info, A2AContext>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
@@ -323,12 +519,40 @@ pub struct CreateA2ASession<'info> {
 }
 
 #[derive(Accounts)]
+pub struct AcceptA2ASession<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = session.responder == responder.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub session: Account<'info, A2ASession>,
+    
+    #[account(
+        init,
+        payer = responder,
+        space = A2AStatus::LEN,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), responder.key().as_ref()],
+        bump
+    )]
+    pub responder_status: Account<'info, A2AStatus>,
+    
+    #[account(mut)]
+    pub responder: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(message_data: A2AMessageData)]
 pub struct SendA2AMessage<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = sender,
-        space = A2AMessage::LEN,
-        seeds = [b"a2a_message", session.key().as_ref(), &session.created_at.to_le_bytes()],
+        space = A2AMessage::space_for(&message_data),
+        seeds = [A2A_MESSAGE_SEED, session.key().as_ref(), &session.message_count.to_le_bytes()],
         bump
     )]
     pub message: Account<'info, A2AMessage>,
@@ -336,6 +560,20 @@ pub struct SendA2AMessage<'info> {
     #[account(mut)]
     pub session: Account<'info, A2ASession>,
     
+    #[account(
+        mut,
+        seeds = [A2A_CONTEXT_SEED, session.key().as_ref()],
+        bump = context.bump
+    )]
+    pub context: Account<'info, A2AContext>,
+    
+    #[account(
+        mut,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), sender.key().as_ref()],
+        bump = sender_status.bump
+    )]
+    pub sender_status: Account<'info, A2AStatus>,
+    
     #[account(mut)]
     pub sender: Signer<'info>,
     
@@ -344,19 +582,57 @@ pub struct SendA2AMessage<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateA2AStatus<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
-        init,
-        payer = updater,
-        space = A2AStatus::LEN,
-        seeds = [b"a2a_status", session.key().as_ref()],
-        bump
+        mut,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), updater.key().as_ref()],
+        bump = status.bump
     )]
     pub status: Account<'info, A2AStatus>,
     
     pub session: Account<'info, A2ASession>,
     
-    #[account(mut)]
     pub updater: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseA2ASession<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub session: Account<'info, A2ASession>,
     
-    pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+    #[account(
+        mut,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), session.initiator.as_ref()],
+        bump = initiator_status.bump
+    )]
+    pub initiator_status: Account<'info, A2AStatus>,
+    
+    /// Required once the session has been accepted
+    #[account(
+        mut,
+        seeds = [A2A_STATUS_SEED, session.key().as_ref(), session.responder.as_ref()],
+        bump = responder_status.bump
+    )]
+    pub responder_status: Option<Account<'info, A2AStatus>>,
+    
+    #[account(
+        mut,
+        seeds = [A2A_CONTEXT_SEED, session.key().as_ref()],
+        bump = context.bump
+    )]
+    pub context: Account<'info, A2AContext>,
+    
+    /// CHECK: Receives the session and initiator status rent
+    #[account(mut, address = session.initiator @ PodAIMarketplaceError::InvalidConfiguration)]
+    pub initiator: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the responder status rent
+    #[account(mut, address = session.responder @ PodAIMarketplaceError::InvalidConfiguration)]
+    pub responder: UncheckedAccount<'info>,
+    
+    /// A participant, or anyone once the session has expired
+    pub closer: Signer<'info>,
+}
@@ -9,27 +9,48 @@ use crate::*;
 use crate::state::{AgentVerificationData};
 use crate::PodAIMarketplaceError;
 use crate::simple_optimization::*;
+use crate::security::ProtocolSubsystem;
 // Optimization utilities now available through simple_optimization module
 
 #[derive(Accounts)]
-#[instruction(agent_type: u8, metadata_uri: String)]
+pub struct InitializeUserRegistry<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = signer,
+        space = UserRegistry::LEN,
+        seeds = [b"user_registry", signer.key().as_ref()],
+        bump
+    )]
+    pub user_registry: Account<'info, UserRegistry>,
+    
+    #[account(mut)]
+    pub signer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(agent_type: u8, metadata_uri: String, agent_id: u64)]
 pub struct RegisterAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    // Seeded by the registering owner and a caller-chosen id so the address
+    // stays fixed across resales and the owner can register again later
     #[account(
         init,
         payer = signer,
         space = Agent::LEN,
-        seeds = [b"agent", signer.key().as_ref()],
+        seeds = [AGENT_SEED, signer.key().as_ref(), &agent_id.to_le_bytes()],
         bump
     )]
     pub agent_account: Account<'info, Agent>,
     
     #[account(
-        init,
-        payer = signer,
-        space = UserRegistry::LEN,
+        mut,
         seeds = [b"user_registry", signer.key().as_ref()],
-        bump,
-        constraint = user_registry.key() == user_registry.key()
+        bump = user_registry.bump
     )]
     pub user_registry: Account<'info, UserRegistry>,
     
@@ -42,10 +63,12 @@ pub struct RegisterAgent<'info> {
 #[derive(Accounts)]
 #[instruction(agent_type: u8, metadata_uri: String)]
 pub struct UpdateAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    // Not re-derived from the signer: a resold agent keeps the address
+    // seeded by its original owner and agent id
     #[account(
         mut,
-        seeds = [b"agent", signer.key().as_ref()],
-        bump = agent_account.bump,
         constraint = agent_account.owner == signer.key() @ PodAIMarketplaceError::InvalidAgentOwner
     )]
     pub agent_account: Account<'info, Agent>,
@@ -56,6 +79,8 @@ pub struct UpdateAgent<'info> {
 
 #[derive(Accounts)]
 pub struct VerifyAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = payer,
@@ -65,8 +90,17 @@ pub struct VerifyAgent<'info> {
     )]
     pub agent_verification: Account<'info, AgentVerification>,
     
-    /// CHECK: This is the agent being verified
-    pub agent: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump = verifier_registry.bump,
+        constraint = verifier_registry.is_verifier(&verifier.key()) @ PodAIMarketplaceError::VerifierNotRegistered
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    
+    pub verifier: Signer<'info>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -74,6 +108,134 @@ pub struct VerifyAgent<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct RenewAgentVerification<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"agent_verification", agent.key().as_ref()],
+        bump = agent_verification.bump
+    )]
+    pub agent_verification: Account<'info, AgentVerification>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump = verifier_registry.bump,
+        constraint = verifier_registry.is_verifier(&verifier.key()) @ PodAIMarketplaceError::VerifierNotRegistered
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    
+    pub verifier: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeAgentVerification<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [b"agent_verification", agent.key().as_ref()],
+        bump = agent_verification.bump,
+        constraint = agent_verification.verifier == authority.key()
+            || verifier_registry.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub agent_verification: Account<'info, AgentVerification>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(seeds = [VERIFIER_REGISTRY_SEED], bump = verifier_registry.bump)]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SyncAgentVerification<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        seeds = [b"agent_verification", agent.key().as_ref()],
+        bump = agent_verification.bump
+    )]
+    pub agent_verification: Account<'info, AgentVerification>,
+    
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    
+    #[account(seeds = [VERIFIER_REGISTRY_SEED], bump = verifier_registry.bump)]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVerifierRegistry<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = VerifierRegistry::LEN,
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == protocol_config.authority @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageVerifiers<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump = verifier_registry.bump,
+        constraint = verifier_registry.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Creates the per-user registry that tracks resource limits
+/// 
+/// Must be called once before the user's first `register_agent`. Kept
+/// separate so the registry survives agent sales and re-registrations.
+/// 
+/// # Errors
+/// 
+/// * `ProtocolPaused` - If the protocol is paused
+pub fn initialize_user_registry(ctx: Context<InitializeUserRegistry>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let user_registry = &mut ctx.accounts.user_registry;
+    let clock = Clock::get()?;
+
+    user_registry.user = ctx.accounts.signer.key();
+    user_registry.agent_count = 0;
+    user_registry.listing_count = 0;
+    user_registry.work_order_count = 0;
+    user_registry.channel_count = 0;
+    user_registry.total_volume_traded = 0;
+    user_registry.last_activity = clock.unix_timestamp;
+    user_registry.created_at = clock.unix_timestamp;
+    user_registry.is_rate_limited = false;
+    user_registry.rate_limit_expiry = 0;
+    user_registry.bump = ctx.bumps.user_registry;
+
+    Ok(())
+}
+
 /// Registers a new AI agent in the marketplace
 /// 
 /// This instruction creates a new agent account with optimized space allocation
@@ -89,11 +251,17 @@ pub struct VerifyAgent<'info> {
 /// - Input sanitization and length validation
 /// - Safe initialization of all numeric fields
 /// - Timestamp validation for creation tracking
+/// 
+/// The agent address is seeded by the signer and `agent_id`, so an owner can
+/// hold several agents and register again after selling one. The signer's
+/// registry must already exist (see `initialize_user_registry`).
 pub fn register_agent(
     ctx: Context<RegisterAgent>,
     agent_type: u8,
     metadata_uri: String,
+    _agent_id: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // Performance monitoring
     {
         // Compute budget optimization placeholder
@@ -114,21 +282,6 @@ pub fn register_agent(
             PodAIMarketplaceError::InvalidConfiguration
         );
 
-        // Initialize user registry if needed
-        if user_registry.user == Pubkey::default() {
-            user_registry.user = ctx.accounts.signer.key();
-            user_registry.agent_count = 0;
-            user_registry.listing_count = 0;
-            user_registry.work_order_count = 0;
-            user_registry.channel_count = 0;
-            user_registry.total_volume_traded = 0;
-            user_registry.last_activity = clock.unix_timestamp;
-            user_registry.created_at = clock.unix_timestamp;
-            user_registry.is_rate_limited = false;
-            user_registry.rate_limit_expiry = 0;
-            user_registry.bump = ctx.bumps.user_registry;
-        }
-
         // SECURITY FIX: Check resource limits
         user_registry.increment_agents()?;
         user_registry.check_rate_limit(clock.unix_timestamp)?;
@@ -185,6 +338,7 @@ pub fn update_agent(
     agent_type: u8,
     metadata_uri: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // Performance monitoring
     {
         // Optimize compute budget for agent update (2 accounts)
@@ -226,39 +380,235 @@ pub fn update_agent(
     }
 }
 
+/// Attests an agent on behalf of a registered verifier
+/// 
+/// The attestation is valid for `validity_period` seconds and immediately
+/// marks the agent as verified.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the agent, attestation and registry accounts
+/// * `service_endpoint` - Endpoint the verifier checked
+/// * `supported_capabilities` - Capability identifiers the verifier confirmed
+/// * `validity_period` - Seconds until the attestation expires (1 day to 1 year)
+/// 
+/// # Errors
+/// 
+/// * `VerifierNotRegistered` - If the verifier is not in the verifier registry
+/// * `UnauthorizedAccess` - If the verifier owns the agent
+/// * `InvalidDuration` - If `validity_period` is out of range
 pub fn verify_agent(
     ctx: Context<VerifyAgent>,
-    agent_pubkey: Pubkey,
     service_endpoint: String,
     supported_capabilities: Vec<u64>,
-    verified_at: i64,
+    validity_period: i64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let agent_verification = &mut ctx.accounts.agent_verification;
+    let agent = &mut ctx.accounts.agent;
+    let verifier = ctx.accounts.verifier.key();
     let clock = Clock::get()?;
 
     // Validate input
     require!(service_endpoint.len() <= 256, PodAIMarketplaceError::MessageTooLong);
     require!(supported_capabilities.len() <= MAX_CAPABILITIES_COUNT, PodAIMarketplaceError::InvalidServiceConfiguration);
     
-    // Initialize verification account
-    agent_verification.agent = agent_pubkey;
-    agent_verification.verifier = ctx.accounts.payer.key();
-    agent_verification.verification_data = AgentVerificationData {
-        agent_pubkey,
-        service_endpoint,
-        supported_capabilities,
-        verified_at,
-    };
-    agent_verification.created_at = clock.unix_timestamp;
-    agent_verification.expires_at = clock.unix_timestamp + (365 * 24 * 60 * 60); // 1 year
-    agent_verification.is_active = true;
+    // SECURITY: Verifiers cannot badge their own agents
+    require!(agent.owner != verifier, PodAIMarketplaceError::UnauthorizedAccess);
+    
+    agent_verification.agent = agent.key();
     agent_verification.bump = ctx.bumps.agent_verification;
+    agent_verification.renew(
+        verifier,
+        AgentVerificationData {
+            agent_pubkey: agent.key(),
+            service_endpoint,
+            supported_capabilities,
+            verified_at: clock.unix_timestamp,
+        },
+        clock.unix_timestamp,
+        validity_period,
+    )?;
+    sync_verification_badge(agent, agent_verification, &ctx.accounts.verifier_registry, clock.unix_timestamp);
+
+    emit!(AgentVerifiedEvent {
+        agent: agent.key(),
+        verifier,
+        expires_at: agent_verification.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Agent verified: {}", agent.key());
+    Ok(())
+}
+
+/// Re-attests an agent whose verification expired or was revoked
+/// 
+/// Any registered verifier may renew; the renewing verifier becomes the
+/// attestation's verifier.
+/// 
+/// # Errors
+/// 
+/// * `VerifierNotRegistered` - If the verifier is not in the verifier registry
+/// * `UnauthorizedAccess` - If the verifier owns the agent
+/// * `InvalidDuration` - If `validity_period` is out of range
+pub fn renew_agent_verification(
+    ctx: Context<RenewAgentVerification>,
+    service_endpoint: String,
+    supported_capabilities: Vec<u64>,
+    validity_period: i64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let agent_verification = &mut ctx.accounts.agent_verification;
+    let agent = &mut ctx.accounts.agent;
+    let verifier = ctx.accounts.verifier.key();
+    let clock = Clock::get()?;
+
+    require!(service_endpoint.len() <= 256, PodAIMarketplaceError::MessageTooLong);
+    require!(supported_capabilities.len() <= MAX_CAPABILITIES_COUNT, PodAIMarketplaceError::InvalidServiceConfiguration);
+    require!(agent.owner != verifier, PodAIMarketplaceError::UnauthorizedAccess);
+    
+    agent_verification.renew(
+        verifier,
+        AgentVerificationData {
+            agent_pubkey: agent.key(),
+            service_endpoint,
+            supported_capabilities,
+            verified_at: clock.unix_timestamp,
+        },
+        clock.unix_timestamp,
+        validity_period,
+    )?;
+    sync_verification_badge(agent, agent_verification, &ctx.accounts.verifier_registry, clock.unix_timestamp);
+
+    emit!(AgentVerifiedEvent {
+        agent: agent.key(),
+        verifier,
+        expires_at: agent_verification.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Revokes an agent's verification and clears its badge
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is neither the attesting verifier nor
+///   the verifier registry authority
+pub fn revoke_agent_verification(ctx: Context<RevokeAgentVerification>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let agent_verification = &mut ctx.accounts.agent_verification;
+    let agent = &mut ctx.accounts.agent;
+    let clock = Clock::get()?;
+
+    agent_verification.revoke();
+    sync_verification_badge(agent, agent_verification, &ctx.accounts.verifier_registry, clock.unix_timestamp);
+
+    SecurityLogger::log_security_event("AGENT_VERIFICATION_REVOKED", ctx.accounts.authority.key(),
+        &format!("agent: {}", agent.key()));
 
-    msg!("Agent verified: {}", agent_pubkey);
+    emit!(AgentVerificationRevokedEvent {
+        agent: agent.key(),
+        revoked_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Syncs `Agent.is_verified` with its attestation
+/// 
+/// Permissionless crank: clears the badge once the attestation expires or
+/// its verifier is removed from the registry, and restores it while valid.
+pub fn sync_agent_verification(ctx: Context<SyncAgentVerification>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let clock = Clock::get()?;
+    sync_verification_badge(
+        &mut ctx.accounts.agent,
+        &ctx.accounts.agent_verification,
+        &ctx.accounts.verifier_registry,
+        clock.unix_timestamp,
+    );
+
+    msg!("Agent verification synced: {} -> {}", ctx.accounts.agent.key(), ctx.accounts.agent.is_verified);
+    Ok(())
+}
+
+/// Mirrors the attestation's validity onto the agent's badge
+fn sync_verification_badge(
+    agent: &mut Agent,
+    verification: &AgentVerification,
+    registry: &VerifierRegistry,
+    current_time: i64,
+) {
+    if registry.attests(verification, current_time) {
+        agent.is_verified = true;
+        agent.verification_timestamp = verification.created_at;
+    } else {
+        agent.is_verified = false;
+        agent.verification_timestamp = 0;
+    }
+}
+
+/// Creates the verifier registry with an empty allowlist
+/// 
+/// Only the protocol config authority can create the registry, so the
+/// singleton cannot be claimed by a front-runner. It becomes the registry
+/// authority; hand it to a multisig or the governance signer with
+/// `set_verifier_registry_authority`.
+pub fn initialize_verifier_registry(ctx: Context<InitializeVerifierRegistry>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let registry = &mut ctx.accounts.verifier_registry;
+    registry.authority = ctx.accounts.authority.key();
+    registry.verifiers = Vec::new();
+    registry.bump = ctx.bumps.verifier_registry;
+
+    msg!("Verifier registry initialized - Authority: {}", registry.authority);
+    Ok(())
+}
+
+/// Adds a verifier to the registry
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the registry authority
+/// * `TooManyVerifiers` - If the allowlist is full
+pub fn add_verifier(ctx: Context<ManageVerifiers>, verifier: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    ctx.accounts.verifier_registry.add_verifier(verifier)?;
+
+    SecurityLogger::log_security_event("VERIFIER_ADDED", ctx.accounts.authority.key(),
+        &format!("verifier: {}", verifier));
+    Ok(())
+}
+
+/// Removes a verifier from the registry
+/// 
+/// Its attestations stop counting immediately; `sync_agent_verification`
+/// clears the affected badges.
+pub fn remove_verifier(ctx: Context<ManageVerifiers>, verifier: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    ctx.accounts.verifier_registry.remove_verifier(verifier)?;
+
+    SecurityLogger::log_security_event("VERIFIER_REMOVED", ctx.accounts.authority.key(),
+        &format!("verifier: {}", verifier));
+    Ok(())
+}
+
+/// Hands the verifier registry to a new authority
+pub fn set_verifier_registry_authority(ctx: Context<ManageVerifiers>, new_authority: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    ctx.accounts.verifier_registry.authority = new_authority;
+
+    SecurityLogger::log_security_event("VERIFIER_REGISTRY_AUTHORITY_UPDATED", ctx.accounts.authority.key(),
+        &format!("authority: {}", new_authority));
     Ok(())
 }
 
 pub fn deactivate_agent(ctx: Context<UpdateAgent>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let agent = &mut ctx.accounts.agent_account;
     
     require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
@@ -269,6 +619,7 @@ pub fn deactivate_agent(ctx: Context<UpdateAgent>) -> Result<()> {
 }
 
 pub fn activate_agent(ctx: Context<UpdateAgent>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let agent = &mut ctx.accounts.agent_account;
     
     require!(!agent.is_active, PodAIMarketplaceError::AgentAlreadyActive);
@@ -278,17 +629,21 @@ pub fn activate_agent(ctx: Context<UpdateAgent>) -> Result<()> {
     Ok(())
 }
 
-pub fn update_agent_reputation(
-    ctx: Context<UpdateAgent>,
-    reputation_score: u64,
-) -> Result<()> {
-    let agent = &mut ctx.accounts.agent_account;
-    
-    // SECURITY: Ensure reputation score fits in u32 and is within valid range
-    require!(reputation_score <= 100, PodAIMarketplaceError::InvalidReputationScore);
-    require!(reputation_score <= u32::MAX as u64, PodAIMarketplaceError::ValueExceedsMaximum);
-    
-    agent.update_reputation(reputation_score);
-    msg!("Agent reputation updated: {} -> {}", agent.owner, reputation_score);
-    Ok(())
-}
\ No newline at end of file
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct AgentVerifiedEvent {
+    pub agent: Pubkey,
+    pub verifier: Pubkey,
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AgentVerificationRevokedEvent {
+    pub agent: Pubkey,
+    pub revoked_by: Pubkey,
+    pub timestamp: i64,
+}
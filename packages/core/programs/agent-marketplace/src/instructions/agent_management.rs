@@ -6,6 +6,7 @@
 
 use anchor_lang::prelude::*;
 use crate::{*, PodAIMarketplaceError};
+use crate::security::ProtocolSubsystem;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct AgentServiceData {
@@ -17,10 +18,12 @@ pub struct AgentServiceData {
 
 #[derive(Accounts)]
 pub struct UpdateAgentService<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    // Not re-derived from the owner: a resold agent keeps the address
+    // seeded by its original owner
     #[account(
         mut,
-        seeds = [b"agent", owner.key().as_ref()],
-        bump = agent.bump,
         has_one = owner
     )]
     pub agent: Account<'info, Agent>,
@@ -63,6 +66,7 @@ pub fn update_agent_service(
     ctx: Context<UpdateAgentService>,
     service_data: AgentServiceData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.owner.is_signer,
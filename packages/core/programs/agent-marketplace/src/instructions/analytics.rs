@@ -7,6 +7,7 @@
 
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::security::ProtocolSubsystem;
 
 /// Creates an analytics dashboard for performance tracking
 /// 
@@ -49,6 +50,7 @@ pub fn create_analytics_dashboard(
     dashboard_id: u64,
     metrics: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let dashboard = &mut ctx.accounts.dashboard;
     
     // Use the struct's initialize method to ensure proper validation
@@ -101,6 +103,7 @@ pub fn update_analytics_dashboard(
     ctx: Context<UpdateAnalyticsDashboard>,
     new_metrics: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let dashboard = &mut ctx.accounts.dashboard;
     
     // Update metrics using the struct's built-in method
@@ -139,6 +142,7 @@ pub fn create_market_analytics(
     period_start: i64,
     period_end: i64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let market_analytics = &mut ctx.accounts.market_analytics;
     
     // Initialize using the struct's built-in method
@@ -182,6 +186,7 @@ pub fn update_market_analytics(
     volume: u64,
     price: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let market_analytics = &mut ctx.accounts.market_analytics;
     
     // Update stats using the struct's built-in method
@@ -219,6 +224,7 @@ pub fn add_top_agent(
     ctx: Context<UpdateMarketAnalytics>,
     agent: Pubkey,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let market_analytics = &mut ctx.accounts.market_analytics;
     
     // Add agent using the struct's built-in method
@@ -236,6 +242,8 @@ pub fn add_top_agent(
 // Context structures
 #[derive(Accounts)]
 pub struct CreateAnalyticsDashboard<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = owner,
@@ -251,6 +259,8 @@ pub struct CreateAnalyticsDashboard<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateAnalyticsDashboard<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         mut,
         seeds = [b"analytics", owner.key().as_ref()],
@@ -263,6 +273,8 @@ pub struct UpdateAnalyticsDashboard<'info> {
 
 #[derive(Accounts)]
 pub struct CreateMarketAnalytics<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = authority,
@@ -278,6 +290,8 @@ pub struct CreateMarketAnalytics<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateMarketAnalytics<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         mut,
         seeds = [b"market_analytics"],
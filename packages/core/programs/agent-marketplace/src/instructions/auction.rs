@@ -2,15 +2,26 @@
  * Auction Instructions Module
  * 
  * Contains all auction-related instruction handlers for the GhostSpeak Protocol.
+ *
+ * Bids are escrowed in a per-auction vault PDA. Outbid bidders withdraw their
+ * escrow with `withdraw_auction_bid`, and settlement opens a work order for the
+ * winner and moves the winning payment into its escrow.
+ *
+ * SealedBid and Vickrey auctions take hashed commitments during bidding, which
+ * are revealed in a window after the auction ends. Unrevealed deposits are
+ * forfeited to the creator when settled.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::*;
 use crate::state::*;
 use crate::simple_optimization::{SecurityLogger, FormalVerification};
+use crate::token_utils::{net_transfer_amount, transfer_tokens};
 
 // Import constants explicitly to avoid ambiguity
 use crate::state::{MIN_PAYMENT_AMOUNT, MAX_PAYMENT_AMOUNT, MIN_BID_INCREMENT, MIN_AUCTION_DURATION, MAX_AUCTION_DURATION, MAX_BIDS_PER_AUCTION_PER_USER};
+use crate::security::ProtocolSubsystem;
 
 // =====================================================
 // AUCTION INSTRUCTIONS
@@ -45,9 +56,10 @@ use crate::state::{MIN_PAYMENT_AMOUNT, MAX_PAYMENT_AMOUNT, MIN_BID_INCREMENT, MI
 /// 
 /// # Auction Types
 /// 
-/// - **Lowest Price**: Winner is lowest bidder
-/// - **Best Value**: Considers price and agent quality
-/// - **Dutch Auction**: Price decreases over time
+/// - **English**: Ascending open bids, highest bidder wins
+/// - **Dutch**: Price descends from `starting_price` to `reserve_price`; first taker wins
+/// - **SealedBid**: Commit-reveal bidding, highest revealed bid wins and pays its bid
+/// - **Vickrey**: Commit-reveal bidding, highest revealed bid wins and pays the second price
 /// 
 /// # Example
 /// 
@@ -66,6 +78,7 @@ pub fn create_service_auction(
     ctx: Context<CreateServiceAuction>,
     auction_data: AuctionData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.creator.is_signer,
@@ -90,13 +103,21 @@ pub fn create_service_auction(
         PodAIMarketplaceError::InvalidDeadline
     );
     
-    // SECURITY: Formal verification of auction invariants
-    FormalVerification::verify_auction_invariants(
-        auction_data.starting_price,
-        auction_data.starting_price,
-        auction_data.reserve_price,
-        auction_data.minimum_bid_increment,
-    )?;
+    if auction_data.auction_type == AuctionType::Dutch {
+        // Dutch clocks descend from the starting price to the reserve floor
+        require!(
+            auction_data.reserve_price < auction_data.starting_price,
+            PodAIMarketplaceError::InvalidPriceRange
+        );
+    } else {
+        // SECURITY: Formal verification of auction invariants
+        FormalVerification::verify_auction_invariants(
+            auction_data.starting_price,
+            auction_data.starting_price,
+            auction_data.reserve_price,
+            auction_data.minimum_bid_increment,
+        )?;
+    }
     
     let auction = &mut ctx.accounts.auction;
     let agent = &ctx.accounts.agent;
@@ -126,6 +147,14 @@ pub fn create_service_auction(
     auction.created_at = clock.unix_timestamp;
     auction.ended_at = None;
     auction.metadata_uri = String::new();
+    auction.payment_mint = ctx.accounts.payment_mint.key();
+    auction.work_order = None;
+    auction.reveal_end_time = if auction.is_sealed() {
+        auction_data.auction_end_time.saturating_add(SEALED_BID_REVEAL_WINDOW)
+    } else {
+        auction_data.auction_end_time
+    };
+    auction.second_price = 0;
     auction.bump = ctx.bumps.auction;
     
     emit!(ServiceAuctionCreatedEvent {
@@ -163,11 +192,18 @@ pub fn create_service_auction(
 /// * `BidExceedsReserve` - If bid is above reserve price
 /// * `AgentNotEligible` - If agent doesn't meet requirements
 /// * `BidTooLate` - If placed after auction end
+/// * `TooManyBids` - If the auction's bid history is full
+/// * `OperationNotSupported` - If the auction takes sealed bids
+/// 
+/// # Auction Types
+/// 
+/// - **English**: Bid must beat the current price by the minimum increment
+/// - **Dutch**: The first bid at or above the clock price wins and pays the clock price
 /// 
 /// # Bid Rules
 /// 
-/// - Agents can update bids until auction closes
-/// - Bids are binding - withdrawal incurs penalty
+/// - The full bid amount is escrowed in the auction vault
+/// - The leading bid is binding; outbid escrow is withdrawn with `withdraw_auction_bid`
 /// - Winning bid creates automatic work order
 /// 
 /// # Anti-Sniping
@@ -178,6 +214,7 @@ pub fn place_auction_bid(
     ctx: Context<PlaceAuctionBid>,
     bid_amount: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.bidder.is_signer,
@@ -195,13 +232,7 @@ pub fn place_auction_bid(
     
     require!(auction.status == AuctionStatus::Active, PodAIMarketplaceError::InvalidApplicationStatus);
     require!(clock.unix_timestamp < auction.auction_end_time, PodAIMarketplaceError::InvalidDeadline);
-    require!(bid_amount > auction.current_price, PodAIMarketplaceError::InvalidBid);
-    
-    // SECURITY: Prevent self-bidding and bid manipulation
-    require!(
-        Some(ctx.accounts.bidder.key()) != auction.current_winner, 
-        PodAIMarketplaceError::UnauthorizedAccess
-    );
+    require!(auction.bids.len() < MAX_BIDS_COUNT, PodAIMarketplaceError::TooManyBids);
     
     // SECURITY: Prevent auction creator from bidding
     require!(
@@ -209,24 +240,63 @@ pub fn place_auction_bid(
         PodAIMarketplaceError::UnauthorizedAccess
     );
     
-    // SECURITY: Use safe arithmetic to prevent overflow
-    let minimum_bid = safe_add!(auction.current_price, auction.minimum_bid_increment);
-    require!(bid_amount >= minimum_bid, PodAIMarketplaceError::InvalidBid);
-    
-    // SECURITY: Anti-sniping protection - extend auction if bid in final minutes
-    const ANTI_SNIPE_EXTENSION: i64 = 300; // 5 minutes
-    if auction.auction_end_time - clock.unix_timestamp < ANTI_SNIPE_EXTENSION {
-        auction.auction_end_time = auction.auction_end_time.saturating_add(ANTI_SNIPE_EXTENSION);
-        SecurityLogger::log_security_event("AUCTION_EXTENDED", ctx.accounts.bidder.key(), 
-            &format!("auction: {}, new_end_time: {}", auction.key(), auction.auction_end_time));
-    }
+    let charge_amount = match auction.auction_type {
+        AuctionType::English => {
+            require!(bid_amount > auction.current_price, PodAIMarketplaceError::InvalidBid);
+            
+            // SECURITY: Prevent self-bidding and bid manipulation
+            require!(
+                Some(ctx.accounts.bidder.key()) != auction.current_winner, 
+                PodAIMarketplaceError::UnauthorizedAccess
+            );
+            
+            // SECURITY: Use safe arithmetic to prevent overflow
+            let minimum_bid = safe_add!(auction.current_price, auction.minimum_bid_increment);
+            require!(bid_amount >= minimum_bid, PodAIMarketplaceError::InvalidBid);
+            
+            // SECURITY: Anti-sniping protection - extend auction if bid in final minutes
+            const ANTI_SNIPE_EXTENSION: i64 = 300; // 5 minutes
+            if auction.auction_end_time - clock.unix_timestamp < ANTI_SNIPE_EXTENSION {
+                auction.auction_end_time = auction.auction_end_time.saturating_add(ANTI_SNIPE_EXTENSION);
+                SecurityLogger::log_security_event("AUCTION_EXTENDED", ctx.accounts.bidder.key(), 
+                    &format!("auction: {}, new_end_time: {}", auction.key(), auction.auction_end_time));
+            }
+            
+            bid_amount
+        }
+        AuctionType::Dutch => {
+            // First taker wins at the current clock price; `bid_amount` is the
+            // most the bidder is willing to pay
+            require!(auction.current_winner.is_none(), PodAIMarketplaceError::AuctionEnded);
+            let clock_price = auction.dutch_price(clock.unix_timestamp);
+            require!(bid_amount >= clock_price, PodAIMarketplaceError::BidTooLow);
+            
+            // Close bidding so the auction can be finalized immediately
+            auction.auction_end_time = clock.unix_timestamp;
+            clock_price
+        }
+        AuctionType::SealedBid | AuctionType::Vickrey => {
+            // Sealed auctions take bids through commit_sealed_bid and reveal_sealed_bid
+            return Err(PodAIMarketplaceError::OperationNotSupported.into());
+        }
+    };
     
-    // Store previous bidder info for refund
-    let previous_bidder = auction.current_winner;
-    let _previous_bid = auction.current_price;
+    // SECURITY: Lock the bid in the auction vault. The previous leader's escrow
+    // stays in the vault as a withdrawable balance rather than being pushed back,
+    // so a closed token account can never block a higher bid.
+    let escrowed = net_transfer_amount(&ctx.accounts.payment_mint, charge_amount)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.bidder.to_account_info(),
+        charge_amount,
+        &[],
+    )?;
     
     // Update auction with new bid
-    auction.current_price = bid_amount;
+    auction.current_price = charge_amount;
     auction.current_winner = Some(ctx.accounts.bidder.key());
     
     // SECURITY: Use safe arithmetic for bid count
@@ -240,26 +310,27 @@ pub fn place_auction_bid(
     
     // Log bid for security audit
     SecurityLogger::log_security_event("AUCTION_BID_PLACED", ctx.accounts.bidder.key(), 
-        &format!("auction: {}, amount: {}, bid_number: {}", auction.key(), bid_amount, auction.total_bids));
+        &format!("auction: {}, amount: {}, bid_number: {}", auction.key(), charge_amount, auction.total_bids));
+    
+    // Mark previous winning bid as not winning, making its escrow withdrawable
+    for bid in auction.bids.iter_mut() {
+        bid.is_winning = false;
+    }
     
     // Add bid to history
     let new_bid = AuctionBid {
         bidder: ctx.accounts.bidder.key(),
-        amount: bid_amount,
+        amount: charge_amount,
         timestamp: clock.unix_timestamp,
         is_winning: true,
+        escrowed,
     };
     auction.bids.push(new_bid);
     
-    // Mark previous winning bid as not winning
-    if let Some(prev_bid) = auction.bids.iter_mut().find(|b| b.bidder == previous_bidder.unwrap_or_default()) {
-        prev_bid.is_winning = false;
-    }
-    
     emit!(AuctionBidPlacedEvent {
         auction: auction.key(),
         bidder: ctx.accounts.bidder.key(),
-        bid_amount,
+        bid_amount: charge_amount,
         total_bids: auction.total_bids,
     });
     
@@ -268,12 +339,19 @@ pub fn place_auction_bid(
 
 /// Finalizes an auction and determines the winner
 /// 
-/// Called after auction end time to finalize the auction,
-/// determine the winner, and initiate the work order creation.
+/// Called after auction end time to finalize the auction and determine the
+/// winner. On success a work order is opened with the winner as client and the
+/// creator as provider, and the winning payment moves from the auction vault
+/// into that work order's escrow. The creator is paid when the winner approves
+/// the delivery, and the winner is refunded if nothing is delivered in time.
+/// If the auction fails, every bid becomes withdrawable.
 /// 
 /// # Arguments
 /// 
-/// * `ctx` - The context containing auction account
+/// * `ctx` - The context containing auction, vault and work order accounts.
+///   `work_order`, `escrow` and `escrow_vault` must be supplied when the
+///   auction settles and omitted otherwise.
+///   Sealed auctions also need the winner's `winner_commitment` to settle.
 /// 
 /// # Returns
 /// 
@@ -284,15 +362,24 @@ pub fn place_auction_bid(
 /// * `AuctionStillActive` - If auction hasn't ended yet
 /// * `AuctionAlreadyFinalized` - If auction was already finalized
 /// * `NoValidBids` - If no bids meet reserve price
+/// * `AccountNotInitialized` - If the auction settles without the work order and
+///   escrow accounts or, for sealed auctions, without the winner's commitment
+/// 
+/// # Settlement Price
+/// 
+/// English, Dutch and SealedBid winners pay their own bid; Vickrey winners pay
+/// the second-highest revealed bid, floored at the reserve and starting price.
 pub fn finalize_auction(
     ctx: Context<FinalizeAuction>,
 ) -> Result<()> {
-    let auction = &mut ctx.accounts.auction;
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
     let clock = Clock::get()?;
+    let auction_key = ctx.accounts.auction.key();
+    let auction = &mut ctx.accounts.auction;
     
-    // Ensure auction has ended
+    // Ensure bidding (and the reveal phase for sealed auctions) has ended
     require!(
-        clock.unix_timestamp >= auction.auction_end_time,
+        clock.unix_timestamp >= auction.settlement_time(),
         PodAIMarketplaceError::InvalidDeadline
     );
     
@@ -304,31 +391,123 @@ pub fn finalize_auction(
     
     // SECURITY: Formal verification before finalization
     if let Some(winner) = auction.current_winner {
-        // SECURITY: Verify auction invariants before settlement
-        FormalVerification::verify_auction_invariants(
-            auction.current_price,
-            auction.starting_price,
-            auction.reserve_price,
-            auction.minimum_bid_increment,
-        )?;
+        // SECURITY: Verify auction invariants before settlement. Dutch clocks
+        // run from a starting price above the reserve, which these invariants reject.
+        if auction.auction_type != AuctionType::Dutch {
+            FormalVerification::verify_auction_invariants(
+                auction.current_price,
+                auction.starting_price,
+                auction.reserve_price,
+                auction.minimum_bid_increment,
+            )?;
+        }
         
         // Check if bid meets reserve price
         if auction.reserve_price == 0 || auction.current_price >= auction.reserve_price {
+            let price = auction.settlement_price();
             auction.winner = Some(winner);
             auction.status = AuctionStatus::Settled;
+            auction.ended_at = Some(clock.unix_timestamp);
+            
+            // Move the winner's payment into the work order escrow
+            let payment = if auction.is_sealed() {
+                // Sealed winners pay from their commitment deposit; the rest
+                // is returned by settle_sealed_bid
+                let commitment = ctx.accounts.winner_commitment
+                    .as_mut()
+                    .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+                require!(
+                    commitment.auction == auction_key && commitment.bidder == winner,
+                    PodAIMarketplaceError::InvalidConfiguration
+                );
+                commitment.deposit = commitment.deposit
+                    .checked_sub(price)
+                    .ok_or(PodAIMarketplaceError::InsufficientFunds)?;
+                price
+            } else {
+                let winning_bid = auction.bids
+                    .iter_mut()
+                    .rev()
+                    .find(|bid| bid.is_winning)
+                    .ok_or(PodAIMarketplaceError::InvalidBid)?;
+                let escrowed = winning_bid.escrowed;
+                winning_bid.escrowed = 0;
+                escrowed
+            };
+            
+            let auction_seeds: &[&[u8]] = &[
+                b"auction",
+                auction.agent.as_ref(),
+                auction.creator.as_ref(),
+                &[auction.bump],
+            ];
+            let escrow_vault = ctx.accounts.escrow_vault
+                .as_ref()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            let escrowed_amount = net_transfer_amount(&ctx.accounts.payment_mint, payment)?;
+            require!(escrowed_amount > 0, PodAIMarketplaceError::InvalidEscrowAmount);
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.auction_vault,
+                &ctx.accounts.payment_mint,
+                escrow_vault,
+                &auction.to_account_info(),
+                payment,
+                &[auction_seeds],
+            )?;
+            
+            // Open the work order the winner has paid for
+            let work_order = ctx.accounts.work_order
+                .as_mut()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            let deadline = clock.unix_timestamp.saturating_add(AUCTION_DELIVERY_WINDOW);
+            let escrow = ctx.accounts.escrow
+                .as_mut()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            escrow.initialize(EscrowInit {
+                client: winner,
+                agent: auction.creator,
+                task_id: auction_key.to_string(),
+                amount: escrowed_amount,
+                expires_at: deadline,
+                work_order: work_order.key(),
+                token_mint: auction.payment_mint,
+                bump: ctx.bumps.escrow.ok_or(PodAIMarketplaceError::AccountNotInitialized)?,
+            })?;
+            // The auction's fee, not the work order fee, is charged on release
+            escrow.fee_type = FeeTransactionType::Auction;
+            
+            work_order.client = winner;
+            work_order.provider = auction.creator;
+            work_order.provider_agent = auction.agent;
+            work_order.title = format!("Auction {}", auction_key);
+            work_order.description = auction.metadata_uri.clone();
+            work_order.requirements = Vec::new();
+            work_order.payment_amount = price;
+            work_order.payment_token = auction.payment_mint;
+            work_order.status = WorkOrderStatus::InProgress;
+            work_order.created_at = clock.unix_timestamp;
+            work_order.updated_at = clock.unix_timestamp;
+            work_order.deadline = deadline;
+            work_order.delivered_at = None;
+            work_order.bump = ctx.bumps.work_order.ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            auction.work_order = Some(work_order.key());
             
             // Log successful auction completion
             SecurityLogger::log_security_event("AUCTION_FINALIZED", winner, 
-                &format!("auction: {}, winning_bid: {}, total_bids: {}", auction.key(), auction.current_price, auction.total_bids));
+                &format!("auction: {}, winning_bid: {}, total_bids: {}", auction.key(), price, auction.total_bids));
             
             emit!(AuctionFinalizedEvent {
                 auction: auction.key(),
                 winner,
-                winning_bid: auction.current_price,
+                winning_bid: price,
             });
         } else {
             // Reserve not met
+            require!(ctx.accounts.work_order.is_none(), PodAIMarketplaceError::InvalidConfiguration);
+            require!(ctx.accounts.escrow.is_none(), PodAIMarketplaceError::InvalidConfiguration);
             auction.status = AuctionStatus::Cancelled;
+            auction.ended_at = Some(clock.unix_timestamp);
             
             SecurityLogger::log_security_event("AUCTION_FAILED_RESERVE", auction.creator, 
                 &format!("auction: {}, highest_bid: {}, reserve: {}", auction.key(), auction.current_price, auction.reserve_price));
@@ -340,7 +519,10 @@ pub fn finalize_auction(
         }
     } else {
         // No bids received
+        require!(ctx.accounts.work_order.is_none(), PodAIMarketplaceError::InvalidConfiguration);
+        require!(ctx.accounts.escrow.is_none(), PodAIMarketplaceError::InvalidConfiguration);
         auction.status = AuctionStatus::Cancelled;
+        auction.ended_at = Some(clock.unix_timestamp);
         
         SecurityLogger::log_security_event("AUCTION_FAILED_NO_BIDS", auction.creator, 
             &format!("auction: {}", auction.key()));
@@ -354,12 +536,281 @@ pub fn finalize_auction(
     Ok(())
 }
 
+/// Withdraws a bidder's escrow for bids that can no longer win
+/// 
+/// Outbid bids are withdrawable at any time; once an auction is cancelled
+/// (no bids or reserve not met) the leading bid is withdrawable too.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing auction, vault and bidder token accounts
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful withdrawal
+/// 
+/// # Errors
+/// 
+/// * `InsufficientFunds` - If the bidder has nothing to withdraw
+pub fn withdraw_auction_bid(ctx: Context<WithdrawAuctionBid>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
+    let bidder = ctx.accounts.bidder.key();
+    let auction = &mut ctx.accounts.auction;
+    
+    let refund = auction.take_refund(bidder)?;
+    require!(refund > 0, PodAIMarketplaceError::InsufficientFunds);
+    
+    let auction_seeds: &[&[u8]] = &[
+        b"auction",
+        auction.agent.as_ref(),
+        auction.creator.as_ref(),
+        &[auction.bump],
+    ];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.bidder_token_account,
+        &auction.to_account_info(),
+        refund,
+        &[auction_seeds],
+    )?;
+    
+    SecurityLogger::log_security_event("AUCTION_BID_WITHDRAWN", bidder,
+        &format!("auction: {}, amount: {}", auction.key(), refund));
+    
+    emit!(AuctionBidWithdrawnEvent {
+        auction: auction.key(),
+        bidder,
+        amount: refund,
+    });
+    
+    Ok(())
+}
+
+/// Commits a hashed bid to a SealedBid or Vickrey auction
+/// 
+/// The bid amount stays hidden until the reveal phase. The deposit is
+/// escrowed in the auction vault and must cover the amount later revealed.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing auction, vault and commitment accounts
+/// * `commitment` - `sha256(amount_le || salt || bidder)`, see
+///   `SealedBidCommitment::compute_commitment`
+/// * `deposit` - Tokens locked alongside the commitment
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful commitment
+/// 
+/// # Errors
+/// 
+/// * `OperationNotSupported` - If the auction does not take sealed bids
+/// * `InvalidApplicationStatus` - If the auction is not active
+/// * `InvalidDeadline` - If bidding has closed
+/// * `UnauthorizedAccess` - If the auction creator tries to bid
+/// * `BidTooLow` - If the deposit is below the starting price
+/// 
+/// # State Changes
+/// 
+/// - Creates the bidder's `SealedBidCommitment` (one per bidder per auction)
+/// - Increments the auction's bid count
+pub fn commit_sealed_bid(
+    ctx: Context<CommitSealedBid>,
+    commitment: [u8; 32],
+    deposit: u64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
+    let clock = Clock::get()?;
+    let auction = &mut ctx.accounts.auction;
+    let bidder = ctx.accounts.bidder.key();
+    
+    require!(auction.is_sealed(), PodAIMarketplaceError::OperationNotSupported);
+    require!(auction.status == AuctionStatus::Active, PodAIMarketplaceError::InvalidApplicationStatus);
+    require!(clock.unix_timestamp < auction.auction_end_time, PodAIMarketplaceError::InvalidDeadline);
+    
+    // SECURITY: Prevent auction creator from bidding
+    require!(bidder != auction.creator, PodAIMarketplaceError::UnauthorizedAccess);
+    
+    // SECURITY: Amount validation
+    require!(
+        (MIN_PAYMENT_AMOUNT..=MAX_PAYMENT_AMOUNT).contains(&deposit),
+        PodAIMarketplaceError::InvalidPaymentAmount
+    );
+    require!(deposit >= auction.starting_price, PodAIMarketplaceError::BidTooLow);
+    
+    let escrowed = net_transfer_amount(&ctx.accounts.payment_mint, deposit)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.bidder_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.bidder.to_account_info(),
+        deposit,
+        &[],
+    )?;
+    
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    sealed_bid.auction = auction.key();
+    sealed_bid.bidder = bidder;
+    sealed_bid.commitment = commitment;
+    sealed_bid.deposit = escrowed;
+    sealed_bid.revealed_amount = None;
+    sealed_bid.committed_at = clock.unix_timestamp;
+    sealed_bid.bump = ctx.bumps.sealed_bid;
+    
+    auction.total_bids = auction.total_bids.saturating_add(1);
+    
+    SecurityLogger::log_security_event("SEALED_BID_COMMITTED", bidder,
+        &format!("auction: {}, deposit: {}", auction.key(), escrowed));
+    
+    emit!(SealedBidCommittedEvent {
+        auction: auction.key(),
+        bidder,
+        deposit: escrowed,
+    });
+    
+    Ok(())
+}
+
+/// Reveals a previously committed sealed bid
+/// 
+/// Must be called after bidding closes and before the reveal window ends.
+/// Only revealed bids can win; unrevealed deposits are forfeited.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing auction and commitment accounts
+/// * `amount` - The committed bid amount
+/// * `salt` - The salt used when computing the commitment
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful reveal
+/// 
+/// # Errors
+/// 
+/// * `InvalidDeadline` - If called outside the reveal phase
+/// * `AccountAlreadyInitialized` - If the bid was already revealed
+/// * `CommitmentMismatch` - If amount and salt do not match the commitment
+/// * `BidTooLow` - If the amount is below the starting price
+/// * `InsufficientFunds` - If the amount exceeds the escrowed deposit
+pub fn reveal_sealed_bid(
+    ctx: Context<RevealSealedBid>,
+    amount: u64,
+    salt: [u8; 32],
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
+    let clock = Clock::get()?;
+    let auction = &mut ctx.accounts.auction;
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    
+    require!(auction.status == AuctionStatus::Active, PodAIMarketplaceError::InvalidApplicationStatus);
+    require!(
+        clock.unix_timestamp >= auction.auction_end_time && clock.unix_timestamp < auction.reveal_end_time,
+        PodAIMarketplaceError::InvalidDeadline
+    );
+    require!(sealed_bid.revealed_amount.is_none(), PodAIMarketplaceError::AccountAlreadyInitialized);
+    
+    // SECURITY: The reveal must reproduce the committed hash exactly
+    require!(sealed_bid.verify_reveal(amount, &salt), PodAIMarketplaceError::CommitmentMismatch);
+    require!(amount >= auction.starting_price, PodAIMarketplaceError::BidTooLow);
+    require!(amount <= sealed_bid.deposit, PodAIMarketplaceError::InsufficientFunds);
+    
+    sealed_bid.revealed_amount = Some(amount);
+    auction.record_reveal(sealed_bid.bidder, amount);
+    
+    SecurityLogger::log_security_event("SEALED_BID_REVEALED", sealed_bid.bidder,
+        &format!("auction: {}, amount: {}", auction.key(), amount));
+    
+    emit!(SealedBidRevealedEvent {
+        auction: auction.key(),
+        bidder: sealed_bid.bidder,
+        amount,
+    });
+    
+    Ok(())
+}
+
+/// Settles a sealed bid commitment once the auction has been finalized
+/// 
+/// Permissionless crank. Revealed bids get their remaining deposit back
+/// (for the winner, whatever is left after paying the settlement price).
+/// Bids that were never revealed forfeit their deposit to the auction creator.
+/// The commitment account is closed and its rent returned to the bidder.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing auction, vault, commitment and the
+///   destination token account (bidder's if revealed, creator's if forfeited)
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful settlement
+/// 
+/// # Errors
+/// 
+/// * `AuctionNotEnded` - If the auction has not been finalized
+/// * `InvalidAccountOwner` - If the destination token account is not the recipient's
+pub fn settle_sealed_bid(ctx: Context<SettleSealedBid>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Auctions)?;
+    let auction = &ctx.accounts.auction;
+    let sealed_bid = &mut ctx.accounts.sealed_bid;
+    
+    require!(auction.status != AuctionStatus::Active, PodAIMarketplaceError::AuctionNotEnded);
+    
+    let forfeited = sealed_bid.revealed_amount.is_none();
+    let recipient = if forfeited { auction.creator } else { sealed_bid.bidder };
+    require!(
+        ctx.accounts.destination_token_account.owner == recipient,
+        PodAIMarketplaceError::InvalidAccountOwner
+    );
+    
+    let amount = sealed_bid.deposit;
+    sealed_bid.deposit = 0;
+    
+    let auction_seeds: &[&[u8]] = &[
+        b"auction",
+        auction.agent.as_ref(),
+        auction.creator.as_ref(),
+        &[auction.bump],
+    ];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.auction_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.destination_token_account,
+        &auction.to_account_info(),
+        amount,
+        &[auction_seeds],
+    )?;
+    
+    SecurityLogger::log_security_event(
+        if forfeited { "SEALED_BID_FORFEITED" } else { "SEALED_BID_REFUNDED" },
+        sealed_bid.bidder,
+        &format!("auction: {}, amount: {}, recipient: {}", auction.key(), amount, recipient),
+    );
+    
+    emit!(SealedBidSettledEvent {
+        auction: auction.key(),
+        bidder: sealed_bid.bidder,
+        recipient,
+        amount,
+        forfeited,
+    });
+    
+    Ok(())
+}
+
 // =====================================================
 // ACCOUNT STRUCTURES
 // =====================================================
 
 #[derive(Accounts)]
 pub struct CreateServiceAuction<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
@@ -373,29 +824,234 @@ pub struct CreateServiceAuction<'info> {
         constraint = agent.is_active @ PodAIMarketplaceError::AgentNotActive
     )]
     pub agent: Account<'info, Agent>,
+    #[account(
+        init,
+        payer = creator,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct PlaceAuctionBid<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == auction.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = bidder_token_account.owner == bidder.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct FinalizeAuction<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         mut,
         constraint = auction.status == AuctionStatus::Active @ PodAIMarketplaceError::InvalidApplicationStatus
     )]
     pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == auction.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    /// Work order for the winner, only created when the auction settles
+    #[account(
+        init,
+        payer = authority,
+        space = WorkOrder::LEN,
+        seeds = [WORK_ORDER_SEED, auction.key().as_ref()],
+        bump
+    )]
+    pub work_order: Option<Account<'info, WorkOrder>>,
+    /// Escrow holding the winning payment until the work is approved
+    #[account(
+        init,
+        payer = authority,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, work_order.as_ref().map(|w| w.key()).unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub escrow: Option<Account<'info, Escrow>>,
+    #[account(
+        init,
+        payer = authority,
+        seeds = [ESCROW_VAULT_SEED, work_order.as_ref().map(|w| w.key()).unwrap_or_default().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Winning commitment, required to settle SealedBid and Vickrey auctions
+    #[account(
+        mut,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), winner_commitment.bidder.as_ref()],
+        bump = winner_commitment.bump
+    )]
+    pub winner_commitment: Option<Account<'info, SealedBidCommitment>>,
     #[account(mut)]
     pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAuctionBid<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == auction.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = bidder_token_account.owner == bidder.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = bidder_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CommitSealedBid<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        init,
+        payer = bidder,
+        space = SealedBidCommitment::LEN,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub sealed_bid: Account<'info, SealedBidCommitment>,
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == auction.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = bidder_token_account.owner == bidder.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSealedBid<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        mut,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump,
+        constraint = sealed_bid.bidder == bidder.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub sealed_bid: Account<'info, SealedBidCommitment>,
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSealedBid<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub auction: Account<'info, AuctionMarketplace>,
+    #[account(
+        mut,
+        close = bidder,
+        seeds = [SEALED_BID_SEED, auction.key().as_ref(), bidder.key().as_ref()],
+        bump = sealed_bid.bump
+    )]
+    pub sealed_bid: Account<'info, SealedBidCommitment>,
+    #[account(
+        mut,
+        seeds = [AUCTION_VAULT_SEED, auction.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = auction,
+        token::token_program = token_program
+    )]
+    pub auction_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == auction.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    /// Bidder's token account for revealed bids, creator's for forfeited ones
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Receives the commitment rent; bound by the commitment seeds
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 // =====================================================
@@ -432,3 +1088,32 @@ pub struct AuctionFailedEvent {
     pub reason: String,
 }
 
+#[event]
+pub struct AuctionBidWithdrawnEvent {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SealedBidCommittedEvent {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub deposit: u64,
+}
+
+#[event]
+pub struct SealedBidRevealedEvent {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SealedBidSettledEvent {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub forfeited: bool,
+}
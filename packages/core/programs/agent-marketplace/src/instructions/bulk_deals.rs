@@ -3,11 +3,23 @@
  * 
  * Implements enterprise and volume discount pricing for large-scale
  * agent service purchases with custom SLA terms.
+ *
+ * Lifecycle: the customer creates the deal, the agent owner accepts its
+ * terms, and the customer draws volume down at the graduated tier price.
+ * Each drawdown opens a work order with its charge in escrow, which is then
+ * delivered, approved, refunded or disputed like any other work order.
+ * Prepaid deals escrow drawdowns from a vault funded up front; postpaid
+ * deals escrow each drawdown from the customer's account as it is drawn.
+ * SLA breaches acknowledged by the agent owner refund a credit from the
+ * drawdown's escrow, and settlement returns any unused prepaid balance.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
-use crate::PodAIMarketplaceError;
+use crate::{PodAIMarketplaceError, WorkOrderCreatedEvent};
+use crate::security::ProtocolSubsystem;
+use crate::token_utils::{net_transfer_amount, transfer_tokens};
 
 /// Creates a bulk or enterprise deal with volume discounts
 /// 
@@ -17,6 +29,8 @@ use crate::PodAIMarketplaceError;
 /// # Arguments
 /// 
 /// * `ctx` - The context containing bulk deal accounts
+/// * `deal_id` - Customer-chosen id distinguishing this deal from others
+///   between the same agent and customer
 /// * `deal_data` - Bulk deal configuration including:
 ///   - `service_bundles` - Services included in deal
 ///   - `minimum_volume` - Minimum purchase commitment
@@ -52,17 +66,32 @@ use crate::PodAIMarketplaceError;
 /// - Custom SLA terms
 pub fn create_bulk_deal(
     ctx: Context<CreateBulkDeal>,
+    deal_id: u64,
     deal_data: BulkDealData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     let deal = &mut ctx.accounts.deal;
     let agent = &ctx.accounts.agent;
     let clock = Clock::get()?;
 
     require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
     require!(deal_data.end_date > clock.unix_timestamp, PodAIMarketplaceError::InvalidDeadline);
+    require!(deal_data.total_volume > 0, PodAIMarketplaceError::InvalidVolume);
+    require!(deal_data.total_value > 0, PodAIMarketplaceError::InvalidValue);
+    require!(
+        deal_data.discount_percentage <= MAX_DISCOUNT_PERCENTAGE,
+        PodAIMarketplaceError::InvalidDiscountPercentage
+    );
+    require!(
+        deal_data.sla_credit_bps as u32 <= MAX_DISCOUNT_PERCENTAGE,
+        PodAIMarketplaceError::InvalidPercentage
+    );
+    require!(deal_data.sla_terms.len() <= MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError::StringTooLong);
+    BulkDeal::validate_volume_tiers(&deal_data.volume_tiers)?;
 
     deal.agent = agent.key();
     deal.customer = ctx.accounts.customer.key();
+    deal.deal_id = deal_id;
     deal.deal_type = deal_data.deal_type;
     deal.total_volume = deal_data.total_volume;
     deal.total_value = deal_data.total_value;
@@ -74,6 +103,15 @@ pub fn create_bulk_deal(
     deal.end_date = deal_data.end_date;
     deal.is_active = true;
     deal.created_at = clock.unix_timestamp;
+    deal.payment_mint = ctx.accounts.payment_mint.key();
+    deal.payment_mode = deal_data.payment_mode;
+    deal.sla_credit_bps = deal_data.sla_credit_bps;
+    deal.accepted_at = None;
+    deal.consumed_volume = 0;
+    deal.drawdown_count = 0;
+    deal.charged_amount = 0;
+    deal.credited_amount = 0;
+    deal.settled_at = None;
     deal.bump = ctx.bumps.deal;
 
     emit!(BulkDealCreatedEvent {
@@ -87,23 +125,566 @@ pub fn create_bulk_deal(
     Ok(())
 }
 
+/// Accepts a bulk deal's pricing and SLA terms on behalf of its agent
+/// 
+/// Volume cannot be drawn down until the agent owner accepts, since the
+/// tiered price and SLA credits bind the provider.
+pub fn accept_bulk_deal(ctx: Context<AcceptBulkDeal>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let deal = &mut ctx.accounts.deal;
+    let clock = Clock::get()?;
+
+    require!(deal.is_active, PodAIMarketplaceError::DealNotActive);
+    require!(deal.accepted_at.is_none(), PodAIMarketplaceError::InvalidStatusTransition);
+    require!(clock.unix_timestamp <= deal.end_date, PodAIMarketplaceError::DealExpired);
+
+    deal.accepted_at = Some(clock.unix_timestamp);
+
+    emit!(BulkDealAcceptedEvent {
+        deal: deal.key(),
+        agent: deal.agent,
+        owner: ctx.accounts.owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Deposits funds into a prepaid bulk deal's vault
+pub fn fund_bulk_deal(ctx: Context<FundBulkDeal>, amount: u64) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let deal = &ctx.accounts.deal;
+
+    require!(deal.is_active, PodAIMarketplaceError::DealNotActive);
+    require!(deal.payment_mode == BulkPaymentMode::Prepaid, PodAIMarketplaceError::InvalidConfiguration);
+    require!(amount > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.customer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.deal_vault,
+        &ctx.accounts.customer.to_account_info(),
+        amount,
+        &[],
+    )?;
+    let deposited = net_transfer_amount(&ctx.accounts.payment_mint, amount)?;
+
+    emit!(BulkDealFundedEvent {
+        deal: deal.key(),
+        customer: deal.customer,
+        amount: deposited,
+    });
+
+    Ok(())
+}
+
+/// Draws down volume from a bulk deal at the tiered price
+/// 
+/// Records a `BulkDealDrawdown` for the units consumed and opens an
+/// `InProgress` work order for them with the charge in escrow: prepaid deals
+/// move it out of the deal vault, postpaid deals take it from the customer's
+/// token account. The provider is paid when the customer approves the
+/// delivery; undelivered or contested work is refunded or disputed through
+/// the work order.
+/// 
+/// # Errors
+/// 
+/// * `DealNotActive` - If the deal is settled or not yet accepted
+/// * `DealExpired` - If the term has ended
+/// * `DealFull` - If `quantity` exceeds the remaining volume
+/// * `InsufficientBalance` - If a prepaid vault can't cover the charge
+/// * `InvalidEscrowAmount` - If the drawdown is priced at nothing
+pub fn draw_bulk_deal(ctx: Context<DrawBulkDeal>, quantity: u32) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let deal = &ctx.accounts.deal;
+
+    require!(deal.is_active && deal.accepted_at.is_some(), PodAIMarketplaceError::DealNotActive);
+    require!(clock.unix_timestamp <= deal.end_date, PodAIMarketplaceError::DealExpired);
+
+    let quote = deal.drawdown_price(quantity)?;
+    let amount = quote.amount;
+    let escrowed_amount = net_transfer_amount(&ctx.accounts.payment_mint, amount)?;
+    require!(escrowed_amount > 0, PodAIMarketplaceError::InvalidEscrowAmount);
+
+    match deal.payment_mode {
+        BulkPaymentMode::Prepaid => {
+            // SECURITY: Prepaid volume must be covered before it is consumed
+            require!(
+                ctx.accounts.deal_vault.amount >= amount,
+                PodAIMarketplaceError::InsufficientBalance
+            );
+            let deal_id = deal.deal_id.to_le_bytes();
+            let seeds: &[&[u8]] = &[
+                BULK_DEAL_SEED,
+                deal.agent.as_ref(),
+                deal.customer.as_ref(),
+                &deal_id,
+                &[deal.bump],
+            ];
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.deal_vault,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.escrow_vault,
+                &deal.to_account_info(),
+                amount,
+                &[seeds],
+            )?;
+        }
+        BulkPaymentMode::Postpaid => {
+            transfer_tokens(
+                &ctx.accounts.token_program,
+                &ctx.accounts.customer_token_account,
+                &ctx.accounts.payment_mint,
+                &ctx.accounts.escrow_vault,
+                &ctx.accounts.customer.to_account_info(),
+                amount,
+                &[],
+            )?;
+        }
+    }
+
+    let provider = ctx.accounts.agent.owner;
+    let drawdown_key = ctx.accounts.drawdown.key();
+    let work_order_key = ctx.accounts.work_order.key();
+    ctx.accounts.escrow.initialize(EscrowInit {
+        client: deal.customer,
+        agent: provider,
+        task_id: drawdown_key.to_string(),
+        amount: escrowed_amount,
+        expires_at: deal.end_date,
+        work_order: work_order_key,
+        token_mint: deal.payment_mint,
+        bump: ctx.bumps.escrow,
+    })?;
+
+    let work_order = &mut ctx.accounts.work_order;
+    work_order.client = deal.customer;
+    work_order.provider = provider;
+    work_order.provider_agent = deal.agent;
+    work_order.title = format!("Bulk deal drawdown {}", deal.drawdown_count);
+    work_order.description = deal.sla_terms.clone();
+    work_order.requirements = Vec::new();
+    work_order.payment_amount = amount;
+    work_order.payment_token = deal.payment_mint;
+    work_order.status = WorkOrderStatus::InProgress;
+    work_order.created_at = clock.unix_timestamp;
+    work_order.updated_at = clock.unix_timestamp;
+    work_order.deadline = deal.end_date;
+    work_order.delivered_at = None;
+    work_order.bump = ctx.bumps.work_order;
+
+    let drawdown = &mut ctx.accounts.drawdown;
+    drawdown.deal = deal.key();
+    drawdown.index = deal.drawdown_count;
+    drawdown.work_order = work_order_key;
+    drawdown.quantity = quantity;
+    drawdown.amount = amount;
+    drawdown.created_at = clock.unix_timestamp;
+    drawdown.sla_breached = false;
+    drawdown.credit = 0;
+    drawdown.breach_reason = String::new();
+    drawdown.bump = ctx.bumps.drawdown;
+
+    let deal = &mut ctx.accounts.deal;
+    deal.apply_drawdown(&quote)?;
+
+    emit!(WorkOrderCreatedEvent {
+        work_order: work_order_key,
+        client: deal.customer,
+        provider,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(BulkDealDrawnEvent {
+        deal: deal.key(),
+        drawdown: drawdown_key,
+        work_order: work_order_key,
+        index: ctx.accounts.drawdown.index,
+        quantity,
+        amount,
+        consumed_volume: deal.consumed_volume,
+    });
+
+    Ok(())
+}
+
+/// Credits the customer for an SLA breach on a drawdown
+/// 
+/// The credit is the deal's `sla_credit_bps` of the drawdown amount, refunded
+/// to the customer from the drawdown's work order escrow. The agent owner
+/// must co-sign to acknowledge the breach; a breach the owner contests is
+/// taken to a dispute on the work order instead, where an arbitrator rules
+/// on the escrow. Each drawdown can be credited once, while its escrow is
+/// still held and within `SLA_BREACH_REPORT_WINDOW` of being drawn.
+/// 
+/// # Errors
+/// 
+/// * `InvalidAgentOwner` - If `agent_owner` does not own the deal's agent
+/// * `SlaBreachAlreadyReported` - If the drawdown was already credited
+/// * `SlaReportWindowClosed` - If the report window has passed
+/// * `InvalidEscrowStatus` - If the drawdown's escrow was already settled
+pub fn report_sla_breach(ctx: Context<ReportSlaBreach>, reason: String) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let deal = &ctx.accounts.deal;
+    let drawdown = &ctx.accounts.drawdown;
+
+    require!(reason.len() <= MAX_SLA_BREACH_REASON_LENGTH, PodAIMarketplaceError::InputTooLong);
+    require!(deal.settled_at.is_none(), PodAIMarketplaceError::DealAlreadyFinalized);
+    require!(!drawdown.sla_breached, PodAIMarketplaceError::SlaBreachAlreadyReported);
+    require!(
+        clock.unix_timestamp <= drawdown.created_at.saturating_add(SLA_BREACH_REPORT_WINDOW),
+        PodAIMarketplaceError::SlaReportWindowClosed
+    );
+    require!(
+        ctx.accounts.escrow.status == EscrowStatus::Active,
+        PodAIMarketplaceError::InvalidEscrowStatus
+    );
+
+    let credit = deal.sla_credit_for(drawdown.amount).min(ctx.accounts.escrow_vault.amount);
+
+    let work_order_key = ctx.accounts.work_order.key();
+    let escrow_seeds: &[&[u8]] = &[ESCROW_SEED, work_order_key.as_ref(), &[ctx.accounts.escrow.bump]];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.customer_token_account,
+        &ctx.accounts.escrow.to_account_info(),
+        credit,
+        &[escrow_seeds],
+    )?;
+
+    let escrow = &mut ctx.accounts.escrow;
+    escrow.amount = escrow.amount.saturating_sub(credit);
+
+    let drawdown = &mut ctx.accounts.drawdown;
+    drawdown.sla_breached = true;
+    drawdown.credit = credit;
+    drawdown.breach_reason = reason;
+
+    let deal = &mut ctx.accounts.deal;
+    deal.credited_amount = deal.credited_amount
+        .checked_add(credit)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    emit!(SlaBreachReportedEvent {
+        deal: deal.key(),
+        drawdown: drawdown.key(),
+        index: drawdown.index,
+        credit,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Settles a bulk deal at the end of its term
+/// 
+/// Callable by either party once the term has ended or all volume is
+/// consumed. Drawdowns are paid through their own work orders, so settlement
+/// only closes the deal to further drawdowns and refunds whatever is left in
+/// a prepaid vault to the customer.
+pub fn settle_bulk_deal(ctx: Context<SettleBulkDeal>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let deal = &ctx.accounts.deal;
+
+    // SECURITY: Only the deal's parties may trigger settlement
+    let caller = ctx.accounts.caller.key();
+    require!(
+        caller == deal.customer || caller == ctx.accounts.agent.owner,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    require!(deal.can_settle(clock.unix_timestamp), PodAIMarketplaceError::DealTermActive);
+
+    let refunded = ctx.accounts.deal_vault.amount;
+    if refunded > 0 {
+        let deal_id = deal.deal_id.to_le_bytes();
+        let seeds: &[&[u8]] = &[
+            BULK_DEAL_SEED,
+            deal.agent.as_ref(),
+            deal.customer.as_ref(),
+            &deal_id,
+            &[deal.bump],
+        ];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.deal_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.customer_token_account,
+            &deal.to_account_info(),
+            refunded,
+            &[seeds],
+        )?;
+    }
+
+    let deal = &mut ctx.accounts.deal;
+    deal.settled_at = Some(clock.unix_timestamp);
+    deal.is_active = false;
+
+    emit!(BulkDealSettledEvent {
+        deal: deal.key(),
+        agent: deal.agent,
+        customer: deal.customer,
+        consumed_volume: deal.consumed_volume,
+        charged_amount: deal.charged_amount,
+        credited_amount: deal.credited_amount,
+        refunded,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // Context structures
 #[derive(Accounts)]
+#[instruction(deal_id: u64)]
 pub struct CreateBulkDeal<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = customer,
         space = BulkDeal::LEN,
-        seeds = [b"bulk_deal", agent.key().as_ref(), customer.key().as_ref()],
+        seeds = [BULK_DEAL_SEED, agent.key().as_ref(), customer.key().as_ref(), &deal_id.to_le_bytes()],
         bump
     )]
     pub deal: Account<'info, BulkDeal>,
+    #[account(
+        init,
+        payer = customer,
+        seeds = [BULK_DEAL_VAULT_SEED, deal.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = deal,
+        token::token_program = token_program
+    )]
+    pub deal_vault: InterfaceAccount<'info, TokenAccount>,
     pub agent: Account<'info, Agent>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub customer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptBulkDeal<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_SEED, deal.agent.as_ref(), deal.customer.as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump
+    )]
+    pub deal: Account<'info, BulkDeal>,
+    #[account(
+        constraint = agent.key() == deal.agent @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundBulkDeal<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        seeds = [BULK_DEAL_SEED, deal.agent.as_ref(), customer.key().as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump,
+        has_one = customer @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub deal: Account<'info, BulkDeal>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_VAULT_SEED, deal.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = deal,
+        token::token_program = token_program
+    )]
+    pub deal_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == customer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub customer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct DrawBulkDeal<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_SEED, deal.agent.as_ref(), customer.key().as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump,
+        has_one = customer @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub deal: Account<'info, BulkDeal>,
+    #[account(
+        init,
+        payer = customer,
+        space = BulkDealDrawdown::LEN,
+        seeds = [BULK_DEAL_DRAWDOWN_SEED, deal.key().as_ref(), &deal.drawdown_count.to_le_bytes()],
+        bump
+    )]
+    pub drawdown: Account<'info, BulkDealDrawdown>,
+    #[account(
+        init,
+        payer = customer,
+        space = WorkOrder::LEN,
+        seeds = [WORK_ORDER_SEED, drawdown.key().as_ref()],
+        bump
+    )]
+    pub work_order: Account<'info, WorkOrder>,
+    #[account(
+        init,
+        payer = customer,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = customer,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    /// The deal's agent; its owner is the work orders' provider
+    #[account(
+        constraint = agent.key() == deal.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_VAULT_SEED, deal.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = deal,
+        token::token_program = token_program
+    )]
+    pub deal_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    /// Pays postpaid drawdowns
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == customer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub customer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportSlaBreach<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_SEED, deal.agent.as_ref(), customer.key().as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump,
+        has_one = customer @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub deal: Account<'info, BulkDeal>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_DRAWDOWN_SEED, deal.key().as_ref(), &drawdown.index.to_le_bytes()],
+        bump = drawdown.bump,
+        has_one = deal @ PodAIMarketplaceError::InvalidConfiguration,
+        has_one = work_order @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub drawdown: Account<'info, BulkDealDrawdown>,
+    pub work_order: Account<'info, WorkOrder>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = agent.key() == deal.agent @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = agent.owner == agent_owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == customer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub customer: Signer<'info>,
+    /// Acknowledges the breach on behalf of the agent
+    pub agent_owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SettleBulkDeal<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_SEED, deal.agent.as_ref(), deal.customer.as_ref(), &deal.deal_id.to_le_bytes()],
+        bump = deal.bump,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub deal: Account<'info, BulkDeal>,
+    #[account(
+        constraint = agent.key() == deal.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [BULK_DEAL_VAULT_SEED, deal.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = deal,
+        token::token_program = token_program
+    )]
+    pub deal_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == deal.customer @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub caller: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 // Events
 #[event]
 pub struct BulkDealCreatedEvent {
@@ -114,15 +695,66 @@ pub struct BulkDealCreatedEvent {
     pub total_value: u64,
 }
 
+#[event]
+pub struct BulkDealAcceptedEvent {
+    pub deal: Pubkey,
+    pub agent: Pubkey,
+    pub owner: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BulkDealFundedEvent {
+    pub deal: Pubkey,
+    pub customer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BulkDealDrawnEvent {
+    pub deal: Pubkey,
+    pub drawdown: Pubkey,
+    pub work_order: Pubkey,
+    pub index: u32,
+    pub quantity: u32,
+    pub amount: u64,
+    pub consumed_volume: u32,
+}
+
+#[event]
+pub struct SlaBreachReportedEvent {
+    pub deal: Pubkey,
+    pub drawdown: Pubkey,
+    pub index: u32,
+    pub credit: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BulkDealSettledEvent {
+    pub deal: Pubkey,
+    pub agent: Pubkey,
+    pub customer: Pubkey,
+    pub consumed_volume: u32,
+    pub charged_amount: u64,
+    pub credited_amount: u64,
+    pub refunded: u64,
+    pub timestamp: i64,
+}
+
 // Data structures
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct BulkDealData {
     pub deal_type: DealType,
     pub total_volume: u32,
     pub total_value: u64,
-    pub discount_percentage: f64,
+    /// Deal-wide discount on top of the tiers, in basis points
+    pub discount_percentage: u32,
     pub volume_tiers: Vec<VolumeTier>,
     pub sla_terms: String,
     pub contract_duration: i64,
     pub end_date: i64,
+    pub payment_mode: BulkPaymentMode,
+    /// Credit per breached drawdown, in basis points of its amount
+    pub sla_credit_bps: u16,
 }
\ No newline at end of file
@@ -7,7 +7,12 @@
  */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
+use crate::token_utils::{net_transfer_amount, transfer_tokens};
+use crate::MAX_GENERAL_STRING_LENGTH;
 
 // =====================================================
 // AUDIT TRAIL INSTRUCTIONS
@@ -20,7 +25,7 @@ pub struct InitializeAuditTrail<'info> {
     #[account(
         init,
         payer = authority,
-        space = AuditTrail::space(),
+        space = AuditTrail::base_space(),
         seeds = [b"audit_trail", authority.key().as_ref(), &trail_id.to_le_bytes()],
         bump
     )]
@@ -49,6 +54,8 @@ pub struct AddAuditEntry<'info> {
     
     #[account(mut)]
     pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -60,6 +67,42 @@ pub struct AddAuditEntryParams {
     pub signature: Option<[u8; 64]>,
 }
 
+/// Continue a full audit trail in a new account
+#[derive(Accounts)]
+#[instruction(trail_id: u64)]
+pub struct RolloverAuditTrail<'info> {
+    #[account(
+        mut,
+        constraint = previous_trail.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub previous_trail: Account<'info, AuditTrail>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = AuditTrail::base_space(),
+        seeds = [b"audit_trail", authority.key().as_ref(), &trail_id.to_le_bytes()],
+        bump
+    )]
+    pub audit_trail: Account<'info, AuditTrail>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Verify an audit trail's hash chain
+/// 
+/// `previous_trail` is required for a trail created by rollover, so the link
+/// between the two accounts is checked as well.
+#[derive(Accounts)]
+pub struct VerifyAuditTrail<'info> {
+    pub audit_trail: Account<'info, AuditTrail>,
+    
+    pub previous_trail: Option<Account<'info, AuditTrail>>,
+}
+
 /// Generate compliance report
 #[derive(Accounts)]
 #[instruction(report_id: u64)]
@@ -103,7 +146,7 @@ pub struct InitializeMultisig<'info> {
     #[account(
         init,
         payer = owner,
-        space = Multisig::space(),
+        space = Multisig::base_space(),
         seeds = [b"multisig", owner.key().as_ref(), &multisig_id.to_le_bytes()],
         bump
     )]
@@ -135,6 +178,8 @@ pub struct CreateMultisigTransaction<'info> {
     
     #[account(mut)]
     pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -160,6 +205,8 @@ pub struct SignMultisigTransaction<'info> {
     
     #[account(mut)]
     pub signer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -170,16 +217,35 @@ pub struct SignMultisigTransactionParams {
 }
 
 /// Execute a fully approved multisig transaction
+/// 
+/// Token transfers use the optional token accounts, with the multisig PDA as
+/// the source authority. Program calls are dispatched to `target` with the
+/// remaining accounts, the first of which must be the target program.
 #[derive(Accounts)]
 pub struct ExecuteMultisigTransaction<'info> {
     #[account(
         mut,
+        seeds = [b"multisig", multisig.owner.as_ref(), &multisig.multisig_id.to_le_bytes()],
+        bump,
         constraint = multisig.signers.contains(&executor.key()) @ PodAIMarketplaceError::UnauthorizedAccess
     )]
     pub multisig: Account<'info, Multisig>,
     
     #[account(mut)]
     pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = source_token_account.owner == multisig.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub source_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -187,30 +253,303 @@ pub struct ExecuteMultisigTransactionParams {
     pub transaction_id: u64,
 }
 
+/// Cancel a pending multisig transaction
+#[derive(Accounts)]
+pub struct CancelMultisigTransaction<'info> {
+    #[account(
+        mut,
+        constraint = multisig.signers.contains(&authority.key()) @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelMultisigTransactionParams {
+    pub transaction_id: u64,
+}
+
 // =====================================================
 // GOVERNANCE PROPOSAL INSTRUCTIONS
 // =====================================================
 
+/// Initialize token governance
+/// 
+/// Voters deposit the governance token into a vault owned by the config
+/// PDA. Passed proposals execute signed by a separate signer PDA, so proposal
+/// instructions can never move deposited tokens.
+#[derive(Accounts)]
+#[instruction(params: InitializeGovernanceConfigParams)]
+pub struct InitializeGovernanceConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GovernanceConfig::space_for(
+            &params.proposal_config,
+            &params.token_governance,
+            &params.council_governance,
+            &params.emergency_governance,
+        ),
+        seeds = [GOVERNANCE_CONFIG_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [GOVERNANCE_VAULT_SEED, governance_config.key().as_ref()],
+        bump,
+        token::mint = governance_token_mint,
+        token::authority = governance_config,
+        token::token_program = token_program
+    )]
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: PDA that signs executed proposal instructions; holds no data
+    #[account(
+        seeds = [GOVERNANCE_SIGNER_SEED, governance_config.key().as_ref()],
+        bump
+    )]
+    pub governance_signer: UncheckedAccount<'info>,
+    
+    #[account(
+        constraint = governance_token_mint.key() == params.token_governance.governance_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeGovernanceConfigParams {
+    pub voting_config: VotingConfig,
+    pub proposal_config: ProposalConfig,
+    pub token_governance: TokenGovernance,
+    pub council_governance: Option<CouncilGovernance>,
+    pub emergency_governance: EmergencyGovernance,
+}
+
+/// Register a voter record for a governance configuration
+#[derive(Accounts)]
+pub struct RegisterGovernanceVoter<'info> {
+    #[account(
+        init,
+        payer = voter,
+        space = VoterRecord::space(),
+        seeds = [VOTER_RECORD_SEED, governance_config.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposit governance tokens for voting power
+/// 
+/// `delegate_record` is required while the voter's power is delegated.
+#[derive(Accounts)]
+pub struct DepositGovernanceTokens<'info> {
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED, governance_config.authority.as_ref()],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, governance_config.key().as_ref(), voter.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        mut,
+        constraint = delegate_record.governance_config == governance_config.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub delegate_record: Option<Account<'info, VoterRecord>>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_VAULT_SEED, governance_config.key().as_ref()],
+        bump,
+        token::mint = governance_token_mint,
+        token::authority = governance_config,
+        token::token_program = token_program
+    )]
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = governance_token_mint.key() == governance_config.token_governance.governance_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub voter: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Withdraw unlocked governance tokens
+#[derive(Accounts)]
+pub struct WithdrawGovernanceTokens<'info> {
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED, governance_config.authority.as_ref()],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, governance_config.key().as_ref(), voter.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        mut,
+        constraint = delegate_record.governance_config == governance_config.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub delegate_record: Option<Account<'info, VoterRecord>>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_VAULT_SEED, governance_config.key().as_ref()],
+        bump,
+        token::mint = governance_token_mint,
+        token::authority = governance_config,
+        token::token_program = token_program
+    )]
+    pub governance_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = governance_token_mint.key() == governance_config.token_governance.governance_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.owner == voter.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub voter: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Delegate voting power to another voter
+/// 
+/// The delegate co-signs, so nobody can churn another voter's checkpoint
+/// history with unwanted delegations.
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(
+        constraint = governance_config.voting_config.delegation_enabled @ PodAIMarketplaceError::FeatureNotEnabled
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, governance_config.key().as_ref(), delegator.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        mut,
+        constraint = delegate_record.governance_config == governance_config.key() @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = delegate_record.key() != voter_record.key() @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = delegate_record.voter == delegate.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub delegate_record: Account<'info, VoterRecord>,
+    
+    pub delegator: Signer<'info>,
+    
+    /// The delegate, consenting to receive the delegation
+    pub delegate: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DelegateVotesParams {
+    pub scope: DelegationScope,
+    pub expires_at: Option<i64>,
+}
+
+/// Revoke a delegation
+/// 
+/// The delegator may revoke at any time; anyone may revoke an expired one.
+#[derive(Accounts)]
+pub struct UndelegateVotes<'info> {
+    #[account(
+        mut,
+        constraint = voter_record.delegate == Some(delegate_record.voter) @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        mut,
+        constraint = delegate_record.governance_config == voter_record.governance_config @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub delegate_record: Account<'info, VoterRecord>,
+    
+    pub authority: Signer<'info>,
+}
+
 /// Create a governance proposal
 #[derive(Accounts)]
-#[instruction(proposal_id: u64)]
+#[instruction(params: CreateGovernanceProposalParams)]
 pub struct CreateGovernanceProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = GovernanceProposal::space(),
-        seeds = [b"proposal", proposer.key().as_ref(), &proposal_id.to_le_bytes()],
+        space = GovernanceProposal::space_for(
+            &params.title,
+            &params.description,
+            &params.execution_params,
+            &params.metadata,
+        ),
+        seeds = [b"proposal", proposer.key().as_ref(), &params.proposal_id.to_le_bytes()],
         bump
     )]
     pub proposal: Account<'info, GovernanceProposal>,
     
     #[account(
+        mut,
         constraint = governance_config.authority == proposer.key() || 
                     governance_config.proposal_config.proposer_requirements.approved_proposers
                         .as_ref().map_or(true, |approved| approved.contains(&proposer.key())) @ PodAIMarketplaceError::UnauthorizedAccess
     )]
     pub governance_config: Account<'info, GovernanceConfig>,
     
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, governance_config.key().as_ref(), proposer.key().as_ref()],
+        bump = proposer_record.bump
+    )]
+    pub proposer_record: Account<'info, VoterRecord>,
+    
     #[account(mut)]
     pub proposer: Signer<'info>,
     
@@ -233,22 +572,59 @@ pub struct CreateGovernanceProposalParams {
 /// Cast a vote on a governance proposal
 #[derive(Accounts)]
 pub struct CastVote<'info> {
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = proposal.status == ProposalStatus::Active @ PodAIMarketplaceError::InvalidStatusTransition
+    )]
     pub proposal: Account<'info, GovernanceProposal>,
     
+    #[account(
+        init,
+        payer = voter,
+        space = VoteRecord::space(),
+        seeds = [VOTE_RECORD_SEED, proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    
+    #[account(
+        seeds = [VOTER_RECORD_SEED, proposal.governance_config.as_ref(), voter.key().as_ref()],
+        bump = voter_record.bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
     #[account(mut)]
     pub voter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CastVoteParams {
     pub choice: VoteChoice,
-    pub voting_power: u64,
     pub reasoning: Option<String>,
-    pub delegation_info: Option<DelegationInfo>,
+}
+
+/// Tally a proposal once voting has ended
+#[derive(Accounts)]
+pub struct TallyGovernanceProposal<'info> {
+    #[account(
+        mut,
+        constraint = proposal.status == ProposalStatus::Active @ PodAIMarketplaceError::InvalidStatusTransition
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        constraint = governance_config.key() == proposal.governance_config @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
 }
 
 /// Execute an approved governance proposal
+/// 
+/// Every account referenced by the proposal instructions, including the
+/// target programs, is passed in the remaining accounts.
 #[derive(Accounts)]
 pub struct ExecuteGovernanceProposal<'info> {
     #[account(
@@ -257,10 +633,41 @@ pub struct ExecuteGovernanceProposal<'info> {
     )]
     pub proposal: Account<'info, GovernanceProposal>,
     
+    #[account(
+        constraint = governance_config.key() == proposal.governance_config @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    /// CHECK: PDA that signs the proposal instructions; holds no data
+    #[account(
+        seeds = [GOVERNANCE_SIGNER_SEED, governance_config.key().as_ref()],
+        bump = governance_config.signer_bump
+    )]
+    pub governance_signer: UncheckedAccount<'info>,
+    
     #[account(mut)]
     pub executor: Signer<'info>,
 }
 
+/// Cancel a governance proposal
+#[derive(Accounts)]
+pub struct CancelGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        constraint = governance_config.key() == proposal.governance_config @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        constraint = authority.key() == proposal.proposer ||
+                    authority.key() == governance_config.authority @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub authority: Signer<'info>,
+}
+
 // =====================================================
 // ROLE-BASED ACCESS CONTROL INSTRUCTIONS
 // =====================================================
@@ -526,6 +933,14 @@ pub fn initialize_audit_trail(
     let audit_trail = &mut ctx.accounts.audit_trail;
     let clock = Clock::get()?;
     
+    // The config must fit the base allocation and allow at least one entry
+    require!(params.config.max_entries > 0, PodAIMarketplaceError::InvalidConfiguration);
+    require!(
+        params.config.approval_levels.len() <= 10 &&
+            params.config.approval_levels.iter().all(|level| level.required_approvers.len() <= 10),
+        PodAIMarketplaceError::InputTooLong
+    );
+    
     audit_trail.authority = ctx.accounts.authority.key();
     audit_trail.trail_id = params.trail_id;
     audit_trail.created_at = clock.unix_timestamp;
@@ -557,6 +972,10 @@ pub fn initialize_audit_trail(
         compliance_officers: vec![ctx.accounts.authority.key()],
     };
     audit_trail.hash_chain = Vec::new();
+    audit_trail.previous_trail = None;
+    audit_trail.next_trail = None;
+    audit_trail.genesis_hash = [0; 32];
+    audit_trail.first_entry_id = 0;
     audit_trail.reserved = [0; 128];
     
     // Emit audit trail creation event
@@ -576,35 +995,17 @@ pub fn add_audit_entry(
     let audit_trail = &mut ctx.accounts.audit_trail;
     let clock = Clock::get()?;
     
-    // Check if audit trail is at capacity
-    if audit_trail.entries.len() >= audit_trail.config.max_entries as usize {
-        return Err(PodAIMarketplaceError::InputTooLong.into());
-    }
-    
-    // Calculate entry hash
-    let entry_id = audit_trail.entries.len() as u64 + 1;
-    let previous_hash = audit_trail.hash_chain.last()
-        .copied()
-        .unwrap_or([0; 32]);
-    
-    // Create audit entry
-    let audit_entry = AuditEntry {
-        entry_id,
-        timestamp: clock.unix_timestamp,
-        action: params.action,
-        actor: ctx.accounts.authority.key(),
-        target: params.target,
-        context: params.context,
-        compliance_flags: params.compliance_flags,
-        previous_hash,
-        entry_hash: [0; 32], // Would be calculated using proper hash function
-        signature: params.signature,
-    };
-    
-    // Add entry to trail
-    audit_trail.entries.push(audit_entry);
-    audit_trail.hash_chain.push([0; 32]); // Would be actual hash
-    audit_trail.updated_at = clock.unix_timestamp;
+    // Entries are hash-chained; a full trail must be rolled over first
+    let entry_id = audit_trail.add_entry(
+        params.action,
+        ctx.accounts.authority.key(),
+        params.target,
+        params.context,
+        params.compliance_flags,
+        params.signature,
+        clock.unix_timestamp,
+    )?;
+    resize_account(audit_trail, &ctx.accounts.authority, &ctx.accounts.system_program)?;
     
     // Emit audit entry event
     emit!(AuditEntryAddedEvent {
@@ -618,6 +1019,87 @@ pub fn add_audit_entry(
     Ok(())
 }
 
+/// Seals a full audit trail and continues its hash chain in a new account
+/// 
+/// The new trail starts from the previous chain head and entry numbering,
+/// and inherits its configuration and compliance status.
+pub fn rollover_audit_trail(
+    ctx: Context<RolloverAuditTrail>,
+    trail_id: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let previous_trail = &mut ctx.accounts.previous_trail;
+    
+    require!(previous_trail.next_trail.is_none(), PodAIMarketplaceError::InvalidStatusTransition);
+    require!(previous_trail.is_full(), PodAIMarketplaceError::InvalidStatusTransition);
+    
+    let audit_trail = &mut ctx.accounts.audit_trail;
+    audit_trail.authority = ctx.accounts.authority.key();
+    audit_trail.trail_id = trail_id;
+    audit_trail.created_at = clock.unix_timestamp;
+    audit_trail.updated_at = clock.unix_timestamp;
+    audit_trail.version = previous_trail.version;
+    audit_trail.entries = Vec::new();
+    audit_trail.config = previous_trail.config.clone();
+    audit_trail.compliance_status = previous_trail.compliance_status.clone();
+    audit_trail.hash_chain = Vec::new();
+    audit_trail.previous_trail = Some(previous_trail.key());
+    audit_trail.next_trail = None;
+    audit_trail.genesis_hash = previous_trail.chain_head();
+    audit_trail.first_entry_id = previous_trail.next_entry_id();
+    audit_trail.reserved = [0; 128];
+    
+    previous_trail.next_trail = Some(audit_trail.key());
+    previous_trail.updated_at = clock.unix_timestamp;
+    
+    // Inherited compliance status may not fit the base allocation
+    resize_account(audit_trail, &ctx.accounts.authority, &ctx.accounts.system_program)?;
+    
+    emit!(AuditTrailRolledOverEvent {
+        previous_trail: previous_trail.key(),
+        audit_trail: audit_trail.key(),
+        trail_id,
+        genesis_hash: audit_trail.genesis_hash,
+        first_entry_id: audit_trail.first_entry_id,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Recomputes an audit trail's hash chain, failing with
+/// `DataCorruptionDetected` on any mismatch
+/// 
+/// Read-only and permissionless, so reviewers can verify a trail by
+/// simulating this instruction.
+pub fn verify_audit_trail(ctx: Context<VerifyAuditTrail>) -> Result<()> {
+    let audit_trail = &ctx.accounts.audit_trail;
+    
+    if let Some(previous_key) = audit_trail.previous_trail {
+        let previous_trail = ctx.accounts.previous_trail.as_ref()
+            .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+        require!(
+            previous_trail.key() == previous_key &&
+                previous_trail.next_trail == Some(audit_trail.key()) &&
+                previous_trail.chain_head() == audit_trail.genesis_hash &&
+                previous_trail.next_entry_id() == audit_trail.first_entry_id,
+            PodAIMarketplaceError::DataCorruptionDetected
+        );
+    }
+    
+    let chain_head = audit_trail.verify_chain()?;
+    
+    emit!(AuditTrailVerifiedEvent {
+        audit_trail: audit_trail.key(),
+        trail_id: audit_trail.trail_id,
+        entries_verified: audit_trail.entries.len() as u32,
+        chain_head,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 pub fn initialize_multisig(
     ctx: Context<InitializeMultisig>,
     params: InitializeMultisigParams,
@@ -630,6 +1112,12 @@ pub fn initialize_multisig(
         return Err(PodAIMarketplaceError::InvalidOffer.into());
     }
     
+    // Duplicate signers would let one key count toward the threshold twice
+    require!(params.signers.len() <= MAX_MULTISIG_SIGNERS, PodAIMarketplaceError::InputTooLong);
+    for (i, signer) in params.signers.iter().enumerate() {
+        require!(!params.signers[..i].contains(signer), PodAIMarketplaceError::InvalidConfiguration);
+    }
+    
     multisig.multisig_id = params.multisig_id;
     multisig.threshold = params.threshold;
     multisig.signers = params.signers;
@@ -661,30 +1149,82 @@ pub fn create_multisig_transaction(
     let multisig = &mut ctx.accounts.multisig;
     let clock = Clock::get()?;
     
+    // Free queue space held by finished and expired transactions
+    multisig.prune_transactions(clock.unix_timestamp);
+    
     // Check if transaction limit reached
-    if multisig.pending_transactions.len() >= 100 {
+    if multisig.pending_transactions.len() >= MAX_PENDING_TRANSACTIONS {
         return Err(PodAIMarketplaceError::InputTooLong.into());
     }
     
+    require!(
+        !multisig.pending_transactions.iter().any(|tx| tx.transaction_id == params.transaction_id),
+        PodAIMarketplaceError::AccountAlreadyInitialized
+    );
+    require!(multisig.allows(params.transaction_type), PodAIMarketplaceError::OperationNotSupported);
+    require!(params.expires_at > clock.unix_timestamp, PodAIMarketplaceError::InvalidDeadline);
+    require!(params.data.len() <= MAX_MULTISIG_TRANSACTION_DATA, PodAIMarketplaceError::InputTooLong);
+    require!(
+        params.execution_conditions.len() <= MAX_EXECUTION_CONDITIONS,
+        PodAIMarketplaceError::InputTooLong
+    );
+    
+    // Only conditions the program can check itself are accepted; anything
+    // else would need a trusted party to flip `met`
+    let early_unlock_conditions = params.time_lock
+        .as_ref()
+        .map(|time_lock| time_lock.early_unlock_conditions.as_slice())
+        .unwrap_or_default();
+    for condition in params.execution_conditions.iter().chain(early_unlock_conditions) {
+        require!(condition.condition_type.is_on_chain(), PodAIMarketplaceError::OperationNotSupported);
+    }
+    
+    // The lock starts once the threshold is reached, not when proposed
+    let time_lock = match params.time_lock {
+        Some(time_lock) => {
+            require!(time_lock.duration >= 0, PodAIMarketplaceError::InvalidDeadline);
+            Some(TimeLock {
+                locked_at: 0,
+                unlocks_at: 0,
+                ..time_lock
+            })
+        }
+        None => None,
+    };
+    
     // Create pending transaction
-    let pending_transaction = PendingTransaction {
+    let mut pending_transaction = PendingTransaction {
         transaction_id: params.transaction_id,
         transaction_type: params.transaction_type,
         target: params.target,
         data: params.data,
+        proposer: ctx.accounts.creator.key(),
         required_signatures: multisig.threshold,
         signatures: Vec::new(),
+        cancel_votes: Vec::new(),
         created_at: clock.unix_timestamp,
         expires_at: params.expires_at,
         priority: params.priority,
         execution_conditions: params.execution_conditions,
         status: TransactionStatus::Pending,
-        time_lock: params.time_lock,
+        time_lock,
     };
+    for condition in pending_transaction.execution_conditions.iter_mut() {
+        condition.current_value = 0;
+        condition.met = false;
+    }
+    if let Some(time_lock) = pending_transaction.time_lock.as_mut() {
+        for condition in time_lock.early_unlock_conditions.iter_mut() {
+            condition.current_value = 0;
+            condition.met = false;
+        }
+    }
     
     multisig.pending_transactions.push(pending_transaction);
     multisig.updated_at = clock.unix_timestamp;
     
+    resize_account(multisig, &ctx.accounts.creator, &ctx.accounts.system_program)?;
+    
     // Emit transaction creation event
     emit!(MultisigTransactionCreatedEvent {
         multisig_id: multisig.multisig_id,
@@ -697,6 +1237,898 @@ pub fn create_multisig_transaction(
     Ok(())
 }
 
+/// Adds the caller's signature to a pending multisig transaction
+/// 
+/// The transaction signature of `signer` is the approval; `params.signature`
+/// is recorded for off-chain reference. Reaching the threshold marks the
+/// transaction `FullyApproved` and starts its time lock.
+pub fn sign_multisig_transaction(
+    ctx: Context<SignMultisigTransaction>,
+    params: SignMultisigTransactionParams,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let clock = Clock::get()?;
+    let signer = ctx.accounts.signer.key();
+    
+    multisig.prune_transactions(clock.unix_timestamp);
+    
+    let signers = multisig.signers.clone();
+    let threshold = multisig.threshold;
+    let transaction = multisig.find_transaction_mut(params.transaction_id)?;
+    
+    require!(
+        matches!(transaction.status, TransactionStatus::Pending | TransactionStatus::PartiallyApproved),
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+    require!(!transaction.has_signed(&signer), PodAIMarketplaceError::AlreadySigned);
+    if let Some(verification_data) = &params.verification_data {
+        require!(verification_data.len() <= 256, PodAIMarketplaceError::InputTooLong);
+    }
+    
+    transaction.signatures.push(MultisigSignature {
+        signer,
+        signature: params.signature,
+        signed_at: clock.unix_timestamp,
+        signature_method: "transaction_signer".to_string(),
+        verification_data: params.verification_data,
+    });
+    
+    // Threshold changes after creation apply to transactions already queued
+    let required = threshold.max(transaction.required_signatures) as usize;
+    let signature_count = transaction.valid_signature_count(&signers);
+    if signature_count >= required {
+        transaction.status = TransactionStatus::FullyApproved;
+        transaction.start_time_lock(clock.unix_timestamp);
+    } else {
+        transaction.status = TransactionStatus::PartiallyApproved;
+    }
+    let status = transaction.status;
+    
+    multisig.updated_at = clock.unix_timestamp;
+    resize_account(multisig, &ctx.accounts.signer, &ctx.accounts.system_program)?;
+    
+    emit!(MultisigTransactionSignedEvent {
+        multisig_id: multisig.multisig_id,
+        transaction_id: params.transaction_id,
+        signer,
+        signature_count: signature_count as u8,
+        status,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Executes a fully approved multisig transaction
+/// 
+/// Requires the threshold of current signers, an elapsed time lock and all
+/// execution conditions. Dispatch by `TransactionType`:
+/// 
+/// - `Transfer`, `Withdrawal`, `EscrowRelease`: token transfer of the
+///   little-endian `u64` in `data` from the multisig-owned source account to
+///   `target`
+/// - `SignerAddition`, `SignerRemoval`: `data` is the signer's public key
+/// - `ThresholdUpdate`: `data` is the new threshold byte
+/// - `ConfigUpdate`: `data` is a serialized `MultisigConfig`
+/// - `EmergencyFreeze`, `EmergencyUnfreeze`: toggle the freeze
+/// - Everything else: CPI into the `target` program with `data`, signed by
+///   the multisig PDA
+pub fn execute_multisig_transaction<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteMultisigTransaction<'info>>,
+    params: ExecuteMultisigTransactionParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let multisig_key = ctx.accounts.multisig.key();
+    let multisig_bump = ctx.bumps.multisig;
+    let token_balance = ctx.accounts.source_token_account.as_ref().map(|account| account.amount);
+    let multisig = &mut ctx.accounts.multisig;
+    
+    let frozen = multisig.is_frozen(clock.unix_timestamp);
+    let signers = multisig.signers.clone();
+    let threshold = multisig.threshold;
+    let transaction = multisig.find_transaction_mut(params.transaction_id)?;
+    transaction.authorize_execution(clock.unix_timestamp, &signers, threshold, token_balance, frozen)?;
+    
+    let transaction_type = transaction.transaction_type;
+    let target = transaction.target;
+    let data = transaction.data.clone();
+    
+    multisig.nonce = multisig.nonce.saturating_add(1);
+    multisig.updated_at = clock.unix_timestamp;
+    
+    match transaction_type {
+        _ if transaction_type.is_token_transfer() => {
+            let amount = PendingTransaction::transfer_amount(&data)?;
+            let source = ctx.accounts.source_token_account.as_ref()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            let destination = ctx.accounts.destination_token_account.as_ref()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            let mint = ctx.accounts.token_mint.as_ref()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            let token_program = ctx.accounts.token_program.as_ref()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            require!(destination.key() == target, PodAIMarketplaceError::InvalidConfiguration);
+            
+            let owner_key = multisig.owner;
+            let id_bytes = multisig.multisig_id.to_le_bytes();
+            let multisig_seeds: &[&[u8]] = &[b"multisig", owner_key.as_ref(), &id_bytes, &[multisig_bump]];
+            transfer_tokens(
+                token_program,
+                source,
+                mint,
+                destination,
+                &multisig.to_account_info(),
+                amount,
+                &[multisig_seeds],
+            )?;
+        }
+        _ if transaction_type.targets_multisig() => {
+            multisig.apply_transaction(transaction_type, &data, clock.unix_timestamp)?;
+        }
+        _ => {
+            // SECURITY: Persist the Executed status before handing control to
+            // another program, so a re-entrant call cannot execute it twice
+            multisig.exit(&crate::ID)?;
+            
+            let (program, accounts) = ctx.remaining_accounts
+                .split_first()
+                .ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+            require!(program.key() == target && program.executable, PodAIMarketplaceError::InvalidConfiguration);
+            
+            let instruction = Instruction {
+                program_id: target,
+                accounts: accounts
+                    .iter()
+                    .map(|account| AccountMeta {
+                        pubkey: account.key(),
+                        is_signer: account.is_signer || account.key() == multisig_key,
+                        is_writable: account.is_writable,
+                    })
+                    .collect(),
+                data,
+            };
+            
+            let owner_key = multisig.owner;
+            let id_bytes = multisig.multisig_id.to_le_bytes();
+            let multisig_seeds: &[&[u8]] = &[b"multisig", owner_key.as_ref(), &id_bytes, &[multisig_bump]];
+            let mut account_infos = accounts.to_vec();
+            account_infos.push(multisig.to_account_info());
+            invoke_signed(&instruction, &account_infos, &[multisig_seeds])?;
+        }
+    }
+    
+    emit!(MultisigTransactionExecutedEvent {
+        multisig_id: multisig.multisig_id,
+        transaction_id: params.transaction_id,
+        executor: ctx.accounts.executor.key(),
+        transaction_type,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Cancels a queued multisig transaction
+/// 
+/// Before it reaches the threshold, the proposer may withdraw a transaction.
+/// Once approved, each signer's call is a vote to cancel, and the transaction
+/// is cancelled when the votes reach the approval threshold. A transaction with
+/// a non-cancellable time lock can no longer be cancelled once approved.
+pub fn cancel_multisig_transaction(
+    ctx: Context<CancelMultisigTransaction>,
+    params: CancelMultisigTransactionParams,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let clock = Clock::get()?;
+    let authority = ctx.accounts.authority.key();
+    
+    let signers = multisig.signers.clone();
+    let threshold = multisig.threshold;
+    let transaction = multisig.find_transaction_mut(params.transaction_id)?;
+    
+    let cancelled = match transaction.status {
+        TransactionStatus::Pending | TransactionStatus::PartiallyApproved => {
+            // SECURITY: Only the proposer can withdraw a transaction before approval
+            require!(authority == transaction.proposer, PodAIMarketplaceError::UnauthorizedAccess);
+            true
+        }
+        TransactionStatus::FullyApproved => {
+            let locked_in = transaction.time_lock
+                .as_ref()
+                .is_some_and(|time_lock| !time_lock.cancellable);
+            require!(!locked_in, PodAIMarketplaceError::ResourceLocked);
+            // SECURITY: Overturning an approval takes the same threshold
+            transaction.vote_to_cancel(authority, &signers, threshold)?
+        }
+        _ => return Err(PodAIMarketplaceError::InvalidStatusTransition.into()),
+    };
+    let cancel_votes = transaction.cancel_votes.len() as u8;
+    
+    if cancelled {
+        transaction.status = TransactionStatus::Cancelled;
+        multisig.prune_transactions(clock.unix_timestamp);
+    }
+    multisig.updated_at = clock.unix_timestamp;
+    resize_account(multisig, &ctx.accounts.authority, &ctx.accounts.system_program)?;
+    
+    emit!(MultisigTransactionCancelledEvent {
+        multisig_id: multisig.multisig_id,
+        transaction_id: params.transaction_id,
+        cancelled_by: authority,
+        cancel_votes,
+        cancelled,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Grows a variable-size account to fit its serialized state, topping up
+/// rent from `payer`. Callers grow it by at most one entry per instruction,
+/// which stays within the per-instruction realloc limit.
+fn resize_account<'info, T>(
+    account: &Account<'info, T>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize + AnchorSerialize + Owner + Clone,
+{
+    let mut data = Vec::new();
+    account.serialize(&mut data)?;
+    let required_len = 8 + data.len();
+    
+    let account_info = account.to_account_info();
+    if required_len <= account_info.data_len() {
+        return Ok(());
+    }
+    
+    let required_lamports = Rent::get()?.minimum_balance(required_len);
+    let shortfall = required_lamports.saturating_sub(account_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    
+    account_info.resize(required_len)?;
+    Ok(())
+}
+
+/// Sets up token governance with an empty vault
+/// 
+/// Voting power is snapshotted at proposal creation, so only the
+/// `ProposalCreation` snapshot strategy and public votes are supported.
+pub fn initialize_governance_config(
+    ctx: Context<InitializeGovernanceConfig>,
+    params: InitializeGovernanceConfigParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let voting_config = &params.voting_config;
+    
+    require!(
+        voting_config.snapshot_strategy == SnapshotStrategy::ProposalCreation &&
+            voting_config.vote_privacy == VotePrivacy::Public &&
+            params.token_governance.voting_power_method != VotingPowerMethod::Custom,
+        PodAIMarketplaceError::OperationNotSupported
+    );
+    require!(
+        voting_config.voting_period > 0 && voting_config.voting_delay >= 0,
+        PodAIMarketplaceError::InvalidDuration
+    );
+    require!(
+        voting_config.default_quorum_threshold <= 100 && voting_config.default_approval_threshold <= 100,
+        PodAIMarketplaceError::InvalidPercentage
+    );
+    require!(
+        params.token_governance.lockup_requirements.minimum_lockup_period >= 0,
+        PodAIMarketplaceError::InvalidDuration
+    );
+    
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.authority = ctx.accounts.authority.key();
+    governance_config.version = 1;
+    governance_config.created_at = clock.unix_timestamp;
+    governance_config.updated_at = clock.unix_timestamp;
+    governance_config.voting_config = params.voting_config;
+    governance_config.proposal_config = params.proposal_config;
+    governance_config.token_governance = params.token_governance;
+    governance_config.council_governance = params.council_governance;
+    governance_config.emergency_governance = params.emergency_governance;
+    governance_config.total_voting_power = 0;
+    governance_config.active_proposals = 0;
+    governance_config.bump = ctx.bumps.governance_config;
+    governance_config.signer_bump = ctx.bumps.governance_signer;
+    governance_config.reserved = [0; 128];
+    
+    emit!(GovernanceInitializedEvent {
+        governance_config: governance_config.key(),
+        authority: governance_config.authority,
+        governance_token: governance_config.token_governance.governance_token,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+pub fn register_governance_voter(ctx: Context<RegisterGovernanceVoter>) -> Result<()> {
+    let voter_record = &mut ctx.accounts.voter_record;
+    
+    voter_record.governance_config = ctx.accounts.governance_config.key();
+    voter_record.voter = ctx.accounts.voter.key();
+    voter_record.deposited_amount = 0;
+    voter_record.own_voting_power = 0;
+    voter_record.delegated_power = 0;
+    voter_record.lockup_expires_at = 0;
+    voter_record.delegate = None;
+    voter_record.delegation = None;
+    voter_record.last_proposal_at = 0;
+    voter_record.checkpoints = Vec::new();
+    voter_record.bump = ctx.bumps.voter_record;
+    
+    Ok(())
+}
+
+/// Deposits governance tokens, optionally extending the lockup
+/// 
+/// Voting power is recomputed from the whole deposit and the remaining
+/// lockup, so time-weighted multipliers reflect the lockup chosen at the
+/// latest deposit.
+pub fn deposit_governance_tokens(
+    ctx: Context<DepositGovernanceTokens>,
+    amount: u64,
+    lockup_duration: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.governance_config;
+    let lockup_requirements = &config.token_governance.lockup_requirements;
+    
+    require!(amount > 0, PodAIMarketplaceError::InvalidAmount);
+    require!(
+        lockup_duration >= lockup_requirements.minimum_lockup_period,
+        PodAIMarketplaceError::InvalidDuration
+    );
+    
+    let voter_record = &ctx.accounts.voter_record;
+    let lockup_expires_at = voter_record.lockup_expires_at
+        .max(clock.unix_timestamp.saturating_add(lockup_duration));
+    
+    // An active lockup can only be pushed out when extensions are allowed
+    require!(
+        lockup_requirements.extensions_allowed ||
+            voter_record.lockup_expires_at <= clock.unix_timestamp ||
+            lockup_expires_at == voter_record.lockup_expires_at,
+        PodAIMarketplaceError::ResourceLocked
+    );
+    
+    // Credit what the vault actually receives for Token-2022 fee mints
+    let received_amount = net_transfer_amount(&ctx.accounts.governance_token_mint, amount)?;
+    require!(received_amount > 0, PodAIMarketplaceError::InvalidAmount);
+    
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.voter_token_account,
+        &ctx.accounts.governance_token_mint,
+        &ctx.accounts.governance_vault,
+        &ctx.accounts.voter.to_account_info(),
+        amount,
+        &[],
+    )?;
+    
+    let deposited_amount = voter_record.deposited_amount
+        .checked_add(received_amount)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    let voting_power = config.token_governance.voting_power_for(
+        deposited_amount,
+        lockup_expires_at.saturating_sub(clock.unix_timestamp),
+    )?;
+    
+    let voter_record = &mut ctx.accounts.voter_record;
+    voter_record.deposited_amount = deposited_amount;
+    voter_record.lockup_expires_at = lockup_expires_at;
+    apply_own_voting_power(
+        &mut ctx.accounts.governance_config,
+        voter_record,
+        ctx.accounts.delegate_record.as_mut(),
+        voting_power,
+        clock.unix_timestamp,
+    )?;
+    
+    emit!(GovernanceTokensDepositedEvent {
+        governance_config: ctx.accounts.governance_config.key(),
+        voter: ctx.accounts.voter.key(),
+        amount: received_amount,
+        voting_power,
+        lockup_expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Withdraws governance tokens once the lockup has expired
+/// 
+/// Votes already cast keep their snapshot weight.
+pub fn withdraw_governance_tokens(
+    ctx: Context<WithdrawGovernanceTokens>,
+    amount: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let voter_record = &ctx.accounts.voter_record;
+    
+    require!(amount > 0, PodAIMarketplaceError::InvalidAmount);
+    require!(
+        clock.unix_timestamp >= voter_record.lockup_expires_at,
+        PodAIMarketplaceError::ResourceLocked
+    );
+    let deposited_amount = voter_record.deposited_amount
+        .checked_sub(amount)
+        .ok_or(PodAIMarketplaceError::InsufficientBalance)?;
+    let voting_power = ctx.accounts.governance_config.token_governance.voting_power_for(deposited_amount, 0)?;
+    
+    let config = &ctx.accounts.governance_config;
+    let authority_key = config.authority;
+    let config_seeds: &[&[u8]] = &[GOVERNANCE_CONFIG_SEED, authority_key.as_ref(), &[config.bump]];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.governance_vault,
+        &ctx.accounts.governance_token_mint,
+        &ctx.accounts.voter_token_account,
+        &config.to_account_info(),
+        amount,
+        &[config_seeds],
+    )?;
+    
+    let voter_record = &mut ctx.accounts.voter_record;
+    voter_record.deposited_amount = deposited_amount;
+    apply_own_voting_power(
+        &mut ctx.accounts.governance_config,
+        voter_record,
+        ctx.accounts.delegate_record.as_mut(),
+        voting_power,
+        clock.unix_timestamp,
+    )?;
+    
+    emit!(GovernanceTokensWithdrawnEvent {
+        governance_config: ctx.accounts.governance_config.key(),
+        voter: ctx.accounts.voter.key(),
+        amount,
+        voting_power,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Delegates the voter's own voting power to another registered voter
+/// 
+/// Delegation is not transitive: the delegate votes with its own power plus
+/// power delegated directly to it. Only `DelegationScope::All` is supported,
+/// since power moves between checkpoints rather than per proposal. The
+/// delegate must co-sign: every delegation adds a checkpoint to its history,
+/// and the oldest checkpoints are dropped once the history is full.
+pub fn delegate_votes(
+    ctx: Context<DelegateVotes>,
+    params: DelegateVotesParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let voter_record = &mut ctx.accounts.voter_record;
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    
+    require!(params.scope == DelegationScope::All, PodAIMarketplaceError::OperationNotSupported);
+    require!(voter_record.delegate.is_none(), PodAIMarketplaceError::InvalidStatusTransition);
+    if let Some(expires_at) = params.expires_at {
+        require!(expires_at > clock.unix_timestamp, PodAIMarketplaceError::InvalidDeadline);
+    }
+    
+    delegate_record.delegated_power = delegate_record.delegated_power
+        .checked_add(voter_record.own_voting_power)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    voter_record.delegate = Some(delegate_record.voter);
+    voter_record.delegation = Some(DelegationInfo {
+        delegator: voter_record.voter,
+        delegated_at: clock.unix_timestamp,
+        scope: params.scope,
+        expires_at: params.expires_at,
+    });
+    
+    voter_record.record_checkpoint(clock.unix_timestamp);
+    delegate_record.record_checkpoint(clock.unix_timestamp);
+    
+    emit!(VotesDelegatedEvent {
+        governance_config: voter_record.governance_config,
+        delegator: voter_record.voter,
+        delegate: delegate_record.voter,
+        voting_power: voter_record.own_voting_power,
+        expires_at: params.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Returns delegated voting power to the delegator
+pub fn undelegate_votes(ctx: Context<UndelegateVotes>) -> Result<()> {
+    let clock = Clock::get()?;
+    let voter_record = &mut ctx.accounts.voter_record;
+    let delegate_record = &mut ctx.accounts.delegate_record;
+    
+    // SECURITY: Only the delegator may revoke before expiry
+    require!(
+        ctx.accounts.authority.key() == voter_record.voter || voter_record.delegation_expired(clock.unix_timestamp),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    
+    delegate_record.delegated_power = delegate_record.delegated_power
+        .checked_sub(voter_record.own_voting_power)
+        .ok_or(PodAIMarketplaceError::ArithmeticUnderflow)?;
+    voter_record.delegate = None;
+    voter_record.delegation = None;
+    
+    voter_record.record_checkpoint(clock.unix_timestamp);
+    delegate_record.record_checkpoint(clock.unix_timestamp);
+    
+    emit!(VotesUndelegatedEvent {
+        governance_config: voter_record.governance_config,
+        delegator: voter_record.voter,
+        delegate: delegate_record.voter,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Creates a proposal and snapshots voting power
+/// 
+/// The proposer must meet the configured token and cooldown requirements.
+/// Quorum and approval thresholds may be raised above the configured
+/// defaults but never lowered.
+pub fn create_governance_proposal(
+    ctx: Context<CreateGovernanceProposal>,
+    params: CreateGovernanceProposalParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &ctx.accounts.governance_config;
+    let proposer_record = &ctx.accounts.proposer_record;
+    let requirements = &config.proposal_config.proposer_requirements;
+    let voting_config = &config.voting_config;
+    
+    // SECURITY: Proposer eligibility
+    require!(
+        !requirements.banned_proposers.contains(&ctx.accounts.proposer.key()),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    require!(
+        proposer_record.deposited_amount >= requirements.minimum_token_balance,
+        PodAIMarketplaceError::InsufficientVotingPower
+    );
+    require!(
+        proposer_record.last_proposal_at == 0 ||
+            clock.unix_timestamp >= proposer_record.last_proposal_at.saturating_add(config.proposal_config.proposal_cooldown),
+        PodAIMarketplaceError::RateLimitExceeded
+    );
+    require!(
+        config.proposal_config.max_active_proposals == 0 ||
+            config.active_proposals < config.proposal_config.max_active_proposals,
+        PodAIMarketplaceError::RateLimitExceeded
+    );
+    
+    // Content bounds
+    require!(
+        !params.title.is_empty() && params.title.len() <= MAX_GENERAL_STRING_LENGTH,
+        PodAIMarketplaceError::InputTooLong
+    );
+    require!(params.description.len() <= 2048, PodAIMarketplaceError::InputTooLong);
+    let execution_params = &params.execution_params;
+    require!(
+        execution_params.instructions.len() <= MAX_PROPOSAL_INSTRUCTIONS &&
+            execution_params.instructions.iter().all(|ix| {
+                ix.accounts.len() <= MAX_PROPOSAL_INSTRUCTION_ACCOUNTS && ix.data.len() <= MAX_PROPOSAL_INSTRUCTION_DATA
+            }),
+        PodAIMarketplaceError::InputTooLong
+    );
+    require!(
+        execution_params.execution_conditions.len() <= MAX_EXECUTION_CONDITIONS,
+        PodAIMarketplaceError::InputTooLong
+    );
+    require!(
+        execution_params.execution_conditions.iter().all(|condition| {
+            matches!(condition.condition_type, ConditionType::TimeDelay | ConditionType::VoteCount)
+        }),
+        PodAIMarketplaceError::OperationNotSupported
+    );
+    require!(execution_params.execution_delay >= 0, PodAIMarketplaceError::InvalidDuration);
+    
+    // Voting window
+    require!(
+        params.voting_starts_at >= clock.unix_timestamp.saturating_add(voting_config.voting_delay) &&
+            params.voting_ends_at >= params.voting_starts_at.saturating_add(voting_config.voting_period),
+        PodAIMarketplaceError::InvalidDuration
+    );
+    
+    // SECURITY: Proposals cannot lower the configured quorum or approval bar
+    let quorum = &params.quorum_requirements;
+    require!(
+        quorum.minimum_participation <= 100 && quorum.approval_threshold <= 100,
+        PodAIMarketplaceError::InvalidPercentage
+    );
+    require!(
+        quorum.minimum_participation >= voting_config.default_quorum_threshold &&
+            quorum.approval_threshold >= voting_config.default_approval_threshold,
+        PodAIMarketplaceError::GovernanceProposalInvalid
+    );
+    
+    let weighted_voting = voting_config.weighted_voting_enabled;
+    let total_voting_power = config.total_voting_power;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.proposal_id = params.proposal_id;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.governance_config = ctx.accounts.governance_config.key();
+    proposal.title = params.title;
+    proposal.description = params.description;
+    proposal.proposal_type = params.proposal_type;
+    proposal.created_at = clock.unix_timestamp;
+    proposal.voting_starts_at = params.voting_starts_at;
+    proposal.voting_ends_at = params.voting_ends_at;
+    // Checkpoints written later in this same second are excluded
+    proposal.snapshot_at = clock.unix_timestamp - 1;
+    proposal.execution_timestamp = None;
+    proposal.status = ProposalStatus::Active;
+    proposal.voting_results = VotingResults {
+        votes_for: 0,
+        votes_against: 0,
+        votes_abstain: 0,
+        total_voting_power,
+        participation_rate: 0,
+        weighted_voting,
+        quorum_reached: false,
+        approval_threshold_met: false,
+    };
+    proposal.execution_params = params.execution_params;
+    for condition in proposal.execution_params.execution_conditions.iter_mut() {
+        condition.current_value = 0;
+        condition.met = false;
+    }
+    proposal.quorum_requirements = params.quorum_requirements;
+    proposal.metadata = params.metadata;
+    proposal.bump = ctx.bumps.proposal;
+    proposal.reserved = [0; 64];
+    
+    ctx.accounts.proposer_record.last_proposal_at = clock.unix_timestamp;
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.active_proposals = governance_config.active_proposals.saturating_add(1);
+    
+    emit!(GovernanceProposalCreatedEvent {
+        proposal: proposal.key(),
+        proposal_id: proposal.proposal_id,
+        proposer: proposal.proposer,
+        proposal_type: proposal.proposal_type,
+        voting_starts_at: proposal.voting_starts_at,
+        voting_ends_at: proposal.voting_ends_at,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Casts a vote weighted by the voter's power at the proposal snapshot
+pub fn cast_vote(
+    ctx: Context<CastVote>,
+    params: CastVoteParams,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(clock.unix_timestamp >= proposal.voting_starts_at, PodAIMarketplaceError::VotingNotStarted);
+    require!(clock.unix_timestamp < proposal.voting_ends_at, PodAIMarketplaceError::VotingPeriodEnded);
+    if let Some(reasoning) = &params.reasoning {
+        require!(reasoning.len() <= 512, PodAIMarketplaceError::InputTooLong);
+    }
+    
+    // SECURITY: Power comes from the snapshot, so tokens deposited or
+    // delegated after the proposal was created cannot vote on it
+    let voting_power = ctx.accounts.voter_record.voting_power_at(proposal.snapshot_at);
+    require!(voting_power > 0, PodAIMarketplaceError::InsufficientVotingPower);
+    
+    proposal.voting_results.record_vote(params.choice, voting_power)?;
+    
+    let vote_record = &mut ctx.accounts.vote_record;
+    vote_record.proposal = proposal.key();
+    vote_record.vote = Vote {
+        voter: ctx.accounts.voter.key(),
+        choice: params.choice,
+        voting_power,
+        voted_at: clock.unix_timestamp,
+        reasoning: params.reasoning,
+        delegation_info: None,
+    };
+    vote_record.bump = ctx.bumps.vote_record;
+    
+    emit!(VoteCastEvent {
+        proposal: proposal.key(),
+        voter: ctx.accounts.voter.key(),
+        choice: params.choice,
+        voting_power,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Closes voting and records whether the proposal passed
+/// 
+/// Permissionless once the voting period has ended.
+pub fn tally_governance_proposal(ctx: Context<TallyGovernanceProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(clock.unix_timestamp >= proposal.voting_ends_at, PodAIMarketplaceError::VotingStillActive);
+    
+    let passed = proposal.tally();
+    
+    let governance_config = &mut ctx.accounts.governance_config;
+    governance_config.active_proposals = governance_config.active_proposals.saturating_sub(1);
+    
+    emit!(GovernanceProposalTalliedEvent {
+        proposal: proposal.key(),
+        passed,
+        votes_for: proposal.voting_results.votes_for,
+        votes_against: proposal.voting_results.votes_against,
+        votes_abstain: proposal.voting_results.votes_abstain,
+        participation_rate: proposal.voting_results.participation_rate,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Executes the instructions of a passed proposal
+/// 
+/// Runs after the execution delay once every execution condition holds.
+/// Only the proposer or the execution authority may execute unless
+/// `auto_execute` is set. Instructions are signed by the governance signer
+/// PDA; any other signer must have signed the transaction.
+pub fn execute_governance_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteGovernanceProposal<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let executor = ctx.accounts.executor.key();
+    let signer_key = ctx.accounts.governance_signer.key();
+    let config_key = ctx.accounts.governance_config.key();
+    let signer_bump = ctx.accounts.governance_config.signer_bump;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(
+        proposal.execution_params.auto_execute ||
+            executor == proposal.proposer ||
+            executor == proposal.execution_params.execution_authority,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    require!(
+        proposal.execution_timestamp.is_some_and(|timestamp| clock.unix_timestamp >= timestamp),
+        PodAIMarketplaceError::TimeLockActive
+    );
+    require!(proposal.evaluate_conditions(clock.unix_timestamp), PodAIMarketplaceError::ExecutionConditionNotMet);
+    
+    // SECURITY: Persist the Executed status before handing control to other
+    // programs, so a re-entrant call cannot execute the proposal twice
+    proposal.status = ProposalStatus::Executed;
+    proposal.exit(&crate::ID)?;
+    
+    let signer_seeds: &[&[u8]] = &[GOVERNANCE_SIGNER_SEED, config_key.as_ref(), &[signer_bump]];
+    for proposal_instruction in &proposal.execution_params.instructions {
+        let find_account = |pubkey: &Pubkey| -> Result<AccountInfo<'info>> {
+            if *pubkey == signer_key {
+                return Ok(ctx.accounts.governance_signer.to_account_info());
+            }
+            ctx.remaining_accounts
+                .iter()
+                .find(|account| account.key == pubkey)
+                .cloned()
+                .ok_or_else(|| error!(PodAIMarketplaceError::AccountNotInitialized))
+        };
+        
+        let program = find_account(&proposal_instruction.program_id)?;
+        require!(program.executable, PodAIMarketplaceError::InvalidConfiguration);
+        
+        let mut account_infos = Vec::with_capacity(proposal_instruction.accounts.len() + 1);
+        let mut account_metas = Vec::with_capacity(proposal_instruction.accounts.len());
+        for account in &proposal_instruction.accounts {
+            account_infos.push(find_account(&account.pubkey)?);
+            account_metas.push(AccountMeta {
+                pubkey: account.pubkey,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            });
+        }
+        account_infos.push(program);
+        
+        let instruction = Instruction {
+            program_id: proposal_instruction.program_id,
+            accounts: account_metas,
+            data: proposal_instruction.data.clone(),
+        };
+        invoke_signed(&instruction, &account_infos, &[signer_seeds])?;
+    }
+    
+    emit!(GovernanceProposalExecutedEvent {
+        proposal: proposal.key(),
+        executor,
+        instruction_count: proposal.execution_params.instructions.len() as u8,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Cancels a proposal
+/// 
+/// The proposer or the governance authority may cancel while voting is open,
+/// or after the proposal passed if it was created as cancellable.
+pub fn cancel_governance_proposal(ctx: Context<CancelGovernanceProposal>) -> Result<()> {
+    let clock = Clock::get()?;
+    let proposal = &mut ctx.accounts.proposal;
+    
+    match proposal.status {
+        ProposalStatus::Active => {
+            require!(clock.unix_timestamp < proposal.voting_ends_at, PodAIMarketplaceError::VotingPeriodEnded);
+            let governance_config = &mut ctx.accounts.governance_config;
+            governance_config.active_proposals = governance_config.active_proposals.saturating_sub(1);
+        }
+        ProposalStatus::Passed => {
+            require!(proposal.execution_params.cancellable, PodAIMarketplaceError::ResourceLocked);
+        }
+        _ => return Err(PodAIMarketplaceError::InvalidStatusTransition.into()),
+    }
+    proposal.status = ProposalStatus::Cancelled;
+    
+    emit!(GovernanceProposalCancelledEvent {
+        proposal: proposal.key(),
+        cancelled_by: ctx.accounts.authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Moves a voter's own power to `voting_power`, updating whichever record
+/// currently holds it and the governance total
+fn apply_own_voting_power(
+    governance_config: &mut GovernanceConfig,
+    voter_record: &mut VoterRecord,
+    delegate_record: Option<&mut Account<VoterRecord>>,
+    voting_power: u64,
+    now: i64,
+) -> Result<()> {
+    let previous_power = voter_record.own_voting_power;
+    voter_record.own_voting_power = voting_power;
+    
+    if let Some(delegate) = voter_record.delegate {
+        let delegate_record = delegate_record.ok_or(PodAIMarketplaceError::AccountNotInitialized)?;
+        require!(delegate_record.voter == delegate, PodAIMarketplaceError::InvalidConfiguration);
+        delegate_record.delegated_power = delegate_record.delegated_power
+            .checked_sub(previous_power)
+            .and_then(|power| power.checked_add(voting_power))
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        delegate_record.record_checkpoint(now);
+    }
+    voter_record.record_checkpoint(now);
+    
+    governance_config.total_voting_power = governance_config.total_voting_power
+        .checked_sub(previous_power)
+        .and_then(|power| power.checked_add(voting_power))
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    Ok(())
+}
+
 pub fn initialize_rbac(
     ctx: Context<InitializeRbac>,
     params: InitializeRbacParams,
@@ -783,6 +2215,25 @@ pub struct AuditEntryAddedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AuditTrailRolledOverEvent {
+    pub previous_trail: Pubkey,
+    pub audit_trail: Pubkey,
+    pub trail_id: u64,
+    pub genesis_hash: [u8; 32],
+    pub first_entry_id: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AuditTrailVerifiedEvent {
+    pub audit_trail: Pubkey,
+    pub trail_id: u64,
+    pub entries_verified: u32,
+    pub chain_head: [u8; 32],
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MultisigCreatedEvent {
     pub multisig_id: u64,
@@ -801,6 +2252,128 @@ pub struct MultisigTransactionCreatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MultisigTransactionSignedEvent {
+    pub multisig_id: u64,
+    pub transaction_id: u64,
+    pub signer: Pubkey,
+    pub signature_count: u8,
+    pub status: TransactionStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MultisigTransactionExecutedEvent {
+    pub multisig_id: u64,
+    pub transaction_id: u64,
+    pub executor: Pubkey,
+    pub transaction_type: TransactionType,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MultisigTransactionCancelledEvent {
+    pub multisig_id: u64,
+    pub transaction_id: u64,
+    pub cancelled_by: Pubkey,
+    /// Cancel votes so far; zero when the proposer withdrew the transaction
+    pub cancel_votes: u8,
+    /// False while an approved transaction is still short of cancel votes
+    pub cancelled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceInitializedEvent {
+    pub governance_config: Pubkey,
+    pub authority: Pubkey,
+    pub governance_token: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceTokensDepositedEvent {
+    pub governance_config: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub voting_power: u64,
+    pub lockup_expires_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceTokensWithdrawnEvent {
+    pub governance_config: Pubkey,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesDelegatedEvent {
+    pub governance_config: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub voting_power: u64,
+    pub expires_at: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VotesUndelegatedEvent {
+    pub governance_config: Pubkey,
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalCreatedEvent {
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub proposer: Pubkey,
+    pub proposal_type: ProposalType,
+    pub voting_starts_at: i64,
+    pub voting_ends_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VoteCastEvent {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub choice: VoteChoice,
+    pub voting_power: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalTalliedEvent {
+    pub proposal: Pubkey,
+    pub passed: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub votes_abstain: u64,
+    pub participation_rate: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalExecutedEvent {
+    pub proposal: Pubkey,
+    pub executor: Pubkey,
+    pub instruction_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GovernanceProposalCancelledEvent {
+    pub proposal: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RbacInitializedEvent {
     pub authority: Pubkey,
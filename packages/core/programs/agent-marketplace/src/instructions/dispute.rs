@@ -3,11 +3,29 @@
  * 
  * Implements automated and manual dispute resolution mechanisms
  * for work quality, payment, and contract disagreements.
+ *
+ * Disputes filed against an escrowed work order lock the escrow until an
+ * arbitrator (allowlisted or staked) or, after escalation, the registry
+ * authority resolves it with a basis-point split of the vault.
  */
 
 use anchor_lang::prelude::*;
-use crate::state::dispute::{DisputeCase, DisputeStatus, DisputeEvidence};
-use crate::PodAIMarketplaceError;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::dispute::{
+    DisputeCase, DisputeStatus, DisputeEvidence, ArbitratorRegistry, ArbitratorStake,
+    ARBITRATOR_REGISTRY_SEED, ARBITRATOR_STAKE_SEED, ARBITRATOR_STAKE_VAULT_SEED,
+    DISPUTE_SPLIT_BASIS_POINTS,
+};
+use crate::state::escrow::{Escrow, ESCROW_SEED, ESCROW_VAULT_SEED};
+use crate::state::reputation::{AgentReputation, AGENT_REPUTATION_SEED};
+use crate::state::royalty::ROYALTY_STREAM_SEED;
+use crate::instructions::royalty::pay_royalty;
+use crate::state::work_order::{WorkOrder, WorkOrderStatus};
+use crate::simple_optimization::SecurityLogger;
+use crate::token_utils::{close_token_account, transfer_tokens};
+use crate::{Agent, PodAIMarketplaceError};
+use crate::security::ProtocolSubsystem;
+use crate::state::protocol_config::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
 
 /// Files a dispute for work quality, payment, or contract issues
 /// 
@@ -33,6 +51,10 @@ use crate::PodAIMarketplaceError;
 /// * `WorkNotCompleted` - If disputing incomplete work
 /// * `DisputeWindowClosed` - If past 30-day dispute period
 /// * `AlreadyDisputed` - If work already has open dispute
+/// * `UnauthorizedAccess` - If the parties are not the escrow's client and agent
+/// 
+/// The escrow of the disputed work order moves to `Disputed` and records the
+/// dispute, blocking release and refunds until that dispute is resolved.
 /// 
 /// # Dispute Process
 /// 
@@ -50,6 +72,19 @@ pub fn file_dispute(
     ctx: Context<FileDispute>,
     reason: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    // SECURITY: Only the escrow's client and agent can dispute each other
+    require!(
+        ctx.accounts.escrow.is_party_pair(
+            ctx.accounts.complainant.key(),
+            ctx.accounts.respondent.key(),
+        ),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    // Lock the escrow so neither party can settle it unilaterally
+    let dispute_key = ctx.accounts.dispute.key();
+    ctx.accounts.escrow.dispute(dispute_key, reason.clone())?;
+
     let dispute = &mut ctx.accounts.dispute;
     let clock = Clock::get()?;
 
@@ -118,6 +153,7 @@ pub fn submit_dispute_evidence(
     evidence_type: String,
     evidence_data: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     let dispute = &mut ctx.accounts.dispute;
     let clock = Clock::get()?;
 
@@ -145,9 +181,373 @@ pub fn submit_dispute_evidence(
     Ok(())
 }
 
+// =====================================================
+// ARBITRATOR REGISTRY
+// =====================================================
+
+/// Creates the arbitrator registry that governs who may moderate disputes
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the registry account
+/// * `stake_mint` - Mint arbitrators stake to join the staked pool
+/// * `min_stake` - Minimum stake for pool eligibility (0 disables the staked pool)
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful initialization
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the protocol authority
+pub fn initialize_arbitrator_registry(
+    ctx: Context<InitializeArbitratorRegistry>,
+    stake_mint: Pubkey,
+    min_stake: u64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let registry = &mut ctx.accounts.registry;
+    registry.authority = ctx.accounts.authority.key();
+    registry.arbitrators = Vec::new();
+    registry.stake_mint = stake_mint;
+    registry.min_stake = min_stake;
+    registry.bump = ctx.bumps.registry;
+
+    msg!("Arbitrator registry initialized - Min stake: {}", min_stake);
+    Ok(())
+}
+
+/// Adds an arbitrator to the allowlist
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the registry authority
+/// * `TooManyArbitrators` - If the allowlist is full
+pub fn add_arbitrator(ctx: Context<ManageArbitrators>, arbitrator: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    ctx.accounts.registry.add_arbitrator(arbitrator)?;
+
+    SecurityLogger::log_security_event("ARBITRATOR_ADDED", ctx.accounts.authority.key(),
+        &format!("arbitrator: {}", arbitrator));
+    Ok(())
+}
+
+/// Removes an arbitrator from the allowlist
+/// 
+/// Disputes already assigned to the arbitrator keep their moderator; the
+/// registry authority can reassign them with `assign_dispute_moderator`.
+pub fn remove_arbitrator(ctx: Context<ManageArbitrators>, arbitrator: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    ctx.accounts.registry.remove_arbitrator(arbitrator)?;
+
+    SecurityLogger::log_security_event("ARBITRATOR_REMOVED", ctx.accounts.authority.key(),
+        &format!("arbitrator: {}", arbitrator));
+    Ok(())
+}
+
+/// Stakes tokens to join the staked arbitrator pool
+/// 
+/// # Errors
+/// 
+/// * `ValueBelowMinimum` - If `amount` is below the registry minimum
+/// * `FeatureNotEnabled` - If the registry has no staked pool
+pub fn stake_arbitrator(ctx: Context<StakeArbitrator>, amount: u64) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let min_stake = ctx.accounts.registry.min_stake;
+    require!(min_stake > 0, PodAIMarketplaceError::FeatureNotEnabled);
+    require!(amount >= min_stake, PodAIMarketplaceError::ValueBelowMinimum);
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.arbitrator_token_account,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.arbitrator.to_account_info(),
+        amount,
+        &[],
+    )?;
+
+    let clock = Clock::get()?;
+    let stake = &mut ctx.accounts.arbitrator_stake;
+    stake.arbitrator = ctx.accounts.arbitrator.key();
+    // The vault balance is authoritative; Token-2022 fees may reduce it below `amount`
+    ctx.accounts.stake_vault.reload()?;
+    stake.amount = ctx.accounts.stake_vault.amount;
+    stake.staked_at = clock.unix_timestamp;
+    stake.last_assigned_at = 0;
+    stake.bump = ctx.bumps.arbitrator_stake;
+
+    SecurityLogger::log_security_event("ARBITRATOR_STAKED", ctx.accounts.arbitrator.key(),
+        &format!("amount: {}", stake.amount));
+    Ok(())
+}
+
+/// Withdraws an arbitrator's stake and leaves the staked pool
+/// 
+/// The stake vault and stake record are closed so the arbitrator can stake again later.
+/// 
+/// # Errors
+/// 
+/// * `ResourceLocked` - If the arbitrator was assigned a dispute within the cooldown
+pub fn unstake_arbitrator(ctx: Context<UnstakeArbitrator>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.arbitrator_stake.can_unstake(clock.unix_timestamp),
+        PodAIMarketplaceError::ResourceLocked
+    );
+
+    let arbitrator_key = ctx.accounts.arbitrator.key();
+    let stake_seeds: &[&[u8]] = &[
+        ARBITRATOR_STAKE_SEED,
+        arbitrator_key.as_ref(),
+        &[ctx.accounts.arbitrator_stake.bump],
+    ];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.stake_mint,
+        &ctx.accounts.arbitrator_token_account,
+        &ctx.accounts.arbitrator_stake.to_account_info(),
+        ctx.accounts.stake_vault.amount,
+        &[stake_seeds],
+    )?;
+    close_token_account(
+        &ctx.accounts.token_program,
+        &ctx.accounts.stake_vault,
+        &ctx.accounts.arbitrator.to_account_info(),
+        &ctx.accounts.arbitrator_stake.to_account_info(),
+        &[stake_seeds],
+    )?;
+
+    SecurityLogger::log_security_event("ARBITRATOR_UNSTAKED", arbitrator_key,
+        &format!("amount: {}", ctx.accounts.arbitrator_stake.amount));
+    Ok(())
+}
+
+// =====================================================
+// DISPUTE LIFECYCLE
+// =====================================================
+
+/// Assigns an eligible arbitrator as the dispute's moderator
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing dispute and registry accounts
+/// * `moderator` - Arbitrator to assign; must be allowlisted or staked
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful assignment
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the registry authority
+/// * `ArbitratorNotEligible` - If the moderator is neither allowlisted nor staked,
+///   or is a party to the dispute
+/// * `InvalidDisputeStatus` - If the dispute is escalated or closed
+pub fn assign_dispute_moderator(
+    ctx: Context<AssignDisputeModerator>,
+    moderator: Pubkey,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let staked_amount = match ctx.accounts.arbitrator_stake.as_mut() {
+        Some(stake) => {
+            stake.last_assigned_at = clock.unix_timestamp;
+            stake.amount
+        }
+        None => 0,
+    };
+
+    require!(
+        ctx.accounts.registry.is_eligible(moderator, staked_amount),
+        PodAIMarketplaceError::ArbitratorNotEligible
+    );
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.assign_moderator(moderator)?;
+
+    emit!(DisputeModeratorAssignedEvent {
+        dispute: dispute.key(),
+        moderator,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Escalates a dispute to human review by the registry authority
+/// 
+/// Either party or the assigned moderator may escalate. Once escalated only
+/// the registry authority can resolve the dispute.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not a party or the moderator
+/// * `InvalidDisputeStatus` - If the dispute is already escalated or closed
+pub fn escalate_dispute(ctx: Context<EscalateDispute>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let dispute = &mut ctx.accounts.dispute;
+    let signer = ctx.accounts.signer.key();
+
+    require!(
+        signer == dispute.complainant
+            || signer == dispute.respondent
+            || dispute.moderator == Some(signer),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    dispute.escalate()?;
+
+    emit!(DisputeEscalatedEvent {
+        dispute: dispute.key(),
+        escalated_by: signer,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Resolves a dispute by splitting the locked escrow between the parties
+/// 
+/// The agent creator's royalty, if the provider agent has an active stream,
+/// is taken from the provider's share.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing dispute, escrow, vault and party accounts
+/// * `complainant_share_bps` - Complainant's share of the escrow in basis points;
+///   the respondent receives the remainder
+/// * `resolution` - Resolution notes recorded on the dispute and escrow
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful resolution
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the moderator, or the registry
+///   authority for escalated disputes
+/// * `InvalidDisputeStatus` - If no moderator is assigned and it is not escalated
+/// * `InvalidPercentage` - If the share exceeds 10,000 basis points
+/// * `InvalidEscrowStatus` - If the escrow was not locked by this dispute
+/// 
+/// # Reputation
+/// 
+/// The ruling is recorded on the provider agent's reputation as a rating
+/// proportional to the provider's share, weighted by the escrowed amount like
+/// any other review. The reputation account must be initialized beforehand.
+pub fn resolve_dispute(
+    ctx: Context<ResolveDispute>,
+    complainant_share_bps: u16,
+    resolution: String,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let resolver = ctx.accounts.dispute.resolver(ctx.accounts.registry.authority);
+    require!(resolver.is_some(), PodAIMarketplaceError::InvalidDisputeStatus);
+    require!(
+        resolver == Some(ctx.accounts.resolver.key()),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    let vault_amount = ctx.accounts.escrow_vault.amount;
+    let (complainant_amount, respondent_amount) =
+        DisputeCase::calculate_split(vault_amount, complainant_share_bps)?;
+
+    ctx.accounts.escrow.resolve(resolution.clone())?;
+    ctx.accounts.dispute.resolve(resolution)?;
+
+    let work_order_key = ctx.accounts.work_order.key();
+    let escrow_seeds: &[&[u8]] = &[ESCROW_SEED, work_order_key.as_ref(), &[ctx.accounts.escrow.bump]];
+
+    // The work order is only considered paid if the provider received funds
+    let provider_is_complainant = ctx.accounts.dispute.complainant == ctx.accounts.escrow.agent;
+    let provider_amount = if provider_is_complainant {
+        complainant_amount
+    } else {
+        respondent_amount
+    };
+    let provider_paid = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.escrow.to_account_info(),
+        &[escrow_seeds],
+        ctx.accounts.work_order.provider_agent,
+        work_order_key,
+        provider_amount,
+    )?;
+    let (complainant_amount, respondent_amount) = if provider_is_complainant {
+        (provider_paid, respondent_amount)
+    } else {
+        (complainant_amount, provider_paid)
+    };
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.complainant_token_account,
+        &ctx.accounts.escrow.to_account_info(),
+        complainant_amount,
+        &[escrow_seeds],
+    )?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.respondent_token_account,
+        &ctx.accounts.escrow.to_account_info(),
+        respondent_amount,
+        &[escrow_seeds],
+    )?;
+
+    let clock = Clock::get()?;
+    let work_order = &mut ctx.accounts.work_order;
+    work_order.status = if provider_amount > 0 {
+        WorkOrderStatus::Completed
+    } else {
+        WorkOrderStatus::Cancelled
+    };
+    work_order.updated_at = clock.unix_timestamp;
+
+    let provider_share_bps = if provider_is_complainant {
+        complainant_share_bps
+    } else {
+        DISPUTE_SPLIT_BASIS_POINTS - complainant_share_bps
+    };
+    let rating = DisputeCase::ruling_rating(provider_share_bps);
+    ctx.accounts.provider_agent.reputation_score = ctx.accounts.provider_reputation.record(
+        rating,
+        vault_amount,
+        ctx.accounts.token_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+
+    SecurityLogger::log_security_event("DISPUTE_RESOLVED", ctx.accounts.resolver.key(),
+        &format!("dispute: {}, complainant: {}, respondent: {}",
+            ctx.accounts.dispute.key(), complainant_amount, respondent_amount));
+
+    emit!(DisputeResolvedEvent {
+        dispute: ctx.accounts.dispute.key(),
+        resolver: ctx.accounts.resolver.key(),
+        complainant_amount,
+        respondent_amount,
+        complainant_share_bps,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // Context structures
 #[derive(Accounts)]
 pub struct FileDispute<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = complainant,
@@ -162,16 +562,238 @@ pub struct FileDispute<'info> {
     pub complainant: Signer<'info>,
     /// CHECK: This is the respondent in the dispute
     pub respondent: AccountInfo<'info>,
+    /// Escrow of the disputed work order, locked while the dispute is open
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, transaction.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct SubmitDisputeEvidence<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub dispute: Account<'info, DisputeCase>,
     pub submitter: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeArbitratorRegistry<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = ArbitratorRegistry::LEN,
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    #[account(
+        mut,
+        constraint = authority.key() == protocol_config.authority @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ManageArbitrators<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump = registry.bump,
+        constraint = registry.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StakeArbitrator<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    #[account(
+        init,
+        payer = arbitrator,
+        space = ArbitratorStake::LEN,
+        seeds = [ARBITRATOR_STAKE_SEED, arbitrator.key().as_ref()],
+        bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+    #[account(
+        init,
+        payer = arbitrator,
+        seeds = [ARBITRATOR_STAKE_VAULT_SEED, arbitrator.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = arbitrator_stake,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = stake_mint.key() == registry.stake_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = arbitrator_token_account.owner == arbitrator.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub arbitrator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub arbitrator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeArbitrator<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        close = arbitrator,
+        seeds = [ARBITRATOR_STAKE_SEED, arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Account<'info, ArbitratorStake>,
+    #[account(
+        mut,
+        seeds = [ARBITRATOR_STAKE_VAULT_SEED, arbitrator.key().as_ref()],
+        bump,
+        token::mint = stake_mint,
+        token::authority = arbitrator_stake,
+        token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    pub stake_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = arbitrator_token_account.owner == arbitrator.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
+    pub arbitrator_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub arbitrator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(moderator: Pubkey)]
+pub struct AssignDisputeModerator<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub dispute: Account<'info, DisputeCase>,
+    #[account(
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump = registry.bump,
+        constraint = registry.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    /// Stake of the moderator when they qualify through the staked pool
+    #[account(
+        mut,
+        seeds = [ARBITRATOR_STAKE_SEED, moderator.as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Option<Account<'info, ArbitratorStake>>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EscalateDispute<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub dispute: Account<'info, DisputeCase>,
+    pub signer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub dispute: Account<'info, DisputeCase>,
+    #[account(
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    #[account(
+        mut,
+        constraint = work_order.key() == dispute.transaction @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub work_order: Account<'info, WorkOrder>,
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump = escrow.bump,
+        constraint = escrow.is_locked_by(dispute.key()) @ PodAIMarketplaceError::InvalidEscrowStatus
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = token_mint.key() == escrow.token_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = complainant_token_account.owner == dispute.complainant @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = complainant_token_account.mint == token_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub complainant_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = respondent_token_account.owner == dispute.respondent @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = respondent_token_account.mint == token_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub respondent_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = work_order.provider_agent @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub provider_agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [AGENT_REPUTATION_SEED, work_order.provider_agent.as_ref()],
+        bump = provider_reputation.bump
+    )]
+    pub provider_reputation: Account<'info, AgentReputation>,
+    /// CHECK: The provider agent's royalty stream PDA. Always required so a
+    /// ruling cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, work_order.provider_agent.as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub resolver: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 // Events
 #[event]
 pub struct DisputeFiledEvent {
@@ -186,4 +808,28 @@ pub struct DisputeEvidenceSubmittedEvent {
     pub dispute: Pubkey,
     pub submitter: Pubkey,
     pub evidence_count: u32,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct DisputeModeratorAssignedEvent {
+    pub dispute: Pubkey,
+    pub moderator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeEscalatedEvent {
+    pub dispute: Pubkey,
+    pub escalated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeResolvedEvent {
+    pub dispute: Pubkey,
+    pub resolver: Pubkey,
+    pub complainant_amount: u64,
+    pub respondent_amount: u64,
+    pub complainant_share_bps: u16,
+    pub timestamp: i64,
+}
@@ -7,6 +7,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::token_utils::{collect_protocol_fee, net_transfer_amount, transfer_tokens};
 use crate::{
     Agent, Payment, WorkOrder,
     WorkOrderStatus,
@@ -15,6 +16,13 @@ use crate::{
     MIN_PAYMENT_AMOUNT,
     MAX_PAYMENT_AMOUNT,
 };
+use crate::security::ProtocolSubsystem;
+use crate::state::protocol_config::{
+    FeeTransactionType, ProtocolConfig, PROTOCOL_CONFIG_SEED, TREASURY_VAULT_SEED,
+};
+use crate::state::escrow::ESCROW_SEED;
+use crate::state::royalty::ROYALTY_STREAM_SEED;
+use crate::instructions::royalty::pay_royalty;
 
 // =====================================================
 // INSTRUCTION CONTEXTS
@@ -22,6 +30,8 @@ use crate::{
 
 #[derive(Accounts)]
 pub struct ProcessPayment<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = payer,
@@ -31,23 +41,70 @@ pub struct ProcessPayment<'info> {
     )]
     pub payment: Account<'info, Payment>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = work_order.client == payer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
     pub work_order: Account<'info, WorkOrder>,
     
-    #[account(mut)]
+    /// CHECK: The work order's escrow PDA. Must not exist: escrowed work
+    /// orders are settled from their vault through approval or refund
+    #[account(
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = work_order.provider_agent @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
     pub provider_agent: Account<'info, Agent>,
     
     #[account(mut)]
     pub payer: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
     pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == work_order.provider @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
     pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
     
+    #[account(
+        constraint = token_mint.key() == work_order.payment_token @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = *token_mint.to_account_info().owner == token_program.key() @ PodAIMarketplaceError::InvalidAccountOwner
+    )]
     pub token_mint: InterfaceAccount<'info, Mint>,
     
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: The provider agent's royalty stream PDA. Always required so a
+    /// payer cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, provider_agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
     pub token_program: Interface<'info, TokenInterface>,
     
     pub system_program: Program<'info, System>,
@@ -59,6 +116,17 @@ pub struct ProcessPayment<'info> {
 
 /// Processes payment from client to provider for completed work order
 /// 
+/// Moves `amount` from the payer's token account to the provider's token
+/// account with a `transfer_checked` CPI. For Token-2022 mints with the
+/// transfer fee extension, the recorded payment and provider earnings are
+/// the net amount the provider actually receives. The protocol fee for
+/// `Payment` settlements is paid to the treasury out of `amount`. If the
+/// provider agent has an active royalty stream, the creator's royalty is then
+/// paid out of the remainder before the provider is credited.
+/// 
+/// Only for work orders without an escrow; escrowed orders are settled from
+/// their vault by `approve_work_order`.
+/// 
 /// # Performance Optimizations
 /// - Compute units: ~25,000 CU
 /// - Safe arithmetic operations
@@ -66,6 +134,7 @@ pub struct ProcessPayment<'info> {
 /// 
 /// # Security Features
 /// - Authorization verification
+/// - Token account ownership and mint checks
 /// - Amount validation
 /// - Safe arithmetic operations
 /// - State consistency checks
@@ -74,6 +143,7 @@ pub fn process_payment(
     amount: u64,
     use_confidential_transfer: bool,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     msg!("Processing payment - Amount: {}", amount);
     
     // SECURITY: Verify signer authorization
@@ -104,6 +174,13 @@ pub fn process_payment(
         PodAIMarketplaceError::ValueExceedsMaximum
     );
 
+    // Confidential transfers need client-generated proofs and cannot be
+    // performed with a plain transfer_checked CPI
+    require!(
+        !use_confidential_transfer,
+        PodAIMarketplaceError::FeatureNotEnabled
+    );
+
     // SECURITY: Verify work order is in correct state for payment
     let work_order = &ctx.accounts.work_order;
     require!(
@@ -111,6 +188,58 @@ pub fn process_payment(
         PodAIMarketplaceError::InvalidStatusTransition
     );
 
+    // SECURITY: Paying an escrowed order directly would pay the provider twice
+    // and strand the vault, since only an open order can be approved or refunded
+    require!(
+        ctx.accounts.escrow.data_is_empty(),
+        PodAIMarketplaceError::WorkOrderEscrowed
+    );
+
+    require!(
+        ctx.accounts.payer_token_account.amount >= amount,
+        PodAIMarketplaceError::InsufficientBalance
+    );
+
+    let provider_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::Payment,
+        &ctx.accounts.token_program,
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.payer.to_account_info(),
+        amount,
+        &[],
+    )?;
+
+    let provider_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.payer.to_account_info(),
+        &[],
+        ctx.accounts.provider_agent.key(),
+        ctx.accounts.work_order.key(),
+        provider_amount,
+    )?;
+
+    // Account for Token-2022 transfer fees so the recorded amount matches
+    // what lands in the provider's token account
+    let received_amount = net_transfer_amount(&ctx.accounts.token_mint, provider_amount)?;
+    require!(received_amount > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.payer_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.payer.to_account_info(),
+        provider_amount,
+        &[],
+    )?;
+
     let payment = &mut ctx.accounts.payment;
     let work_order = &mut ctx.accounts.work_order;
     let provider_agent = &mut ctx.accounts.provider_agent;
@@ -120,7 +249,7 @@ pub fn process_payment(
     payment.work_order = work_order.key();
     payment.payer = ctx.accounts.payer.key();
     payment.recipient = provider_agent.owner;
-    payment.amount = amount;
+    payment.amount = received_amount;
     payment.token_mint = ctx.accounts.token_mint.key();
     payment.is_confidential = use_confidential_transfer;
     payment.paid_at = clock.unix_timestamp;
@@ -128,7 +257,7 @@ pub fn process_payment(
 
     // SECURITY: Safe arithmetic for provider earnings update
     provider_agent.total_earnings = provider_agent.total_earnings
-        .checked_add(amount)
+        .checked_add(received_amount)
         .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
     
     // SECURITY: Safe arithmetic for job completion count
@@ -139,23 +268,17 @@ pub fn process_payment(
     // Update work order status
     work_order.status = WorkOrderStatus::Completed;
     work_order.updated_at = clock.unix_timestamp;
-    
-    // Calculate and update reputation score with overflow protection
-    let reputation_increment = std::cmp::min((amount / 1_000_000) as u64, 10u64); // Max 10 points per payment
-    provider_agent.reputation_score = provider_agent.reputation_score
-        .checked_add(reputation_increment as u32)
-        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
 
     // Emit payment event
     emit!(PaymentProcessedEvent {
         work_order: work_order.key(),
         from: ctx.accounts.payer.key(),
         to: provider_agent.owner,
-        amount,
+        amount: received_amount,
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("Payment processed successfully");
+    msg!("Payment processed successfully - Sent: {}, Received: {}", amount, received_amount);
     Ok(())
 }
 
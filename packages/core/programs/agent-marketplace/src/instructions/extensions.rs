@@ -8,6 +8,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::{Extension, ExtensionStatus, ExtensionMetadata};
+use crate::security::ProtocolSubsystem;
 
 /// Registers a third-party extension or plugin
 /// 
@@ -56,6 +57,7 @@ pub fn register_extension(
     metadata: ExtensionMetadata,
     code_hash: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let extension = &mut ctx.accounts.extension;
     let clock = Clock::get()?;
 
@@ -83,6 +85,8 @@ pub fn register_extension(
 // Context structures
 #[derive(Accounts)]
 pub struct RegisterExtension<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = developer,
@@ -8,6 +8,8 @@
 use anchor_lang::prelude::*;
 use crate::state::incentives::{IncentiveProgram, IncentiveConfig, AgentIncentives};
 use crate::PodAIMarketplaceError;
+use crate::security::ProtocolSubsystem;
+use crate::state::protocol_config::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
 
 /// Creates an incentive program to encourage specific behaviors
 /// 
@@ -55,6 +57,7 @@ pub fn create_incentive_program(
     ctx: Context<CreateIncentiveProgram>,
     config: IncentiveConfig,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     let program = &mut ctx.accounts.program;
     let clock = Clock::get()?;
 
@@ -126,6 +129,7 @@ pub fn distribute_incentives(
     incentive_type: String,
     amount: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.distributor.is_signer,
@@ -185,6 +189,8 @@ pub fn distribute_incentives(
 // Context structures
 #[derive(Accounts)]
 pub struct CreateIncentiveProgram<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
@@ -200,6 +206,8 @@ pub struct CreateIncentiveProgram<'info> {
 
 #[derive(Accounts)]
 pub struct DistributeIncentives<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub program: Account<'info, IncentiveProgram>,
     #[account(mut)]
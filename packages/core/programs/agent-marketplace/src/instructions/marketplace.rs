@@ -1,8 +1,17 @@
 use anchor_lang::prelude::*;
 use crate::{*, PodAIMarketplaceError, state::{ApplicationStatus, ContractStatus}};
 use crate::simple_optimization::{InputValidator, SecurityLogger};
-use crate::state::commerce::{ServiceListingData, ServicePurchaseData, JobPostingData, JobApplicationData};
+use crate::state::commerce::{ServiceListingData, ServicePurchaseData, JobPostingData, JobApplicationData, JobCompletionData};
+use crate::state::work_order::{MAX_DELIVERABLES, MAX_IPFS_HASH_LENGTH};
 use crate::state::marketplace::*;
+use crate::security::ProtocolSubsystem;
+use crate::token_utils::{collect_protocol_fee, net_transfer_amount, transfer_tokens};
+use crate::state::protocol_config::{FeeTransactionType, TREASURY_VAULT_SEED};
+use crate::state::pricing::{ListingTiers, TieredPricingData, DYNAMIC_PRICING_SEED, LISTING_TIERS_SEED};
+use crate::instructions::pricing::{load_pricing_engine, store_pricing_engine};
+use crate::instructions::royalty::pay_royalty;
+use crate::state::royalty::ROYALTY_STREAM_SEED;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // =====================================================
 // SERVICE LISTING INSTRUCTIONS
@@ -50,6 +59,7 @@ pub fn create_service_listing(
     ctx: Context<CreateServiceListing>,
     listing_data: ServiceListingData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.creator.is_signer,
@@ -87,6 +97,7 @@ pub fn create_service_listing(
     listing.is_active = true;
     listing.total_orders = 0;
     listing.rating = 0.0;
+    listing.rating_count = 0;
     listing.created_at = clock.unix_timestamp;
     listing.updated_at = clock.unix_timestamp;
     listing.bump = ctx.bumps.service_listing;
@@ -100,6 +111,92 @@ pub fn create_service_listing(
     Ok(())
 }
 
+/// Adds priced tiers to a service listing
+/// 
+/// Once a listing has tiers, every purchase must select one. The listing
+/// price is set to the cheapest tier so it still advertises a starting price.
+/// Listing-wide `tier_benefits` and `tier_requirements` are not stored.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the listing and its tiers account
+/// * `tier_data` - Tiers ordered cheapest first, plus `upgrade_cost` and
+///   `downgrade_penalty` applied when a purchase changes tier
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer does not own the listing
+/// * `InvalidPricingTier` - If there are no tiers or more than `MAX_TIERS`
+/// * `InvalidPriceConfiguration` - If tiers are not strictly cheapest first
+pub fn set_listing_tiers(
+    ctx: Context<SetListingTiers>,
+    tier_data: TieredPricingData,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let bump = ctx.bumps.listing_tiers;
+    ctx.accounts.listing_tiers.listing = ctx.accounts.service_listing.key();
+    ctx.accounts.listing_tiers.bump = bump;
+    apply_listing_tiers(&mut ctx.accounts.service_listing, &mut ctx.accounts.listing_tiers, tier_data)
+}
+
+/// Replaces the tiers of a tiered service listing
+/// 
+/// Existing purchases keep the unit price they paid; tier changes are
+/// priced against it.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer does not own the listing
+/// * `InvalidPricingTier` - If there are no tiers or more than `MAX_TIERS`
+/// * `InvalidPriceConfiguration` - If tiers are not strictly cheapest first
+pub fn update_listing_tiers(
+    ctx: Context<UpdateListingTiers>,
+    tier_data: TieredPricingData,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    apply_listing_tiers(&mut ctx.accounts.service_listing, &mut ctx.accounts.listing_tiers, tier_data)
+}
+
+fn apply_listing_tiers(
+    listing: &mut Account<ServiceListing>,
+    listing_tiers: &mut Account<ListingTiers>,
+    tier_data: TieredPricingData,
+) -> Result<()> {
+    tier_data.validate()?;
+    for tier in &tier_data.tiers {
+        InputValidator::validate_payment_amount(tier.unit_price(), "tier_price")?;
+    }
+
+    let clock = Clock::get()?;
+    listing.price = tier_data.tiers[0].unit_price();
+    listing.updated_at = clock.unix_timestamp;
+
+    listing_tiers.tiers = tier_data.tiers;
+    listing_tiers.upgrade_cost = tier_data.upgrade_cost;
+    listing_tiers.downgrade_penalty = tier_data.downgrade_penalty;
+    listing_tiers.updated_at = clock.unix_timestamp;
+
+    emit!(ListingTiersUpdatedEvent {
+        listing: listing.key(),
+        tier_count: listing_tiers.tiers.len() as u8,
+        starting_price: listing.price,
+        upgrade_cost: listing_tiers.upgrade_cost,
+        downgrade_penalty: listing_tiers.downgrade_penalty,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Deserializes a listing's tiers, if the listing has any
+fn load_listing_tiers(info: &AccountInfo) -> Result<Option<ListingTiers>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(ListingTiers::try_deserialize(&mut &data[..])?))
+}
+
 /// Purchases a service from an AI agent (for human customers)
 /// 
 /// Enables human customers to purchase services from AI agents. Creates a purchase order
@@ -114,6 +211,9 @@ pub fn create_service_listing(
 ///   - `requirements` - Specific requirements for the service
 ///   - `custom_instructions` - Additional instructions for the agent
 ///   - `deadline` - Expected completion deadline
+///   - `nonce` - Distinguishes repeat purchases of the same listing
+///   - `tier` - Listing tier to buy; required exactly when the listing has tiers
+///   - `max_unit_price` - Highest unit price the buyer accepts
 /// 
 /// # Returns
 /// 
@@ -122,19 +222,21 @@ pub fn create_service_listing(
 /// # Errors
 /// 
 /// * `ServiceNotActive` - If the service listing is inactive
+/// * `InvalidPricingTier` - If the tier selection doesn't match the listing
+/// * `PriceAboveMaximum` - If the unit price exceeds `max_unit_price`
 /// * `InsufficientFunds` - If buyer lacks funds for payment
 /// 
 /// # Payment Flow
 /// 
 /// - Calculates total payment based on quantity
-/// - Transfers funds to escrow account
+/// - Transfers funds into the purchase vault
 /// - Creates purchase order for agent fulfillment
 /// - Updates listing order count
-/// - Transfers funds to escrow
 pub fn purchase_service(
     ctx: Context<PurchaseService>,
     purchase_data: ServicePurchaseData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.buyer.is_signer,
@@ -147,12 +249,42 @@ pub fn purchase_service(
     InputValidator::validate_future_timestamp(purchase_data.deadline, "deadline")?;
 
     let purchase = &mut ctx.accounts.service_purchase;
-    let listing = &ctx.accounts.service_listing;
+    let listing = &mut ctx.accounts.service_listing;
     let clock = Clock::get()?;
 
     // Verify listing is active
     require!(listing.is_active, PodAIMarketplaceError::ServiceNotActive);
 
+    // Listings of agents with an active pricing engine sell at the engine's quote
+    let engine_info = ctx.accounts.pricing_engine.to_account_info();
+    if let Some(mut engine) = load_pricing_engine(&engine_info)? {
+        if engine.is_quoting() {
+            if listing.price != engine.current_price {
+                listing.price = engine.current_price;
+                listing.updated_at = clock.unix_timestamp;
+            }
+            engine.record_order(purchase_data.quantity as u64, clock.unix_timestamp);
+            store_pricing_engine(&engine_info, &engine)?;
+        }
+    }
+
+    // Tiered listings sell each tier at its own price instead
+    let unit_price = match load_listing_tiers(&ctx.accounts.listing_tiers.to_account_info())? {
+        Some(tiers) => {
+            let tier = purchase_data.tier.ok_or(PodAIMarketplaceError::InvalidPricingTier)?;
+            tiers.tier(tier)?.unit_price()
+        }
+        None => {
+            require!(purchase_data.tier.is_none(), PodAIMarketplaceError::InvalidPricingTier);
+            listing.price
+        }
+    };
+    // SECURITY: The quote can move between signing and execution
+    require!(
+        unit_price <= purchase_data.max_unit_price,
+        PodAIMarketplaceError::PriceAboveMaximum
+    );
+
     purchase.listing = listing.key();
     purchase.customer = ctx.accounts.buyer.key();
     purchase.agent = listing.agent;
@@ -162,7 +294,9 @@ pub fn purchase_service(
     purchase.custom_instructions = purchase_data.custom_instructions.clone();
     purchase.deadline = purchase_data.deadline;
     // SECURITY: Use safe arithmetic with overflow protection
-    purchase.payment_amount = listing.price
+    purchase.tier = purchase_data.tier;
+    purchase.unit_price = unit_price;
+    purchase.payment_amount = unit_price
         .checked_mul(purchase_data.quantity as u64)
         .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
     
@@ -173,10 +307,30 @@ pub fn purchase_service(
     msg!("Security Event: SERVICE_PURCHASED - Buyer: {}, listing_id: {}, quantity: {}, amount: {}", 
         ctx.accounts.buyer.key(), purchase_data.listing_id, purchase_data.quantity, purchase.payment_amount);
     purchase.payment_token = listing.payment_token;
+    purchase.status = PurchaseStatus::Pending;
     purchase.purchased_at = clock.unix_timestamp;
     purchase.updated_at = clock.unix_timestamp;
+    purchase.nonce = purchase_data.nonce;
+    purchase.delivery_reference = String::new();
+    purchase.accepted_at = None;
+    purchase.delivered_at = None;
     purchase.bump = ctx.bumps.service_purchase;
 
+    listing.total_orders = listing.total_orders
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    // The full price is held by the purchase until it is confirmed or cancelled
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.purchase_vault,
+        &ctx.accounts.buyer.to_account_info(),
+        purchase.payment_amount,
+        &[],
+    )?;
+
     emit!(crate::ServicePurchasedEvent {
         service: listing.key(),
         buyer: ctx.accounts.buyer.key(),
@@ -187,6 +341,325 @@ pub fn purchase_service(
     Ok(())
 }
 
+/// Accepts a pending purchase on behalf of the selling agent
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the agent owner
+/// * `InvalidStatusTransition` - If the purchase is not pending
+/// * `DeadlinePassed` - If the purchase deadline has already passed
+pub fn accept_service_purchase(ctx: Context<FulfillServicePurchase>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let clock = Clock::get()?;
+    let purchase = &mut ctx.accounts.service_purchase;
+    purchase.accept(clock.unix_timestamp)?;
+
+    emit!(ServicePurchaseAcceptedEvent {
+        purchase: purchase.key(),
+        agent: purchase.agent,
+        customer: purchase.customer,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Marks an accepted purchase as delivered and starts the review window
+/// 
+/// The customer then has `PURCHASE_REVIEW_WINDOW` to confirm the delivery
+/// before anyone may release the escrow to the agent.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the purchase and agent accounts
+/// * `delivery_reference` - URI or content hash of the delivered work
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the agent owner
+/// * `InvalidStatusTransition` - If the purchase was not accepted
+/// * `InputTooLong` - If `delivery_reference` exceeds `MAX_DELIVERY_REFERENCE_LENGTH`
+pub fn deliver_service_purchase(
+    ctx: Context<FulfillServicePurchase>,
+    delivery_reference: String,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let clock = Clock::get()?;
+    let purchase = &mut ctx.accounts.service_purchase;
+    purchase.deliver(delivery_reference, clock.unix_timestamp)?;
+
+    emit!(ServicePurchaseDeliveredEvent {
+        purchase: purchase.key(),
+        agent: purchase.agent,
+        customer: purchase.customer,
+        delivery_reference: purchase.delivery_reference.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Confirms a delivered purchase and releases the escrow to the agent owner
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the customer
+/// * `InvalidStatusTransition` - If the purchase has not been delivered
+pub fn confirm_service_purchase(ctx: Context<SettleServicePurchase>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    // SECURITY: Only the customer can confirm before the review window closes
+    require!(
+        ctx.accounts.caller.key() == ctx.accounts.service_purchase.customer,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    release_purchase_escrow(ctx, false)
+}
+
+/// Releases a delivered purchase the customer did not confirm in time
+/// 
+/// Permissionless crank: anyone may trigger it once `PURCHASE_REVIEW_WINDOW`
+/// has passed since delivery, but funds only ever go to the agent owner.
+/// 
+/// # Errors
+/// 
+/// * `ReviewWindowActive` - If the review window is still open
+pub fn release_service_purchase(ctx: Context<SettleServicePurchase>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require!(
+        ctx.accounts.service_purchase.review_window_elapsed(Clock::get()?.unix_timestamp),
+        PodAIMarketplaceError::ReviewWindowActive
+    );
+
+    release_purchase_escrow(ctx, true)
+}
+
+/// Pays the vault balance less the protocol fee and any creator royalty to the
+/// agent owner and completes the purchase
+fn release_purchase_escrow(ctx: Context<SettleServicePurchase>, auto_released: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    ctx.accounts.service_purchase.complete(clock.unix_timestamp)?;
+
+    let release_amount = ctx.accounts.purchase_vault.amount;
+
+    let purchase = &ctx.accounts.service_purchase;
+    let nonce = purchase.nonce.to_le_bytes();
+    let purchase_seeds: &[&[u8]] = &[
+        SERVICE_PURCHASE_SEED,
+        purchase.customer.as_ref(),
+        purchase.listing.as_ref(),
+        &nonce,
+        &[purchase.bump],
+    ];
+    let agent_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::ServicePurchase,
+        &ctx.accounts.token_program,
+        &ctx.accounts.purchase_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.treasury_vault,
+        &purchase.to_account_info(),
+        release_amount,
+        &[purchase_seeds],
+    )?;
+    let agent_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.purchase_vault,
+        &ctx.accounts.payment_mint,
+        &purchase.to_account_info(),
+        &[purchase_seeds],
+        ctx.accounts.agent.key(),
+        purchase.key(),
+        agent_amount,
+    )?;
+    let received_amount = net_transfer_amount(&ctx.accounts.payment_mint, agent_amount)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.purchase_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.agent_token_account,
+        &purchase.to_account_info(),
+        agent_amount,
+        &[purchase_seeds],
+    )?;
+
+    let agent = &mut ctx.accounts.agent;
+    agent.total_earnings = agent.total_earnings
+        .checked_add(received_amount)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    agent.total_jobs_completed = agent.total_jobs_completed
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    SecurityLogger::log_security_event("SERVICE_PURCHASE_RELEASED", ctx.accounts.caller.key(),
+        &format!("purchase: {}, amount: {}, auto_released: {}", purchase.key(), release_amount, auto_released));
+
+    emit!(ServicePurchaseCompletedEvent {
+        purchase: purchase.key(),
+        agent: purchase.agent,
+        customer: purchase.customer,
+        amount: received_amount,
+        auto_released,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Cancels an undelivered purchase and refunds the customer
+/// 
+/// The agent owner may decline any time before delivering. The customer may
+/// cancel before the agent accepts, or once the deadline passes without a
+/// delivery. Delivered purchases can only be confirmed or released.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is neither the customer nor the agent owner
+/// * `InvalidStatusTransition` - If the purchase can no longer be cancelled by the signer
+pub fn cancel_service_purchase(ctx: Context<CancelServicePurchase>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let authority = ctx.accounts.authority.key();
+    let by_customer = authority == ctx.accounts.service_purchase.customer;
+
+    // SECURITY: Only the two parties to the purchase can cancel it
+    require!(
+        by_customer || authority == ctx.accounts.agent.owner,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    let clock = Clock::get()?;
+    ctx.accounts.service_purchase.cancel(by_customer, clock.unix_timestamp)?;
+
+    let refund_amount = ctx.accounts.purchase_vault.amount;
+    let received_amount = net_transfer_amount(&ctx.accounts.payment_mint, refund_amount)?;
+
+    let purchase = &ctx.accounts.service_purchase;
+    let nonce = purchase.nonce.to_le_bytes();
+    let purchase_seeds: &[&[u8]] = &[
+        SERVICE_PURCHASE_SEED,
+        purchase.customer.as_ref(),
+        purchase.listing.as_ref(),
+        &nonce,
+        &[purchase.bump],
+    ];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.purchase_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.customer_token_account,
+        &purchase.to_account_info(),
+        refund_amount,
+        &[purchase_seeds],
+    )?;
+
+    SecurityLogger::log_security_event("SERVICE_PURCHASE_REFUNDED", authority,
+        &format!("purchase: {}, amount: {}", purchase.key(), refund_amount));
+
+    emit!(ServicePurchaseCancelledEvent {
+        purchase: purchase.key(),
+        customer: purchase.customer,
+        cancelled_by: authority,
+        amount: received_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Moves an undelivered tiered purchase to another tier of its listing
+/// 
+/// An upgrade charges the customer the price difference for the purchased
+/// quantity plus the listing's `upgrade_cost`. A downgrade refunds the
+/// difference less the `downgrade_penalty`, which stays in escrow for the
+/// agent. Once the agent has accepted the purchase it can only be upgraded,
+/// since the agent committed to the work at the accepted tier.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the purchase, its vault and the listing tiers
+/// * `new_tier` - Index of the tier to move to
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the customer
+/// * `InvalidPricingTier` - If the purchase is untiered, or `new_tier` is missing or current
+/// * `InvalidStatusTransition` - If the purchase was already delivered or settled,
+///   or is a downgrade of an accepted purchase
+pub fn change_purchase_tier(ctx: Context<ChangePurchaseTier>, new_tier: u8) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+
+    let purchase = &ctx.accounts.service_purchase;
+    let current_tier = purchase.tier.ok_or(PodAIMarketplaceError::InvalidPricingTier)?;
+    require!(new_tier != current_tier, PodAIMarketplaceError::InvalidPricingTier);
+    require!(
+        matches!(purchase.status, PurchaseStatus::Pending | PurchaseStatus::Confirmed),
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+
+    let tiers = &ctx.accounts.listing_tiers;
+    let amount = tiers.tier_change_amount(purchase.unit_price, new_tier, purchase.quantity)?;
+    let new_unit_price = tiers.tier(new_tier)?.unit_price();
+
+    // SECURITY: The customer can't cut the price of work the agent already accepted
+    require!(
+        new_unit_price > purchase.unit_price || purchase.status == PurchaseStatus::Pending,
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+
+    if amount > 0 {
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.customer_token_account,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.purchase_vault,
+            &ctx.accounts.customer.to_account_info(),
+            amount as u64,
+            &[],
+        )?;
+    } else if amount < 0 {
+        let nonce = purchase.nonce.to_le_bytes();
+        let purchase_seeds: &[&[u8]] = &[
+            SERVICE_PURCHASE_SEED,
+            purchase.customer.as_ref(),
+            purchase.listing.as_ref(),
+            &nonce,
+            &[purchase.bump],
+        ];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.purchase_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.customer_token_account,
+            &purchase.to_account_info(),
+            amount.unsigned_abs() as u64,
+            &[purchase_seeds],
+        )?;
+    }
+
+    let clock = Clock::get()?;
+    let purchase = &mut ctx.accounts.service_purchase;
+    let payment_amount = purchase.payment_amount as i128 + amount;
+    purchase.payment_amount = u64::try_from(payment_amount)
+        .map_err(|_| PodAIMarketplaceError::ArithmeticOverflow)?;
+    purchase.tier = Some(new_tier);
+    purchase.unit_price = new_unit_price;
+    purchase.updated_at = clock.unix_timestamp;
+
+    emit!(PurchaseTierChangedEvent {
+        purchase: purchase.key(),
+        customer: purchase.customer,
+        from_tier: current_tier,
+        to_tier: new_tier,
+        amount: amount as i64,
+        payment_amount: purchase.payment_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // =====================================================
 // JOB POSTING INSTRUCTIONS
 // =====================================================
@@ -236,6 +709,7 @@ pub fn create_job_posting(
     ctx: Context<CreateJobPosting>,
     job_data: JobPostingData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.employer.is_signer,
@@ -327,6 +801,7 @@ pub fn apply_to_job(
     ctx: Context<ApplyToJob>,
     application_data: JobApplicationData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.agent_owner.is_signer,
@@ -398,11 +873,15 @@ pub fn apply_to_job(
 /// Accepts a job application and creates a work contract
 /// 
 /// Allows employers to accept an agent's application and establish a binding work contract.
-/// This initiates the escrow process and sets deliverable expectations.
+/// The contract's vault is created here and funded one milestone at a time.
 /// 
 /// # Arguments
 /// 
-/// * `ctx` - The context containing contract creation accounts and employer authority
+/// * `ctx` - The context containing contract creation accounts and employer authority.
+///   The other applications to the posting may be passed as writable remaining
+///   accounts to reject them in the same transaction.
+/// * `milestones` - Payout schedule summing to the proposed rate; empty for a
+///   single milestone covering the whole rate
 /// 
 /// # Returns
 /// 
@@ -411,27 +890,21 @@ pub fn apply_to_job(
 /// # Errors
 /// 
 /// * `UnauthorizedAccess` - If caller is not the job poster
-/// * `InvalidApplicationStatus` - If application is not in `Submitted` status
-/// * `InsufficientFunds` - If employer lacks funds for escrow
-/// 
-/// # Contract Creation
-/// 
-/// Creates a job contract with:
-/// - Agreed payment terms from the application
-/// - Milestone schedule if applicable
-/// - Delivery deadline
-/// - Dispute resolution terms
+/// * `InvalidJobStatus` - If application is not in `Submitted` status
+/// * `TooManyMilestones` - If more than `MAX_JOB_MILESTONES` are given
+/// * `InvalidPaymentAmount` - If the milestone amounts do not add up to the rate
 /// 
 /// # State Changes
 /// 
-/// - Creates new job contract account
+/// - Creates new job contract account and its vault
 /// - Updates application status to `Accepted`
 /// - Marks job posting as inactive
-/// - Transfers funds to escrow
-/// - Updates other applications to `Rejected`
-pub fn accept_job_application(
-    ctx: Context<AcceptJobApplication>,
+/// - Updates the other passed applications to `Rejected`
+pub fn accept_job_application<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AcceptJobApplication<'info>>,
+    milestones: Vec<JobMilestoneData>,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.employer.is_signer,
@@ -460,7 +933,9 @@ pub fn accept_job_application(
     job_contract.status = ContractStatus::Active;
     job_contract.created_at = clock.unix_timestamp;
     job_contract.updated_at = clock.unix_timestamp;
+    job_contract.released_amount = 0;
     job_contract.bump = ctx.bumps.job_contract;
+    job_contract.set_milestones(milestones)?;
 
     // Update application status
     application.status = ApplicationStatus::Accepted;
@@ -469,6 +944,8 @@ pub fn accept_job_application(
     job_posting.is_active = false;
     job_posting.updated_at = clock.unix_timestamp;
 
+    reject_applications(job_posting.key(), Some(application.key()), ctx.remaining_accounts)?;
+
     emit!(crate::state::marketplace::JobApplicationAcceptedEvent {
         application: application.key(),
         job_posting: job_posting.key(),
@@ -480,6 +957,388 @@ pub fn accept_job_application(
     Ok(())
 }
 
+/// Rejects pending applications to one of the employer's job postings
+/// 
+/// The applications are passed as writable remaining accounts, so postings
+/// with many applicants can be cleared over several transactions. Applications
+/// that are no longer pending are skipped.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the job poster
+/// * `InvalidConfiguration` - If an application belongs to another posting
+pub fn reject_job_applications<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RejectJobApplications<'info>>,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    reject_applications(ctx.accounts.job_posting.key(), None, ctx.remaining_accounts)?;
+    Ok(())
+}
+
+/// Marks every pending application in `applications` as rejected, except `skip`
+fn reject_applications<'info>(
+    job_posting: Pubkey,
+    skip: Option<Pubkey>,
+    applications: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    for info in applications {
+        if Some(info.key()) == skip {
+            continue;
+        }
+        require!(info.is_writable, PodAIMarketplaceError::InvalidConfiguration);
+
+        let mut application = Account::<JobApplication>::try_from(info)?;
+        require!(application.job_posting == job_posting, PodAIMarketplaceError::InvalidConfiguration);
+        if application.status != ApplicationStatus::Submitted {
+            continue;
+        }
+
+        application.status = ApplicationStatus::Rejected;
+        application.exit(&crate::ID)?;
+
+        emit!(JobApplicationRejectedEvent {
+            application: info.key(),
+            job_posting,
+            agent: application.agent,
+            timestamp,
+        });
+    }
+
+    Ok(())
+}
+
+// =====================================================
+// JOB CONTRACT INSTRUCTIONS
+// =====================================================
+
+/// Escrows one milestone of a job contract in the contract vault
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the employer
+/// * `MilestoneNotFound` - If `milestone_index` is out of range
+/// * `InvalidStatusTransition` - If the milestone is already funded
+/// * `InvalidContractStatus` - If the contract is no longer active
+pub fn fund_job_milestone(ctx: Context<FundJobMilestone>, milestone_index: u8) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let contract = &mut ctx.accounts.job_contract;
+    let amount = contract.milestone_mut(milestone_index)?.amount;
+    let escrowed = net_transfer_amount(&ctx.accounts.payment_mint, amount)?;
+    contract.fund_milestone(milestone_index, escrowed)?;
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.employer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.contract_vault,
+        &ctx.accounts.employer.to_account_info(),
+        amount,
+        &[],
+    )?;
+
+    let clock = Clock::get()?;
+    contract.updated_at = clock.unix_timestamp;
+
+    emit!(JobMilestoneFundedEvent {
+        contract: contract.key(),
+        milestone_index,
+        amount: escrowed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Submits the agent's work for a funded milestone
+/// 
+/// Each submission gets its own completion account, numbered by the
+/// milestone's revision count, so earlier rejected submissions are kept.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the contract, completion and agent accounts
+/// * `milestone_index` - Milestone the work is delivered for
+/// * `revision` - The milestone's current revision count
+/// * `completion_data` - Deliverables, IPFS hash, metadata URI and summary
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer does not own the contracted agent
+/// * `InvalidStatusTransition` - If the milestone is not funded, or `revision` is stale
+/// * `TooManyDeliverables` - If more than `MAX_DELIVERABLES` are listed
+pub fn submit_job_completion(
+    ctx: Context<SubmitJobCompletion>,
+    milestone_index: u8,
+    revision: u8,
+    completion_data: JobCompletionData,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(completion_data.deliverables.len() <= MAX_DELIVERABLES, PodAIMarketplaceError::TooManyDeliverables);
+    require!(completion_data.ipfs_hash.len() <= MAX_IPFS_HASH_LENGTH, PodAIMarketplaceError::IpfsHashTooLong);
+    require!(completion_data.metadata_uri.len() <= 200, PodAIMarketplaceError::MetadataUriTooLong);
+    InputValidator::validate_string(&completion_data.work_summary, 1000, "work_summary")?;
+
+    let contract = &mut ctx.accounts.job_contract;
+    let current_revision = contract.submit_milestone(milestone_index)?;
+    require!(revision == current_revision, PodAIMarketplaceError::InvalidStatusTransition);
+
+    let clock = Clock::get()?;
+    contract.updated_at = clock.unix_timestamp;
+
+    let completion = &mut ctx.accounts.job_completion;
+    completion.contract = contract.key();
+    completion.agent = contract.agent;
+    completion.employer = contract.employer;
+    completion.deliverables = completion_data.deliverables;
+    completion.work_summary = completion_data.work_summary;
+    completion.ipfs_hash = completion_data.ipfs_hash;
+    completion.metadata_uri = completion_data.metadata_uri;
+    completion.completed_at = clock.unix_timestamp;
+    completion.is_approved = false;
+    completion.milestone_index = milestone_index;
+    completion.revision = revision;
+    completion.feedback = String::new();
+    completion.reviewed_at = None;
+    completion.bump = ctx.bumps.job_completion;
+
+    emit!(JobCompletionSubmittedEvent {
+        contract: contract.key(),
+        completion: completion.key(),
+        milestone_index,
+        revision,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Approves a submitted milestone and pays its escrow to the agent owner
+/// 
+/// The protocol fee for `JobContract` settlements goes to the treasury and
+/// any creator royalty to the agent's creator. Approving the last
+/// outstanding milestone completes the contract.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the employer
+/// * `InvalidStatusTransition` - If the completion is not the pending submission
+pub fn approve_job_completion(ctx: Context<ApproveJobCompletion>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    // SECURITY: Only the employer can approve before the review window closes
+    require!(
+        ctx.accounts.caller.key() == ctx.accounts.job_contract.employer,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    release_job_milestone_escrow(ctx, false)
+}
+
+/// Releases a submitted milestone the employer did not review in time
+/// 
+/// Permissionless crank: anyone may trigger it once `MILESTONE_REVIEW_WINDOW`
+/// has passed since the submission, but funds only ever go to the agent owner.
+/// 
+/// # Errors
+/// 
+/// * `ReviewWindowActive` - If the review window is still open
+/// * `InvalidStatusTransition` - If the completion is not the pending submission
+pub fn release_job_milestone(ctx: Context<ApproveJobCompletion>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require!(
+        ctx.accounts.job_completion.review_window_elapsed(Clock::get()?.unix_timestamp),
+        PodAIMarketplaceError::ReviewWindowActive
+    );
+
+    release_job_milestone_escrow(ctx, true)
+}
+
+/// Pays a submitted milestone's escrow less the protocol fee and any creator
+/// royalty to the agent owner
+fn release_job_milestone_escrow(ctx: Context<ApproveJobCompletion>, auto_released: bool) -> Result<()> {
+    let clock = Clock::get()?;
+    let completion = &mut ctx.accounts.job_completion;
+    let contract = &mut ctx.accounts.job_contract;
+    let milestone_index = completion.milestone_index;
+
+    // SECURITY: Only the latest submission of a milestone can be approved
+    require!(
+        completion.revision == contract.milestone_mut(milestone_index)?.revisions,
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+    let release_amount = contract.release_milestone(milestone_index)?;
+    contract.updated_at = clock.unix_timestamp;
+    completion.is_approved = true;
+    completion.reviewed_at = Some(clock.unix_timestamp);
+
+    let contract_seeds: &[&[u8]] = &[
+        b"job_contract",
+        contract.job_posting.as_ref(),
+        contract.application.as_ref(),
+        &[contract.bump],
+    ];
+    let agent_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::JobContract,
+        &ctx.accounts.token_program,
+        &ctx.accounts.contract_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.treasury_vault,
+        &contract.to_account_info(),
+        release_amount,
+        &[contract_seeds],
+    )?;
+    let agent_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.contract_vault,
+        &ctx.accounts.payment_mint,
+        &contract.to_account_info(),
+        &[contract_seeds],
+        ctx.accounts.agent.key(),
+        contract.key(),
+        agent_amount,
+    )?;
+    let received_amount = net_transfer_amount(&ctx.accounts.payment_mint, agent_amount)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.contract_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.agent_token_account,
+        &contract.to_account_info(),
+        agent_amount,
+        &[contract_seeds],
+    )?;
+
+    let agent = &mut ctx.accounts.agent;
+    agent.total_earnings = agent.total_earnings
+        .checked_add(received_amount)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    if contract.status == ContractStatus::Completed {
+        agent.total_jobs_completed = agent.total_jobs_completed
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    }
+
+    SecurityLogger::log_security_event("JOB_MILESTONE_RELEASED", ctx.accounts.caller.key(),
+        &format!("contract: {}, milestone: {}, amount: {}, auto_released: {}",
+            contract.key(), milestone_index, release_amount, auto_released));
+
+    emit!(JobCompletionApprovedEvent {
+        contract: contract.key(),
+        completion: completion.key(),
+        milestone_index,
+        amount: received_amount,
+        contract_completed: contract.status == ContractStatus::Completed,
+        auto_released,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Refunds a milestone the agent did not deliver by the contract deadline
+/// 
+/// Funded milestones return their escrow to the employer; unfunded ones are
+/// withdrawn so the contract can settle. Once every milestone is released
+/// or refunded the contract completes, or is cancelled if nothing was paid.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the employer
+/// * `InvalidDeadline` - If the contract deadline has not passed
+/// * `InvalidStatusTransition` - If the milestone was submitted or already settled
+/// * `InvalidContractStatus` - If the contract is no longer active
+pub fn refund_job_milestone(ctx: Context<RefundJobMilestone>, milestone_index: u8) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let contract = &mut ctx.accounts.job_contract;
+    let refund_amount = contract.refund_milestone(milestone_index, clock.unix_timestamp)?;
+    contract.updated_at = clock.unix_timestamp;
+
+    let received_amount = net_transfer_amount(&ctx.accounts.payment_mint, refund_amount)?;
+    if refund_amount > 0 {
+        let contract_seeds: &[&[u8]] = &[
+            b"job_contract",
+            contract.job_posting.as_ref(),
+            contract.application.as_ref(),
+            &[contract.bump],
+        ];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.contract_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.employer_token_account,
+            &contract.to_account_info(),
+            refund_amount,
+            &[contract_seeds],
+        )?;
+    }
+
+    if contract.status == ContractStatus::Completed {
+        let agent = &mut ctx.accounts.agent;
+        agent.total_jobs_completed = agent.total_jobs_completed
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    }
+
+    SecurityLogger::log_security_event("JOB_MILESTONE_REFUNDED", ctx.accounts.employer.key(),
+        &format!("contract: {}, milestone: {}, amount: {}", contract.key(), milestone_index, refund_amount));
+
+    emit!(JobMilestoneRefundedEvent {
+        contract: contract.key(),
+        milestone_index,
+        amount: received_amount,
+        contract_status: contract.status,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Rejects a submitted milestone and asks the agent for a revision
+/// 
+/// The milestone stays funded. After `MAX_MILESTONE_REVISIONS` rejections the
+/// next submission can only be approved.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the employer
+/// * `InvalidStatusTransition` - If the completion is not the pending submission
+/// * `TooManyRevisions` - If the revision limit has been reached
+/// * `InputTooLong` - If `feedback` exceeds `MAX_COMPLETION_FEEDBACK_LENGTH`
+pub fn reject_job_completion(ctx: Context<RejectJobCompletion>, feedback: String) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(feedback.len() <= MAX_COMPLETION_FEEDBACK_LENGTH, PodAIMarketplaceError::InputTooLong);
+
+    let clock = Clock::get()?;
+    let completion = &mut ctx.accounts.job_completion;
+    let contract = &mut ctx.accounts.job_contract;
+    let milestone_index = completion.milestone_index;
+
+    require!(
+        completion.revision == contract.milestone_mut(milestone_index)?.revisions,
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+    contract.request_revision(milestone_index)?;
+    contract.updated_at = clock.unix_timestamp;
+    completion.feedback = feedback;
+    completion.reviewed_at = Some(clock.unix_timestamp);
+
+    emit!(JobRevisionRequestedEvent {
+        contract: contract.key(),
+        completion: completion.key(),
+        milestone_index,
+        revisions: completion.revision + 1,
+        feedback: completion.feedback.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // =====================================================
 // ACCOUNT STRUCTURES
 // =====================================================
@@ -487,6 +1346,8 @@ pub fn accept_job_application(
 #[derive(Accounts)]
 #[instruction(listing_data: ServiceListingData)]
 pub struct CreateServiceListing<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
@@ -502,24 +1363,252 @@ pub struct CreateServiceListing<'info> {
 }
 
 #[derive(Accounts)]
+pub struct SetListingTiers<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = owner,
+        space = ListingTiers::LEN,
+        seeds = [LISTING_TIERS_SEED, service_listing.key().as_ref()],
+        bump
+    )]
+    pub listing_tiers: Account<'info, ListingTiers>,
+    #[account(
+        mut,
+        constraint = service_listing.owner == owner.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub service_listing: Account<'info, ServiceListing>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateListingTiers<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [LISTING_TIERS_SEED, service_listing.key().as_ref()],
+        bump = listing_tiers.bump
+    )]
+    pub listing_tiers: Account<'info, ListingTiers>,
+    #[account(
+        mut,
+        constraint = service_listing.owner == owner.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub service_listing: Account<'info, ServiceListing>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(purchase_data: ServicePurchaseData)]
 pub struct PurchaseService<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = buyer,
         space = ServicePurchase::LEN,
-        seeds = [b"service_purchase", buyer.key().as_ref(), service_listing.key().as_ref()],
+        seeds = [
+            SERVICE_PURCHASE_SEED,
+            buyer.key().as_ref(),
+            service_listing.key().as_ref(),
+            &purchase_data.nonce.to_le_bytes()
+        ],
         bump
     )]
     pub service_purchase: Account<'info, ServicePurchase>,
     #[account(mut)]
     pub service_listing: Account<'info, ServiceListing>,
+    /// CHECK: The listing agent's pricing engine PDA, which may not exist;
+    /// deserialized by the handler and required so a quote can't be skipped
+    #[account(
+        mut,
+        seeds = [DYNAMIC_PRICING_SEED, service_listing.agent.as_ref()],
+        bump
+    )]
+    pub pricing_engine: UncheckedAccount<'info>,
+    /// CHECK: The listing's tiers PDA, which may not exist; deserialized by
+    /// the handler and required so a tier price can't be skipped
+    #[account(
+        seeds = [LISTING_TIERS_SEED, service_listing.key().as_ref()],
+        bump
+    )]
+    pub listing_tiers: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [SERVICE_PURCHASE_VAULT_SEED, service_purchase.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = service_purchase,
+        token::token_program = token_program
+    )]
+    pub purchase_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == service_listing.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = buyer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
     #[account(mut)]
     pub buyer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct FulfillServicePurchase<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = service_purchase.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub service_purchase: Account<'info, ServicePurchase>,
+    #[account(
+        constraint = agent.owner == agent_owner.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub agent: Account<'info, Agent>,
+    pub agent_owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleServicePurchase<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = service_purchase.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub service_purchase: Account<'info, ServicePurchase>,
+    #[account(
+        mut,
+        seeds = [SERVICE_PURCHASE_VAULT_SEED, service_purchase.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = service_purchase,
+        token::token_program = token_program
+    )]
+    pub purchase_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.owner @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = agent_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == service_purchase.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The agent's royalty stream PDA. Always required so a release
+    /// cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// Customer when confirming, anyone when releasing after the review window
+    pub caller: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelServicePurchase<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = service_purchase.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub service_purchase: Account<'info, ServicePurchase>,
+    #[account(
+        mut,
+        seeds = [SERVICE_PURCHASE_VAULT_SEED, service_purchase.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = service_purchase,
+        token::token_program = token_program
+    )]
+    pub purchase_vault: InterfaceAccount<'info, TokenAccount>,
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == service_purchase.customer @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == service_purchase.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    /// Customer or agent owner
+    pub authority: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ChangePurchaseTier<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = service_purchase.customer == customer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub service_purchase: Account<'info, ServicePurchase>,
+    #[account(
+        mut,
+        seeds = [SERVICE_PURCHASE_VAULT_SEED, service_purchase.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = service_purchase,
+        token::token_program = token_program
+    )]
+    pub purchase_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [LISTING_TIERS_SEED, service_purchase.listing.as_ref()],
+        bump = listing_tiers.bump
+    )]
+    pub listing_tiers: Account<'info, ListingTiers>,
+    #[account(
+        mut,
+        constraint = customer_token_account.owner == customer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = customer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub customer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == service_purchase.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    pub customer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 #[derive(Accounts)]
 pub struct CreateJobPosting<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = employer,
@@ -537,6 +1626,8 @@ pub struct CreateJobPosting<'info> {
 
 #[derive(Accounts)]
 pub struct ApplyToJob<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = agent_owner,
@@ -559,6 +1650,8 @@ pub struct ApplyToJob<'info> {
 
 #[derive(Accounts)]
 pub struct AcceptJobApplication<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = employer,
@@ -568,14 +1661,336 @@ pub struct AcceptJobApplication<'info> {
     )]
     pub job_contract: Account<'info, JobContract>,
     
-    #[account(mut)]
+    #[account(
+        init,
+        payer = employer,
+        seeds = [JOB_CONTRACT_VAULT_SEED, job_contract.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = job_contract,
+        token::token_program = token_program
+    )]
+    pub contract_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = job_posting.employer == employer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
     pub job_posting: Account<'info, JobPosting>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = job_application.job_posting == job_posting.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
     pub job_application: Account<'info, JobApplication>,
     
+    #[account(
+        constraint = payment_mint.key() == job_posting.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
     #[account(mut)]
     pub employer: Signer<'info>,
     
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectJobApplications<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        constraint = job_posting.employer == employer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub job_posting: Account<'info, JobPosting>,
+    pub employer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FundJobMilestone<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = job_contract.employer == employer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub job_contract: Account<'info, JobContract>,
+    #[account(
+        mut,
+        seeds = [JOB_CONTRACT_VAULT_SEED, job_contract.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = job_contract,
+        token::token_program = token_program
+    )]
+    pub contract_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == job_contract.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = employer_token_account.owner == employer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = employer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub employer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub employer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(milestone_index: u8, revision: u8)]
+pub struct SubmitJobCompletion<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = job_contract.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub job_contract: Account<'info, JobContract>,
+    #[account(
+        init,
+        payer = agent_owner,
+        space = JobCompletion::LEN,
+        seeds = [JOB_COMPLETION_SEED, job_contract.key().as_ref(), &[milestone_index], &[revision]],
+        bump
+    )]
+    pub job_completion: Account<'info, JobCompletion>,
+    #[account(
+        constraint = agent.owner == agent_owner.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub agent_owner: Signer<'info>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct ApproveJobCompletion<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = job_contract.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub job_contract: Account<'info, JobContract>,
+    #[account(
+        mut,
+        constraint = job_completion.contract == job_contract.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub job_completion: Account<'info, JobCompletion>,
+    #[account(
+        mut,
+        seeds = [JOB_CONTRACT_VAULT_SEED, job_contract.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = job_contract,
+        token::token_program = token_program
+    )]
+    pub contract_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.owner @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = agent_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        constraint = payment_mint.key() == job_contract.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The agent's royalty stream PDA. Always required so a release
+    /// cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// The employer when approving; anyone once the review window elapsed
+    pub caller: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundJobMilestone<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = job_contract.employer == employer.key() @ PodAIMarketplaceError::UnauthorizedAccess,
+        constraint = job_contract.agent == agent.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub job_contract: Account<'info, JobContract>,
+    #[account(
+        mut,
+        seeds = [JOB_CONTRACT_VAULT_SEED, job_contract.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = job_contract,
+        token::token_program = token_program
+    )]
+    pub contract_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        constraint = payment_mint.key() == job_contract.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = employer_token_account.owner == employer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = employer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub employer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub employer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RejectJobCompletion<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = job_contract.employer == employer.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub job_contract: Account<'info, JobContract>,
+    #[account(
+        mut,
+        constraint = job_completion.contract == job_contract.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub job_completion: Account<'info, JobCompletion>,
+    pub employer: Signer<'info>,
+}
+
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct ServicePurchaseAcceptedEvent {
+    pub purchase: Pubkey,
+    pub agent: Pubkey,
+    pub customer: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ServicePurchaseDeliveredEvent {
+    pub purchase: Pubkey,
+    pub agent: Pubkey,
+    pub customer: Pubkey,
+    pub delivery_reference: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ServicePurchaseCompletedEvent {
+    pub purchase: Pubkey,
+    pub agent: Pubkey,
+    pub customer: Pubkey,
+    pub amount: u64,
+    pub auto_released: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ServicePurchaseCancelledEvent {
+    pub purchase: Pubkey,
+    pub customer: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobApplicationRejectedEvent {
+    pub application: Pubkey,
+    pub job_posting: Pubkey,
+    pub agent: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobMilestoneFundedEvent {
+    pub contract: Pubkey,
+    pub milestone_index: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobCompletionSubmittedEvent {
+    pub contract: Pubkey,
+    pub completion: Pubkey,
+    pub milestone_index: u8,
+    pub revision: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobCompletionApprovedEvent {
+    pub contract: Pubkey,
+    pub completion: Pubkey,
+    pub milestone_index: u8,
+    pub amount: u64,
+    pub contract_completed: bool,
+    pub auto_released: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobMilestoneRefundedEvent {
+    pub contract: Pubkey,
+    pub milestone_index: u8,
+    pub amount: u64,
+    pub contract_status: ContractStatus,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct JobRevisionRequestedEvent {
+    pub contract: Pubkey,
+    pub completion: Pubkey,
+    pub milestone_index: u8,
+    pub revisions: u8,
+    pub feedback: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ListingTiersUpdatedEvent {
+    pub listing: Pubkey,
+    pub tier_count: u8,
+    pub starting_price: u64,
+    pub upgrade_cost: u64,
+    pub downgrade_penalty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PurchaseTierChangedEvent {
+    pub purchase: Pubkey,
+    pub customer: Pubkey,
+    pub from_tier: u8,
+    pub to_tier: u8,
+    /// Charged to the customer when positive, refunded when negative
+    pub amount: i64,
+    pub payment_amount: u64,
+    pub timestamp: i64,
+}
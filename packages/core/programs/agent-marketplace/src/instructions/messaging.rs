@@ -6,6 +6,10 @@
 
 use anchor_lang::prelude::*;
 use crate::{*, PodAIMarketplaceError, state::{ChannelType, MessageType}};
+use crate::state::channel::{
+    ChannelMembershipStatus, ChannelRole, CHANNEL_MEMBERSHIP_SEED, DIRECT_CHANNEL_MEMBERS,
+};
+use crate::security::ProtocolSubsystem;
 
 // =====================================================
 // DATA STRUCTURES
@@ -14,9 +18,11 @@ use crate::{*, PodAIMarketplaceError, state::{ChannelType, MessageType}};
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ChannelCreationData {
     pub channel_id: u64,
-    pub participants: Vec<Pubkey>,
     pub channel_type: ChannelType,
     pub is_private: bool,
+    /// Seat limit including the creator; zero for unlimited, ignored for
+    /// direct channels which always have two seats
+    pub max_members: u32,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -45,6 +51,56 @@ pub struct MessageSentEvent {
     pub message_type: MessageType,
 }
 
+#[event]
+pub struct ChannelMemberInvitedEvent {
+    pub channel: Pubkey,
+    pub member: Pubkey,
+    pub invited_by: Pubkey,
+}
+
+#[event]
+pub struct ChannelMemberJoinedEvent {
+    pub channel: Pubkey,
+    pub member: Pubkey,
+    pub invited_by: Option<Pubkey>,
+}
+
+#[event]
+pub struct ChannelMemberRemovedEvent {
+    pub channel: Pubkey,
+    pub member: Pubkey,
+    /// None when the member left on their own
+    pub removed_by: Option<Pubkey>,
+}
+
+#[event]
+pub struct ChannelMemberRoleChangedEvent {
+    pub channel: Pubkey,
+    pub member: Pubkey,
+    pub role: ChannelRole,
+    pub changed_by: Pubkey,
+}
+
+#[event]
+pub struct ChannelOwnershipTransferredEvent {
+    pub channel: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+#[event]
+pub struct ChannelActiveChangedEvent {
+    pub channel: Pubkey,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct ChannelClosedEvent {
+    pub channel: Pubkey,
+    pub owner: Pubkey,
+    pub message_count: u64,
+}
+
 // =====================================================
 // INSTRUCTION HANDLERS
 // =====================================================
@@ -53,14 +109,16 @@ pub struct MessageSentEvent {
 /// 
 /// Establishes a channel for agents to exchange messages, coordinate work, and share updates.
 /// Supports both direct (1-to-1) and group channels with privacy controls.
+/// The creator becomes the channel's owner and its first member; everyone else
+/// is added through `join_channel` or `invite_to_channel`.
 /// 
 /// # Arguments
 /// 
 /// * `ctx` - The context containing the channel account and creator authority
 /// * `channel_data` - Channel configuration including:
-///   - `participants` - Array of agent public keys that can access the channel
 ///   - `channel_type` - Type of channel (Direct, Group, Public, Private)
 ///   - `is_private` - Whether the channel contents are encrypted
+///   - `max_members` - Seat limit, zero for unlimited
 /// 
 /// # Returns
 /// 
@@ -68,7 +126,6 @@ pub struct MessageSentEvent {
 /// 
 /// # Errors
 /// 
-/// * `InvalidParticipants` - If participant list is empty or contains invalid keys
 /// * `UnauthorizedAccess` - If creator is not a verified agent
 /// 
 /// # Security
@@ -78,30 +135,43 @@ pub fn create_channel(
     ctx: Context<CreateChannel>,
     channel_data: ChannelCreationData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.creator.is_signer,
         PodAIMarketplaceError::UnauthorizedAccess
     );
 
-    // SECURITY: Input validation for participants list
-    require!(
-        !channel_data.participants.is_empty() && channel_data.participants.len() <= MAX_PARTICIPANTS_COUNT,
-        PodAIMarketplaceError::InputTooLong
-    );
-
     let channel = &mut ctx.accounts.channel;
     let clock = Clock::get()?;
 
     channel.creator = ctx.accounts.creator.key();
-    channel.participants = channel_data.participants.clone();
-    channel.channel_type = channel_data.channel_type.clone();
+    channel.owner = ctx.accounts.creator.key();
+    channel.channel_type = channel_data.channel_type;
     channel.is_private = channel_data.is_private;
+    channel.member_count = 0;
+    channel.max_members = if channel_data.channel_type == ChannelType::Direct {
+        DIRECT_CHANNEL_MEMBERS
+    } else {
+        channel_data.max_members
+    };
     channel.message_count = 0;
     channel.created_at = clock.unix_timestamp;
     channel.last_activity = clock.unix_timestamp;
     channel.is_active = true;
     channel.bump = ctx.bumps.channel;
+    channel.add_member()?;
+
+    let membership = &mut ctx.accounts.creator_membership;
+    membership.channel = channel.key();
+    membership.member = ctx.accounts.creator.key();
+    membership.role = ChannelRole::Admin;
+    membership.status = ChannelMembershipStatus::Active;
+    membership.invited_by = None;
+    membership.payer = ctx.accounts.creator.key();
+    membership.created_at = clock.unix_timestamp;
+    membership.joined_at = Some(clock.unix_timestamp);
+    membership.bump = ctx.bumps.creator_membership;
 
     emit!(ChannelCreatedEvent {
         channel: channel.key(),
@@ -131,7 +201,7 @@ pub fn create_channel(
 /// 
 /// # Errors
 /// 
-/// * `UnauthorizedAccess` - If sender is not a channel participant
+/// * `UnauthorizedAccess` - If sender's membership is still a pending invite
 /// * `ChannelInactive` - If the channel has been deactivated
 /// * `MessageTooLarge` - If message content exceeds 4KB limit
 /// 
@@ -148,6 +218,7 @@ pub fn send_message(
     ctx: Context<SendMessage>,
     message_data: MessageData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.sender.is_signer,
@@ -165,9 +236,10 @@ pub fn send_message(
     let channel = &mut ctx.accounts.channel;
     let clock = Clock::get()?;
 
-    // SECURITY FIX: Verify sender is a participant in the channel
+    // SECURITY FIX: Verify sender is a member of the channel; the membership's
+    // seeds bind it to this channel and sender
     require!(
-        channel.participants.contains(&ctx.accounts.sender.key()),
+        ctx.accounts.sender_membership.is_active(),
         PodAIMarketplaceError::UnauthorizedAccess
     );
 
@@ -207,6 +279,252 @@ pub fn send_message(
     Ok(())
 }
 
+/// Joins a public channel
+///
+/// The member pays for their own membership and gets it back on leaving.
+///
+/// # Errors
+///
+/// * `InvalidChannelConfiguration` - If the channel is not public
+/// * `ChannelNotFound` - If the channel has been deactivated
+/// * `ChannelFull` - If every seat is taken
+pub fn join_channel(ctx: Context<JoinChannel>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &mut ctx.accounts.channel;
+    let clock = Clock::get()?;
+
+    // SECURITY: Only public channels are open to anyone
+    require!(
+        channel.channel_type == ChannelType::Public,
+        PodAIMarketplaceError::InvalidChannelConfiguration
+    );
+    require!(channel.is_active, PodAIMarketplaceError::ChannelNotFound);
+    channel.add_member()?;
+
+    let membership = &mut ctx.accounts.membership;
+    membership.channel = channel.key();
+    membership.member = ctx.accounts.member.key();
+    membership.role = ChannelRole::Member;
+    membership.status = ChannelMembershipStatus::Active;
+    membership.invited_by = None;
+    membership.payer = ctx.accounts.member.key();
+    membership.created_at = clock.unix_timestamp;
+    membership.joined_at = Some(clock.unix_timestamp);
+    membership.bump = ctx.bumps.membership;
+
+    emit!(ChannelMemberJoinedEvent {
+        channel: channel.key(),
+        member: ctx.accounts.member.key(),
+        invited_by: None,
+    });
+
+    Ok(())
+}
+
+/// Invites an agent into a channel
+///
+/// Who may invite depends on the channel type: any member of a group, a
+/// moderator or above in public and private channels, and only the owner of a
+/// direct channel. The inviter pays for the invitee's membership, which holds
+/// a seat but cannot post until accepted.
+///
+/// # Errors
+///
+/// * `UnauthorizedAccess` - If the inviter may not invite into this channel
+/// * `ChannelNotFound` - If the channel has been deactivated
+/// * `ChannelFull` - If every seat is taken
+pub fn invite_to_channel(ctx: Context<InviteToChannel>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &mut ctx.accounts.channel;
+    let clock = Clock::get()?;
+
+    require!(channel.is_active, PodAIMarketplaceError::ChannelNotFound);
+    // SECURITY: Invite rights follow the channel type and the inviter's role
+    require!(
+        channel.can_invite(&ctx.accounts.inviter_membership),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    channel.add_member()?;
+
+    let membership = &mut ctx.accounts.membership;
+    membership.channel = channel.key();
+    membership.member = ctx.accounts.invitee.key();
+    membership.role = ChannelRole::Member;
+    membership.status = ChannelMembershipStatus::Invited;
+    membership.invited_by = Some(ctx.accounts.inviter.key());
+    membership.payer = ctx.accounts.inviter.key();
+    membership.created_at = clock.unix_timestamp;
+    membership.joined_at = None;
+    membership.bump = ctx.bumps.membership;
+
+    emit!(ChannelMemberInvitedEvent {
+        channel: channel.key(),
+        member: ctx.accounts.invitee.key(),
+        invited_by: ctx.accounts.inviter.key(),
+    });
+
+    Ok(())
+}
+
+/// Accepts a pending channel invite, after which the member can post
+pub fn accept_channel_invite(ctx: Context<AcceptChannelInvite>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    require!(ctx.accounts.channel.is_active, PodAIMarketplaceError::ChannelNotFound);
+    let clock = Clock::get()?;
+
+    let membership = &mut ctx.accounts.membership;
+    membership.accept(clock.unix_timestamp)?;
+
+    emit!(ChannelMemberJoinedEvent {
+        channel: ctx.accounts.channel.key(),
+        member: ctx.accounts.member.key(),
+        invited_by: membership.invited_by,
+    });
+
+    Ok(())
+}
+
+/// Leaves a channel, or declines a pending invite
+///
+/// The membership's rent goes back to whoever paid for it. The owner has to
+/// transfer ownership before leaving.
+pub fn leave_channel(ctx: Context<LeaveChannel>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &mut ctx.accounts.channel;
+
+    // SECURITY: A channel always keeps its owner
+    require!(
+        channel.owner != ctx.accounts.member.key(),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    channel.remove_member()?;
+
+    emit!(ChannelMemberRemovedEvent {
+        channel: channel.key(),
+        member: ctx.accounts.member.key(),
+        removed_by: None,
+    });
+
+    Ok(())
+}
+
+/// Removes a member or revokes a pending invite
+///
+/// Moderators and above can remove anyone they outrank; the membership's
+/// rent goes back to whoever paid for it.
+pub fn remove_channel_member(ctx: Context<RemoveChannelMember>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &mut ctx.accounts.channel;
+
+    // SECURITY: Only moderators and above, and only over lower ranks
+    require!(
+        channel.can_remove(&ctx.accounts.actor_membership, &ctx.accounts.membership),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    channel.remove_member()?;
+
+    emit!(ChannelMemberRemovedEvent {
+        channel: channel.key(),
+        member: ctx.accounts.membership.member,
+        removed_by: Some(ctx.accounts.actor.key()),
+    });
+
+    Ok(())
+}
+
+/// Changes a member's role
+///
+/// Admins and above can promote or demote members they outrank, up to a role
+/// below their own; only the owner can appoint admins.
+pub fn set_channel_member_role(
+    ctx: Context<SetChannelMemberRole>,
+    role: ChannelRole,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &ctx.accounts.channel;
+
+    // SECURITY: Roles can only be handed out below the actor's own rank
+    require!(
+        channel.can_set_role(&ctx.accounts.actor_membership, &ctx.accounts.membership, role),
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    ctx.accounts.membership.role = role;
+
+    emit!(ChannelMemberRoleChangedEvent {
+        channel: channel.key(),
+        member: ctx.accounts.membership.member,
+        role,
+        changed_by: ctx.accounts.actor.key(),
+    });
+
+    Ok(())
+}
+
+/// Hands channel ownership to another active member
+///
+/// The new owner is made an admin so the role survives a later transfer; the
+/// previous owner stays on as an admin.
+pub fn transfer_channel_ownership(ctx: Context<TransferChannelOwnership>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    require!(
+        ctx.accounts.new_owner_membership.is_active(),
+        PodAIMarketplaceError::InvalidStatusTransition
+    );
+
+    let channel = &mut ctx.accounts.channel;
+    let previous_owner = channel.owner;
+    channel.owner = ctx.accounts.new_owner_membership.member;
+    ctx.accounts.new_owner_membership.role = ChannelRole::Admin;
+
+    emit!(ChannelOwnershipTransferredEvent {
+        channel: channel.key(),
+        previous_owner,
+        new_owner: channel.owner,
+    });
+
+    Ok(())
+}
+
+/// Deactivates or reactivates a channel
+///
+/// An inactive channel accepts no messages, joins or invites; members can
+/// still leave.
+pub fn set_channel_active(ctx: Context<SetChannelActive>, is_active: bool) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &mut ctx.accounts.channel;
+    channel.is_active = is_active;
+
+    emit!(ChannelActiveChangedEvent {
+        channel: channel.key(),
+        is_active,
+    });
+
+    Ok(())
+}
+
+/// Closes a channel once the owner is its only member
+///
+/// Returns the channel's rent to its creator and the owner's membership rent
+/// to whoever paid for it. Message accounts are left in place.
+///
+/// # Errors
+///
+/// * `ChannelNotEmpty` - If other members or pending invites remain
+pub fn close_channel(ctx: Context<CloseChannel>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Messaging)?;
+    let channel = &ctx.accounts.channel;
+
+    require!(channel.member_count == 1, PodAIMarketplaceError::ChannelNotEmpty);
+
+    emit!(ChannelClosedEvent {
+        channel: channel.key(),
+        owner: channel.owner,
+        message_count: channel.message_count,
+    });
+
+    Ok(())
+}
+
 // =====================================================
 // ACCOUNT STRUCTS
 // =====================================================
@@ -214,6 +532,8 @@ pub fn send_message(
 #[derive(Accounts)]
 #[instruction(channel_data: ChannelCreationData)]
 pub struct CreateChannel<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
@@ -223,6 +543,15 @@ pub struct CreateChannel<'info> {
     )]
     pub channel: Account<'info, Channel>,
     
+    #[account(
+        init,
+        payer = creator,
+        space = ChannelMembership::LEN,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_membership: Account<'info, ChannelMembership>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
@@ -232,6 +561,8 @@ pub struct CreateChannel<'info> {
 #[derive(Accounts)]
 #[instruction(message_data: MessageData)]
 pub struct SendMessage<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = sender,
@@ -244,8 +575,219 @@ pub struct SendMessage<'info> {
     #[account(mut)]
     pub channel: Account<'info, Channel>,
     
+    #[account(
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), sender.key().as_ref()],
+        bump = sender_membership.bump
+    )]
+    pub sender_membership: Account<'info, ChannelMembership>,
+    
     #[account(mut)]
     pub sender: Signer<'info>,
     
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct JoinChannel<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        init,
+        payer = member,
+        space = ChannelMembership::LEN,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), member.key().as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    #[account(mut)]
+    pub member: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InviteToChannel<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), inviter.key().as_ref()],
+        bump = inviter_membership.bump
+    )]
+    pub inviter_membership: Account<'info, ChannelMembership>,
+    
+    #[account(
+        init,
+        payer = inviter,
+        space = ChannelMembership::LEN,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), invitee.key().as_ref()],
+        bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    /// CHECK: Only identifies the invitee; nothing is read from or written to it
+    pub invitee: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub inviter: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptChannelInvite<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        mut,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), member.key().as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct LeaveChannel<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), member.key().as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    /// CHECK: Receives the membership's rent; must be whoever paid for it
+    #[account(mut, address = membership.payer)]
+    pub payer: UncheckedAccount<'info>,
+    
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveChannelMember<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), actor.key().as_ref()],
+        bump = actor_membership.bump
+    )]
+    pub actor_membership: Account<'info, ChannelMembership>,
+    
+    #[account(
+        mut,
+        close = payer,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), membership.member.as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    /// CHECK: Receives the membership's rent; must be whoever paid for it
+    #[account(mut, address = membership.payer)]
+    pub payer: UncheckedAccount<'info>,
+    
+    pub actor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetChannelMemberRole<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), actor.key().as_ref()],
+        bump = actor_membership.bump
+    )]
+    pub actor_membership: Account<'info, ChannelMembership>,
+    
+    #[account(
+        mut,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), membership.member.as_ref()],
+        bump = membership.bump
+    )]
+    pub membership: Account<'info, ChannelMembership>,
+    
+    pub actor: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TransferChannelOwnership<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        has_one = owner @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        mut,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), new_owner_membership.member.as_ref()],
+        bump = new_owner_membership.bump
+    )]
+    pub new_owner_membership: Account<'info, ChannelMembership>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetChannelActive<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        has_one = owner @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub channel: Account<'info, Channel>,
+    
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CloseChannel<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        close = creator,
+        has_one = owner @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = creator @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub channel: Account<'info, Channel>,
+    
+    #[account(
+        mut,
+        close = membership_payer,
+        seeds = [CHANNEL_MEMBERSHIP_SEED, channel.key().as_ref(), owner.key().as_ref()],
+        bump = owner_membership.bump
+    )]
+    pub owner_membership: Account<'info, ChannelMembership>,
+    
+    /// CHECK: Receives the owner membership's rent; must be whoever paid for it
+    #[account(mut, address = owner_membership.payer)]
+    pub membership_payer: UncheckedAccount<'info>,
+    
+    /// CHECK: Receives the channel's rent; checked against the channel's creator
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+    
+    pub owner: Signer<'info>,
+}
@@ -0,0 +1,770 @@
+/*!
+ * Metering Instructions Module
+ *
+ * Usage-metered billing for agents priced per task, per hour or per token.
+ * A client opens a meter with an agent at a per-unit rate and prepays into
+ * its vault. The agent's reporter key signs each usage increment; anyone may
+ * submit it with an ed25519 verification instruction placed immediately
+ * before. The client can dispute a report within the meter's window, and
+ * undisputed reports are charged from the prepaid balance once it closes.
+ * Disputed reports are held until an arbitrator from the dispute registry
+ * rules what share of them the client pays.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::PodAIMarketplaceError;
+use crate::security::{verify_ed25519_signature, ProtocolSubsystem};
+use crate::token_utils::{close_token_account, collect_protocol_fee, net_transfer_amount, transfer_tokens};
+use crate::instructions::royalty::pay_royalty;
+
+/// Opens a usage meter between the signing client and an agent
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the meter, its vault and the agent
+/// * `meter_id` - Client-chosen id distinguishing meters with the same agent
+/// * `rate_per_unit` - Price charged per reported unit
+/// * `dispute_window` - Seconds the client has to dispute each report
+///
+/// # Errors
+///
+/// * `AgentNotActive` - If the agent is deactivated
+/// * `InvalidPaymentAmount` - If the rate is zero
+/// * `InvalidDuration` - If the dispute window is out of range
+pub fn open_usage_meter(
+    ctx: Context<OpenUsageMeter>,
+    meter_id: u64,
+    rate_per_unit: u64,
+    dispute_window: i64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let agent = &ctx.accounts.agent;
+
+    require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
+    require!(rate_per_unit > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+    require!(
+        (MIN_USAGE_DISPUTE_WINDOW..=MAX_USAGE_DISPUTE_WINDOW).contains(&dispute_window),
+        PodAIMarketplaceError::InvalidDuration
+    );
+
+    let meter = &mut ctx.accounts.meter;
+    meter.agent = agent.key();
+    meter.client = ctx.accounts.client.key();
+    meter.meter_id = meter_id;
+    meter.reporter = agent.owner;
+    meter.payment_mint = ctx.accounts.payment_mint.key();
+    meter.rate_per_unit = rate_per_unit;
+    meter.dispute_window = dispute_window;
+    meter.next_sequence = 0;
+    meter.open_reports = 0;
+    meter.units_reported = 0;
+    meter.units_settled = 0;
+    meter.units_disputed = 0;
+    meter.total_charged = 0;
+    meter.created_at = Clock::get()?.unix_timestamp;
+    meter.bump = ctx.bumps.meter;
+
+    emit!(UsageMeterOpenedEvent {
+        meter: meter.key(),
+        agent: meter.agent,
+        client: meter.client,
+        rate_per_unit,
+        dispute_window,
+    });
+
+    Ok(())
+}
+
+/// Adds to a meter's prepaid balance
+pub fn deposit_usage_balance(ctx: Context<DepositUsageBalance>, amount: u64) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require!(amount > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.client_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.meter_vault,
+        &ctx.accounts.client.to_account_info(),
+        amount,
+        &[],
+    )?;
+    let deposited = net_transfer_amount(&ctx.accounts.payment_mint, amount)?;
+
+    emit!(UsageBalanceDepositedEvent {
+        meter: ctx.accounts.meter.key(),
+        amount: deposited,
+    });
+
+    Ok(())
+}
+
+/// Delegates report signing to another key, e.g. the agent's runtime
+pub fn set_usage_reporter(ctx: Context<SetUsageReporter>, reporter: Pubkey) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(reporter != Pubkey::default(), PodAIMarketplaceError::InvalidConfiguration);
+
+    let meter = &mut ctx.accounts.meter;
+    meter.reporter = reporter;
+
+    emit!(UsageReporterChangedEvent {
+        meter: meter.key(),
+        reporter,
+    });
+
+    Ok(())
+}
+
+/// Records a usage increment signed by the meter's reporter
+///
+/// The transaction must include an ed25519 program instruction immediately
+/// before this one, verifying the reporter's signature over
+/// `UsageMeter::report_message` for this meter instance and its next
+/// sequence number.
+///
+/// # Errors
+///
+/// * `SignatureVerificationFailed` - If the preceding instruction doesn't
+///   verify the reporter's signature over the expected message
+/// * `InvalidVolume` - If `units` is zero
+pub fn submit_usage_report(ctx: Context<SubmitUsageReport>, units: u64) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require!(units > 0, PodAIMarketplaceError::InvalidVolume);
+
+    let meter = &ctx.accounts.meter;
+    let sequence = meter.next_sequence;
+    let amount = meter.charge_for(units)?;
+
+    // SECURITY: Only usage signed by the agent's reporter can be billed
+    let message = UsageMeter::report_message(
+        &meter.key(),
+        meter.created_at,
+        sequence,
+        units,
+        meter.rate_per_unit,
+    );
+    verify_ed25519_signature(&ctx.accounts.instructions_sysvar, &meter.reporter, &message)?;
+
+    let clock = Clock::get()?;
+    let report = &mut ctx.accounts.report;
+    report.meter = meter.key();
+    report.sequence = sequence;
+    report.units = units;
+    report.amount = amount;
+    report.submitter = ctx.accounts.submitter.key();
+    report.reported_at = clock.unix_timestamp;
+    report.disputed = false;
+    report.resolved = false;
+    report.bump = ctx.bumps.report;
+
+    let meter = &mut ctx.accounts.meter;
+    meter.next_sequence = sequence
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    meter.open_reports = meter.open_reports
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    meter.units_reported = meter.units_reported
+        .checked_add(units)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    emit!(UsageReportedEvent {
+        meter: meter.key(),
+        report: report.key(),
+        sequence,
+        units,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Disputes a usage report before its window closes
+///
+/// A disputed report can't be settled until an arbitrator rules on it with
+/// `resolve_usage_dispute`.
+pub fn dispute_usage_report(ctx: Context<DisputeUsageReport>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let report = &mut ctx.accounts.report;
+
+    require!(!report.disputed, PodAIMarketplaceError::InvalidStatusTransition);
+    require!(
+        report.dispute_window_open(ctx.accounts.meter.dispute_window, clock.unix_timestamp),
+        PodAIMarketplaceError::DisputeWindowClosed
+    );
+
+    report.disputed = true;
+
+    let meter = &mut ctx.accounts.meter;
+    meter.units_disputed = meter.units_disputed
+        .checked_add(report.units)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    emit!(UsageDisputedEvent {
+        meter: meter.key(),
+        report: report.key(),
+        sequence: report.sequence,
+        units: report.units,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Rules on a disputed usage report
+///
+/// The arbitrator must be allowlisted in the arbitrator registry or staked
+/// in its pool, and can't be the client or the agent owner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the meter, report and arbitrator registry
+/// * `charge_bps` - Share of the reported charge the client pays, in basis
+///   points; the rest is waived
+///
+/// # Errors
+///
+/// * `ArbitratorNotEligible` - If the signer is not an eligible arbitrator or
+///   is a party to the meter
+/// * `InvalidDisputeStatus` - If the report isn't awaiting a ruling
+/// * `InvalidPercentage` - If `charge_bps` exceeds 10,000
+pub fn resolve_usage_dispute(ctx: Context<ResolveUsageDispute>, charge_bps: u16) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let arbitrator = ctx.accounts.arbitrator.key();
+    let staked_amount = match ctx.accounts.arbitrator_stake.as_mut() {
+        Some(stake) => {
+            stake.last_assigned_at = clock.unix_timestamp;
+            stake.amount
+        }
+        None => 0,
+    };
+
+    // SECURITY: Only an eligible arbitrator outside the meter may rule
+    require!(
+        ctx.accounts.registry.is_eligible(arbitrator, staked_amount),
+        PodAIMarketplaceError::ArbitratorNotEligible
+    );
+    require!(
+        arbitrator != ctx.accounts.meter.client && arbitrator != ctx.accounts.agent.owner,
+        PodAIMarketplaceError::ArbitratorNotEligible
+    );
+
+    let report = &mut ctx.accounts.report;
+    let charged = report.resolve_dispute(charge_bps)?;
+
+    emit!(UsageDisputeResolvedEvent {
+        meter: ctx.accounts.meter.key(),
+        report: report.key(),
+        sequence: report.sequence,
+        arbitrator,
+        charge_bps,
+        charged,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Settles a usage report once its dispute window has closed
+/// (permissionless crank)
+///
+/// The report's charge, or the arbitrator's award if it was disputed, is
+/// paid from the prepaid balance to the agent owner, less the protocol fee
+/// and any creator royalty.
+/// The report is closed and its rent returned to whoever submitted it.
+///
+/// # Errors
+///
+/// * `DisputeWindowActive` - If the client can still dispute the report
+/// * `InvalidDisputeStatus` - If the report is disputed and not yet ruled on
+/// * `InsufficientBalance` - If the prepaid balance can't cover the charge
+pub fn settle_usage_report(ctx: Context<SettleUsageReport>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let meter = &ctx.accounts.meter;
+    let report = &ctx.accounts.report;
+
+    require!(
+        !report.dispute_window_open(meter.dispute_window, clock.unix_timestamp),
+        PodAIMarketplaceError::DisputeWindowActive
+    );
+    require!(!report.awaiting_ruling(), PodAIMarketplaceError::InvalidDisputeStatus);
+
+    let mut provider_received = 0;
+    let charged = report.amount;
+
+    if charged > 0 {
+        require!(ctx.accounts.meter_vault.amount >= charged, PodAIMarketplaceError::InsufficientBalance);
+
+        let agent_key = meter.agent;
+        let client_key = meter.client;
+        let meter_id = meter.meter_id.to_le_bytes();
+        let bump = [meter.bump];
+        let seeds: &[&[u8]] = &[USAGE_METER_SEED, agent_key.as_ref(), client_key.as_ref(), &meter_id, &bump];
+        let meter_info = ctx.accounts.meter.to_account_info();
+
+        let provider_amount = collect_protocol_fee(
+            &ctx.accounts.protocol_config,
+            FeeTransactionType::Payment,
+            &ctx.accounts.token_program,
+            &ctx.accounts.meter_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.treasury_vault,
+            &meter_info,
+            charged,
+            &[seeds],
+        )?;
+        let provider_amount = pay_royalty(
+            &ctx.accounts.royalty_stream.to_account_info(),
+            ctx.accounts.royalty_token_account.as_ref(),
+            &ctx.accounts.token_program,
+            &ctx.accounts.meter_vault,
+            &ctx.accounts.payment_mint,
+            &meter_info,
+            &[seeds],
+            agent_key,
+            meter_info.key(),
+            provider_amount,
+        )?;
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.meter_vault,
+            &ctx.accounts.payment_mint,
+            &ctx.accounts.provider_token_account,
+            &meter_info,
+            provider_amount,
+            &[seeds],
+        )?;
+        provider_received = net_transfer_amount(&ctx.accounts.payment_mint, provider_amount)?;
+
+        let agent = &mut ctx.accounts.agent;
+        agent.total_earnings = agent.total_earnings
+            .checked_add(provider_received)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    }
+
+    let units = ctx.accounts.report.units;
+    let sequence = ctx.accounts.report.sequence;
+    let meter = &mut ctx.accounts.meter;
+    meter.open_reports = meter.open_reports.saturating_sub(1);
+    if charged > 0 {
+        meter.units_settled = meter.units_settled
+            .checked_add(units)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        meter.total_charged = meter.total_charged
+            .checked_add(charged)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    }
+
+    emit!(UsageSettledEvent {
+        meter: meter.key(),
+        sequence,
+        units,
+        charged,
+        provider_received,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Closes a meter, refunding the unused prepaid balance to the client
+///
+/// Either party may close once every submitted report has been settled.
+pub fn close_usage_meter(ctx: Context<CloseUsageMeter>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let meter = &ctx.accounts.meter;
+
+    // SECURITY: Only the client or the provider may close the meter
+    let caller = ctx.accounts.caller.key();
+    require!(
+        caller == meter.client || caller == ctx.accounts.agent.owner,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    require!(meter.open_reports == 0, PodAIMarketplaceError::UsageReportsOutstanding);
+
+    let agent_key = meter.agent;
+    let client_key = meter.client;
+    let meter_id = meter.meter_id.to_le_bytes();
+    let bump = [meter.bump];
+    let seeds: &[&[u8]] = &[USAGE_METER_SEED, agent_key.as_ref(), client_key.as_ref(), &meter_id, &bump];
+    let meter_info = ctx.accounts.meter.to_account_info();
+
+    let refunded = ctx.accounts.meter_vault.amount;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.meter_vault,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.client_token_account,
+        &meter_info,
+        refunded,
+        &[seeds],
+    )?;
+    close_token_account(
+        &ctx.accounts.token_program,
+        &ctx.accounts.meter_vault,
+        &ctx.accounts.client.to_account_info(),
+        &meter_info,
+        &[seeds],
+    )?;
+
+    emit!(UsageMeterClosedEvent {
+        meter: meter_info.key(),
+        closed_by: caller,
+        total_charged: ctx.accounts.meter.total_charged,
+        refunded,
+    });
+
+    Ok(())
+}
+
+// =====================================================
+// ACCOUNT CONTEXTS
+// =====================================================
+
+#[derive(Accounts)]
+#[instruction(meter_id: u64)]
+pub struct OpenUsageMeter<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = client,
+        space = UsageMeter::LEN,
+        seeds = [USAGE_METER_SEED, agent.key().as_ref(), client.key().as_ref(), &meter_id.to_le_bytes()],
+        bump
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        init,
+        payer = client,
+        seeds = [USAGE_METER_VAULT_SEED, meter.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = meter,
+        token::token_program = token_program
+    )]
+    pub meter_vault: InterfaceAccount<'info, TokenAccount>,
+    pub agent: Account<'info, Agent>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub client: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositUsageBalance<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), client.key().as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump,
+        has_one = client @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_VAULT_SEED, meter.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = meter,
+        token::token_program = token_program
+    )]
+    pub meter_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = client_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub client_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub client: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetUsageReporter<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), meter.client.as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        constraint = agent.key() == meter.agent @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitUsageReport<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), meter.client.as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        init,
+        payer = submitter,
+        space = UsageReport::LEN,
+        seeds = [USAGE_REPORT_SEED, meter.key().as_ref(), &meter.next_sequence.to_le_bytes()],
+        bump
+    )]
+    pub report: Account<'info, UsageReport>,
+    /// CHECK: The instructions sysvar, read to find the ed25519 verification
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DisputeUsageReport<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), client.key().as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump,
+        has_one = client @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        mut,
+        seeds = [USAGE_REPORT_SEED, meter.key().as_ref(), &report.sequence.to_le_bytes()],
+        bump = report.bump,
+        has_one = meter @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub report: Account<'info, UsageReport>,
+    pub client: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveUsageDispute<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), meter.client.as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        mut,
+        seeds = [USAGE_REPORT_SEED, meter.key().as_ref(), &report.sequence.to_le_bytes()],
+        bump = report.bump,
+        has_one = meter @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub report: Account<'info, UsageReport>,
+    #[account(
+        constraint = agent.key() == meter.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        seeds = [ARBITRATOR_REGISTRY_SEED],
+        bump = registry.bump
+    )]
+    pub registry: Account<'info, ArbitratorRegistry>,
+    /// Stake of the arbitrator when they qualify through the staked pool
+    #[account(
+        mut,
+        seeds = [ARBITRATOR_STAKE_SEED, arbitrator.key().as_ref()],
+        bump = arbitrator_stake.bump
+    )]
+    pub arbitrator_stake: Option<Account<'info, ArbitratorStake>>,
+    pub arbitrator: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleUsageReport<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), meter.client.as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        mut,
+        seeds = [USAGE_REPORT_SEED, meter.key().as_ref(), &report.sequence.to_le_bytes()],
+        bump = report.bump,
+        has_one = meter @ PodAIMarketplaceError::InvalidConfiguration,
+        has_one = submitter @ PodAIMarketplaceError::InvalidConfiguration,
+        close = submitter
+    )]
+    pub report: Account<'info, UsageReport>,
+    #[account(
+        mut,
+        constraint = agent.key() == meter.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_VAULT_SEED, meter.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = meter,
+        token::token_program = token_program
+    )]
+    pub meter_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == agent.owner @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = provider_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The agent's royalty stream PDA. Always required so settlement
+    /// cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: Receives the report's rent; matched against `report.submitter`
+    #[account(mut)]
+    pub submitter: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CloseUsageMeter<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_SEED, meter.agent.as_ref(), client.key().as_ref(), &meter.meter_id.to_le_bytes()],
+        bump = meter.bump,
+        has_one = client @ PodAIMarketplaceError::UnauthorizedAccess,
+        has_one = payment_mint @ PodAIMarketplaceError::InvalidConfiguration,
+        close = client
+    )]
+    pub meter: Account<'info, UsageMeter>,
+    #[account(
+        constraint = agent.key() == meter.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        mut,
+        seeds = [USAGE_METER_VAULT_SEED, meter.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = meter,
+        token::token_program = token_program
+    )]
+    pub meter_vault: InterfaceAccount<'info, TokenAccount>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = client_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub client_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub client: SystemAccount<'info>,
+    pub caller: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct UsageMeterOpenedEvent {
+    pub meter: Pubkey,
+    pub agent: Pubkey,
+    pub client: Pubkey,
+    pub rate_per_unit: u64,
+    pub dispute_window: i64,
+}
+
+#[event]
+pub struct UsageBalanceDepositedEvent {
+    pub meter: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct UsageReporterChangedEvent {
+    pub meter: Pubkey,
+    pub reporter: Pubkey,
+}
+
+#[event]
+pub struct UsageReportedEvent {
+    pub meter: Pubkey,
+    pub report: Pubkey,
+    pub sequence: u64,
+    pub units: u64,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsageDisputedEvent {
+    pub meter: Pubkey,
+    pub report: Pubkey,
+    pub sequence: u64,
+    pub units: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsageDisputeResolvedEvent {
+    pub meter: Pubkey,
+    pub report: Pubkey,
+    pub sequence: u64,
+    pub arbitrator: Pubkey,
+    pub charge_bps: u16,
+    pub charged: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsageSettledEvent {
+    pub meter: Pubkey,
+    pub sequence: u64,
+    pub units: u64,
+    pub charged: u64,
+    pub provider_received: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UsageMeterClosedEvent {
+    pub meter: Pubkey,
+    pub closed_by: Pubkey,
+    pub total_charged: u64,
+    pub refunded: u64,
+}
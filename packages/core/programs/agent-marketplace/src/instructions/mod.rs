@@ -19,10 +19,14 @@ pub mod extensions;
 pub mod incentives;
 pub mod marketplace;
 pub mod messaging;
+pub mod metering;
 pub mod negotiation;
 pub mod pricing;
+pub mod protocol_config;
 pub mod replication;
+pub mod reputation;
 pub mod royalty;
+pub mod subscription;
 pub mod work_orders;
 pub mod compliance_governance;
 
@@ -3,11 +3,22 @@
  * 
  * Implements price negotiation and deal-making capabilities between
  * agents and clients with automated negotiation support.
+ *
+ * Either the client or the provider may initiate; the initiator declares its
+ * side. Accepting an offer locks the agreed price from the client in an
+ * escrow vault and opens a work order in the same transaction; rejected and
+ * expired negotiations are closed.
+ * Negotiations are seeded by an initiator-chosen id, so a client and provider
+ * can negotiate again after a deal.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
-use crate::PodAIMarketplaceError;
+use crate::simple_optimization::{InputValidator, SecurityLogger};
+use crate::token_utils::{net_transfer_amount, transfer_tokens};
+use crate::{PodAIMarketplaceError, WorkOrderCreatedEvent};
+use crate::security::ProtocolSubsystem;
 
 /// Initiates a price negotiation session between buyer and seller
 /// 
@@ -17,12 +28,16 @@ use crate::PodAIMarketplaceError;
 /// # Arguments
 /// 
 /// * `ctx` - The context containing negotiation accounts
+/// * `negotiation_id` - Initiator-chosen id, unique per counterparty
 /// * `negotiation_data` - Initial negotiation parameters including:
 ///   - `service_or_job` - Reference to service/job being negotiated
 ///   - `initial_offer` - Opening offer amount
 ///   - `negotiation_terms` - What's negotiable (price, timeline, scope)
 ///   - `max_rounds` - Maximum negotiation rounds (default: 10)
 ///   - `auto_accept_threshold` - Price to auto-accept
+/// * `payment_token` - Mint the agreed price will be paid in
+/// * `initiator_is_client` - Whether the initiator is the buyer; otherwise the
+///   initiator is the provider and the counterparty funds the work order
 /// 
 /// # Returns
 /// 
@@ -48,10 +63,14 @@ use crate::PodAIMarketplaceError;
 /// - Bundle services for better deals
 pub fn initiate_negotiation(
     ctx: Context<InitiateNegotiation>,
+    negotiation_id: u64,
     initial_offer: u64,
     auto_accept_threshold: u64,
     negotiation_deadline: i64,
+    payment_token: Pubkey,
+    initiator_is_client: bool,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.initiator.is_signer,
@@ -79,17 +98,26 @@ pub fn initiate_negotiation(
         PodAIMarketplaceError::InvalidDeadline
     );
     
-    // SECURITY: Auto-accept threshold validation removed - it depends on negotiation direction
-    // The threshold logic should be validated during counter-offer processing based on who is buyer/seller
+    // The auto-accept threshold is interpreted against the recorded client side
+    // when counter-offers are made
 
     negotiation.initiator = ctx.accounts.initiator.key();
     negotiation.counterparty = ctx.accounts.counterparty.key();
+    negotiation.negotiation_id = negotiation_id;
     negotiation.initial_offer = initial_offer;
     negotiation.current_offer = initial_offer;
     negotiation.status = NegotiationStatus::InitialOffer;
     negotiation.auto_accept_threshold = auto_accept_threshold;
     negotiation.negotiation_deadline = negotiation_deadline;
     negotiation.created_at = clock.unix_timestamp;
+    negotiation.payment_token = payment_token;
+    negotiation.last_offer_by = ctx.accounts.initiator.key();
+    negotiation.work_order = None;
+    negotiation.client = if initiator_is_client {
+        ctx.accounts.initiator.key()
+    } else {
+        ctx.accounts.counterparty.key()
+    };
     negotiation.bump = ctx.bumps.negotiation;
 
     // Initialize terms vector and other fields
@@ -127,7 +155,7 @@ pub fn initiate_negotiation(
 /// 
 /// # Errors
 /// 
-/// * `NotYourTurn` - If it's not the party's turn
+/// * `UnauthorizedAccess` - If the sender is not a party or it's not their turn
 /// * `OfferNotImproved` - If offer doesn't improve terms
 /// * `NegotiationExpired` - If round timeout exceeded
 /// * `MaxRoundsReached` - If negotiation limit hit
@@ -148,42 +176,21 @@ pub fn make_counter_offer(
     counter_offer: u64,
     message: String,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let negotiation = &mut ctx.accounts.negotiation;
-    let clock = Clock::get()?;
 
-    require!(negotiation.status == NegotiationStatus::InitialOffer || 
-             negotiation.status == NegotiationStatus::CounterOffer, 
-             PodAIMarketplaceError::InvalidApplicationStatus);
-    require!(clock.unix_timestamp < negotiation.negotiation_deadline, PodAIMarketplaceError::InvalidDeadline);
-
-    // Store current offer before modifying
-    let current_offer = negotiation.current_offer;
-    negotiation.counter_offers.push(current_offer);
-    negotiation.current_offer = counter_offer;
-    negotiation.status = NegotiationStatus::CounterOffer;
+    require!(
+        (MIN_PAYMENT_AMOUNT..=MAX_PAYMENT_AMOUNT).contains(&counter_offer),
+        PodAIMarketplaceError::InvalidPaymentAmount
+    );
 
-    // Update last activity
-    negotiation.last_activity = clock.unix_timestamp;
+    // Records the previous offer and hands the turn to the other party
+    negotiation.make_counter_offer(ctx.accounts.sender.key(), counter_offer)?;
 
-    // Check for auto-acceptance
-    // SECURITY: Fixed auto-accept logic to consider negotiation direction
-    // If initiator is buyer (offering to pay), auto-accept when seller's counter is at or below threshold
-    // If initiator is seller (asking for payment), auto-accept when buyer's counter is at or above threshold
-    let should_auto_accept = if ctx.accounts.sender.key() == negotiation.initiator {
-        // Initiator making counter-offer (likely adjusting their own offer)
-        false // Don't auto-accept own offers
-    } else {
-        // Counterparty making counter-offer
-        if negotiation.initial_offer > 0 {
-            // Assume initiator is buyer if they made positive initial offer
-            counter_offer <= negotiation.auto_accept_threshold
-        } else {
-            // Complex case - would need additional context
-            counter_offer >= negotiation.auto_accept_threshold
-        }
-    };
-    
-    if should_auto_accept {
+    // SECURITY: Auto-accept follows the recorded client side: a client
+    // initiator accepts counters at or below the threshold, a provider
+    // initiator accepts counters at or above it
+    if negotiation.meets_auto_accept(ctx.accounts.sender.key(), counter_offer) {
         negotiation.status = NegotiationStatus::AutoAccepted;
     }
 
@@ -198,14 +205,205 @@ pub fn make_counter_offer(
     Ok(())
 }
 
+/// Accepts the standing offer and opens a funded work order
+/// 
+/// The party that did not make the current offer accepts it; auto-accepted
+/// offers can be converted by either party, even after the deadline. The
+/// agreed price is moved from the client's token account into a work order
+/// escrow vault and a work order is created at that price with the negotiated
+/// terms as requirements, all in one transaction. When the provider accepts,
+/// the client co-signs to fund the escrow.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing negotiation, work order and escrow accounts
+/// * `title` - Work order title
+/// * `description` - Work order description
+/// * `deadline` - Delivery deadline for the work order
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful acceptance
+/// 
+/// # Errors
+/// 
+/// * `NegotiationExpired` - If the deadline of an open negotiation has passed
+/// * `InvalidNegotiationStatus` - If the negotiation is neither open nor auto-accepted
+/// * `UnauthorizedAccess` - If the acceptor made the standing offer, or the
+///   funding signer is not the recorded client
+/// * `InsufficientBalance` - If the client cannot fund the escrow
+/// 
+/// # State Changes
+/// 
+/// - Negotiation status becomes `Accepted`
+/// - Creates the work order (status `InProgress`), escrow and escrow vault
+/// - Transfers the agreed price from the client into the vault
+pub fn accept_negotiation(
+    ctx: Context<AcceptNegotiation>,
+    title: String,
+    description: String,
+    deadline: i64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    InputValidator::validate_string(&title, MAX_TITLE_LENGTH, "title")?;
+    InputValidator::validate_string(&description, MAX_DESCRIPTION_LENGTH, "description")?;
+    InputValidator::validate_future_timestamp(deadline, "deadline")?;
+    
+    let clock = Clock::get()?;
+    let negotiation = &mut ctx.accounts.negotiation;
+    negotiation.accept_offer(ctx.accounts.acceptor.key(), clock.unix_timestamp)?;
+    
+    let price = negotiation.current_offer;
+    require!(
+        ctx.accounts.client_token_account.amount >= price,
+        PodAIMarketplaceError::InsufficientBalance
+    );
+    
+    // Lock the agreed price before the work order exists, so an accepted
+    // negotiation is never left without funding
+    let escrowed_amount = net_transfer_amount(&ctx.accounts.payment_mint, price)?;
+    require!(escrowed_amount > 0, PodAIMarketplaceError::InvalidEscrowAmount);
+    
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.client_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.client.to_account_info(),
+        price,
+        &[],
+    )?;
+    
+    let negotiation_key = negotiation.key();
+    let work_order_key = ctx.accounts.work_order.key();
+    let provider = negotiation.provider();
+    ctx.accounts.escrow.initialize(EscrowInit {
+        client: negotiation.client,
+        agent: provider,
+        task_id: negotiation_key.to_string(),
+        amount: escrowed_amount,
+        expires_at: deadline,
+        work_order: work_order_key,
+        token_mint: ctx.accounts.payment_mint.key(),
+        bump: ctx.bumps.escrow,
+    })?;
+    
+    let work_order = &mut ctx.accounts.work_order;
+    work_order.client = negotiation.client;
+    work_order.provider = provider;
+    work_order.provider_agent = ctx.accounts.provider_agent.key();
+    work_order.title = title;
+    work_order.description = description;
+    work_order.requirements = negotiation.terms.clone();
+    work_order.payment_amount = price;
+    work_order.payment_token = negotiation.payment_token;
+    work_order.status = WorkOrderStatus::InProgress;
+    work_order.created_at = clock.unix_timestamp;
+    work_order.updated_at = clock.unix_timestamp;
+    work_order.deadline = deadline;
+    work_order.delivered_at = None;
+    work_order.bump = ctx.bumps.work_order;
+    
+    negotiation.work_order = Some(work_order_key);
+    
+    SecurityLogger::log_security_event("NEGOTIATION_ACCEPTED", ctx.accounts.acceptor.key(),
+        &format!("negotiation: {}, work_order: {}, price: {}", negotiation_key, work_order_key, price));
+    
+    emit!(WorkOrderCreatedEvent {
+        work_order: work_order_key,
+        client: negotiation.client,
+        provider,
+        amount: price,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    emit!(NegotiationAcceptedEvent {
+        negotiation: negotiation_key,
+        acceptor: ctx.accounts.acceptor.key(),
+        work_order: work_order_key,
+        agreed_price: price,
+    });
+    
+    Ok(())
+}
+
+/// Rejects an open negotiation
+/// 
+/// Either party can walk away while the negotiation is open. The account is
+/// closed and its rent returned to the initiator, so the pair can negotiate again.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the negotiation account
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful rejection
+/// 
+/// # Errors
+/// 
+/// * `InvalidNegotiationStatus` - If the negotiation is no longer open or was auto-accepted
+/// * `UnauthorizedAccess` - If the signer is not a party to the negotiation
+pub fn reject_negotiation(ctx: Context<RejectNegotiation>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let negotiation = &mut ctx.accounts.negotiation;
+    negotiation.reject_offer(ctx.accounts.rejector.key(), Clock::get()?.unix_timestamp)?;
+    
+    emit!(NegotiationRejectedEvent {
+        negotiation: negotiation.key(),
+        rejector: ctx.accounts.rejector.key(),
+        last_offer: negotiation.current_offer,
+    });
+    
+    Ok(())
+}
+
+/// Expires a negotiation whose deadline has passed
+/// 
+/// Permissionless crank. Stale open negotiations are marked `Expired` and
+/// closed, returning rent to the initiator. Auto-accepted negotiations do not
+/// expire.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the negotiation account
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful expiry
+/// 
+/// # Errors
+/// 
+/// * `InvalidNegotiationStatus` - If the negotiation is not open or not yet past its deadline
+pub fn expire_negotiation(ctx: Context<ExpireNegotiation>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let clock = Clock::get()?;
+    let negotiation = &mut ctx.accounts.negotiation;
+    negotiation.check_expiry(clock.unix_timestamp);
+    require!(
+        negotiation.status == NegotiationStatus::Expired,
+        PodAIMarketplaceError::InvalidNegotiationStatus
+    );
+    
+    emit!(NegotiationExpiredEvent {
+        negotiation: negotiation.key(),
+        expired_at: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 // Context structures
 #[derive(Accounts)]
+#[instruction(negotiation_id: u64)]
 pub struct InitiateNegotiation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = initiator,
         space = NegotiationChatbot::LEN,
-        seeds = [b"negotiation", initiator.key().as_ref(), counterparty.key().as_ref()],
+        seeds = [b"negotiation", initiator.key().as_ref(), counterparty.key().as_ref(), &negotiation_id.to_le_bytes()],
         bump
     )]
     pub negotiation: Account<'info, NegotiationChatbot>,
@@ -218,11 +416,130 @@ pub struct InitiateNegotiation<'info> {
 
 #[derive(Accounts)]
 pub struct MakeCounterOffer<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub negotiation: Account<'info, NegotiationChatbot>,
     pub sender: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct AcceptNegotiation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [
+            b"negotiation",
+            negotiation.initiator.as_ref(),
+            negotiation.counterparty.as_ref(),
+            &negotiation.negotiation_id.to_le_bytes()
+        ],
+        bump = negotiation.bump
+    )]
+    pub negotiation: Account<'info, NegotiationChatbot>,
+    #[account(
+        init,
+        payer = client,
+        space = WorkOrder::LEN,
+        seeds = [WORK_ORDER_SEED, negotiation.key().as_ref()],
+        bump
+    )]
+    pub work_order: Account<'info, WorkOrder>,
+    #[account(
+        init,
+        payer = client,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = client,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    /// Agent the provider delivers the work with
+    #[account(
+        constraint = provider_agent.owner == negotiation.provider() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub provider_agent: Account<'info, Agent>,
+    #[account(
+        constraint = payment_mint.key() == negotiation.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = client_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub client_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// The recorded client, who funds the work order
+    #[account(
+        mut,
+        constraint = client.key() == negotiation.client @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub client: Signer<'info>,
+    /// The party accepting the standing offer; the client or the provider
+    pub acceptor: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RejectNegotiation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [
+            b"negotiation",
+            negotiation.initiator.as_ref(),
+            negotiation.counterparty.as_ref(),
+            &negotiation.negotiation_id.to_le_bytes()
+        ],
+        bump = negotiation.bump
+    )]
+    pub negotiation: Account<'info, NegotiationChatbot>,
+    /// CHECK: Receives the negotiation rent; must be the recorded initiator
+    #[account(
+        mut,
+        constraint = initiator.key() == negotiation.initiator @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub initiator: AccountInfo<'info>,
+    pub rejector: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExpireNegotiation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        close = initiator,
+        seeds = [
+            b"negotiation",
+            negotiation.initiator.as_ref(),
+            negotiation.counterparty.as_ref(),
+            &negotiation.negotiation_id.to_le_bytes()
+        ],
+        bump = negotiation.bump
+    )]
+    pub negotiation: Account<'info, NegotiationChatbot>,
+    /// CHECK: Receives the negotiation rent; must be the recorded initiator
+    #[account(
+        mut,
+        constraint = initiator.key() == negotiation.initiator @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub initiator: AccountInfo<'info>,
+}
+
 // Events
 #[event]
 pub struct NegotiationInitiatedEvent {
@@ -239,4 +556,25 @@ pub struct CounterOfferMadeEvent {
     pub counter_offer: u64,
     pub message: String,
     pub auto_accepted: bool,
-}
\ No newline at end of file
+}
+
+#[event]
+pub struct NegotiationAcceptedEvent {
+    pub negotiation: Pubkey,
+    pub acceptor: Pubkey,
+    pub work_order: Pubkey,
+    pub agreed_price: u64,
+}
+
+#[event]
+pub struct NegotiationRejectedEvent {
+    pub negotiation: Pubkey,
+    pub rejector: Pubkey,
+    pub last_offer: u64,
+}
+
+#[event]
+pub struct NegotiationExpiredEvent {
+    pub negotiation: Pubkey,
+    pub expired_at: i64,
+}
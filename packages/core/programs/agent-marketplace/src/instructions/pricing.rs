@@ -8,6 +8,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::PodAIMarketplaceError;
+use crate::security::ProtocolSubsystem;
 
 /// Creates a dynamic pricing engine for automated price optimization
 /// 
@@ -53,6 +54,7 @@ pub fn create_dynamic_pricing_engine(
     ctx: Context<CreateDynamicPricingEngine>,
     config: DynamicPricingConfig,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.owner.is_signer,
@@ -82,6 +84,22 @@ pub fn create_dynamic_pricing_engine(
         config.update_frequency >= MIN_UPDATE_FREQUENCY,
         PodAIMarketplaceError::InvalidDeadline
     );
+    require!(
+        config.volatility_threshold as u64 <= PRICING_BPS,
+        PodAIMarketplaceError::InvalidPercentage
+    );
+    require!(
+        [
+            config.demand_elasticity,
+            config.supply_elasticity,
+            config.demand_multiplier,
+            config.reputation_multiplier,
+            config.surge_multiplier,
+        ]
+        .iter()
+        .all(|&factor| factor <= MAX_PRICING_MULTIPLIER),
+        PodAIMarketplaceError::InvalidPriceConfiguration
+    );
 
     let engine = &mut ctx.accounts.engine;
     let agent = &ctx.accounts.agent;
@@ -90,14 +108,13 @@ pub fn create_dynamic_pricing_engine(
     require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
     require!(agent.owner == ctx.accounts.owner.key(), PodAIMarketplaceError::UnauthorizedAccess);
 
-    engine.engine_id = clock.unix_timestamp as u64;  // Use timestamp as unique ID
-    engine.agent = agent.key();
-    engine.algorithm = config.algorithm;
-    engine.config = config.clone();  // Clone here
-    engine.current_price = config.base_price;
-    engine.last_updated = clock.unix_timestamp;
-    engine.is_active = true;
-    engine.bump = ctx.bumps.engine;
+    engine.initialize(
+        clock.unix_timestamp as u64, // Use timestamp as unique ID
+        agent.key(),
+        config.clone(),
+        clock.unix_timestamp,
+        ctx.bumps.engine,
+    );
 
     emit!(DynamicPricingEngineCreatedEvent {
         engine: engine.key(),
@@ -112,17 +129,13 @@ pub fn create_dynamic_pricing_engine(
 
 /// Updates dynamic pricing based on real-time market conditions
 /// 
-/// Recalculates and applies new pricing based on current market data,
-/// demand patterns, and configured pricing strategy.
+/// Recalculates the engine's price from the demand counted since the last
+/// update and the latest oracle observations. Anyone may crank the update
+/// once `update_frequency` has passed; the inputs are all held on-chain.
 /// 
 /// # Arguments
 /// 
-/// * `ctx` - The context containing pricing engine and service accounts
-/// * `market_data` - Current market conditions including:
-///   - `current_demand` - Number of pending requests
-///   - `competitor_avg_price` - Average competitor pricing
-///   - `utilization_rate` - Agent's current workload (0-100%)
-///   - `recent_performance` - Recent rating average
+/// * `ctx` - The context containing the pricing engine and its agent
 /// 
 /// # Returns
 /// 
@@ -130,89 +143,167 @@ pub fn create_dynamic_pricing_engine(
 /// 
 /// # Errors
 /// 
-/// * `UnauthorizedAccess` - If caller is not the agent owner
-/// * `UpdateTooSoon` - If called before update frequency allows
-/// * `MarketDataStale` - If market data is outdated
+/// * `UpdateFrequencyTooHigh` - If called before update frequency allows
+/// * `PriceModelNotSupported` - If the engine is inactive or disabled
 /// 
-/// # Pricing Algorithm
+/// # Pricing Algorithms
 /// 
-/// ```text
-/// new_price = base_price * demand_multiplier * competition_factor * performance_bonus
+/// All math is integer basis points. `pressure` is demand against its
+/// moving average weighted by `demand_elasticity`, plus demand against
+/// declared supply weighted by `supply_elasticity`.
 /// 
-/// Where:
-/// - demand_multiplier: 0.8-1.5x based on demand
-/// - competition_factor: 0.9-1.1x based on market position
-/// - performance_bonus: 0.95-1.2x based on ratings
+/// ```text
+/// Linear:           base * (1 + pressure)
+/// Exponential:      base * e^pressure
+/// Logarithmic:      base * (1 + ln(1 + pressure))
+/// Sigmoid:          logistic curve through base, saturating at min/max
+/// MarketBased:      base * (1 + (demand/supply - 1) * supply_elasticity)
+/// MLOptimized:      base * oracle model multiplier
+/// DemandBased:      base * (1 + excess demand * demand_multiplier)
+/// ReputationBased:  base * (1 + (reputation - 50%) * reputation_multiplier)
+/// SurgePricing:     surge_multiplier applied once load exceeds 150%
+/// MarketAverage:    oracle market average price
+/// PerformanceBased: base * (1 + (performance - 50%) * reputation_multiplier)
+/// Seasonal:         base * (1 + this hour's demand share * demand_elasticity)
 /// ```
 /// 
 /// # Safety Limits
 /// 
-/// - Single update capped at strategy maximum
+/// - Single update capped at `volatility_threshold` of the current price
 /// - Always respects min/max price bounds
-/// - Gradual changes to avoid price shocks
-pub fn update_dynamic_pricing(
-    ctx: Context<UpdateDynamicPricing>,
-    demand_metrics: DemandMetrics,
-) -> Result<()> {
-    // SECURITY: Verify signer authorization
-    require!(
-        ctx.accounts.updater.is_signer,
-        PodAIMarketplaceError::UnauthorizedAccess
-    );
+pub fn update_dynamic_pricing(ctx: Context<UpdateDynamicPricing>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
 
     let engine = &mut ctx.accounts.engine;
-    
     let clock = Clock::get()?;
 
-    // Check update frequency
+    require!(engine.is_quoting(), PodAIMarketplaceError::PriceModelNotSupported);
     require!(
-        clock.unix_timestamp >= engine.last_updated + engine.config.adjustment_frequency, 
+        engine.should_update(clock.unix_timestamp),
         PodAIMarketplaceError::UpdateFrequencyTooHigh
     );
 
-    let _old_price = engine.current_price;
-    let mut new_price = engine.current_price;
+    let old_price = engine.current_price;
+    let demand = engine.reprice(ctx.accounts.agent.reputation_score, clock.unix_timestamp);
+    let new_price = engine.current_price;
 
-    // Apply demand-based pricing
-    if engine.algorithm == PricingAlgorithm::DemandBased {
-        let demand_multiplier = if demand_metrics.current_demand > 10 { 1.5 } else { 1.0 };
-        new_price = (new_price as f64 * demand_multiplier) as u64;
-    }
+    emit!(DynamicPricingUpdatedEvent {
+        engine: engine.key(),
+        agent: engine.agent,
+        updater: ctx.accounts.updater.key(),
+        old_price,
+        new_price,
+        demand,
+        supply: engine.supply,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
 
-    // Apply surge pricing
-    if engine.algorithm == PricingAlgorithm::SurgePricing {
-        let hour = (clock.unix_timestamp / 3600) % 24;
-        let surge_multiplier = if (9..=17).contains(&hour) { 1.3 } else { 1.0 };
-        new_price = (new_price as f64 * surge_multiplier) as u64;
+/// Posts market inputs to a pricing engine
+/// 
+/// Only the agent owner or the engine's designated oracle may post, and only
+/// the oracle may override the demand counted from purchases. Values take
+/// effect at the next `update_dynamic_pricing`.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If the signer is neither the owner nor the oracle,
+///   or the owner reports demand
+/// * `InvalidPriceConfiguration` - If the model multiplier exceeds 10x
+/// * `InvalidPercentage` - If the performance score exceeds 100%
+pub fn submit_pricing_observation(
+    ctx: Context<SubmitPricingObservation>,
+    observation: PricingObservation,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+
+    let engine = &mut ctx.accounts.engine;
+    let signer = ctx.accounts.authority.key();
+    let is_oracle = engine.oracle != Pubkey::default() && signer == engine.oracle;
+
+    // SECURITY: Market inputs move prices buyers pay, so only trusted keys may post them
+    require!(
+        signer == ctx.accounts.agent.owner || is_oracle,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    // SECURITY: The owner could otherwise inflate demand to raise their own price
+    if observation.demand.is_some() {
+        require!(is_oracle, PodAIMarketplaceError::UnauthorizedAccess);
+    }
+    if let Some(price) = observation.market_average_price {
+        require!(
+            (MIN_PAYMENT_AMOUNT..=MAX_PAYMENT_AMOUNT).contains(&price),
+            PodAIMarketplaceError::InvalidPaymentAmount
+        );
     }
 
-    // Ensure price stays within bounds
-    let (min_price, max_price) = engine.config.price_range;
-    new_price = new_price.max(min_price).min(max_price);
+    engine.apply_observation(&observation)?;
 
-    engine.current_price = new_price;
-    engine.last_updated = clock.unix_timestamp;
+    emit!(PricingObservationSubmittedEvent {
+        engine: engine.key(),
+        agent: engine.agent,
+        authority: signer,
+        demand: observation.demand,
+        supply: observation.supply,
+        market_average_price: observation.market_average_price,
+        model_multiplier: observation.model_multiplier,
+        performance_score: observation.performance_score,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
 
-    emit!(DynamicPricingUpdatedEvent {
+    Ok(())
+}
+
+/// Designates the oracle allowed to post observations, or removes it
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If the signer does not own the agent
+pub fn set_pricing_oracle(
+    ctx: Context<SetPricingOracle>,
+    oracle: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+
+    let engine = &mut ctx.accounts.engine;
+    engine.oracle = oracle.unwrap_or_default();
+
+    emit!(PricingOracleSetEvent {
         engine: engine.key(),
         agent: engine.agent,
-        owner: ctx.accounts.updater.key(),
-        new_price,
-        demand_multiplier: demand_metrics.current_demand as f64 / 10.0,
-        supply_multiplier: 1.0,
+        oracle,
     });
 
     Ok(())
 }
 
+/// Deserializes an agent's pricing engine, if one has been created
+pub(crate) fn load_pricing_engine(info: &AccountInfo) -> Result<Option<DynamicPricingEngine>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(DynamicPricingEngine::try_deserialize(&mut &data[..])?))
+}
+
+/// Writes an updated pricing engine back to its account
+pub(crate) fn store_pricing_engine(info: &AccountInfo, engine: &DynamicPricingEngine) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    engine.try_serialize(&mut &mut data[..])
+}
+
 // Context structures
 #[derive(Accounts)]
 pub struct CreateDynamicPricingEngine<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = owner,
         space = DynamicPricingEngine::LEN,
-        seeds = [b"dynamic_pricing", agent.key().as_ref()],
+        seeds = [DYNAMIC_PRICING_SEED, agent.key().as_ref()],
         bump
     )]
     pub engine: Account<'info, DynamicPricingEngine>,
@@ -224,11 +315,49 @@ pub struct CreateDynamicPricingEngine<'info> {
 
 #[derive(Accounts)]
 pub struct UpdateDynamicPricing<'info> {
-    #[account(mut)]
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [DYNAMIC_PRICING_SEED, agent.key().as_ref()],
+        bump = engine.bump
+    )]
     pub engine: Account<'info, DynamicPricingEngine>,
+    pub agent: Account<'info, Agent>,
     pub updater: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SubmitPricingObservation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [DYNAMIC_PRICING_SEED, agent.key().as_ref()],
+        bump = engine.bump
+    )]
+    pub engine: Account<'info, DynamicPricingEngine>,
+    pub agent: Account<'info, Agent>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPricingOracle<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [DYNAMIC_PRICING_SEED, agent.key().as_ref()],
+        bump = engine.bump
+    )]
+    pub engine: Account<'info, DynamicPricingEngine>,
+    #[account(
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
 // Events
 #[event]
 pub struct DynamicPricingEngineCreatedEvent {
@@ -243,8 +372,30 @@ pub struct DynamicPricingEngineCreatedEvent {
 pub struct DynamicPricingUpdatedEvent {
     pub engine: Pubkey,
     pub agent: Pubkey,
-    pub owner: Pubkey,
+    pub updater: Pubkey,
+    pub old_price: u64,
     pub new_price: u64,
-    pub demand_multiplier: f64,
-    pub supply_multiplier: f64,
-}
\ No newline at end of file
+    pub demand: u64,
+    pub supply: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PricingObservationSubmittedEvent {
+    pub engine: Pubkey,
+    pub agent: Pubkey,
+    pub authority: Pubkey,
+    pub demand: Option<u64>,
+    pub supply: Option<u64>,
+    pub market_average_price: Option<u64>,
+    pub model_multiplier: Option<u32>,
+    pub performance_score: Option<u32>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PricingOracleSetEvent {
+    pub engine: Pubkey,
+    pub agent: Pubkey,
+    pub oracle: Option<Pubkey>,
+}
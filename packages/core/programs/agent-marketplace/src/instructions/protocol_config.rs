@@ -0,0 +1,370 @@
+/*!
+ * Protocol Config Instructions
+ *
+ * Creates the singleton protocol config, toggles the emergency pause,
+ * globally or for a single subsystem (payments, auctions, messaging), and
+ * manages the fee schedule and the per-mint treasury vaults fees are paid into.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::security::{EmergencyControls, ProtocolSubsystem};
+use crate::simple_optimization::SecurityLogger;
+use crate::state::governance::Multisig;
+use crate::token_utils::transfer_tokens;
+use crate::PodAIMarketplaceError;
+use crate::state::protocol_config::{
+    FeeSchedule, FeeTransactionType, ProtocolConfig, PROTOCOL_CONFIG_SEED, TREASURY_VAULT_SEED,
+};
+
+/// Creates the protocol config with nothing paused
+///
+/// Only the program's upgrade authority can create the config, and it becomes
+/// the config authority, so the singleton cannot be claimed by a front-runner.
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the protocol config account
+/// * `pause_authority` - Key allowed to pause and unpause; typically a
+///   multisig or governance PDA, or the authority itself
+pub fn initialize_protocol_config(
+    ctx: Context<InitializeProtocolConfig>,
+    pause_authority: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.authority = ctx.accounts.authority.key();
+    config.emergency = EmergencyControls::new(pause_authority);
+    config.fee_schedule = FeeSchedule::default();
+    config.treasury_multisig = Pubkey::default();
+    config.bump = ctx.bumps.protocol_config;
+
+    msg!("Protocol config initialized - Pause authority: {}", pause_authority);
+    Ok(())
+}
+
+/// Pauses the whole protocol or a single subsystem
+///
+/// # Errors
+///
+/// * `UnauthorizedAccess` - If signer is not the pause authority
+/// * `InputTooLong` - If `reason` exceeds `MAX_PAUSE_REASON_LENGTH`
+pub fn pause_protocol(
+    ctx: Context<SetProtocolPause>,
+    subsystem: ProtocolSubsystem,
+    reason: String,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let pause_authority = ctx.accounts.pause_authority.key();
+    ctx.accounts.protocol_config.emergency.pause(
+        pause_authority,
+        subsystem,
+        reason.clone(),
+        clock.unix_timestamp,
+    )?;
+
+    SecurityLogger::log_security_event("PROTOCOL_PAUSED", pause_authority,
+        &format!("subsystem: {:?}", subsystem));
+
+    emit!(ProtocolPausedEvent {
+        subsystem,
+        pause_authority,
+        reason,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Lifts a pause set by `pause_protocol`
+///
+/// Unpausing `Global` leaves individually paused subsystems paused.
+///
+/// # Errors
+///
+/// * `UnauthorizedAccess` - If signer is not the pause authority
+pub fn unpause_protocol(ctx: Context<SetProtocolPause>, subsystem: ProtocolSubsystem) -> Result<()> {
+    let pause_authority = ctx.accounts.pause_authority.key();
+    ctx.accounts.protocol_config.emergency.unpause(pause_authority, subsystem)?;
+
+    SecurityLogger::log_security_event("PROTOCOL_UNPAUSED", pause_authority,
+        &format!("subsystem: {:?}", subsystem));
+
+    emit!(ProtocolUnpausedEvent {
+        subsystem,
+        pause_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Rotates the config authority and the pause authority
+///
+/// # Errors
+///
+/// * `UnauthorizedAccess` - If signer is not the config authority
+pub fn update_protocol_authority(
+    ctx: Context<UpdateProtocolAuthority>,
+    new_authority: Pubkey,
+    new_pause_authority: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.protocol_config;
+    config.authority = new_authority;
+    config.emergency.pause_authority = new_pause_authority;
+
+    SecurityLogger::log_security_event("PROTOCOL_AUTHORITY_UPDATED", ctx.accounts.authority.key(),
+        &format!("authority: {}, pause authority: {}", new_authority, new_pause_authority));
+
+    emit!(ProtocolAuthorityUpdatedEvent {
+        authority: new_authority,
+        pause_authority: new_pause_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Replaces the protocol fee schedule
+/// 
+/// Typically signed by a governance or multisig PDA through CPI.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the config authority
+/// * `InvalidPercentage` - If any rate exceeds `MAX_PROTOCOL_FEE_BPS`
+/// * `InvalidConfiguration` - If there are too many or duplicate mint overrides
+pub fn update_fee_schedule(ctx: Context<UpdateProtocolAuthority>, fee_schedule: FeeSchedule) -> Result<()> {
+    fee_schedule.validate()?;
+    ctx.accounts.protocol_config.fee_schedule = fee_schedule.clone();
+
+    SecurityLogger::log_security_event("FEE_SCHEDULE_UPDATED", ctx.accounts.authority.key(),
+        &format!("overrides: {}", fee_schedule.mint_overrides.len()));
+
+    emit!(FeeScheduleUpdatedEvent {
+        fee_schedule,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Sets the multisig allowed to withdraw from the treasury
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the config authority
+pub fn set_treasury_multisig(ctx: Context<SetTreasuryMultisig>) -> Result<()> {
+    let multisig = ctx.accounts.treasury_multisig.key();
+    ctx.accounts.protocol_config.treasury_multisig = multisig;
+
+    SecurityLogger::log_security_event("TREASURY_MULTISIG_SET", ctx.accounts.authority.key(),
+        &format!("multisig: {}", multisig));
+
+    Ok(())
+}
+
+/// Creates the treasury vault that collects protocol fees in `mint`
+/// 
+/// Permissionless; settlements in a mint require its vault to exist.
+pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+    msg!("Treasury vault initialized - Mint: {}", ctx.accounts.mint.key());
+    Ok(())
+}
+
+/// Withdraws collected fees from a treasury vault
+/// 
+/// Only the configured treasury multisig can withdraw, by signing through
+/// `execute_multisig_transaction` once its threshold is met.
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the treasury multisig
+/// * `InsufficientBalance` - If the vault holds less than `amount`
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    require!(
+        ctx.accounts.treasury_vault.amount >= amount,
+        PodAIMarketplaceError::InsufficientBalance
+    );
+
+    let config_seeds: &[&[u8]] = &[PROTOCOL_CONFIG_SEED, &[ctx.accounts.protocol_config.bump]];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.mint,
+        &ctx.accounts.destination_token_account,
+        &ctx.accounts.protocol_config.to_account_info(),
+        amount,
+        &[config_seeds],
+    )?;
+
+    SecurityLogger::log_security_event("TREASURY_WITHDRAWN", ctx.accounts.multisig.key(),
+        &format!("mint: {}, amount: {}, destination: {}",
+            ctx.accounts.mint.key(), amount, ctx.accounts.destination_token_account.key()));
+
+    emit!(TreasuryWithdrawnEvent {
+        mint: ctx.accounts.mint.key(),
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// =====================================================
+// ACCOUNT CONTEXTS
+// =====================================================
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = ProtocolConfig::LEN,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ PodAIMarketplaceError::UnauthorizedAccess)]
+    pub program: Program<'info, crate::program::PodaiMarketplace>,
+    /// This program's upgrade data, naming its upgrade authority
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PodAIMarketplaceError::UnauthorizedAccess)]
+    pub program_data: Account<'info, ProgramData>,
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolPause<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    /// Pause authority; a multisig or governance PDA signs through CPI
+    pub pause_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateProtocolAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasuryMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.authority == authority.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    pub treasury_multisig: Account<'info, Multisig>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [PROTOCOL_CONFIG_SEED],
+        bump = protocol_config.bump,
+        constraint = protocol_config.treasury_multisig != Pubkey::default() @ PodAIMarketplaceError::FeatureNotEnabled,
+        constraint = protocol_config.treasury_multisig == multisig.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Treasury multisig PDA, signing through CPI
+    pub multisig: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct ProtocolPausedEvent {
+    pub subsystem: ProtocolSubsystem,
+    pub pause_authority: Pubkey,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolUnpausedEvent {
+    pub subsystem: ProtocolSubsystem,
+    pub pause_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolAuthorityUpdatedEvent {
+    pub authority: Pubkey,
+    pub pause_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeScheduleUpdatedEvent {
+    pub fee_schedule: FeeSchedule,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolFeeCollectedEvent {
+    pub transaction_type: FeeTransactionType,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawnEvent {
+    pub mint: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
@@ -3,10 +3,19 @@
  * 
  * Contains all replication-related instruction handlers for the GhostSpeak Protocol.
  * This module manages agent template creation and replication functionality.
+ *
+ * Replicating pays the template's fee to its creator and opens a royalty
+ * stream on the replica, through which `process_payment` routes the
+ * creator's share of the replica's earnings.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::*;
+use crate::security::ProtocolSubsystem;
+use crate::state::protocol_config::{FeeTransactionType, TREASURY_VAULT_SEED};
+use crate::state::royalty::{RoyaltyConfig, RoyaltyStream, ROYALTY_STREAM_SEED};
+use crate::token_utils::{collect_protocol_fee, net_transfer_amount, transfer_tokens};
 
 /// Data structure for creating a replication template
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -15,6 +24,10 @@ pub struct ReplicationTemplateData {
     pub base_capabilities: Vec<String>,
     pub replication_fee: u64,
     pub max_replications: u32,
+    /// Mint the replication fee is paid in
+    pub payment_mint: Pubkey,
+    /// Royalty the creator receives from each replica's earnings
+    pub royalty: RoyaltyConfig,
 }
 
 /// Data structure for agent customization during replication
@@ -37,10 +50,10 @@ pub struct AgentCustomization {
 /// 
 /// * `ctx` - The context containing template and agent accounts
 /// * `template_data` - Template configuration including:
-///   - `base_price` - One-time fee for replication
-///   - `royalty_percentage` - Ongoing royalty from earnings
+///   - `replication_fee` - One-time fee for replication
 ///   - `max_replications` - Maximum allowed replications
-///   - `replication_config` - Custom replication parameters
+///   - `payment_mint` - Mint the fee is paid in
+///   - `royalty` - Ongoing royalty from earnings, bounded per payment
 /// 
 /// # Returns
 /// 
@@ -51,6 +64,7 @@ pub struct AgentCustomization {
 /// * `UnauthorizedAccess` - If caller is not the agent owner
 /// * `AgentNotReplicable` - If agent doesn't allow replication
 /// * `InvalidConfiguration` - If template parameters are invalid
+/// * `InvalidRoyaltyPercentage` - If the royalty exceeds `MAX_ROYALTY_PERCENTAGE`
 /// 
 /// # Pricing Model
 /// 
@@ -60,6 +74,7 @@ pub fn create_replication_template(
     ctx: Context<CreateReplicationTemplate>,
     template_data: ReplicationTemplateData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.creator.is_signer,
@@ -86,6 +101,7 @@ pub fn create_replication_template(
         template_data.max_replications > 0,
         PodAIMarketplaceError::InvalidPaymentAmount
     );
+    template_data.royalty.validate()?;
 
     let template = &mut ctx.accounts.replication_template;
     let agent = &ctx.accounts.source_agent;
@@ -104,6 +120,8 @@ pub fn create_replication_template(
     template.current_replications = 0;
     template.is_active = true;
     template.created_at = Clock::get()?.unix_timestamp;
+    template.payment_mint = template_data.payment_mint;
+    template.royalty = template_data.royalty;
     template.bump = ctx.bumps.replication_template;
 
     emit!(crate::state::replication::ReplicationTemplateCreatedEvent {
@@ -129,6 +147,7 @@ pub fn create_replication_template(
 ///   - `template` - Template to replicate from
 ///   - `custom_name` - Name for the new agent
 ///   - `modifications` - Custom modifications to template
+/// * `agent_id` - Buyer-chosen id seeding the new agent's address
 /// 
 /// # Returns
 /// 
@@ -143,7 +162,7 @@ pub fn create_replication_template(
 /// # Replication Process
 /// 
 /// 1. Validates template availability
-/// 2. Charges base template price
+/// 2. Pays the replication fee to the template creator, less the protocol fee
 /// 3. Creates new agent with template config
 /// 4. Sets up royalty stream to template creator
 /// 5. Applies any custom modifications
@@ -152,11 +171,13 @@ pub fn create_replication_template(
 /// 
 /// - Replicated agents are independent entities
 /// - Template updates don't affect existing replications
-/// - Royalties are automatically deducted from earnings
+/// - Royalties are deducted from earnings paid through `process_payment`
 pub fn replicate_agent(
     ctx: Context<ReplicateAgent>,
     customization: AgentCustomization,
+    _agent_id: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     // SECURITY: Verify signer authorization
     require!(
         ctx.accounts.buyer.is_signer,
@@ -201,6 +222,39 @@ pub fn replicate_agent(
     new_agent.replication_fee = customization.replication_fee.unwrap_or(0);
     new_agent.bump = ctx.bumps.new_agent;
 
+    // The creator's royalty follows the replica for its whole life
+    let royalty_stream = &mut ctx.accounts.royalty_stream;
+    royalty_stream.agent = new_agent.key();
+    royalty_stream.original_creator = template.creator;
+    royalty_stream.config = template.royalty.clone();
+    royalty_stream.total_paid = 0;
+    royalty_stream.last_payment = clock.unix_timestamp;
+    royalty_stream.is_active = true;
+    royalty_stream.created_at = clock.unix_timestamp;
+    royalty_stream.bump = ctx.bumps.royalty_stream;
+
+    let creator_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::Replication,
+        &ctx.accounts.token_program,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.buyer.to_account_info(),
+        template.replication_fee,
+        &[],
+    )?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.creator_token_account,
+        &ctx.accounts.buyer.to_account_info(),
+        creator_amount,
+        &[],
+    )?;
+    let creator_received = net_transfer_amount(&ctx.accounts.payment_mint, creator_amount)?;
+
     replication_record.record_id = 0; // Could be derived from global counter
     replication_record.original_agent = template.source_agent;
     replication_record.replicated_agent = new_agent.key();
@@ -222,6 +276,15 @@ pub fn replicate_agent(
         timestamp: Clock::get()?.unix_timestamp,
     });
 
+    emit!(crate::instructions::royalty::RoyaltyStreamCreatedEvent {
+        stream: royalty_stream.key(),
+        agent: new_agent.key(),
+        creator: template.creator,
+        percentage: royalty_stream.config.percentage,
+    });
+
+    msg!("Replication fee paid - Creator received: {}", creator_received);
+
     Ok(())
 }
 
@@ -232,10 +295,12 @@ pub fn replicate_agent(
 #[derive(Accounts)]
 #[instruction(template_data: ReplicationTemplateData)]
 pub struct CreateReplicationTemplate<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
-        space = crate::state::ReplicationRecord::LEN,
+        space = crate::state::ReplicationTemplate::LEN,
         seeds = [b"replication_template", source_agent.key().as_ref()],
         bump
     )]
@@ -251,16 +316,27 @@ pub struct CreateReplicationTemplate<'info> {
 }
 
 #[derive(Accounts)]
-#[instruction(customization: AgentCustomization)]
+#[instruction(customization: AgentCustomization, agent_id: u64)]
 pub struct ReplicateAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(mut)]
     pub replication_template: Account<'info, crate::state::ReplicationTemplate>,
     
+    #[account(
+        init,
+        payer = buyer,
+        space = RoyaltyStream::LEN,
+        seeds = [ROYALTY_STREAM_SEED, new_agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: Account<'info, RoyaltyStream>,
+    
     #[account(
         init,
         payer = buyer,
         space = Agent::LEN,
-        seeds = [b"agent", buyer.key().as_ref()],
+        seeds = [AGENT_SEED, buyer.key().as_ref(), &agent_id.to_le_bytes()],
         bump
     )]
     pub new_agent: Account<'info, Agent>,
@@ -274,8 +350,38 @@ pub struct ReplicateAgent<'info> {
     )]
     pub replication_record: Account<'info, ReplicationRecord>,
     
+    #[account(
+        constraint = payment_mint.key() == replication_template.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = buyer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == replication_template.creator @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = creator_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    
     #[account(mut)]
     pub buyer: Signer<'info>,
     
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
\ No newline at end of file
@@ -0,0 +1,218 @@
+/*!
+ * Reputation Instructions
+ *
+ * Opens an agent's reputation account and records reviews left by the
+ * paying counterparty of a completed work order, service purchase or job
+ * contract. The agent's `reputation_score` is recomputed from the account
+ * on every review and cannot be set by its owner.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::security::ProtocolSubsystem;
+use crate::state::commerce::ReviewData;
+use crate::state::marketplace::{JobContract, PurchaseStatus, ServiceListing, ServicePurchase};
+use crate::state::protocol_config::{ProtocolConfig, PROTOCOL_CONFIG_SEED};
+use crate::state::reputation::{
+    AgentReputation, Review, ReviewSubject, AGENT_REPUTATION_SEED, REVIEW_SEED,
+};
+use crate::state::work_order::{WorkOrder, WorkOrderStatus};
+use crate::state::ContractStatus;
+use crate::{Agent, PodAIMarketplaceError, MAX_GENERAL_STRING_LENGTH};
+
+/// Opens the reputation account of an agent
+///
+/// Permissionless. Any score the agent carried before reviews existed is
+/// cleared, so the score only ever reflects recorded reviews.
+pub fn initialize_agent_reputation(ctx: Context<InitializeAgentReputation>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let clock = Clock::get()?;
+    let agent = &mut ctx.accounts.agent;
+
+    ctx.accounts.reputation.initialize(agent.key(), clock.unix_timestamp, ctx.bumps.reputation);
+    agent.reputation_score = 0;
+
+    msg!("Agent reputation initialized: {}", agent.key());
+    Ok(())
+}
+
+/// Reviews the agent that delivered a completed job
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the job, review and reputation accounts
+/// * `review_data` - Review details including:
+///   - `subject` - Which kind of job `job` is
+///   - `rating` - 1 to 5 stars
+///   - `comment` - Free-form feedback
+///   - `work_reference` - Optional pointer to the reviewed deliverable
+///
+/// # Errors
+///
+/// * `JobNotCompleted` - If the job has not been completed
+/// * `UnauthorizedAccess` - If the reviewer is not the job's paying party,
+///   or reviews their own agent
+/// * `InvalidAgentOwner` - If `agent` did not deliver the job
+/// * `InvalidRating` - If `rating` is outside 1-5
+/// * `InvalidConfiguration` - If a service purchase is reviewed without its
+///   listing, or `payment_mint` is not the job's payment mint
+///
+/// The review PDA is derived from the job and the reviewer, so a second
+/// review of the same job fails when the account is created.
+pub fn submit_review(ctx: Context<SubmitReview>, review_data: ReviewData) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(review_data.comment.len() <= MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError::InputTooLong);
+    require!(review_data.work_reference.len() <= MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError::InputTooLong);
+
+    let job = ctx.accounts.job.to_account_info();
+    let agent = &mut ctx.accounts.agent;
+    let reviewer = ctx.accounts.reviewer.key();
+
+    // SECURITY: Only completed jobs can be reviewed, and only by the party
+    // that paid for them
+    let mut purchased_listing = None;
+    let (payer, delivered_by_agent, volume, payment_token) = match review_data.subject {
+        ReviewSubject::WorkOrder => {
+            let work_order: WorkOrder = load_job(&job)?;
+            require!(work_order.status == WorkOrderStatus::Completed, PodAIMarketplaceError::JobNotCompleted);
+            (work_order.client, agent.key() == work_order.provider_agent, work_order.payment_amount, work_order.payment_token)
+        }
+        ReviewSubject::ServicePurchase => {
+            let purchase: ServicePurchase = load_job(&job)?;
+            require!(purchase.status == PurchaseStatus::Completed, PodAIMarketplaceError::JobNotCompleted);
+            purchased_listing = Some(purchase.listing);
+            (purchase.customer, purchase.agent == agent.key(), purchase.payment_amount, purchase.payment_token)
+        }
+        ReviewSubject::JobContract => {
+            let contract: JobContract = load_job(&job)?;
+            require!(contract.status == ContractStatus::Completed, PodAIMarketplaceError::JobNotCompleted);
+            (contract.employer, contract.agent == agent.key(), contract.agreed_rate, contract.payment_token)
+        }
+    };
+    require!(payer == reviewer, PodAIMarketplaceError::UnauthorizedAccess);
+    require!(agent.owner != reviewer, PodAIMarketplaceError::UnauthorizedAccess);
+    require!(delivered_by_agent, PodAIMarketplaceError::InvalidAgentOwner);
+    require!(
+        ctx.accounts.payment_mint.key() == payment_token,
+        PodAIMarketplaceError::InvalidConfiguration
+    );
+
+    let clock = Clock::get()?;
+    let score = ctx.accounts.reputation.record(
+        review_data.rating,
+        volume,
+        ctx.accounts.payment_mint.decimals,
+        clock.unix_timestamp,
+    )?;
+    agent.reputation_score = score;
+
+    // Purchase reviews also feed the rating shown on the listing
+    if let Some(listing_key) = purchased_listing {
+        let listing = ctx.accounts.service_listing
+            .as_mut()
+            .ok_or(PodAIMarketplaceError::InvalidConfiguration)?;
+        require!(listing.key() == listing_key, PodAIMarketplaceError::InvalidConfiguration);
+        listing.record_rating(review_data.rating);
+    }
+
+    let review = &mut ctx.accounts.review;
+    review.job = job.key();
+    review.subject = review_data.subject;
+    review.reviewer = reviewer;
+    review.agent = agent.key();
+    review.rating = review_data.rating;
+    review.comment = review_data.comment;
+    review.work_reference = review_data.work_reference;
+    review.volume = volume;
+    review.created_at = clock.unix_timestamp;
+    review.bump = ctx.bumps.review;
+
+    emit!(ReviewSubmittedEvent {
+        review: review.key(),
+        agent: agent.key(),
+        reviewer,
+        job: job.key(),
+        rating: review.rating,
+        reputation_score: score,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Deserializes a job account owned by this program
+fn load_job<T: AccountDeserialize>(info: &AccountInfo) -> Result<T> {
+    require!(info.owner == &crate::ID, PodAIMarketplaceError::InvalidAccountOwner);
+    let data = info.try_borrow_data()?;
+    T::try_deserialize(&mut &data[..])
+}
+
+// =====================================================
+// ACCOUNT CONTEXTS
+// =====================================================
+
+#[derive(Accounts)]
+pub struct InitializeAgentReputation<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = payer,
+        space = AgentReputation::LEN,
+        seeds = [AGENT_REPUTATION_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, AgentReputation>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitReview<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = reviewer,
+        space = Review::LEN,
+        seeds = [REVIEW_SEED, job.key().as_ref(), reviewer.key().as_ref()],
+        bump
+    )]
+    pub review: Account<'info, Review>,
+    /// CHECK: Owner and type are verified in the handler against `review_data.subject`
+    pub job: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [AGENT_REPUTATION_SEED, agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, AgentReputation>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    /// Mint the job was paid in; its decimals normalize the review weight
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    /// Listing of the reviewed purchase; required for service purchase reviews
+    #[account(mut)]
+    pub service_listing: Option<Account<'info, ServiceListing>>,
+    #[account(mut)]
+    pub reviewer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct ReviewSubmittedEvent {
+    pub review: Pubkey,
+    pub agent: Pubkey,
+    pub reviewer: Pubkey,
+    pub job: Pubkey,
+    pub rating: u8,
+    pub reputation_score: u32,
+    pub timestamp: i64,
+}
@@ -3,11 +3,115 @@
  * 
  * Implements royalty streams for creators and secondary market
  * functionality for agent ownership transfers.
+ *
+ * A sold agent keeps its account address: its reputation, verification and
+ * royalty stream are keyed by the agent, so only `Agent.owner` and the
+ * owner-seeded user registries move to the buyer.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::state::*;
 use crate::PodAIMarketplaceError;
+use crate::security::ProtocolSubsystem;
+use crate::token_utils::{net_transfer_amount, transfer_tokens};
+
+/// Deserializes an agent's royalty stream, if one has been created
+pub(crate) fn load_royalty_stream(info: &AccountInfo) -> Result<Option<RoyaltyStream>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let data = info.try_borrow_data()?;
+    Ok(Some(RoyaltyStream::try_deserialize(&mut &data[..])?))
+}
+
+/// Writes an updated royalty stream back to its account
+pub(crate) fn store_royalty_stream(info: &AccountInfo, stream: &RoyaltyStream) -> Result<()> {
+    let mut data = info.try_borrow_mut_data()?;
+    stream.try_serialize(&mut &mut data[..])
+}
+
+/// Pays the creator's royalty out of a provider payout
+///
+/// Every payout to an agent's owner goes through here once the protocol fee
+/// is taken, so an active royalty stream is honoured however the agent was
+/// paid. `royalty_stream` must be the agent's stream PDA, which callers bind
+/// by seeds; it is left uninitialized for agents without one. `source` is
+/// the work order, purchase, contract, subscription or meter being settled.
+///
+/// Returns what is left of `provider_amount` for the provider.
+///
+/// # Errors
+///
+/// * `InvalidConfiguration` - If a royalty is owed and no creator token
+///   account in the payout mint was supplied
+/// * `InvalidAccountOwner` - If the supplied token account isn't the creator's
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn pay_royalty<'info>(
+    royalty_stream: &AccountInfo<'info>,
+    royalty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    agent: Pubkey,
+    source: Pubkey,
+    provider_amount: u64,
+) -> Result<u64> {
+    let Some(mut stream) = load_royalty_stream(royalty_stream)? else {
+        return Ok(provider_amount);
+    };
+    let royalty = if stream.is_active {
+        stream.config.royalty_for(provider_amount)
+    } else {
+        0
+    };
+    if royalty == 0 {
+        return Ok(provider_amount);
+    }
+
+    // SECURITY: Royalties may only be paid to the stream's creator
+    let royalty_token_account = royalty_token_account
+        .ok_or(PodAIMarketplaceError::InvalidConfiguration)?;
+    require!(
+        royalty_token_account.owner == stream.original_creator,
+        PodAIMarketplaceError::InvalidAccountOwner
+    );
+    require!(
+        royalty_token_account.mint == mint.key(),
+        PodAIMarketplaceError::InvalidConfiguration
+    );
+
+    transfer_tokens(
+        token_program,
+        from,
+        mint,
+        royalty_token_account,
+        authority,
+        royalty,
+        signer_seeds,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    stream.total_paid = stream.total_paid
+        .checked_add(royalty)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    stream.last_payment = now;
+    store_royalty_stream(royalty_stream, &stream)?;
+
+    emit!(RoyaltyPaidEvent {
+        stream: royalty_stream.key(),
+        agent,
+        creator: stream.original_creator,
+        source,
+        amount: royalty,
+        total_paid: stream.total_paid,
+        timestamp: now,
+    });
+
+    Ok(provider_amount - royalty)
+}
 
 /// Creates a royalty stream for ongoing revenue sharing
 /// 
@@ -30,7 +134,8 @@ use crate::PodAIMarketplaceError;
 /// 
 /// # Errors
 /// 
-/// * `RoyaltyRateTooHigh` - If percentage exceeds 10%
+/// * `InvalidRoyaltyPercentage` - If percentage exceeds 10%
+/// * `RoyaltyConfigurationInvalid` - If `min_amount` exceeds `max_amount`
 /// * `InvalidBeneficiary` - If beneficiary not verified
 /// * `ConflictingRoyalties` - If total royalties exceed limit
 /// 
@@ -51,12 +156,14 @@ pub fn create_royalty_stream(
     ctx: Context<CreateRoyaltyStream>,
     config: RoyaltyConfig,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     let stream = &mut ctx.accounts.stream;
     let agent = &ctx.accounts.agent;
     let clock = Clock::get()?;
 
     require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
     require!(agent.owner == ctx.accounts.creator.key(), PodAIMarketplaceError::UnauthorizedAccess);
+    config.validate()?;
 
     stream.agent = agent.key();
     stream.original_creator = ctx.accounts.creator.key();
@@ -85,12 +192,7 @@ pub fn create_royalty_stream(
 /// # Arguments
 /// 
 /// * `ctx` - The context containing agent and listing accounts
-/// * `resale_data` - Resale listing details including:
-///   - `asking_price` - Desired sale price
-///   - `minimum_price` - Lowest acceptable offer
-///   - `include_contracts` - Whether active contracts transfer
-///   - `earnings_history` - Past earnings documentation
-///   - `transfer_training` - Whether to include training period
+/// * `listing_price` - Sale price, paid in `payment_mint`
 /// 
 /// # Returns
 /// 
@@ -98,9 +200,9 @@ pub fn create_royalty_stream(
 /// 
 /// # Errors
 /// 
-/// * `AgentNotEligible` - If agent has active disputes
-/// * `ActiveContractsExist` - If contracts can't transfer
-/// * `UnauthorizedOwner` - If caller doesn't own agent
+/// * `AgentNotActive` - If the agent is deactivated
+/// * `UnauthorizedAccess` - If caller doesn't own agent
+/// * `InvalidPaymentAmount` - If the price is zero
 /// 
 /// # Valuation Factors
 /// 
@@ -113,26 +215,36 @@ pub fn create_royalty_stream(
 /// 
 /// # Transfer Process
 /// 
-/// 1. Listing created with terms
-/// 2. Buyers make offers
-/// 3. Owner accepts offer
+/// 1. Listing created at a fixed price
+/// 2. Seller may update the price or delist
+/// 3. Buyer calls `purchase_agent`, paying the seller and the creator royalty
 /// 4. Ownership transfers on payment
-/// 5. 30-day transition support period
+/// 
+/// If the agent has an active royalty stream, its percentage is snapshotted
+/// into the listing and paid to the stream's creator out of the sale price.
 pub fn list_agent_for_resale(
     ctx: Context<ListAgentForResale>,
     listing_price: u64,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     let resale = &mut ctx.accounts.resale;
     let agent = &ctx.accounts.agent;
     let clock = Clock::get()?;
 
     require!(agent.is_active, PodAIMarketplaceError::AgentNotActive);
     require!(agent.owner == ctx.accounts.seller.key(), PodAIMarketplaceError::UnauthorizedAccess);
+    require!(listing_price > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+
+    let royalty_percentage = match load_royalty_stream(&ctx.accounts.royalty_stream)? {
+        Some(stream) if stream.is_active => stream.config.percentage,
+        _ => 0,
+    };
 
     resale.agent = agent.key();
     resale.seller = ctx.accounts.seller.key();
     resale.price = listing_price;
-    resale.royalty_percentage = 500; // 5% in basis points
+    resale.payment_mint = ctx.accounts.payment_mint.key();
+    resale.royalty_percentage = royalty_percentage;
     resale.is_listed = true;
     resale.created_at = clock.unix_timestamp;
     resale.sold_at = None;
@@ -149,14 +261,174 @@ pub fn list_agent_for_resale(
     Ok(())
 }
 
+/// Changes the asking price of an active resale listing
+pub fn update_resale_price(
+    ctx: Context<UpdateResaleListing>,
+    new_price: u64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(new_price > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+
+    let resale = &mut ctx.accounts.resale;
+    require!(resale.is_listed, PodAIMarketplaceError::ListingNotActive);
+    let old_price = resale.price;
+    resale.price = new_price;
+
+    emit!(ResalePriceUpdatedEvent {
+        resale: resale.key(),
+        agent: resale.agent,
+        old_price,
+        new_price,
+    });
+
+    Ok(())
+}
+
+/// Takes an agent off the resale market and closes the listing
+pub fn delist_agent(ctx: Context<DelistAgent>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let resale = &ctx.accounts.resale;
+    require!(resale.is_listed, PodAIMarketplaceError::ListingNotActive);
+
+    emit!(AgentDelistedEvent {
+        resale: resale.key(),
+        agent: resale.agent,
+        seller: resale.seller,
+    });
+
+    Ok(())
+}
+
+/// Buys a listed agent and transfers its ownership
+/// 
+/// The buyer pays the listing price in the listing's mint. The royalty
+/// snapshotted at listing time goes to the agent's royalty stream creator and
+/// the rest to the seller. `Agent.owner` then moves to the buyer, along with
+/// the agent count in the owner-seeded user registries. The listing is closed
+/// to the seller, so they can list the agent again if they buy it back.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing the listing, agent and payment accounts
+/// * `max_price` - Most the buyer will pay, so a price raised after the
+///   buyer signed cannot be charged
+/// 
+/// # Errors
+/// 
+/// * `ListingNotActive` - If the listing was delisted or already sold
+/// * `PriceAboveMaximum` - If the listing price exceeds `max_price`
+/// * `UnauthorizedAccess` - If the seller no longer owns the agent, or the
+///   buyer is the seller
+/// * `InvalidAccountOwner` - If a payout account isn't owned by its recipient
+pub fn purchase_agent(ctx: Context<PurchaseAgent>, max_price: u64) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let buyer = ctx.accounts.buyer.key();
+    let seller = ctx.accounts.resale.seller;
+    let clock = Clock::get()?;
+
+    // SECURITY: A stale listing must not sell an agent the seller gave away
+    require!(ctx.accounts.resale.is_listed, PodAIMarketplaceError::ListingNotActive);
+    require!(ctx.accounts.agent.owner == seller, PodAIMarketplaceError::UnauthorizedAccess);
+    require!(buyer != seller, PodAIMarketplaceError::UnauthorizedAccess);
+
+    let price = ctx.accounts.resale.price;
+    // SECURITY: The seller cannot front-run the purchase with a price increase
+    require!(price <= max_price, PodAIMarketplaceError::PriceAboveMaximum);
+    let royalty = ctx.accounts.resale.royalty_amount();
+    let mut creator = None;
+
+    if royalty > 0 {
+        let royalty_info = ctx.accounts.royalty_stream.to_account_info();
+        let mut stream = load_royalty_stream(&royalty_info)?
+            .ok_or(PodAIMarketplaceError::RoyaltyConfigurationInvalid)?;
+
+        // SECURITY: Royalties may only be paid to the stream's creator
+        let creator_token_account = ctx.accounts.creator_token_account
+            .as_ref()
+            .ok_or(PodAIMarketplaceError::InvalidConfiguration)?;
+        require!(
+            creator_token_account.owner == stream.original_creator,
+            PodAIMarketplaceError::InvalidAccountOwner
+        );
+        require!(
+            creator_token_account.mint == ctx.accounts.payment_mint.key(),
+            PodAIMarketplaceError::InvalidConfiguration
+        );
+
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.buyer_token_account,
+            &ctx.accounts.payment_mint,
+            creator_token_account,
+            &ctx.accounts.buyer.to_account_info(),
+            royalty,
+            &[],
+        )?;
+
+        stream.total_paid = stream.total_paid
+            .checked_add(royalty)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        stream.last_payment = clock.unix_timestamp;
+        store_royalty_stream(&royalty_info, &stream)?;
+        creator = Some(stream.original_creator);
+    }
+
+    let seller_amount = price - royalty;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.seller_token_account,
+        &ctx.accounts.buyer.to_account_info(),
+        seller_amount,
+        &[],
+    )?;
+    let seller_received = net_transfer_amount(&ctx.accounts.payment_mint, seller_amount)?;
+
+    let agent = &mut ctx.accounts.agent;
+    agent.owner = buyer;
+    agent.updated_at = clock.unix_timestamp;
+
+    // Owner-seeded registries follow the agent to its new owner
+    if let Some(seller_registry) = ctx.accounts.seller_registry.as_mut() {
+        seller_registry.decrement_agents();
+        seller_registry.add_volume(price)?;
+        seller_registry.last_activity = clock.unix_timestamp;
+    }
+    if let Some(buyer_registry) = ctx.accounts.buyer_registry.as_mut() {
+        buyer_registry.increment_agents()?;
+        buyer_registry.add_volume(price)?;
+        buyer_registry.last_activity = clock.unix_timestamp;
+    }
+
+    let resale = &mut ctx.accounts.resale;
+    resale.mark_sold(buyer, clock.unix_timestamp)?;
+
+    emit!(AgentPurchasedEvent {
+        resale: resale.key(),
+        agent: agent.key(),
+        seller,
+        buyer,
+        price,
+        seller_received,
+        creator,
+        royalty_paid: royalty,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
 // Context structures
 #[derive(Accounts)]
 pub struct CreateRoyaltyStream<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = creator,
         space = RoyaltyStream::LEN,
-        seeds = [b"royalty_stream", agent.key().as_ref()],
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
         bump
     )]
     pub stream: Account<'info, RoyaltyStream>,
@@ -168,20 +440,120 @@ pub struct CreateRoyaltyStream<'info> {
 
 #[derive(Accounts)]
 pub struct ListAgentForResale<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = seller,
         space = ResaleMarket::LEN,
-        seeds = [b"resale", agent.key().as_ref(), seller.key().as_ref()],
+        seeds = [RESALE_SEED, agent.key().as_ref(), seller.key().as_ref()],
         bump
     )]
     pub resale: Account<'info, ResaleMarket>,
     pub agent: Account<'info, Agent>,
+    /// CHECK: The agent's royalty stream PDA, read to snapshot its
+    /// percentage; left uninitialized for agents without one
+    #[account(seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()], bump)]
+    pub royalty_stream: UncheckedAccount<'info>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
     #[account(mut)]
     pub seller: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateResaleListing<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [RESALE_SEED, resale.agent.as_ref(), seller.key().as_ref()],
+        bump = resale.bump,
+        has_one = seller @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub resale: Account<'info, ResaleMarket>,
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelistAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [RESALE_SEED, resale.agent.as_ref(), seller.key().as_ref()],
+        bump = resale.bump,
+        has_one = seller @ PodAIMarketplaceError::UnauthorizedAccess,
+        close = seller
+    )]
+    pub resale: Account<'info, ResaleMarket>,
+    #[account(mut)]
+    pub seller: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseAgent<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [RESALE_SEED, agent.key().as_ref(), resale.seller.as_ref()],
+        bump = resale.bump,
+        close = seller
+    )]
+    pub resale: Account<'info, ResaleMarket>,
+    #[account(mut)]
+    pub agent: Account<'info, Agent>,
+    /// CHECK: Receives the listing rent; bound to the listing's seller
+    #[account(
+        mut,
+        address = resale.seller @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub seller: UncheckedAccount<'info>,
+    /// CHECK: The agent's royalty stream PDA. Always required so a buyer
+    /// cannot skip the creator royalty; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    #[account(
+        constraint = payment_mint.key() == resale.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = buyer_token_account.owner == buyer.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = buyer_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = seller_token_account.owner == resale.seller @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = seller_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
+    /// Royalty creator's token account; required when the listing carries a royalty
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"user_registry", resale.seller.as_ref()],
+        bump = seller_registry.bump
+    )]
+    pub seller_registry: Option<Account<'info, UserRegistry>>,
+    #[account(
+        mut,
+        seeds = [b"user_registry", buyer.key().as_ref()],
+        bump = buyer_registry.bump
+    )]
+    pub buyer_registry: Option<Account<'info, UserRegistry>>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
 // Events
 #[event]
 pub struct RoyaltyStreamCreatedEvent {
@@ -191,10 +563,51 @@ pub struct RoyaltyStreamCreatedEvent {
     pub percentage: u32,    // Basis points
 }
 
+#[event]
+pub struct RoyaltyPaidEvent {
+    pub stream: Pubkey,
+    pub agent: Pubkey,
+    pub creator: Pubkey,
+    /// Work order, purchase, contract, subscription or meter the royalty
+    /// was taken from
+    pub source: Pubkey,
+    pub amount: u64,
+    pub total_paid: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct AgentListedForResaleEvent {
     pub resale: Pubkey,
     pub agent: Pubkey,
     pub seller: Pubkey,
     pub listing_price: u64,
+}
+
+#[event]
+pub struct ResalePriceUpdatedEvent {
+    pub resale: Pubkey,
+    pub agent: Pubkey,
+    pub old_price: u64,
+    pub new_price: u64,
+}
+
+#[event]
+pub struct AgentDelistedEvent {
+    pub resale: Pubkey,
+    pub agent: Pubkey,
+    pub seller: Pubkey,
+}
+
+#[event]
+pub struct AgentPurchasedEvent {
+    pub resale: Pubkey,
+    pub agent: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub seller_received: u64,
+    pub creator: Option<Pubkey>,
+    pub royalty_paid: u64,
+    pub timestamp: i64,
 }
\ No newline at end of file
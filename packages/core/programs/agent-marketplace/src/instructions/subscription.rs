@@ -0,0 +1,590 @@
+/*!
+ * Subscription Instructions Module
+ *
+ * Recurring billing for agents sold as periodic plans. Agent owners publish
+ * plans; subscribers pay the first period on signup and approve their
+ * subscription account as a token delegate so a permissionless crank can
+ * pull each following period. Failed pulls put the subscription past due,
+ * and it is cancelled once the plan's grace period runs out.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::*;
+use crate::PodAIMarketplaceError;
+use crate::security::ProtocolSubsystem;
+use crate::token_utils::{collect_protocol_fee, net_transfer_amount, transfer_tokens};
+use crate::instructions::royalty::pay_royalty;
+
+/// Publishes a subscription plan for an agent
+///
+/// # Arguments
+///
+/// * `ctx` - The context containing the plan and agent accounts
+/// * `plan_id` - Owner-chosen identifier, unique per agent
+/// * `plan_data` - Price per period, period length, grace period and the
+///   per-period usage cap (zero for unlimited)
+///
+/// # Errors
+///
+/// * `InvalidAgentOwner` - If the signer doesn't own the agent
+/// * `InvalidDuration` - If the period or grace period is out of range
+pub fn create_subscription_plan(
+    ctx: Context<CreateSubscriptionPlan>,
+    plan_id: u64,
+    plan_data: SubscriptionPlanData,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(ctx.accounts.agent.is_active, PodAIMarketplaceError::AgentNotActive);
+    plan_data.validate()?;
+
+    let plan = &mut ctx.accounts.plan;
+    plan.agent = ctx.accounts.agent.key();
+    plan.plan_id = plan_id;
+    plan.name = plan_data.name;
+    plan.price = plan_data.price;
+    plan.period = plan_data.period;
+    plan.grace_period = plan_data.grace_period;
+    plan.usage_cap = plan_data.usage_cap;
+    plan.payment_mint = ctx.accounts.payment_mint.key();
+    plan.total_subscriptions = 0;
+    plan.is_active = true;
+    plan.created_at = Clock::get()?.unix_timestamp;
+    plan.bump = ctx.bumps.plan;
+
+    emit!(SubscriptionPlanCreatedEvent {
+        plan: plan.key(),
+        agent: plan.agent,
+        plan_id,
+        price: plan.price,
+        period: plan.period,
+    });
+
+    Ok(())
+}
+
+/// Opens or closes a plan to new subscribers
+///
+/// Existing subscriptions keep billing at the terms they signed up with.
+pub fn set_subscription_plan_active(
+    ctx: Context<UpdateSubscriptionPlan>,
+    is_active: bool,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let plan = &mut ctx.accounts.plan;
+    plan.is_active = is_active;
+
+    emit!(SubscriptionPlanStatusChangedEvent {
+        plan: plan.key(),
+        is_active,
+    });
+
+    Ok(())
+}
+
+/// Subscribes to a plan and pays the first period
+///
+/// Each period's price is split between the protocol fee, the agent
+/// creator's royalty if the agent has an active stream, and the provider.
+/// The plan's price, period, grace period and usage cap are copied onto the
+/// subscription. For later periods to be billed, the subscriber must approve
+/// the subscription account as a delegate on `subscriber_token_account`.
+pub fn subscribe(ctx: Context<Subscribe>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let plan = &ctx.accounts.plan;
+    let clock = Clock::get()?;
+
+    require!(plan.is_active, PodAIMarketplaceError::ServiceNotActive);
+    require!(ctx.accounts.agent.is_active, PodAIMarketplaceError::AgentNotActive);
+
+    let provider_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::Payment,
+        &ctx.accounts.token_program,
+        &ctx.accounts.subscriber_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.subscriber.to_account_info(),
+        plan.price,
+        &[],
+    )?;
+    let provider_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.subscriber_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.subscriber.to_account_info(),
+        &[],
+        ctx.accounts.agent.key(),
+        ctx.accounts.subscription.key(),
+        provider_amount,
+    )?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.subscriber_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.subscriber.to_account_info(),
+        provider_amount,
+        &[],
+    )?;
+    let provider_received = net_transfer_amount(&ctx.accounts.payment_mint, provider_amount)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.plan = plan.key();
+    subscription.agent = plan.agent;
+    subscription.subscriber = ctx.accounts.subscriber.key();
+    subscription.payment_mint = plan.payment_mint;
+    subscription.price = plan.price;
+    subscription.period = plan.period;
+    subscription.grace_period = plan.grace_period;
+    subscription.usage_cap = plan.usage_cap;
+    subscription.usage_this_period = 0;
+    subscription.current_period_start = clock.unix_timestamp;
+    subscription.next_billing = clock.unix_timestamp;
+    subscription.past_due_since = None;
+    subscription.status = SubscriptionStatus::Active;
+    subscription.periods_billed = 0;
+    subscription.total_paid = 0;
+    subscription.created_at = clock.unix_timestamp;
+    subscription.bump = ctx.bumps.subscription;
+    subscription.record_payment(plan.price, clock.unix_timestamp)?;
+
+    let agent = &mut ctx.accounts.agent;
+    agent.total_earnings = agent.total_earnings
+        .checked_add(provider_received)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    let plan = &mut ctx.accounts.plan;
+    plan.total_subscriptions = plan.total_subscriptions
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    emit!(SubscriptionCreatedEvent {
+        subscription: subscription.key(),
+        plan: plan.key(),
+        subscriber: subscription.subscriber,
+        price: subscription.price,
+        next_billing: subscription.next_billing,
+    });
+
+    Ok(())
+}
+
+/// Bills the next period of a subscription (permissionless crank)
+///
+/// Pulls the period's price from the subscriber through the delegate
+/// approval held by the subscription account. If the approval or balance is
+/// insufficient, the subscription goes past due instead of failing the
+/// transaction, and is cancelled by a later crank once the grace period has
+/// elapsed without payment.
+///
+/// # Errors
+///
+/// * `BillingNotDue` - If the current period hasn't ended or the
+///   subscription is cancelled
+pub fn bill_subscription(ctx: Context<BillSubscription>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    let clock = Clock::get()?;
+    let subscription = &ctx.accounts.subscription;
+
+    require!(subscription.is_due(clock.unix_timestamp), PodAIMarketplaceError::BillingNotDue);
+
+    let price = subscription.price;
+    let source = &ctx.accounts.subscriber_token_account;
+    let can_pay = source.delegate == COption::Some(subscription.key())
+        && source.delegated_amount >= price
+        && source.amount >= price;
+
+    if !can_pay {
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.record_failed_payment(clock.unix_timestamp);
+
+        emit!(SubscriptionPaymentFailedEvent {
+            subscription: subscription.key(),
+            subscriber: subscription.subscriber,
+            status: subscription.status,
+            past_due_since: subscription.past_due_since,
+            timestamp: clock.unix_timestamp,
+        });
+        return Ok(());
+    }
+
+    let plan_key = subscription.plan;
+    let subscriber_key = subscription.subscriber;
+    let bump = [subscription.bump];
+    let seeds: &[&[u8]] = &[SUBSCRIPTION_SEED, plan_key.as_ref(), subscriber_key.as_ref(), &bump];
+    let subscription_info = ctx.accounts.subscription.to_account_info();
+
+    let provider_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        FeeTransactionType::Payment,
+        &ctx.accounts.token_program,
+        source,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.treasury_vault,
+        &subscription_info,
+        price,
+        &[seeds],
+    )?;
+    let provider_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        source,
+        &ctx.accounts.payment_mint,
+        &subscription_info,
+        &[seeds],
+        ctx.accounts.agent.key(),
+        subscription_info.key(),
+        provider_amount,
+    )?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        source,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.provider_token_account,
+        &subscription_info,
+        provider_amount,
+        &[seeds],
+    )?;
+    let provider_received = net_transfer_amount(&ctx.accounts.payment_mint, provider_amount)?;
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.record_payment(price, clock.unix_timestamp)?;
+
+    let agent = &mut ctx.accounts.agent;
+    agent.total_earnings = agent.total_earnings
+        .checked_add(provider_received)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+    emit!(SubscriptionBilledEvent {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        amount: price,
+        provider_received,
+        periods_billed: subscription.periods_billed,
+        next_billing: subscription.next_billing,
+    });
+
+    Ok(())
+}
+
+/// Records usage against the current period's cap
+///
+/// Called by the agent owner as work is served. Usage is rejected once the
+/// cap is reached, or while the subscription is past due or cancelled.
+pub fn record_subscription_usage(
+    ctx: Context<RecordSubscriptionUsage>,
+    units: u64,
+) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    require!(units > 0, PodAIMarketplaceError::InvalidVolume);
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.record_usage(units, Clock::get()?.unix_timestamp)?;
+
+    emit!(SubscriptionUsageRecordedEvent {
+        subscription: subscription.key(),
+        units,
+        usage_this_period: subscription.usage_this_period,
+        usage_cap: subscription.usage_cap,
+    });
+
+    Ok(())
+}
+
+/// Cancels a subscription and returns its rent to the subscriber
+///
+/// Either the subscriber or the agent owner may cancel. No refund is made
+/// for the current period.
+pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
+    let subscription = &ctx.accounts.subscription;
+
+    // SECURITY: Only the subscriber or the provider may cancel
+    let caller = ctx.accounts.caller.key();
+    require!(
+        caller == subscription.subscriber || caller == ctx.accounts.agent.owner,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+
+    emit!(SubscriptionCancelledEvent {
+        subscription: subscription.key(),
+        subscriber: subscription.subscriber,
+        cancelled_by: caller,
+        periods_billed: subscription.periods_billed,
+        total_paid: subscription.total_paid,
+    });
+
+    Ok(())
+}
+
+// =====================================================
+// ACCOUNT CONTEXTS
+// =====================================================
+
+#[derive(Accounts)]
+#[instruction(plan_id: u64)]
+pub struct CreateSubscriptionPlan<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        init,
+        payer = owner,
+        space = SubscriptionPlan::LEN,
+        seeds = [SUBSCRIPTION_PLAN_SEED, agent.key().as_ref(), &plan_id.to_le_bytes()],
+        bump
+    )]
+    pub plan: Account<'info, SubscriptionPlan>,
+    #[account(
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateSubscriptionPlan<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_PLAN_SEED, plan.agent.as_ref(), &plan.plan_id.to_le_bytes()],
+        bump = plan.bump
+    )]
+    pub plan: Account<'info, SubscriptionPlan>,
+    #[account(
+        constraint = agent.key() == plan.agent @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Subscribe<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_PLAN_SEED, plan.agent.as_ref(), &plan.plan_id.to_le_bytes()],
+        bump = plan.bump
+    )]
+    pub plan: Account<'info, SubscriptionPlan>,
+    #[account(
+        init,
+        payer = subscriber,
+        space = Subscription::LEN,
+        seeds = [SUBSCRIPTION_SEED, plan.key().as_ref(), subscriber.key().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        constraint = agent.key() == plan.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        constraint = payment_mint.key() == plan.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = subscriber_token_account.owner == subscriber.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = subscriber_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == agent.owner @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = provider_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The agent's royalty stream PDA. Always required so billing
+    /// cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub subscriber: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BillSubscription<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.plan.as_ref(), subscription.subscriber.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        constraint = agent.key() == subscription.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(
+        constraint = payment_mint.key() == subscription.payment_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = subscriber_token_account.owner == subscription.subscriber @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = subscriber_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub subscriber_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == agent.owner @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = provider_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, payment_mint.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The agent's royalty stream PDA. Always required so billing
+    /// cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RecordSubscriptionUsage<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.plan.as_ref(), subscription.subscriber.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        constraint = agent.key() == subscription.agent @ PodAIMarketplaceError::InvalidConfiguration,
+        constraint = agent.owner == owner.key() @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub agent: Account<'info, Agent>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        seeds = [SUBSCRIPTION_SEED, subscription.plan.as_ref(), subscriber.key().as_ref()],
+        bump = subscription.bump,
+        has_one = subscriber @ PodAIMarketplaceError::UnauthorizedAccess,
+        close = subscriber
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        constraint = agent.key() == subscription.agent @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub agent: Account<'info, Agent>,
+    #[account(mut)]
+    pub subscriber: SystemAccount<'info>,
+    pub caller: Signer<'info>,
+}
+
+// =====================================================
+// EVENTS
+// =====================================================
+
+#[event]
+pub struct SubscriptionPlanCreatedEvent {
+    pub plan: Pubkey,
+    pub agent: Pubkey,
+    pub plan_id: u64,
+    pub price: u64,
+    pub period: i64,
+}
+
+#[event]
+pub struct SubscriptionPlanStatusChangedEvent {
+    pub plan: Pubkey,
+    pub is_active: bool,
+}
+
+#[event]
+pub struct SubscriptionCreatedEvent {
+    pub subscription: Pubkey,
+    pub plan: Pubkey,
+    pub subscriber: Pubkey,
+    pub price: u64,
+    pub next_billing: i64,
+}
+
+#[event]
+pub struct SubscriptionBilledEvent {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub amount: u64,
+    pub provider_received: u64,
+    pub periods_billed: u32,
+    pub next_billing: i64,
+}
+
+#[event]
+pub struct SubscriptionPaymentFailedEvent {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub status: SubscriptionStatus,
+    pub past_due_since: Option<i64>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubscriptionUsageRecordedEvent {
+    pub subscription: Pubkey,
+    pub units: u64,
+    pub usage_this_period: u64,
+    pub usage_cap: u64,
+}
+
+#[event]
+pub struct SubscriptionCancelledEvent {
+    pub subscription: Pubkey,
+    pub subscriber: Pubkey,
+    pub cancelled_by: Pubkey,
+    pub periods_billed: u32,
+    pub total_paid: u64,
+}
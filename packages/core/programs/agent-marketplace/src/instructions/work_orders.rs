@@ -1,13 +1,18 @@
 /*!
  * Work Orders Module
  * 
- * Handles work order creation and delivery submission for the GhostSpeak Protocol.
+ * Handles work order creation, delivery submission and escrow settlement for
+ * the GhostSpeak Protocol. Each work order locks its payment in a program-owned
+ * token vault that is released to the provider on approval or refunded to the
+ * client on cancellation or timeout.
  */
 
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
     WorkOrderCreatedEvent, WorkDeliverySubmittedEvent,
-    PodAIMarketplaceError,
+    WorkOrderEscrowReleasedEvent, WorkOrderRefundedEvent,
+    PodAIMarketplaceError, Agent,
     MIN_PAYMENT_AMOUNT, MAX_PAYMENT_AMOUNT, MAX_GENERAL_STRING_LENGTH,
     MAX_TITLE_LENGTH, MAX_DESCRIPTION_LENGTH, MAX_REQUIREMENTS_ITEMS,
     validate_payment, validate_string, require_signer,
@@ -16,7 +21,15 @@ use crate::state::work_order::{
     WorkOrder, WorkDelivery, WorkOrderStatus, 
     WorkOrderData, WorkDeliveryData, Deliverable
 };
+use crate::state::escrow::{Escrow, EscrowInit, EscrowStatus, ESCROW_SEED, ESCROW_VAULT_SEED};
+use crate::state::royalty::ROYALTY_STREAM_SEED;
+use crate::instructions::royalty::pay_royalty;
 use crate::simple_optimization::{InputValidator, SecurityLogger, FormalVerification};
+use crate::token_utils::{collect_protocol_fee, net_transfer_amount, transfer_tokens};
+use crate::security::ProtocolSubsystem;
+use crate::state::protocol_config::{
+    ProtocolConfig, PROTOCOL_CONFIG_SEED, TREASURY_VAULT_SEED,
+};
 // Security utilities integrated into core module
 
 // =====================================================
@@ -33,7 +46,7 @@ use crate::simple_optimization::{InputValidator, SecurityLogger, FormalVerificat
 /// 
 /// * `ctx` - The context containing work order and escrow accounts
 /// * `work_order_data` - Work order details including:
-///   - `provider` - Public key of the agent who will perform the work
+///   - `provider` - Owner of `provider_agent`, who is paid on approval
 ///   - `title` - Brief title of the work (max 128 chars)
 ///   - `description` - Detailed work description (max 4KB)
 ///   - `requirements` - Array of specific requirements
@@ -47,19 +60,21 @@ use crate::simple_optimization::{InputValidator, SecurityLogger, FormalVerificat
 /// 
 /// # Errors
 /// 
-/// * `InvalidProvider` - If provider is not a verified agent
+/// * `InvalidAgentOwner` - If `provider_agent` is not owned by `provider`
 /// * `InvalidDeadline` - If deadline is in the past
-/// * `InsufficientFunds` - If client lacks funds for escrow
+/// * `InsufficientBalance` - If client lacks funds for escrow
+/// * `InvalidConfiguration` - If the mint does not match `payment_token`
 /// 
 /// # State Changes
 /// 
 /// - Creates work order account with status `Created`
-/// - Transfers payment amount to escrow account
-/// - Sets up PDA for secure fund management
+/// - Creates the escrow record and its PDA token vault
+/// - Transfers payment amount from the client into the vault
 pub fn create_work_order(
     ctx: Context<CreateWorkOrder>,
     work_order_data: WorkOrderData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
     // SECURITY: Comprehensive authorization and validation
     require_signer!(ctx.accounts.client);
     
@@ -72,17 +87,48 @@ pub fn create_work_order(
     // SECURITY: Validate deadline
     InputValidator::validate_future_timestamp(work_order_data.deadline, "deadline")?;
     
-    // SECURITY: Verify provider exists and is active (additional check would be needed)
-    // This would require provider account validation in a full implementation
-    
     // Log work order creation for security audit
     SecurityLogger::log_security_event("WORK_ORDER_CREATED", ctx.accounts.client.key(), 
         &format!("provider: {}, title: {}, amount: {}", work_order_data.provider, work_order_data.title, work_order_data.payment_amount));
     
+    // SECURITY: Client must be able to cover the full escrow deposit
+    require!(
+        ctx.accounts.client_token_account.amount >= work_order_data.payment_amount,
+        PodAIMarketplaceError::InsufficientBalance
+    );
+    
+    // Lock the payment in the vault before recording the work order. The
+    // escrow holds what actually arrives, net of any Token-2022 transfer fee.
+    let escrowed_amount = net_transfer_amount(&ctx.accounts.payment_mint, work_order_data.payment_amount)?;
+    require!(escrowed_amount > 0, PodAIMarketplaceError::InvalidEscrowAmount);
+    
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.client_token_account,
+        &ctx.accounts.payment_mint,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.client.to_account_info(),
+        work_order_data.payment_amount,
+        &[],
+    )?;
+    
+    let work_order_key = ctx.accounts.work_order.key();
+    ctx.accounts.escrow.initialize(EscrowInit {
+        client: ctx.accounts.client.key(),
+        agent: work_order_data.provider,
+        task_id: work_order_data.order_id.to_string(),
+        amount: escrowed_amount,
+        expires_at: work_order_data.deadline,
+        work_order: work_order_key,
+        token_mint: ctx.accounts.payment_mint.key(),
+        bump: ctx.bumps.escrow,
+    })?;
+    
     let work_order = &mut ctx.accounts.work_order;
     
     work_order.client = ctx.accounts.client.key();
     work_order.provider = work_order_data.provider;
+    work_order.provider_agent = ctx.accounts.provider_agent.key();
     work_order.title = work_order_data.title.clone();
     work_order.description = work_order_data.description.clone();
     work_order.requirements = work_order_data.requirements.clone();
@@ -126,7 +172,8 @@ pub fn create_work_order(
 /// # Errors
 /// 
 /// * `UnauthorizedAccess` - If submitter is not the assigned provider
-/// * `InvalidWorkOrderStatus` - If work order is not in `Open` or `InProgress` status
+/// * `InvalidWorkOrderStatus` - If work order is not in `Created`, `Open` or `InProgress` status
+/// * `DeadlinePassed` - If the work order deadline has already passed
 /// * `InvalidIPFSHash` - If IPFS hash is malformed
 /// 
 /// # State Changes
@@ -148,6 +195,7 @@ pub fn submit_work_delivery(
     ctx: Context<SubmitWorkDelivery>,
     delivery_data: WorkDeliveryData,
 ) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Global)?;
     // SECURITY: Comprehensive authorization and validation
     require_signer!(ctx.accounts.provider);
     
@@ -159,6 +207,21 @@ pub fn submit_work_delivery(
         PodAIMarketplaceError::UnauthorizedAccess
     );
     
+    // SECURITY: Only work that has not been delivered, cancelled or paid can be submitted
+    require!(
+        matches!(
+            work_order.status,
+            WorkOrderStatus::Created | WorkOrderStatus::Open | WorkOrderStatus::InProgress
+        ),
+        PodAIMarketplaceError::InvalidWorkOrderStatus
+    );
+    
+    // SECURITY: Late deliveries cannot block the client's timeout refund
+    require!(
+        Clock::get()?.unix_timestamp <= work_order.deadline,
+        PodAIMarketplaceError::DeadlinePassed
+    );
+    
     // SECURITY: Verify work order state transition is valid
     FormalVerification::verify_work_order_transition(
         work_order.status as u8,
@@ -204,6 +267,227 @@ pub fn submit_work_delivery(
     Ok(())
 }
 
+/// Approves a submitted delivery and releases the escrowed payment
+/// 
+/// The client confirms the provider's delivery, and the vault balance less the
+/// protocol fee and any creator royalty is transferred to the provider's token
+/// account. The provider agent's earnings and completed job count are updated
+/// to match.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing work order, escrow, vault and provider accounts
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful release
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the work order client
+/// * `InvalidWorkOrderStatus` - If no delivery has been submitted
+/// * `InvalidEscrowStatus` - If the escrow was already settled
+/// 
+/// # State Changes
+/// 
+/// - Transfers the protocol fee to the treasury, the royalty to the agent's
+///   creator and the rest to the provider
+/// - Marks escrow `Completed` and work order `Completed`
+pub fn approve_work_order(ctx: Context<ApproveWorkOrder>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require_signer!(ctx.accounts.client);
+    
+    // SECURITY: Payment is only released against a submitted delivery
+    require!(
+        ctx.accounts.work_order.status == WorkOrderStatus::Submitted,
+        PodAIMarketplaceError::InvalidWorkOrderStatus
+    );
+    
+    let release_amount = ctx.accounts.escrow_vault.amount;
+    
+    ctx.accounts.escrow.complete(None)?;
+    
+    let work_order_key = ctx.accounts.work_order.key();
+    let escrow_seeds: &[&[u8]] = &[ESCROW_SEED, work_order_key.as_ref(), &[ctx.accounts.escrow.bump]];
+    let provider_amount = collect_protocol_fee(
+        &ctx.accounts.protocol_config,
+        ctx.accounts.escrow.fee_type,
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.escrow.to_account_info(),
+        release_amount,
+        &[escrow_seeds],
+    )?;
+    let provider_amount = pay_royalty(
+        &ctx.accounts.royalty_stream.to_account_info(),
+        ctx.accounts.royalty_token_account.as_ref(),
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.escrow.to_account_info(),
+        &[escrow_seeds],
+        ctx.accounts.provider_agent.key(),
+        work_order_key,
+        provider_amount,
+    )?;
+    let received_amount = net_transfer_amount(&ctx.accounts.token_mint, provider_amount)?;
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.provider_token_account,
+        &ctx.accounts.escrow.to_account_info(),
+        provider_amount,
+        &[escrow_seeds],
+    )?;
+    
+    let provider_agent = &mut ctx.accounts.provider_agent;
+    provider_agent.total_earnings = provider_agent.total_earnings
+        .checked_add(received_amount)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    provider_agent.total_jobs_completed = provider_agent.total_jobs_completed
+        .checked_add(1)
+        .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+    
+    let clock = Clock::get()?;
+    let work_order = &mut ctx.accounts.work_order;
+    work_order.status = WorkOrderStatus::Completed;
+    work_order.updated_at = clock.unix_timestamp;
+    
+    SecurityLogger::log_security_event("WORK_ORDER_ESCROW_RELEASED", ctx.accounts.client.key(),
+        &format!("work_order: {}, amount: {}", work_order_key, release_amount));
+    
+    emit!(WorkOrderEscrowReleasedEvent {
+        work_order: work_order_key,
+        provider: work_order.provider,
+        amount: received_amount,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+/// Cancels a work order before delivery and refunds the client
+/// 
+/// Only the client can cancel, and only while no delivery has been submitted.
+/// Once work is delivered the funds can only move through approval or a dispute.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing work order, escrow, vault and client accounts
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful refund
+/// 
+/// # Errors
+/// 
+/// * `UnauthorizedAccess` - If signer is not the work order client
+/// * `InvalidWorkOrderStatus` - If a delivery was already submitted
+/// * `InvalidEscrowStatus` - If the escrow was already settled or is disputed
+pub fn cancel_work_order(ctx: Context<RefundWorkOrder>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require_signer!(ctx.accounts.authority);
+    
+    // SECURITY: Only the client may cancel their own work order
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.work_order.client,
+        PodAIMarketplaceError::UnauthorizedAccess
+    );
+    
+    require!(
+        matches!(ctx.accounts.work_order.status, WorkOrderStatus::Created | WorkOrderStatus::Open),
+        PodAIMarketplaceError::InvalidWorkOrderStatus
+    );
+    
+    ctx.accounts.work_order.cancel()?;
+    refund_escrow(ctx, false)
+}
+
+/// Refunds the client once the deadline passes without a delivery
+/// 
+/// Permissionless crank: anyone may trigger it, but funds only ever return to
+/// the client's token account.
+/// 
+/// # Arguments
+/// 
+/// * `ctx` - The context containing work order, escrow, vault and client accounts
+/// 
+/// # Returns
+/// 
+/// Returns `Ok(())` on successful refund
+/// 
+/// # Errors
+/// 
+/// * `EscrowNotExpired` - If the deadline has not yet passed
+/// * `InvalidWorkOrderStatus` - If a delivery was submitted before the deadline
+pub fn refund_expired_work_order(ctx: Context<RefundWorkOrder>) -> Result<()> {
+    ctx.accounts.protocol_config.check_not_paused(ProtocolSubsystem::Payments)?;
+    require_signer!(ctx.accounts.authority);
+    
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.escrow.is_expired(clock.unix_timestamp),
+        PodAIMarketplaceError::EscrowNotExpired
+    );
+    
+    // SECURITY: A delivery submitted in time must be approved or disputed instead
+    require!(
+        matches!(
+            ctx.accounts.work_order.status,
+            WorkOrderStatus::Created | WorkOrderStatus::Open | WorkOrderStatus::InProgress
+        ),
+        PodAIMarketplaceError::InvalidWorkOrderStatus
+    );
+    
+    let work_order = &mut ctx.accounts.work_order;
+    work_order.status = WorkOrderStatus::Cancelled;
+    work_order.updated_at = clock.unix_timestamp;
+    
+    refund_escrow(ctx, true)
+}
+
+/// Returns the full vault balance to the client and closes out the escrow
+fn refund_escrow(ctx: Context<RefundWorkOrder>, expired: bool) -> Result<()> {
+    // SECURITY: Disputed funds can only be released through dispute resolution
+    require!(
+        ctx.accounts.escrow.status == EscrowStatus::Active,
+        PodAIMarketplaceError::InvalidEscrowStatus
+    );
+    
+    let refund_amount = ctx.accounts.escrow_vault.amount;
+    let received_amount = net_transfer_amount(&ctx.accounts.token_mint, refund_amount)?;
+    
+    ctx.accounts.escrow.cancel()?;
+    
+    let work_order_key = ctx.accounts.work_order.key();
+    let escrow_seeds: &[&[u8]] = &[ESCROW_SEED, work_order_key.as_ref(), &[ctx.accounts.escrow.bump]];
+    transfer_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.escrow_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.client_token_account,
+        &ctx.accounts.escrow.to_account_info(),
+        refund_amount,
+        &[escrow_seeds],
+    )?;
+    
+    SecurityLogger::log_security_event("WORK_ORDER_ESCROW_REFUNDED", ctx.accounts.authority.key(),
+        &format!("work_order: {}, amount: {}, expired: {}", work_order_key, refund_amount, expired));
+    
+    emit!(WorkOrderRefundedEvent {
+        work_order: work_order_key,
+        client: ctx.accounts.work_order.client,
+        amount: received_amount,
+        expired,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
 // =====================================================
 // ACCOUNT VALIDATION CONTEXTS
 // =====================================================
@@ -211,6 +495,8 @@ pub fn submit_work_delivery(
 #[derive(Accounts)]
 #[instruction(work_order_data: WorkOrderData)]
 pub struct CreateWorkOrder<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = client,
@@ -220,9 +506,48 @@ pub struct CreateWorkOrder<'info> {
     )]
     pub work_order: Account<'info, WorkOrder>,
     
+    #[account(
+        init,
+        payer = client,
+        space = Escrow::LEN,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        init,
+        payer = client,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// The agent the work is ordered from; the order stays bound to it if resold
+    #[account(
+        constraint = provider_agent.owner == work_order_data.provider @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub provider_agent: Account<'info, Agent>,
+    
+    #[account(
+        constraint = payment_mint.key() == work_order_data.payment_token @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key() @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = client_token_account.mint == payment_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub client_token_account: InterfaceAccount<'info, TokenAccount>,
+    
     #[account(mut)]
     pub client: Signer<'info>,
     
+    pub token_program: Interface<'info, TokenInterface>,
     pub clock: Sysvar<'info, Clock>,
     pub system_program: Program<'info, System>,
 }
@@ -230,6 +555,8 @@ pub struct CreateWorkOrder<'info> {
 #[derive(Accounts)]
 #[instruction(delivery_data: WorkDeliveryData)]
 pub struct SubmitWorkDelivery<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
     #[account(
         init,
         payer = provider,
@@ -247,4 +574,121 @@ pub struct SubmitWorkDelivery<'info> {
     
     pub clock: Sysvar<'info, Clock>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct ApproveWorkOrder<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(
+        mut,
+        constraint = work_order.client == client.key() @ PodAIMarketplaceError::UnauthorizedAccess
+    )]
+    pub work_order: Account<'info, WorkOrder>,
+    
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    // Bound by key rather than re-derived from the provider: agents keep the
+    // address seeded by their first owner when resold, and an order accepted
+    // before a resale still pays the provider who accepted it
+    #[account(
+        mut,
+        address = work_order.provider_agent @ PodAIMarketplaceError::InvalidAgentOwner
+    )]
+    pub provider_agent: Account<'info, Agent>,
+    
+    #[account(
+        mut,
+        constraint = provider_token_account.owner == work_order.provider @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = provider_token_account.mint == token_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub provider_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = token_mint.key() == escrow.token_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_VAULT_SEED, token_mint.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = protocol_config,
+        token::token_program = token_program
+    )]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: The provider agent's royalty stream PDA. Always required so a
+    /// client cannot skip royalties; left uninitialized for agents without one
+    #[account(
+        mut,
+        seeds = [ROYALTY_STREAM_SEED, provider_agent.key().as_ref()],
+        bump
+    )]
+    pub royalty_stream: UncheckedAccount<'info>,
+    
+    /// Original creator's token account; required when the stream is active
+    #[account(mut)]
+    pub royalty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub client: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundWorkOrder<'info> {
+    #[account(seeds = [PROTOCOL_CONFIG_SEED], bump = protocol_config.bump)]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    #[account(mut)]
+    pub work_order: Account<'info, WorkOrder>,
+    
+    #[account(
+        mut,
+        seeds = [ESCROW_SEED, work_order.key().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    
+    #[account(
+        mut,
+        seeds = [ESCROW_VAULT_SEED, work_order.key().as_ref()],
+        bump,
+        token::mint = token_mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub escrow_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = client_token_account.owner == work_order.client @ PodAIMarketplaceError::InvalidAccountOwner,
+        constraint = client_token_account.mint == token_mint.key() @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub client_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        constraint = token_mint.key() == escrow.token_mint @ PodAIMarketplaceError::InvalidConfiguration
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub authority: Signer<'info>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -20,6 +20,8 @@ declare_id!("4nusKGxuNwK7XggWQHCMEE1Ht7taWrSJMhhNfTqswVFP");
 mod instructions;
 pub mod state;
 mod simple_optimization;
+mod token_utils;
+pub mod security;
 
 // Re-export all instruction handlers
 
@@ -61,6 +63,7 @@ pub struct ServicePurchaseData {
     pub requirements: Vec<String>,
     pub custom_instructions: String,
     pub deadline: i64,
+    pub nonce: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -164,6 +167,23 @@ pub struct WorkOrderCreatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct WorkOrderEscrowReleasedEvent {
+    pub work_order: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WorkOrderRefundedEvent {
+    pub work_order: Pubkey,
+    pub client: Pubkey,
+    pub amount: u64,
+    pub expired: bool,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct WorkDeliverySubmittedEvent {
     pub work_order: Pubkey,
@@ -217,6 +237,10 @@ pub struct A2AMessageSentEvent {
     pub message_id: u64,
     pub session_id: u64,
     pub sender: Pubkey,
+    pub chunk_index: u16,
+    pub is_final: bool,
+    /// Chained hash of the whole message, set on its final chunk
+    pub message_hash: Option<[u8; 32]>,
     pub timestamp: i64,
 }
 
@@ -225,6 +249,27 @@ pub struct A2AStatusUpdatedEvent {
     pub agent: Pubkey,
     pub status: String,
     pub availability: bool,
+    pub message_id: u64,
+    pub progress: u8,
+    pub is_final: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct A2ASessionAcceptedEvent {
+    pub session: Pubkey,
+    pub session_id: u64,
+    pub responder: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct A2ASessionClosedEvent {
+    pub session: Pubkey,
+    pub closed_by: Pubkey,
+    pub messages_closed: u64,
+    /// Whether the session account itself was closed
+    pub session_closed: bool,
     pub timestamp: i64,
 }
 
@@ -667,6 +712,111 @@ pub enum PodAIMarketplaceError {
     
     #[msg("Maintenance mode active")]
     MaintenanceModeActive = 2185,
+    
+    #[msg("Escrow has not expired")]
+    EscrowNotExpired = 2187,
+    
+    #[msg("Arbitrator not eligible")]
+    ArbitratorNotEligible = 2188,
+    
+    #[msg("Too many arbitrators")]
+    TooManyArbitrators = 2189,
+    
+    #[msg("Revealed bid does not match commitment")]
+    CommitmentMismatch = 2190,
+    
+    #[msg("Multisig transaction not found")]
+    MultisigTransactionNotFound = 2191,
+    
+    #[msg("Multisig transaction expired")]
+    TransactionExpired = 2192,
+    
+    #[msg("Signer has already signed this transaction")]
+    AlreadySigned = 2193,
+    
+    #[msg("Not enough signatures to execute")]
+    InsufficientSignatures = 2194,
+    
+    #[msg("Time lock has not elapsed")]
+    TimeLockActive = 2195,
+    
+    #[msg("Execution conditions not met")]
+    ExecutionConditionNotMet = 2196,
+    
+    #[msg("Voting has not started")]
+    VotingNotStarted = 2197,
+    
+    #[msg("Voting period has not ended")]
+    VotingStillActive = 2198,
+    
+    #[msg("Protocol is paused")]
+    ProtocolPaused = 2199,
+    
+    #[msg("Job is not completed")]
+    JobNotCompleted = 2200,
+    
+    #[msg("Too many verifiers")]
+    TooManyVerifiers = 2201,
+    
+    #[msg("Verifier not registered")]
+    VerifierNotRegistered = 2202,
+    
+    #[msg("Review window has not elapsed")]
+    ReviewWindowActive = 2203,
+    
+    #[msg("Milestone not found")]
+    MilestoneNotFound = 2204,
+    
+    #[msg("Too many milestones")]
+    TooManyMilestones = 2205,
+    
+    #[msg("Maximum revisions reached")]
+    TooManyRevisions = 2206,
+    
+    #[msg("SLA breach already reported")]
+    SlaBreachAlreadyReported = 2207,
+    
+    #[msg("SLA breach report window has closed")]
+    SlaReportWindowClosed = 2208,
+    
+    #[msg("Deal term is still active")]
+    DealTermActive = 2209,
+    
+    #[msg("Subscription billing is not due")]
+    BillingNotDue = 2210,
+    
+    #[msg("Usage cap exceeded for this period")]
+    UsageCapExceeded = 2211,
+    
+    #[msg("Subscription is not active")]
+    SubscriptionNotActive = 2212,
+    
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed = 2213,
+    
+    #[msg("Dispute window is still open")]
+    DisputeWindowActive = 2214,
+    
+    #[msg("Usage reports are still outstanding")]
+    UsageReportsOutstanding = 2215,
+    
+    #[msg("Pricing tier not found or not selectable")]
+    InvalidPricingTier = 2216,
+    
+    #[msg("A2A session has expired")]
+    A2ASessionExpired = 2217,
+    
+    #[msg("Channel has no free seats")]
+    ChannelFull = 2218,
+    
+    #[msg("Channel still has other members")]
+    ChannelNotEmpty = 2219,
+    
+    #[msg("Work order payment is held in escrow")]
+    WorkOrderEscrowed = 2220,
+    
+    #[msg("Price exceeds the buyer's maximum")]
+    PriceAboveMaximum = 2221,
 }
 
 // =====================================================
@@ -702,6 +852,9 @@ pub mod podai_marketplace {
     // Replication instructions
     pub use instructions::replication::*;
     
+    // Review and reputation instructions
+    pub use instructions::reputation::*;
+    
     // Pricing instructions
     pub use instructions::pricing::*;
     
@@ -714,6 +867,12 @@ pub mod podai_marketplace {
     // Royalty instructions
     pub use instructions::royalty::*;
     
+    // Subscription instructions
+    pub use instructions::subscription::*;
+    
+    // Usage metering instructions
+    pub use instructions::metering::*;
+    
     // Dispute resolution instructions
     pub use instructions::dispute::*;
     
@@ -728,4 +887,7 @@ pub mod podai_marketplace {
     
     // Compliance and governance instructions
     pub use instructions::compliance_governance::*;
+    
+    // Protocol config and emergency pause instructions
+    pub use instructions::protocol_config::*;
 }
\ No newline at end of file
@@ -6,16 +6,19 @@
  */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::{PodAIMarketplaceError, MAX_PAYMENT_AMOUNT, MIN_PAYMENT_AMOUNT};
 
 // =====================================================
 // SECURITY CONSTANTS
 // =====================================================
 
-pub const MAX_TITLE_LENGTH: usize = 128;
-pub const MAX_NAME_LENGTH: usize = 64;
-pub const MAX_DESCRIPTION_LENGTH: usize = 512;
-pub const MAX_PAYMENT_AMOUNT: u64 = 1_000_000_000_000;
-pub const MIN_PAYMENT_AMOUNT: u64 = 1_000;
+/// Maximum length of the reason recorded when the protocol is paused
+pub const MAX_PAUSE_REASON_LENGTH: usize = 128;
 
 // =====================================================
 // SECURITY MACROS
@@ -25,9 +28,11 @@ pub const MIN_PAYMENT_AMOUNT: u64 = 1_000;
 #[macro_export]
 macro_rules! validate_string {
     ($string:expr, $max_length:expr, $field_name:expr) => {
-        if $string.is_empty() || $string.len() > $max_length {
-            return Err(PodAIMarketplaceError::InputTooLong.into());
-        }
+        $crate::simple_optimization::ValidationHelper::validate_string_length(
+            $string,
+            $max_length,
+            $field_name
+        )?;
     };
 }
 
@@ -35,9 +40,12 @@ macro_rules! validate_string {
 #[macro_export]
 macro_rules! validate_payment {
     ($amount:expr, $field_name:expr) => {
-        if $amount < MIN_PAYMENT_AMOUNT || $amount > MAX_PAYMENT_AMOUNT {
-            return Err(PodAIMarketplaceError::InvalidPaymentAmount.into());
-        }
+        $crate::simple_optimization::ValidationHelper::validate_range(
+            $amount,
+            $crate::MIN_PAYMENT_AMOUNT,
+            $crate::MAX_PAYMENT_AMOUNT,
+            $field_name
+        )?;
     };
 }
 
@@ -91,10 +99,9 @@ macro_rules! error_with_context {
 macro_rules! monitor_performance {
     ($operation_name:expr, $block:block) => {
         {
-            let start_time = Clock::get()?.unix_timestamp;
+            msg!("Starting operation: {}", $operation_name);
             let result = $block;
-            let end_time = Clock::get()?.unix_timestamp;
-            msg!("Operation {} took {} seconds", $operation_name, end_time - start_time);
+            msg!("Completed operation: {}", $operation_name);
             result
         }
     };
@@ -320,17 +327,17 @@ pub fn validate_escrow_config(
     auto_release_time: i64,
 ) -> Result<()> {
     // Validate payment amount
-    if amount < MIN_PAYMENT_AMOUNT || amount > MAX_PAYMENT_AMOUNT {
+    if !(MIN_PAYMENT_AMOUNT..=MAX_PAYMENT_AMOUNT).contains(&amount) {
         return Err(PodAIMarketplaceError::InvalidPaymentAmount.into());
     }
     
     // Validate dispute resolution time (1 hour to 30 days)
-    if dispute_resolution_time < 3600 || dispute_resolution_time > 2_592_000 {
+    if !(3600..=2_592_000).contains(&dispute_resolution_time) {
         return Err(PodAIMarketplaceError::InvalidDeadline.into());
     }
     
     // Validate auto release time (1 day to 90 days)
-    if auto_release_time < 86400 || auto_release_time > 7_776_000 {
+    if !(86400..=7_776_000).contains(&auto_release_time) {
         return Err(PodAIMarketplaceError::InvalidDeadline.into());
     }
     
@@ -350,7 +357,7 @@ pub fn validate_auction_params(
     min_bid_increment: u64,
 ) -> Result<()> {
     // Validate prices
-    if start_price < MIN_PAYMENT_AMOUNT || start_price > MAX_PAYMENT_AMOUNT {
+    if !(MIN_PAYMENT_AMOUNT..=MAX_PAYMENT_AMOUNT).contains(&start_price) {
         return Err(PodAIMarketplaceError::InvalidPaymentAmount.into());
     }
     
@@ -359,7 +366,7 @@ pub fn validate_auction_params(
     }
     
     // Validate duration (1 hour to 30 days)
-    if duration < 3600 || duration > 2_592_000 {
+    if !(3600..=2_592_000).contains(&duration) {
         return Err(PodAIMarketplaceError::InvalidDeadline.into());
     }
     
@@ -371,6 +378,80 @@ pub fn validate_auction_params(
     Ok(())
 }
 
+// =====================================================
+// SIGNATURE VERIFICATION
+// =====================================================
+
+/// Size of one `Ed25519SignatureOffsets` entry in an ed25519 program instruction
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_PUBKEY_LEN: usize = 32;
+
+/// Extracts the public key and message from ed25519 program instruction data
+/// 
+/// Only single-signature instructions whose key, signature and message all
+/// live in the instruction's own data are accepted, so what is returned is
+/// exactly what the ed25519 program verified.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<(Pubkey, &[u8])> {
+    require!(
+        data.len() >= ED25519_OFFSETS_START + ED25519_OFFSETS_LEN && data[0] == 1,
+        PodAIMarketplaceError::SignatureVerificationFailed
+    );
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let offsets = ED25519_OFFSETS_START;
+    let signature_ix = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix = read_u16(offsets + 12);
+
+    // SECURITY: u16::MAX points at the ed25519 instruction itself
+    require!(
+        signature_ix == u16::MAX && pubkey_ix == u16::MAX && message_ix == u16::MAX,
+        PodAIMarketplaceError::SignatureVerificationFailed
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN)
+        .ok_or(PodAIMarketplaceError::SignatureVerificationFailed)?;
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(PodAIMarketplaceError::SignatureVerificationFailed)?;
+
+    let pubkey = Pubkey::try_from(pubkey)
+        .map_err(|_| PodAIMarketplaceError::SignatureVerificationFailed)?;
+    Ok((pubkey, message))
+}
+
+/// Checks that the instruction before the current one is an ed25519 program
+/// instruction verifying `signer`'s signature over `message`
+/// 
+/// The runtime fails the whole transaction if the ed25519 instruction's
+/// signature is invalid, so matching its key and message is sufficient.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, PodAIMarketplaceError::SignatureVerificationFailed);
+
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require!(
+        ix.program_id == ed25519_program::ID && ix.accounts.is_empty(),
+        PodAIMarketplaceError::SignatureVerificationFailed
+    );
+
+    let (verified_signer, verified_message) = parse_ed25519_instruction(&ix.data)?;
+    require!(
+        verified_signer == *signer && verified_message == message,
+        PodAIMarketplaceError::SignatureVerificationFailed
+    );
+    Ok(())
+}
+
 // =====================================================
 // SECURITY TRAITS
 // =====================================================
@@ -442,53 +523,115 @@ impl RateLimit {
 // EMERGENCY CONTROLS
 // =====================================================
 
+/// Protocol areas that can be paused independently of each other
+///
+/// `Global` is the kill switch: while it is set every subsystem is paused.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProtocolSubsystem {
+    Global,
+    Payments,
+    Auctions,
+    Messaging,
+}
+
 /// Emergency pause functionality
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct EmergencyControls {
     pub is_paused: bool,
+    pub payments_paused: bool,
+    pub auctions_paused: bool,
+    pub messaging_paused: bool,
     pub pause_authority: Pubkey,
     pub paused_at: Option<i64>,
     pub pause_reason: String,
 }
 
 impl EmergencyControls {
+    pub const LEN: usize = 1 + // is_paused
+        1 + // payments_paused
+        1 + // auctions_paused
+        1 + // messaging_paused
+        32 + // pause_authority
+        1 + 8 + // paused_at
+        4 + MAX_PAUSE_REASON_LENGTH; // pause_reason
+
     pub fn new(pause_authority: Pubkey) -> Self {
         Self {
             is_paused: false,
+            payments_paused: false,
+            auctions_paused: false,
+            messaging_paused: false,
             pause_authority,
             paused_at: None,
             pause_reason: String::new(),
         }
     }
     
-    pub fn pause(&mut self, authority: Pubkey, reason: String, current_time: i64) -> Result<()> {
+    fn flag_mut(&mut self, subsystem: ProtocolSubsystem) -> &mut bool {
+        match subsystem {
+            ProtocolSubsystem::Global => &mut self.is_paused,
+            ProtocolSubsystem::Payments => &mut self.payments_paused,
+            ProtocolSubsystem::Auctions => &mut self.auctions_paused,
+            ProtocolSubsystem::Messaging => &mut self.messaging_paused,
+        }
+    }
+    
+    /// Returns true if `subsystem` is paused directly or by the global switch
+    pub fn is_paused_for(&self, subsystem: ProtocolSubsystem) -> bool {
+        self.is_paused
+            || match subsystem {
+                ProtocolSubsystem::Global => false,
+                ProtocolSubsystem::Payments => self.payments_paused,
+                ProtocolSubsystem::Auctions => self.auctions_paused,
+                ProtocolSubsystem::Messaging => self.messaging_paused,
+            }
+    }
+    
+    /// Returns true if any subsystem is paused
+    pub fn any_paused(&self) -> bool {
+        self.is_paused || self.payments_paused || self.auctions_paused || self.messaging_paused
+    }
+    
+    pub fn pause(
+        &mut self,
+        authority: Pubkey,
+        subsystem: ProtocolSubsystem,
+        reason: String,
+        current_time: i64,
+    ) -> Result<()> {
         if authority != self.pause_authority {
             return Err(PodAIMarketplaceError::UnauthorizedAccess.into());
         }
+        if reason.len() > MAX_PAUSE_REASON_LENGTH {
+            return Err(PodAIMarketplaceError::InputTooLong.into());
+        }
         
-        self.is_paused = true;
+        *self.flag_mut(subsystem) = true;
         self.paused_at = Some(current_time);
         self.pause_reason = reason;
         
         Ok(())
     }
     
-    pub fn unpause(&mut self, authority: Pubkey) -> Result<()> {
+    pub fn unpause(&mut self, authority: Pubkey, subsystem: ProtocolSubsystem) -> Result<()> {
         if authority != self.pause_authority {
             return Err(PodAIMarketplaceError::UnauthorizedAccess.into());
         }
         
-        self.is_paused = false;
-        self.paused_at = None;
-        self.pause_reason.clear();
+        *self.flag_mut(subsystem) = false;
+        if !self.any_paused() {
+            self.paused_at = None;
+            self.pause_reason.clear();
+        }
         
         Ok(())
     }
     
-    pub fn check_not_paused(&self) -> Result<()> {
-        if self.is_paused {
-            return Err(PodAIMarketplaceError::InvalidStatusTransition.into());
+    pub fn check_not_paused(&self, subsystem: ProtocolSubsystem) -> Result<()> {
+        if self.is_paused_for(subsystem) {
+            msg!("Protocol paused for {:?}: {}", subsystem, self.pause_reason);
+            return Err(PodAIMarketplaceError::ProtocolPaused.into());
         }
         Ok(())
     }
-}
\ No newline at end of file
+}
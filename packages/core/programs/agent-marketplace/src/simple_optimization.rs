@@ -120,19 +120,6 @@ impl SimpleMetrics {
 // UTILITY MACROS (SIMPLIFIED)
 // =====================================================
 
-/// Simple macro for performance monitoring
-#[macro_export]
-macro_rules! monitor_performance {
-    ($name:expr, $block:block) => {
-        {
-            msg!("Starting operation: {}", $name);
-            let result = $block;
-            msg!("Completed operation: {}", $name);
-            result
-        }
-    };
-}
-
 /// Simple macro for compute budget optimization
 #[macro_export]
 macro_rules! optimize_compute_budget {
@@ -200,69 +187,8 @@ impl ValidationHelper {
     }
 }
 
-// =====================================================
-// ADDITIONAL MISSING MACROS
-// =====================================================
-
-/// Safe addition macro
-#[macro_export]
-macro_rules! safe_add {
-    ($a:expr, $b:expr) => {
-        crate::simple_optimization::safe_math::safe_add($a, $b)?
-    };
-}
-
-/// Safe multiplication macro
-#[macro_export]
-macro_rules! safe_mul {
-    ($a:expr, $b:expr) => {
-        crate::simple_optimization::safe_math::safe_mul($a, $b)?
-    };
-}
-
-/// Error with context macro
-#[macro_export]
-macro_rules! error_with_context {
-    ($error:expr, $context:expr) => {
-        {
-            msg!("Error context: {}", $context);
-            $error
-        }
-    };
-}
-
-/// Validate payment macro
-#[macro_export]
-macro_rules! validate_payment {
-    ($amount:expr, $field:expr) => {
-        crate::simple_optimization::ValidationHelper::validate_range(
-            $amount,
-            crate::MIN_PAYMENT_AMOUNT,
-            crate::MAX_PAYMENT_AMOUNT,
-            $field
-        )?;
-    };
-}
-
-/// Validate string macro
-#[macro_export]
-macro_rules! validate_string {
-    ($string:expr, $max_len:expr, $field:expr) => {
-        crate::simple_optimization::ValidationHelper::validate_string_length(
-            $string,
-            $max_len,
-            $field
-        )?;
-    };
-}
-
-/// Require signer macro
-#[macro_export]
-macro_rules! require_signer {
-    ($signer:expr) => {
-        require!($signer.is_signer, crate::PodAIMarketplaceError::UnauthorizedAccess);
-    };
-}
+// Validation and arithmetic macros (`validate_string!`, `validate_payment!`,
+// `require_signer!`, `safe_add!`, ...) are exported from `security`.
 
 // =====================================================
 // STUB IMPLEMENTATIONS FOR MISSING TYPES
@@ -0,0 +1,505 @@
+/*!
+ * A2A Protocol State Module
+ *
+ * Contains agent-to-agent sessions, the message chunks exchanged in them,
+ * each session's context window and each participant's status.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+use super::{MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError};
+
+// PDA Seeds
+pub const A2A_SESSION_SEED: &[u8] = b"a2a_session";
+pub const A2A_MESSAGE_SEED: &[u8] = b"a2a_message";
+pub const A2A_STATUS_SEED: &[u8] = b"a2a_status";
+pub const A2A_CONTEXT_SEED: &[u8] = b"a2a_context";
+
+// Constants
+pub const MAX_A2A_SESSION_DURATION: i64 = 7 * 24 * 60 * 60; // 7 days
+/// Largest chunk that still fits in one transaction with its accounts
+pub const MAX_A2A_CHUNK_LENGTH: usize = 900;
+pub const MAX_A2A_MESSAGE_CHUNKS: u16 = 1024;
+/// Completed messages remembered by a session's context
+pub const A2A_CONTEXT_WINDOW: usize = 16;
+pub const MAX_A2A_PARTIAL_CONTENT_LENGTH: usize = 512;
+pub const MAX_A2A_CAPABILITIES: usize = 10;
+pub const MAX_A2A_CAPABILITY_LENGTH: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct A2ASessionData {
+    pub session_id: u64,
+    pub initiator: Pubkey,
+    pub responder: Pubkey,
+    pub session_type: String,
+    pub metadata: String,
+    pub expires_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct A2AMessageData {
+    pub message_id: u64,
+    pub session_id: u64,
+    pub sender: Pubkey,
+    /// One chunk of the message; clients reassemble chunks by `chunk_index`
+    pub content: String,
+    pub message_type: String,
+    pub timestamp: i64,
+    /// Position of this chunk, starting at 0 for each message
+    pub chunk_index: u16,
+    /// Whether this chunk completes the message
+    pub is_final: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct A2AStatusData {
+    pub status_id: u64,
+    pub agent: Pubkey,
+    pub status: String,
+    pub capabilities: Vec<String>,
+    pub availability: bool,
+    pub last_updated: i64,
+    /// Message the progress refers to
+    pub message_id: u64,
+    /// Percent complete (0-100)
+    pub progress: u8,
+    pub partial_content: String,
+    pub is_final: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum A2ASessionStatus {
+    /// Created by the initiator, waiting for the responder to accept
+    Pending,
+    Active,
+    /// No further messages; message accounts may still be awaiting cleanup
+    Closed,
+}
+
+/// A conversation between an initiator and a responder
+///
+/// The initiator pays the session's rent and each sender pays for their own
+/// messages; `close_a2a_session` returns all of it to whoever paid.
+#[account]
+pub struct A2ASession {
+    pub session_id: u64,
+    pub initiator: Pubkey,
+    pub responder: Pubkey,
+    pub session_type: String,
+    pub metadata: String,
+    pub status: A2ASessionStatus,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub accepted_at: Option<i64>,
+    pub closed_at: Option<i64>,
+    /// Sequence number of the next message, also the count sent so far
+    pub message_count: u64,
+    /// Message accounts not yet closed
+    pub open_messages: u64,
+    pub bump: u8,
+}
+
+impl A2ASession {
+    pub const LEN: usize = 8 + // discriminator
+        8 + // session_id
+        32 + // initiator
+        32 + // responder
+        4 + MAX_GENERAL_STRING_LENGTH + // session_type
+        4 + MAX_GENERAL_STRING_LENGTH + // metadata
+        1 + // status
+        8 + // created_at
+        8 + // expires_at
+        1 + 8 + // accepted_at
+        1 + 8 + // closed_at
+        8 + // message_count
+        8 + // open_messages
+        1; // bump
+
+    pub fn is_participant(&self, key: &Pubkey) -> bool {
+        *key == self.initiator || *key == self.responder
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+
+    pub fn accept(&mut self, now: i64) -> Result<()> {
+        require!(self.status == A2ASessionStatus::Pending, PodAIMarketplaceError::InvalidStatusTransition);
+        require!(!self.is_expired(now), PodAIMarketplaceError::A2ASessionExpired);
+        self.status = A2ASessionStatus::Active;
+        self.accepted_at = Some(now);
+        Ok(())
+    }
+
+    /// Claims the next message sequence number
+    pub fn record_message(&mut self, now: i64) -> Result<u64> {
+        require!(self.status == A2ASessionStatus::Active, PodAIMarketplaceError::InvalidStatusTransition);
+        require!(!self.is_expired(now), PodAIMarketplaceError::A2ASessionExpired);
+        let sequence = self.message_count;
+        self.message_count = sequence
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.open_messages = self.open_messages
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(sequence)
+    }
+
+    /// Whether `closer` may close the session: participants at any time,
+    /// anyone once it has expired
+    pub fn can_close(&self, closer: &Pubkey, now: i64) -> bool {
+        self.is_participant(closer) || self.is_expired(now) || self.status == A2ASessionStatus::Closed
+    }
+
+    pub fn mark_closed(&mut self, now: i64) {
+        if self.status != A2ASessionStatus::Closed {
+            self.status = A2ASessionStatus::Closed;
+            self.closed_at = Some(now);
+        }
+    }
+}
+
+/// One chunk of a message
+#[account]
+pub struct A2AMessage {
+    pub message_id: u64,
+    pub session: Pubkey,
+    pub sender: Pubkey,
+    /// Position of the chunk in the session
+    pub sequence: u64,
+    pub chunk_index: u16,
+    pub is_final: bool,
+    pub content: String,
+    pub message_type: String,
+    pub sent_at: i64,
+    pub bump: u8,
+}
+
+impl A2AMessage {
+    /// Size without the bytes of `content` and `message_type`
+    pub const BASE_LEN: usize = 8 + // discriminator
+        8 + // message_id
+        32 + // session
+        32 + // sender
+        8 + // sequence
+        2 + // chunk_index
+        1 + // is_final
+        4 + // content
+        4 + // message_type
+        8 + // sent_at
+        1; // bump
+
+    /// Messages are sized to their content so senders only pay rent for
+    /// what they store
+    pub fn space_for(message_data: &A2AMessageData) -> usize {
+        Self::BASE_LEN + message_data.content.len() + message_data.message_type.len()
+    }
+}
+
+/// A completed message remembered by the session context
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct A2AContextEntry {
+    pub message_id: u64,
+    pub sender: Pubkey,
+    /// Chained hash of the message's chunks, see `A2AStatus::record_chunk`
+    pub hash: [u8; 32],
+}
+
+impl A2AContextEntry {
+    pub const LEN: usize = 8 + 32 + 32;
+}
+
+/// Ring buffer of the last `A2A_CONTEXT_WINDOW` completed messages of a
+/// session, oldest evicted first
+#[account]
+pub struct A2AContext {
+    pub session: Pubkey,
+    pub entries: Vec<A2AContextEntry>,
+    /// Slot the next entry is written to once the window is full
+    pub head: u8,
+    /// Completed messages over the session's lifetime
+    pub total_messages: u64,
+    pub bump: u8,
+}
+
+impl A2AContext {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // session
+        4 + A2AContextEntry::LEN * A2A_CONTEXT_WINDOW + // entries
+        1 + // head
+        8 + // total_messages
+        1; // bump
+
+    pub fn push(&mut self, entry: A2AContextEntry) {
+        if self.entries.len() < A2A_CONTEXT_WINDOW {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head as usize] = entry;
+            self.head = ((self.head as usize + 1) % A2A_CONTEXT_WINDOW) as u8;
+        }
+        self.total_messages = self.total_messages.saturating_add(1);
+    }
+
+    /// Entries from oldest to newest
+    pub fn ordered(&self) -> Vec<A2AContextEntry> {
+        let head = self.head as usize;
+        self.entries[head..].iter().chain(self.entries[..head].iter()).copied().collect()
+    }
+}
+
+/// A participant's status within a session
+#[account]
+pub struct A2AStatus {
+    pub session: Pubkey,
+    pub agent: Pubkey,
+    pub status: String,
+    pub capabilities: Vec<String>,
+    pub availability: bool,
+    pub last_updated: i64,
+    /// Message the reported progress refers to
+    pub message_id: u64,
+    pub progress: u8,
+    pub partial_content: String,
+    pub is_final: bool,
+    /// Whether the agent is partway through sending a chunked message
+    pub streaming: bool,
+    pub streaming_message_id: u64,
+    pub next_chunk_index: u16,
+    /// Chained hash of the chunks sent so far
+    pub stream_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl A2AStatus {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // session
+        32 + // agent
+        4 + MAX_GENERAL_STRING_LENGTH + // status
+        4 + (4 + MAX_A2A_CAPABILITY_LENGTH) * MAX_A2A_CAPABILITIES + // capabilities
+        1 + // availability
+        8 + // last_updated
+        8 + // message_id
+        1 + // progress
+        4 + MAX_A2A_PARTIAL_CONTENT_LENGTH + // partial_content
+        1 + // is_final
+        1 + // streaming
+        8 + // streaming_message_id
+        2 + // next_chunk_index
+        32 + // stream_hash
+        1; // bump
+
+    pub fn initialize(&mut self, session: Pubkey, agent: Pubkey, now: i64, bump: u8) {
+        self.session = session;
+        self.agent = agent;
+        self.status = String::new();
+        self.capabilities = Vec::new();
+        self.availability = true;
+        self.last_updated = now;
+        self.message_id = 0;
+        self.progress = 0;
+        self.partial_content = String::new();
+        self.is_final = false;
+        self.streaming = false;
+        self.streaming_message_id = 0;
+        self.next_chunk_index = 0;
+        self.stream_hash = [0; 32];
+        self.bump = bump;
+    }
+
+    /// Tracks a chunk of this agent's outgoing message
+    ///
+    /// Chunk 0 starts a new message, abandoning any unfinished one; later
+    /// chunks must continue the current message in order. Each chunk is
+    /// chained into `stream_hash`, so the final hash commits to the whole
+    /// message. Returns the message hash once the final chunk is recorded.
+    pub fn record_chunk(
+        &mut self,
+        message_id: u64,
+        chunk_index: u16,
+        is_final: bool,
+        content: &[u8],
+    ) -> Result<Option<[u8; 32]>> {
+        require!(chunk_index < MAX_A2A_MESSAGE_CHUNKS, PodAIMarketplaceError::InvalidA2AProtocolMessage);
+        if chunk_index == 0 {
+            self.streaming_message_id = message_id;
+            self.stream_hash = [0; 32];
+        } else {
+            require!(
+                self.streaming
+                    && message_id == self.streaming_message_id
+                    && chunk_index == self.next_chunk_index,
+                PodAIMarketplaceError::InvalidA2AProtocolMessage
+            );
+        }
+
+        self.stream_hash = hashv(&[&self.stream_hash, &chunk_index.to_le_bytes(), content]).to_bytes();
+        if is_final {
+            self.streaming = false;
+            self.next_chunk_index = 0;
+            Ok(Some(self.stream_hash))
+        } else {
+            self.streaming = true;
+            self.next_chunk_index = chunk_index + 1;
+            Ok(None)
+        }
+    }
+
+    /// Persists progress on a message; progress on the same message never
+    /// goes backwards and stops once it is final
+    pub fn record_progress(
+        &mut self,
+        message_id: u64,
+        progress: u8,
+        partial_content: String,
+        is_final: bool,
+    ) -> Result<()> {
+        require!(progress <= 100, PodAIMarketplaceError::InvalidPercentage);
+        require!(
+            partial_content.len() <= MAX_A2A_PARTIAL_CONTENT_LENGTH,
+            PodAIMarketplaceError::InputTooLong
+        );
+        if message_id == self.message_id {
+            require!(
+                !self.is_final && progress >= self.progress,
+                PodAIMarketplaceError::InvalidStatusTransition
+            );
+        }
+        self.message_id = message_id;
+        self.progress = if is_final { 100 } else { progress };
+        self.partial_content = partial_content;
+        self.is_final = is_final;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_a2a_session_lifecycle() {
+        let initiator = Pubkey::new_unique();
+        let responder = Pubkey::new_unique();
+        let expires_at = 1_000;
+        let mut session = A2ASession {
+            session_id: 1,
+            initiator,
+            responder,
+            session_type: "Direct".to_string(),
+            metadata: String::new(),
+            status: A2ASessionStatus::Pending,
+            created_at: 0,
+            expires_at,
+            accepted_at: None,
+            closed_at: None,
+            message_count: 0,
+            open_messages: 0,
+            bump: 255,
+        };
+
+        // Nothing is sent before the responder accepts
+        assert!(session.record_message(10).is_err());
+        assert!(session.accept(expires_at).is_err());
+        session.accept(10).unwrap();
+        assert_eq!(session.status, A2ASessionStatus::Active);
+        assert!(session.accept(20).is_err());
+
+        assert_eq!(session.record_message(20).unwrap(), 0);
+        assert_eq!(session.record_message(30).unwrap(), 1);
+        assert_eq!(session.open_messages, 2);
+        assert!(session.record_message(expires_at).is_err());
+
+        // Outsiders can only clean up expired sessions
+        let outsider = Pubkey::new_unique();
+        assert!(session.can_close(&responder, 40));
+        assert!(!session.can_close(&outsider, 40));
+        assert!(session.can_close(&outsider, expires_at));
+
+        session.mark_closed(50);
+        session.mark_closed(60);
+        assert_eq!(session.closed_at, Some(50));
+        assert!(session.record_message(70).is_err());
+        assert!(session.can_close(&outsider, 70));
+
+        let message_data = A2AMessageData {
+            message_id: 9,
+            session_id: 1,
+            sender: initiator,
+            content: "hello".to_string(),
+            message_type: "text".to_string(),
+            timestamp: 0,
+            chunk_index: 0,
+            is_final: true,
+        };
+        assert_eq!(A2AMessage::space_for(&message_data), A2AMessage::BASE_LEN + 9);
+    }
+
+    #[test]
+    fn test_a2a_chunked_messages_and_context() {
+        let agent = Pubkey::new_unique();
+        let mut status = A2AStatus {
+            session: Pubkey::new_unique(),
+            agent,
+            status: String::new(),
+            capabilities: Vec::new(),
+            availability: false,
+            last_updated: 0,
+            message_id: 0,
+            progress: 0,
+            partial_content: String::new(),
+            is_final: false,
+            streaming: false,
+            streaming_message_id: 0,
+            next_chunk_index: 0,
+            stream_hash: [0; 32],
+            bump: 0,
+        };
+        status.initialize(status.session, agent, 1, 255);
+
+        // Chunks must continue the current message in order
+        assert!(status.record_chunk(7, 1, false, b"late").is_err());
+        assert_eq!(status.record_chunk(7, 0, false, b"hello ").unwrap(), None);
+        assert!(status.record_chunk(8, 1, false, b"other").is_err());
+        assert!(status.record_chunk(7, 2, false, b"skipped").is_err());
+        let hash = status.record_chunk(7, 1, true, b"world").unwrap().unwrap();
+        assert!(!status.streaming);
+        assert!(status.record_chunk(7, 2, true, b"after final").is_err());
+
+        // The hash commits to every chunk and its position
+        let mut other = status.clone();
+        other.record_chunk(9, 0, false, b"hello").unwrap();
+        assert_ne!(other.record_chunk(9, 1, true, b" world").unwrap().unwrap(), hash);
+        other.record_chunk(9, 0, false, b"hello ").unwrap();
+        assert_eq!(other.record_chunk(9, 1, true, b"world").unwrap().unwrap(), hash);
+        assert!(status.record_chunk(1, MAX_A2A_MESSAGE_CHUNKS, true, b"x").is_err());
+
+        let mut context = A2AContext {
+            session: status.session,
+            entries: Vec::new(),
+            head: 0,
+            total_messages: 0,
+            bump: 255,
+        };
+        for message_id in 0..(A2A_CONTEXT_WINDOW as u64 + 3) {
+            context.push(A2AContextEntry { message_id, sender: agent, hash });
+        }
+        assert_eq!(context.entries.len(), A2A_CONTEXT_WINDOW);
+        assert_eq!(context.total_messages, A2A_CONTEXT_WINDOW as u64 + 3);
+        let ordered: Vec<u64> = context.ordered().iter().map(|e| e.message_id).collect();
+        assert_eq!(ordered, (3..A2A_CONTEXT_WINDOW as u64 + 3).collect::<Vec<_>>());
+
+        // Progress is persisted, never regresses and finishes at 100%
+        status.record_progress(7, 40, "draft".to_string(), false).unwrap();
+        assert!(status.record_progress(7, 30, String::new(), false).is_err());
+        assert!(status.record_progress(7, 101, String::new(), false).is_err());
+        assert!(status
+            .record_progress(7, 50, "x".repeat(MAX_A2A_PARTIAL_CONTENT_LENGTH + 1), false)
+            .is_err());
+        status.record_progress(7, 90, "done".to_string(), true).unwrap();
+        assert_eq!(status.progress, 100);
+        assert_eq!(status.partial_content, "done");
+        assert!(status.record_progress(7, 100, String::new(), true).is_err());
+        status.record_progress(8, 10, String::new(), false).unwrap();
+        assert_eq!(status.message_id, 8);
+    }
+}
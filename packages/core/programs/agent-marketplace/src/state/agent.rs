@@ -13,6 +13,12 @@ use crate::PricingModel;
 // PDA Seeds
 pub const AGENT_SEED: &[u8] = b"agent";
 pub const AGENT_VERIFICATION_SEED: &[u8] = b"agent_verification";
+pub const VERIFIER_REGISTRY_SEED: &[u8] = b"verifier_registry";
+
+// Verification limits
+pub const MAX_VERIFIERS: usize = 32;
+pub const MIN_VERIFICATION_VALIDITY: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_VERIFICATION_VALIDITY: i64 = 365 * 24 * 60 * 60; // 1 year
 
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
@@ -58,6 +64,7 @@ pub struct Agent {
     pub description: String,
     pub capabilities: Vec<String>,
     pub pricing_model: PricingModel,
+    /// Review-backed score in basis points, mirrored from `AgentReputation`
     pub reputation_score: u32,
     pub total_jobs_completed: u32,
     pub total_earnings: u64,
@@ -108,13 +115,6 @@ impl Agent {
         self.is_active = true;
     }
 
-    /// Update agent reputation score
-    pub fn update_reputation(&mut self, reputation_score: u64) {
-        // SECURITY: Safe conversion with bounds checking
-        self.reputation_score = reputation_score.min(u32::MAX as u64) as u32;
-        self.updated_at = Clock::get().unwrap().unix_timestamp;
-    }
-
     /// Initialize a new agent
     pub fn initialize(
         &mut self,
@@ -199,6 +199,29 @@ impl AgentVerification {
         self.is_active = false;
     }
 
+    /// Re-attests the agent, reactivating a revoked or expired verification
+    pub fn renew(
+        &mut self,
+        verifier: Pubkey,
+        verification_data: AgentVerificationData,
+        current_time: i64,
+        validity_period: i64,
+    ) -> Result<()> {
+        require!(
+            (MIN_VERIFICATION_VALIDITY..=MAX_VERIFICATION_VALIDITY).contains(&validity_period),
+            PodAIMarketplaceError::InvalidDuration
+        );
+
+        self.verifier = verifier;
+        self.verification_data = verification_data;
+        self.created_at = current_time;
+        self.expires_at = current_time
+            .checked_add(validity_period)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.is_active = true;
+        Ok(())
+    }
+
     /// Initialize a new agent verification
     pub fn initialize(
         &mut self,
@@ -220,4 +243,102 @@ impl AgentVerification {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+/// Governed allowlist of wallets allowed to attest agents
+///
+/// The authority is expected to be a multisig or the governance signer PDA.
+/// Attestations from a verifier stop counting as soon as it is removed.
+#[account]
+pub struct VerifierRegistry {
+    pub authority: Pubkey,
+    pub verifiers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl VerifierRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        4 + (MAX_VERIFIERS * 32) + // verifiers
+        1; // bump
+
+    pub fn add_verifier(&mut self, verifier: Pubkey) -> Result<()> {
+        require!(!self.verifiers.contains(&verifier), PodAIMarketplaceError::AccountAlreadyInitialized);
+        require!(self.verifiers.len() < MAX_VERIFIERS, PodAIMarketplaceError::TooManyVerifiers);
+        self.verifiers.push(verifier);
+        Ok(())
+    }
+
+    pub fn remove_verifier(&mut self, verifier: Pubkey) -> Result<()> {
+        let index = self.verifiers
+            .iter()
+            .position(|v| *v == verifier)
+            .ok_or(PodAIMarketplaceError::VerifierNotRegistered)?;
+        self.verifiers.swap_remove(index);
+        Ok(())
+    }
+
+    pub fn is_verifier(&self, verifier: &Pubkey) -> bool {
+        self.verifiers.contains(verifier)
+    }
+
+    /// An attestation counts while it is valid and its verifier is still listed
+    pub fn attests(&self, verification: &AgentVerification, current_time: i64) -> bool {
+        verification.is_valid(current_time) && self.is_verifier(&verification.verifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verifier_registry_attestations() {
+        let verifier = Pubkey::new_unique();
+        let mut registry = VerifierRegistry {
+            authority: Pubkey::new_unique(),
+            verifiers: Vec::new(),
+            bump: 255,
+        };
+        registry.add_verifier(verifier).unwrap();
+        assert!(registry.add_verifier(verifier).is_err(), "Duplicate verifiers are rejected");
+
+        let agent = Pubkey::new_unique();
+        let data = AgentVerificationData {
+            agent_pubkey: agent,
+            service_endpoint: "https://agent.example".to_string(),
+            supported_capabilities: vec![1, 2],
+            verified_at: 1_000,
+        };
+        let mut verification = AgentVerification {
+            agent,
+            verifier: Pubkey::default(),
+            verification_data: data.clone(),
+            created_at: 0,
+            expires_at: 0,
+            is_active: false,
+            bump: 255,
+        };
+
+        // Validity is bounded between one day and one year
+        assert!(verification.renew(verifier, data.clone(), 1_000, MIN_VERIFICATION_VALIDITY - 1).is_err());
+        assert!(verification.renew(verifier, data.clone(), 1_000, MAX_VERIFICATION_VALIDITY + 1).is_err());
+        verification.renew(verifier, data.clone(), 1_000, MIN_VERIFICATION_VALIDITY).unwrap();
+        let expires_at = 1_000 + MIN_VERIFICATION_VALIDITY;
+        assert_eq!(verification.expires_at, expires_at);
+
+        assert!(registry.attests(&verification, expires_at - 1));
+        assert!(!registry.attests(&verification, expires_at), "Expired attestations do not count");
+
+        // Revoked attestations stop counting until renewed
+        verification.revoke();
+        assert!(!registry.attests(&verification, 1_000));
+        verification.renew(verifier, data, 2_000, MIN_VERIFICATION_VALIDITY).unwrap();
+        assert!(registry.attests(&verification, 2_000));
+
+        // Removing the verifier invalidates everything it attested
+        registry.remove_verifier(verifier).unwrap();
+        assert!(!registry.attests(&verification, 2_000));
+        assert!(registry.remove_verifier(verifier).is_err());
+    }
+}
@@ -5,12 +5,15 @@
  */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use super::{MAX_GENERAL_STRING_LENGTH, MIN_BID_INCREMENT, MIN_AUCTION_DURATION, MAX_AUCTION_DURATION, PodAIMarketplaceError};
 
 // PDA Seeds
 pub const SERVICE_AUCTION_SEED: &[u8] = b"service_auction";
 pub const AUCTION_MARKETPLACE_SEED: &[u8] = b"auction_marketplace";
 pub const NEGOTIATION_SEED: &[u8] = b"negotiation";
+pub const AUCTION_VAULT_SEED: &[u8] = b"auction_vault";
+pub const SEALED_BID_SEED: &[u8] = b"sealed_bid";
 
 // Constants
 pub const MAX_BIDS_COUNT: usize = 100;
@@ -19,6 +22,8 @@ pub const MAX_TERMS_COUNT: usize = 5;
 pub const MAX_TERM_LENGTH: usize = 100;
 pub const MAX_FEATURES_COUNT: usize = 10;
 pub const MAX_FEATURE_LENGTH: usize = 100;
+pub const AUCTION_DELIVERY_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days to deliver after settlement
+pub const SEALED_BID_REVEAL_WINDOW: i64 = 24 * 60 * 60; // 24 hours to reveal after bidding closes
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum AuctionType {
@@ -64,6 +69,7 @@ pub struct AuctionBid {
     pub amount: u64,
     pub timestamp: i64,
     pub is_winning: bool,
+    pub escrowed: u64, // Tokens held in the auction vault for this bid, zero once refunded or settled
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
@@ -122,6 +128,26 @@ pub struct AuctionMarketplace {
     pub created_at: i64,
     pub ended_at: Option<i64>,
     pub metadata_uri: String,
+    pub payment_mint: Pubkey,
+    pub work_order: Option<Pubkey>,
+    pub reveal_end_time: i64,  // End of the reveal phase for sealed auctions
+    pub second_price: u64,     // Second-highest revealed bid, used for Vickrey settlement
+    pub bump: u8,
+}
+
+/// Hash commitment for a SealedBid or Vickrey auction bid
+///
+/// The deposit is escrowed in the auction vault when committing and must
+/// cover the revealed amount. Bidders who never reveal forfeit the deposit
+/// to the auction creator.
+#[account]
+pub struct SealedBidCommitment {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub commitment: [u8; 32],
+    pub deposit: u64,
+    pub revealed_amount: Option<u64>,
+    pub committed_at: i64,
     pub bump: u8,
 }
 
@@ -350,10 +376,14 @@ impl AuctionMarketplace {
         8 + // minimum_bid_increment
         4 + // total_bids
         1 + // status
-        4 + (MAX_BIDS_COUNT * (32 + 8 + 8 + 1)) + // bids (bidder + amount + timestamp + is_winning)
+        4 + (MAX_BIDS_COUNT * (32 + 8 + 8 + 1 + 8)) + // bids (bidder + amount + timestamp + is_winning + escrowed)
         8 + // created_at
         1 + 8 + // ended_at (Option<i64>)
         4 + MAX_GENERAL_STRING_LENGTH + // metadata_uri
+        32 + // payment_mint
+        1 + 32 + // work_order (Option<Pubkey>)
+        8 + // reveal_end_time
+        8 + // second_price
         1; // bump
 
     pub fn initialize(
@@ -367,6 +397,7 @@ impl AuctionMarketplace {
         auction_end_time: i64,
         minimum_bid_increment: u64,
         metadata_uri: String,
+        payment_mint: Pubkey,
         bump: u8,
     ) -> Result<()> {
         let clock = Clock::get()?;
@@ -390,6 +421,14 @@ impl AuctionMarketplace {
         self.created_at = clock.unix_timestamp;
         self.ended_at = None;
         self.metadata_uri = metadata_uri;
+        self.payment_mint = payment_mint;
+        self.work_order = None;
+        self.reveal_end_time = if matches!(auction_type, AuctionType::SealedBid | AuctionType::Vickrey) {
+            auction_end_time.saturating_add(SEALED_BID_REVEAL_WINDOW)
+        } else {
+            auction_end_time
+        };
+        self.second_price = 0;
         self.bump = bump;
         
         Ok(())
@@ -421,6 +460,7 @@ impl AuctionMarketplace {
             amount,
             timestamp: clock.unix_timestamp,
             is_winning: true,
+            escrowed: amount,
         });
         
         self.current_price = amount;
@@ -454,4 +494,262 @@ impl AuctionMarketplace {
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Total escrow a bidder can withdraw: every outbid bid, or all of their
+    /// bids once the auction is cancelled
+    pub fn refundable_amount(&self, bidder: Pubkey) -> Result<u64> {
+        self.bids
+            .iter()
+            .filter(|bid| bid.bidder == bidder && self.is_refundable(bid))
+            .try_fold(0u64, |total, bid| total.checked_add(bid.escrowed))
+            .ok_or_else(|| error!(PodAIMarketplaceError::ArithmeticOverflow))
+    }
+
+    /// Marks a bidder's refundable bids as withdrawn and returns the amount owed
+    pub fn take_refund(&mut self, bidder: Pubkey) -> Result<u64> {
+        let amount = self.refundable_amount(bidder)?;
+        let cancelled = self.status == AuctionStatus::Cancelled;
+        for bid in self.bids.iter_mut() {
+            if bid.bidder == bidder && (cancelled || !bid.is_winning) {
+                bid.escrowed = 0;
+            }
+        }
+        Ok(amount)
+    }
+
+    fn is_refundable(&self, bid: &AuctionBid) -> bool {
+        self.status == AuctionStatus::Cancelled || !bid.is_winning
+    }
+
+    /// Whether bids are placed as hash commitments and revealed later
+    pub fn is_sealed(&self) -> bool {
+        matches!(self.auction_type, AuctionType::SealedBid | AuctionType::Vickrey)
+    }
+
+    /// Earliest time the auction can be finalized
+    pub fn settlement_time(&self) -> i64 {
+        if self.is_sealed() {
+            self.reveal_end_time
+        } else {
+            self.auction_end_time
+        }
+    }
+
+    /// Current Dutch clock price, descending linearly from the starting price
+    /// at creation to the reserve price at the auction end time
+    pub fn dutch_price(&self, now: i64) -> u64 {
+        if now <= self.created_at {
+            return self.starting_price;
+        }
+        if now >= self.auction_end_time || self.auction_end_time <= self.created_at {
+            return self.reserve_price;
+        }
+        
+        let elapsed = (now - self.created_at) as u128;
+        let duration = (self.auction_end_time - self.created_at) as u128;
+        let price_range = self.starting_price.saturating_sub(self.reserve_price) as u128;
+        let decrease = (price_range * elapsed / duration) as u64;
+        
+        self.starting_price.saturating_sub(decrease)
+    }
+
+    /// Records a revealed sealed bid, tracking the highest and second-highest amounts
+    ///
+    /// Ties keep the earlier revealer as the leader.
+    pub fn record_reveal(&mut self, bidder: Pubkey, amount: u64) {
+        if self.current_winner.is_none() {
+            self.current_winner = Some(bidder);
+            self.current_price = amount;
+        } else if amount > self.current_price {
+            self.second_price = self.current_price;
+            self.current_winner = Some(bidder);
+            self.current_price = amount;
+        } else if amount > self.second_price {
+            self.second_price = amount;
+        }
+    }
+
+    /// Price the winner pays: the second-highest bid for Vickrey auctions
+    /// (never below the reserve or starting price), otherwise the winning bid
+    pub fn settlement_price(&self) -> u64 {
+        match self.auction_type {
+            AuctionType::Vickrey => self.second_price
+                .max(self.reserve_price)
+                .max(self.starting_price)
+                .min(self.current_price),
+            _ => self.current_price,
+        }
+    }
+}
+
+impl SealedBidCommitment {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // auction
+        32 + // bidder
+        32 + // commitment
+        8 + // deposit
+        1 + 8 + // revealed_amount (Option<u64>)
+        8 + // committed_at
+        1; // bump
+
+    /// Computes the commitment a bidder submits: sha256(amount || salt || bidder)
+    ///
+    /// Binding the bidder key prevents others from replaying a commitment.
+    pub fn compute_commitment(amount: u64, salt: &[u8; 32], bidder: &Pubkey) -> [u8; 32] {
+        hashv(&[&amount.to_le_bytes(), salt, bidder.as_ref()]).to_bytes()
+    }
+
+    pub fn verify_reveal(&self, amount: u64, salt: &[u8; 32]) -> bool {
+        Self::compute_commitment(amount, salt, &self.bidder) == self.commitment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auction_bid_refunds() {
+        let outbid = Pubkey::new_unique();
+        let leader = Pubkey::new_unique();
+        let bid = |bidder: Pubkey, amount: u64, is_winning: bool| AuctionBid {
+            bidder,
+            amount,
+            timestamp: 1640995200,
+            is_winning,
+            escrowed: amount,
+        };
+
+        let mut auction = AuctionMarketplace {
+            auction: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            auction_type: AuctionType::English,
+            starting_price: 1_000,
+            reserve_price: 0,
+            current_price: 3_000,
+            current_winner: Some(leader),
+            winner: None,
+            auction_end_time: 1641081600,
+            minimum_bid_increment: 100,
+            total_bids: 3,
+            status: AuctionStatus::Active,
+            bids: vec![bid(outbid, 1_000, false), bid(leader, 2_000, false), bid(leader, 3_000, true)],
+            created_at: 1640995200,
+            ended_at: None,
+            metadata_uri: String::new(),
+            payment_mint: Pubkey::new_unique(),
+            work_order: None,
+            reveal_end_time: 1641081600,
+            second_price: 0,
+            bump: 255,
+        };
+
+        // Only outbid escrow is withdrawable while the auction runs
+        assert_eq!(auction.refundable_amount(outbid).unwrap(), 1_000);
+        assert_eq!(auction.refundable_amount(leader).unwrap(), 2_000);
+
+        // Withdrawing clears the escrow so it cannot be claimed twice
+        assert_eq!(auction.take_refund(leader).unwrap(), 2_000);
+        assert_eq!(auction.refundable_amount(leader).unwrap(), 0);
+
+        // A cancelled auction releases the leading bid as well
+        auction.status = AuctionStatus::Cancelled;
+        assert_eq!(auction.take_refund(leader).unwrap(), 3_000);
+        assert_eq!(auction.take_refund(outbid).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_dutch_and_vickrey_pricing() {
+        let mut auction = AuctionMarketplace {
+            auction: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            auction_type: AuctionType::Dutch,
+            starting_price: 10_000,
+            reserve_price: 2_000,
+            current_price: 10_000,
+            current_winner: None,
+            winner: None,
+            auction_end_time: 1_000,
+            minimum_bid_increment: 100,
+            total_bids: 0,
+            status: AuctionStatus::Active,
+            bids: Vec::new(),
+            created_at: 0,
+            ended_at: None,
+            metadata_uri: String::new(),
+            payment_mint: Pubkey::new_unique(),
+            work_order: None,
+            reveal_end_time: 1_000,
+            second_price: 0,
+            bump: 255,
+        };
+
+        // Dutch clock descends linearly and bottoms out at the reserve
+        assert_eq!(auction.dutch_price(0), 10_000);
+        assert_eq!(auction.dutch_price(500), 6_000);
+        assert_eq!(auction.dutch_price(1_000), 2_000);
+        assert_eq!(auction.dutch_price(5_000), 2_000);
+        assert!(!auction.is_sealed());
+
+        // Vickrey winners pay the second-highest revealed bid
+        auction.auction_type = AuctionType::Vickrey;
+        auction.reserve_price = 0;
+        auction.starting_price = 1_000;
+        auction.reveal_end_time = 2_000;
+        assert!(auction.is_sealed());
+        assert_eq!(auction.settlement_time(), 2_000);
+
+        let (first, second, third) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        auction.record_reveal(first, 5_000);
+        auction.record_reveal(second, 8_000);
+        auction.record_reveal(third, 8_000);
+        assert_eq!(auction.current_winner, Some(second), "Ties keep the earlier revealer");
+        assert_eq!(auction.current_price, 8_000);
+        assert_eq!(auction.settlement_price(), 8_000);
+
+        // A lone bidder pays the starting price
+        auction.current_winner = None;
+        auction.second_price = 0;
+        auction.record_reveal(first, 5_000);
+        assert_eq!(auction.settlement_price(), 1_000);
+
+        // The reserve is a floor, and the winner never pays more than they bid
+        auction.reserve_price = 3_000;
+        assert_eq!(auction.settlement_price(), 3_000);
+        auction.second_price = 4_000;
+        assert_eq!(auction.settlement_price(), 4_000);
+        auction.reserve_price = 6_000;
+        assert_eq!(auction.settlement_price(), 5_000);
+        auction.reserve_price = 0;
+        auction.second_price = 0;
+
+        // First-price sealed auctions charge the winning bid
+        auction.auction_type = AuctionType::SealedBid;
+        assert_eq!(auction.settlement_price(), 5_000);
+    }
+
+    #[test]
+    fn test_sealed_bid_commitment() {
+        let bidder = Pubkey::new_unique();
+        let salt = [7u8; 32];
+        let commitment = SealedBidCommitment {
+            auction: Pubkey::new_unique(),
+            bidder,
+            commitment: SealedBidCommitment::compute_commitment(5_000, &salt, &bidder),
+            deposit: 6_000,
+            revealed_amount: None,
+            committed_at: 0,
+            bump: 255,
+        };
+
+        assert!(commitment.verify_reveal(5_000, &salt));
+        assert!(!commitment.verify_reveal(5_001, &salt), "Amount must match");
+        assert!(!commitment.verify_reveal(5_000, &[8u8; 32]), "Salt must match");
+
+        // Commitments are bound to the bidder and cannot be replayed by others
+        let other = SealedBidCommitment { bidder: Pubkey::new_unique(), ..commitment };
+        assert!(!other.verify_reveal(5_000, &salt));
+    }
+}
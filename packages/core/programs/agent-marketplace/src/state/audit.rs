@@ -6,6 +6,7 @@
  */
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
 use std::collections::BTreeMap;
 use super::PodAIMarketplaceError;
 
@@ -50,6 +51,18 @@ pub struct AuditTrail {
     /// Hash chain for integrity verification
     pub hash_chain: Vec<[u8; 32]>,
     
+    /// Trail this one continues from after rollover
+    pub previous_trail: Option<Pubkey>,
+    
+    /// Trail that continues this one once it is full
+    pub next_trail: Option<Pubkey>,
+    
+    /// Chain head the first entry links to (the previous trail's last hash)
+    pub genesis_hash: [u8; 32],
+    
+    /// ID of the first entry, continuing the numbering across rollovers
+    pub first_entry_id: u64,
+    
     /// Reserved space for future extensions
     pub reserved: [u8; 128],
 }
@@ -672,8 +685,9 @@ pub struct SubmissionDetails {
 // CONSTANTS
 // =====================================================
 
-/// Maximum audit entries per trail
-pub const MAX_AUDIT_ENTRIES: usize = 10000;
+/// Maximum audit entries per trail account; a full trail rolls over to a
+/// new account that continues the hash chain
+pub const MAX_AUDIT_ENTRIES: usize = 256;
 
 /// Maximum metadata entries
 pub const MAX_METADATA_ENTRIES: usize = 50;
@@ -698,18 +712,26 @@ pub const SIGNATURE_ALGORITHM: &str = "Ed25519";
 // =====================================================
 
 impl AuditTrail {
-    /// Calculate required space for audit trail account
-    pub const fn space() -> usize {
+    /// Space for a new trail with no entries
+    ///
+    /// The account grows as entries are appended.
+    pub const fn base_space() -> usize {
         8 + // discriminator
         32 + // authority
         8 + // trail_id
         8 + // created_at
         8 + // updated_at
         1 + // version
-        4 + (MAX_AUDIT_ENTRIES * AuditEntry::size()) + // entries
+        4 + // entries
         AuditConfig::size() + // config
-        ComplianceStatus::size() + // compliance_status
-        4 + (32 * MAX_AUDIT_ENTRIES) + // hash_chain
+        1 + 8 + 8 + 4 + 4 + // compliance_status (no violations or jurisdictions)
+        1 + 8 + 8 + 4 + 4 + RiskThresholds::size() + // compliance_status.risk_assessment
+        4 + 32 + // compliance_status.compliance_officers (one officer)
+        4 + // hash_chain
+        1 + 32 + // previous_trail
+        1 + 32 + // next_trail
+        32 + // genesis_hash
+        8 + // first_entry_id
         128 // reserved
     }
 
@@ -753,12 +775,38 @@ impl AuditTrail {
             compliance_officers: Vec::new(),
         };
         self.hash_chain = Vec::new();
+        self.previous_trail = None;
+        self.next_trail = None;
+        self.genesis_hash = [0; 32];
+        self.first_entry_id = 0;
         self.reserved = [0; 128];
         
         Ok(())
     }
 
-    /// Add a new audit entry
+    /// Entries this trail holds before it must roll over
+    pub fn capacity(&self) -> usize {
+        (self.config.max_entries as usize).min(MAX_AUDIT_ENTRIES)
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= self.capacity()
+    }
+
+    /// Hash the next entry links to
+    pub fn chain_head(&self) -> [u8; 32] {
+        self.hash_chain.last().copied().unwrap_or(self.genesis_hash)
+    }
+
+    /// ID the next entry receives
+    pub fn next_entry_id(&self) -> u64 {
+        self.first_entry_id + self.entries.len() as u64
+    }
+
+    /// Appends a hash-chained entry and returns its ID
+    ///
+    /// A trail that has rolled over is sealed and accepts no more entries.
+    #[allow(clippy::too_many_arguments)]
     pub fn add_entry(
         &mut self,
         action: AuditAction,
@@ -766,41 +814,80 @@ impl AuditTrail {
         target: Option<Pubkey>,
         context: AuditContext,
         compliance_flags: ComplianceFlags,
-    ) -> Result<()> {
-        require!(self.entries.len() < MAX_AUDIT_ENTRIES, PodAIMarketplaceError::TooManyAuditEntries);
-        
-        let clock = Clock::get()?;
-        let entry_id = self.entries.len() as u64;
-        
-        // Calculate previous hash
-        let previous_hash = if let Some(last_entry) = self.entries.last() {
-            last_entry.entry_hash
-        } else {
-            [0; 32]
-        };
+        signature: Option<[u8; 64]>,
+        timestamp: i64,
+    ) -> Result<u64> {
+        require!(self.next_trail.is_none(), PodAIMarketplaceError::ResourceLocked);
+        require!(!self.is_full(), PodAIMarketplaceError::TooManyAuditEntries);
         
-        // Create new entry
-        let entry = AuditEntry {
+        let entry_id = self.next_entry_id();
+        let mut entry = AuditEntry {
             entry_id,
-            timestamp: clock.unix_timestamp,
+            timestamp,
             action,
             actor,
             target,
             context,
             compliance_flags,
-            previous_hash,
-            entry_hash: [0; 32], // Should be calculated with proper hash function
-            signature: None,
+            previous_hash: self.chain_head(),
+            entry_hash: [0; 32],
+            signature,
         };
+        entry.entry_hash = entry.compute_hash()?;
         
+        self.hash_chain.push(entry.entry_hash);
         self.entries.push(entry);
-        self.updated_at = clock.unix_timestamp;
+        self.updated_at = timestamp;
         
-        Ok(())
+        Ok(entry_id)
+    }
+
+    /// Recomputes every entry hash and link from the genesis hash
+    ///
+    /// Returns the chain head, or `DataCorruptionDetected` if any entry,
+    /// link, ID or stored chain hash does not match.
+    pub fn verify_chain(&self) -> Result<[u8; 32]> {
+        require!(
+            self.hash_chain.len() == self.entries.len(),
+            PodAIMarketplaceError::DataCorruptionDetected
+        );
+        
+        let mut head = self.genesis_hash;
+        for (index, (entry, chain_hash)) in self.entries.iter().zip(&self.hash_chain).enumerate() {
+            require!(
+                entry.entry_id == self.first_entry_id + index as u64 &&
+                    entry.previous_hash == head &&
+                    entry.entry_hash == *chain_hash &&
+                    entry.compute_hash()? == entry.entry_hash,
+                PodAIMarketplaceError::DataCorruptionDetected
+            );
+            head = entry.entry_hash;
+        }
+        
+        Ok(head)
     }
 }
 
 impl AuditEntry {
+    /// SHA-256 over the previous chain head followed by the Borsh encoding of
+    /// `entry_id`, `timestamp`, `action`, `actor`, `target`, `context` and
+    /// `compliance_flags`, in that order
+    ///
+    /// `entry_hash` and `signature` are excluded, so an entry can be signed
+    /// over its hash.
+    pub fn compute_hash(&self) -> Result<[u8; 32]> {
+        let mut data = Vec::new();
+        self.entry_id.serialize(&mut data)?;
+        self.timestamp.serialize(&mut data)?;
+        self.action.serialize(&mut data)?;
+        self.actor.serialize(&mut data)?;
+        self.target.serialize(&mut data)?;
+        self.context.serialize(&mut data)?;
+        self.compliance_flags.serialize(&mut data)?;
+        
+        Ok(hashv(&[&self.previous_hash, &data]).to_bytes())
+    }
+
     pub const fn size() -> usize {
         8 + // entry_id
         8 + // timestamp
@@ -1091,4 +1178,130 @@ impl SubmissionDetails {
         1 + 8 + // acknowledged_at
         1 + 4 + 512 // regulatory_response
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_trail_hash_chain() {
+        use crate::state::audit::ComplianceStatus;
+        use sha2::{Digest, Sha256};
+
+        let new_trail = |genesis_hash: [u8; 32], first_entry_id: u64| AuditTrail {
+            authority: Pubkey::new_unique(),
+            trail_id: 1,
+            created_at: 0,
+            updated_at: 0,
+            version: 1,
+            entries: Vec::new(),
+            config: AuditConfig {
+                max_entries: 3,
+                retention_period: 0,
+                auto_archive: false,
+                reporting_frequency: ReportingFrequency::OnDemand,
+                approval_levels: Vec::new(),
+                encryption_required: false,
+                backup_frequency: BackupFrequency::Daily,
+            },
+            compliance_status: ComplianceStatus {
+                compliance_score: 100,
+                last_review: 0,
+                next_review: 0,
+                active_violations: Vec::new(),
+                regulatory_status: Vec::new(),
+                risk_assessment: RiskAssessment {
+                    risk_score: 0,
+                    last_assessment: 0,
+                    next_assessment: 0,
+                    risk_factors: Vec::new(),
+                    mitigation_strategies: Vec::new(),
+                    risk_thresholds: RiskThresholds {
+                        low_threshold: 25,
+                        medium_threshold: 50,
+                        high_threshold: 75,
+                        critical_threshold: 90,
+                        auto_mitigation_threshold: 80,
+                        manual_review_threshold: 60,
+                    },
+                },
+                compliance_officers: Vec::new(),
+            },
+            hash_chain: Vec::new(),
+            previous_trail: None,
+            next_trail: None,
+            genesis_hash,
+            first_entry_id,
+            reserved: [0; 128],
+        };
+        let context = |amount: u64| AuditContext {
+            transaction_signature: None,
+            amount: Some(amount),
+            token: None,
+            metadata: vec![("order".to_string(), "42".to_string())],
+            risk_score: None,
+            location: None,
+            client_info: None,
+        };
+        let flags = || ComplianceFlags {
+            requires_reporting: false,
+            high_risk: false,
+            sensitive_data: false,
+            cross_border: false,
+            large_amount: false,
+            suspicious: false,
+            manual_review: false,
+            jurisdiction: None,
+        };
+        let actor = Pubkey::new_unique();
+
+        let mut trail = new_trail([0; 32], 0);
+        for (i, amount) in [100u64, 200, 300].into_iter().enumerate() {
+            let entry_id = trail
+                .add_entry(AuditAction::PaymentProcessed, actor, None, context(amount), flags(), None, i as i64)
+                .unwrap();
+            assert_eq!(entry_id, i as u64);
+        }
+        assert!(trail.is_full());
+        assert!(trail.add_entry(AuditAction::PaymentProcessed, actor, None, context(1), flags(), None, 9).is_err());
+
+        // Each entry links to the previous head and is SHA-256 over it plus
+        // the Borsh-encoded fields, so off-chain tools can recompute it
+        let first = &trail.entries[0];
+        let mut preimage = first.previous_hash.to_vec();
+        preimage.extend(first.entry_id.try_to_vec().unwrap());
+        preimage.extend(first.timestamp.try_to_vec().unwrap());
+        preimage.extend(first.action.try_to_vec().unwrap());
+        preimage.extend(first.actor.try_to_vec().unwrap());
+        preimage.extend(first.target.try_to_vec().unwrap());
+        preimage.extend(first.context.try_to_vec().unwrap());
+        preimage.extend(first.compliance_flags.try_to_vec().unwrap());
+        assert_eq!(first.entry_hash, <[u8; 32]>::from(Sha256::digest(&preimage)));
+        assert_eq!(trail.entries[1].previous_hash, first.entry_hash);
+
+        let head = trail.verify_chain().unwrap();
+        assert_eq!(head, trail.chain_head());
+
+        // Rollover continues the chain and numbering
+        let mut continued = new_trail(head, trail.next_entry_id());
+        let entry_id = continued
+            .add_entry(AuditAction::EscrowReleased, actor, None, context(400), flags(), None, 10)
+            .unwrap();
+        assert_eq!(entry_id, 3);
+        assert_eq!(continued.entries[0].previous_hash, head);
+        assert!(continued.verify_chain().is_ok());
+
+        // Any edit to a recorded entry breaks verification
+        let mut tampered = trail.clone();
+        tampered.entries[1].context.amount = Some(201);
+        assert!(tampered.verify_chain().is_err());
+
+        // Recomputing the edited entry's hash still breaks the next link
+        let mut rehashed = trail.clone();
+        rehashed.entries[1].context.amount = Some(201);
+        rehashed.entries[1].entry_hash = rehashed.entries[1].compute_hash().unwrap();
+        rehashed.hash_chain[1] = rehashed.entries[1].entry_hash;
+        assert!(rehashed.verify_chain().is_err());
+    }
+}
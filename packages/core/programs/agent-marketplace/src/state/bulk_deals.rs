@@ -9,10 +9,15 @@ use super::{MAX_GENERAL_STRING_LENGTH, MAX_TITLE_LENGTH, MAX_DESCRIPTION_LENGTH,
 
 // PDA Seeds
 pub const BULK_DEAL_SEED: &[u8] = b"bulk_deal";
+pub const BULK_DEAL_VAULT_SEED: &[u8] = b"bulk_deal_vault";
+pub const BULK_DEAL_DRAWDOWN_SEED: &[u8] = b"bulk_deal_drawdown";
 
 // Constants
 pub const MAX_VOLUME_TIERS: usize = 5;
 pub const MAX_DISCOUNT_PERCENTAGE: u32 = 10000; // 100% in basis points
+pub const MAX_SLA_BREACH_REASON_LENGTH: usize = 256;
+/// How long after a drawdown the customer may report an SLA breach on it
+pub const SLA_BREACH_REPORT_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum DealType {
@@ -22,6 +27,19 @@ pub enum DealType {
     Wholesale,
 }
 
+/// How the customer funds drawn-down volume
+///
+/// Either way each drawdown's charge is escrowed on its work order when it
+/// is drawn and released to the provider when the work is approved.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BulkPaymentMode {
+    /// Customer funds the deal vault up front; drawdowns are escrowed from it
+    Prepaid,
+    /// Nothing is deposited up front; each drawdown is escrowed from the
+    /// customer's token account as it is drawn
+    Postpaid,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
 pub struct VolumeTier {
     pub min_quantity: u32,
@@ -33,10 +51,13 @@ pub struct VolumeTier {
 pub struct BulkDeal {
     pub agent: Pubkey,
     pub customer: Pubkey,
+    /// Customer-chosen id, so a pair can hold several deals over time
+    pub deal_id: u64,
     pub deal_type: DealType,
     pub total_volume: u32,
     pub total_value: u64,
-    pub discount_percentage: f64,
+    /// Deal-wide discount on top of the tiers, in basis points
+    pub discount_percentage: u32,
     pub volume_tiers: Vec<VolumeTier>,
     pub sla_terms: String,
     pub contract_duration: i64,
@@ -44,17 +65,70 @@ pub struct BulkDeal {
     pub end_date: i64,
     pub is_active: bool,
     pub created_at: i64,
+    pub payment_mint: Pubkey,
+    pub payment_mode: BulkPaymentMode,
+    /// Credit per breached drawdown, in basis points of its amount
+    pub sla_credit_bps: u16,
+    /// Set when the agent owner accepts the pricing and SLA terms
+    pub accepted_at: Option<i64>,
+    pub consumed_volume: u32,
+    pub drawdown_count: u32,
+    /// Sum of all drawdown amounts at their tiered price
+    pub charged_amount: u64,
+    /// Sum of SLA credits refunded against drawdowns
+    pub credited_amount: u64,
+    pub settled_at: Option<i64>,
     pub bump: u8,
 }
 
+/// Price and resulting deal totals for a drawdown
+#[derive(Clone, Debug, PartialEq)]
+pub struct DrawdownQuote {
+    pub amount: u64,
+    /// Consumed volume once the drawdown is recorded
+    pub consumed_volume: u32,
+    /// Charged amount once the drawdown is recorded
+    pub charged_amount: u64,
+}
+
+#[account]
+pub struct BulkDealDrawdown {
+    pub deal: Pubkey,
+    pub index: u32,
+    /// Escrowed work order the drawn volume is delivered through
+    pub work_order: Pubkey,
+    pub quantity: u32,
+    pub amount: u64,
+    pub created_at: i64,
+    pub sla_breached: bool,
+    pub credit: u64,
+    pub breach_reason: String,
+    pub bump: u8,
+}
+
+impl BulkDealDrawdown {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // deal
+        4 + // index
+        32 + // work_order
+        4 + // quantity
+        8 + // amount
+        8 + // created_at
+        1 + // sla_breached
+        8 + // credit
+        4 + MAX_SLA_BREACH_REASON_LENGTH + // breach_reason
+        1; // bump
+}
+
 impl BulkDeal {
     pub const LEN: usize = 8 + // discriminator
         32 + // agent
         32 + // customer
+        8 + // deal_id
         1 + // deal_type
         4 + // total_volume
         8 + // total_value
-        8 + // discount_percentage (f64)
+        4 + // discount_percentage
         4 + (MAX_VOLUME_TIERS * (4 + 4 + 4)) + // volume_tiers
         4 + MAX_GENERAL_STRING_LENGTH + // sla_terms
         8 + // contract_duration
@@ -62,16 +136,41 @@ impl BulkDeal {
         8 + // end_date
         1 + // is_active
         8 + // created_at
+        32 + // payment_mint
+        1 + // payment_mode
+        2 + // sla_credit_bps
+        1 + 8 + // accepted_at
+        4 + // consumed_volume
+        4 + // drawdown_count
+        8 + // charged_amount
+        8 + // credited_amount
+        1 + 8 + // settled_at
         1; // bump
 
+    /// Tiers must be well-formed and sorted without overlaps
+    pub fn validate_volume_tiers(volume_tiers: &[VolumeTier]) -> Result<()> {
+        require!(volume_tiers.len() <= MAX_VOLUME_TIERS, PodAIMarketplaceError::TooManyVolumeTiers);
+        for (i, tier) in volume_tiers.iter().enumerate() {
+            require!(tier.max_quantity > tier.min_quantity, PodAIMarketplaceError::InvalidVolumeTier);
+            require!(tier.discount_percentage <= MAX_DISCOUNT_PERCENTAGE, PodAIMarketplaceError::InvalidDiscountPercentage);
+            
+            // Check that tiers don't overlap
+            if i > 0 {
+                require!(tier.min_quantity > volume_tiers[i-1].max_quantity, PodAIMarketplaceError::OverlappingVolumeTiers);
+            }
+        }
+        Ok(())
+    }
+
     pub fn initialize(
         &mut self,
         agent: Pubkey,
         customer: Pubkey,
+        deal_id: u64,
         deal_type: DealType,
         total_volume: u32,
         total_value: u64,
-        discount_percentage: f64,
+        discount_percentage: u32,
         volume_tiers: Vec<VolumeTier>,
         sla_terms: String,
         contract_duration: i64,
@@ -79,28 +178,18 @@ impl BulkDeal {
         bump: u8,
     ) -> Result<()> {
         require!(sla_terms.len() <= MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError::StringTooLong);
-        require!(volume_tiers.len() <= MAX_VOLUME_TIERS, PodAIMarketplaceError::TooManyVolumeTiers);
+        Self::validate_volume_tiers(&volume_tiers)?;
         require!(total_volume > 0, PodAIMarketplaceError::InvalidVolume);
         require!(total_value > 0, PodAIMarketplaceError::InvalidValue);
-        require!(discount_percentage >= 0.0 && discount_percentage <= 100.0, PodAIMarketplaceError::InvalidDiscountPercentage);
+        require!(discount_percentage <= MAX_DISCOUNT_PERCENTAGE, PodAIMarketplaceError::InvalidDiscountPercentage);
         require!(contract_duration > 0, PodAIMarketplaceError::InvalidDuration);
         
         let clock = Clock::get()?;
         require!(end_date > clock.unix_timestamp, PodAIMarketplaceError::InvalidExpiration);
         
-        // Validate volume tiers
-        for (i, tier) in volume_tiers.iter().enumerate() {
-            require!(tier.max_quantity > tier.min_quantity, PodAIMarketplaceError::InvalidVolumeTier);
-            require!(tier.discount_percentage <= MAX_DISCOUNT_PERCENTAGE, PodAIMarketplaceError::InvalidDiscountPercentage);
-            
-            // Check that tiers don't overlap
-            if i > 0 {
-                require!(tier.min_quantity > volume_tiers[i-1].max_quantity, PodAIMarketplaceError::OverlappingVolumeTiers);
-            }
-        }
-        
         self.agent = agent;
         self.customer = customer;
+        self.deal_id = deal_id;
         self.deal_type = deal_type;
         self.total_volume = total_volume;
         self.total_value = total_value;
@@ -135,10 +224,76 @@ impl BulkDeal {
         }
         
         // Apply deal discount percentage
-        let deal_discount = (price as f64 * self.discount_percentage / 100.0) as u64;
-        price = price.saturating_sub(deal_discount);
-        
-        price
+        price.saturating_sub(self.deal_discount(price))
+    }
+
+    /// Deal-wide discount on `price`
+    pub fn deal_discount(&self, price: u64) -> u64 {
+        (price as u128 * self.discount_percentage as u128 / 10_000) as u64
+    }
+
+    /// Price of drawing down the next `quantity` units
+    /// 
+    /// Tiers are graduated over the deal's cumulative consumption: each unit
+    /// gets the discount of the tier its position falls into, so a drawdown
+    /// spanning a tier boundary is priced partly at each rate. The deal-wide
+    /// discount applies on top, as in `calculate_price`.
+    /// 
+    /// The returned quote carries the checked totals for `apply_drawdown`.
+    pub fn drawdown_price(&self, quantity: u32) -> Result<DrawdownQuote> {
+        require!(quantity > 0, PodAIMarketplaceError::InvalidVolume);
+        let end = self.consumed_volume
+            .checked_add(quantity)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        require!(end <= self.total_volume, PodAIMarketplaceError::DealFull);
+
+        let base = |units: u32| -> u64 {
+            (self.total_value as u128 * units as u128 / self.total_volume as u128) as u64
+        };
+
+        // Units are numbered from 1, matching the tier quantity bounds
+        let start = self.consumed_volume + 1;
+        let mut price = base(quantity);
+        for tier in &self.volume_tiers {
+            let lo = start.max(tier.min_quantity);
+            let hi = end.min(tier.max_quantity);
+            if lo <= hi {
+                let tier_units = hi - lo + 1;
+                let discount = (base(tier_units) as u128 * tier.discount_percentage as u128 / 10_000) as u64;
+                price = price.saturating_sub(discount);
+            }
+        }
+
+        let amount = price.saturating_sub(self.deal_discount(price));
+        let charged_amount = self.charged_amount
+            .checked_add(amount)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(DrawdownQuote { amount, consumed_volume: end, charged_amount })
+    }
+
+    /// Records a drawdown priced by `drawdown_price`
+    pub fn apply_drawdown(&mut self, quote: &DrawdownQuote) -> Result<()> {
+        self.consumed_volume = quote.consumed_volume;
+        self.charged_amount = quote.charged_amount;
+        self.drawdown_count = self.drawdown_count
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Drawn-down value escrowed for the provider, net of SLA credits
+    pub fn outstanding_amount(&self) -> u64 {
+        self.charged_amount.saturating_sub(self.credited_amount)
+    }
+
+    /// Settlement is open once the term ends or all volume is consumed
+    pub fn can_settle(&self, now: i64) -> bool {
+        self.settled_at.is_none() && (now > self.end_date || self.consumed_volume >= self.total_volume)
+    }
+
+    /// SLA credit owed for a breached drawdown of `amount`
+    pub fn sla_credit_for(&self, amount: u64) -> u64 {
+        (amount as u128 * self.sla_credit_bps as u128 / 10_000) as u64
     }
 
     pub fn deactivate(&mut self) -> Result<()> {
@@ -155,4 +310,158 @@ impl BulkDeal {
         let clock = Clock::get().unwrap();
         clock.unix_timestamp >= self.start_date && clock.unix_timestamp <= self.end_date
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiered_deal() -> BulkDeal {
+        BulkDeal {
+            agent: Pubkey::new_unique(),
+            customer: Pubkey::new_unique(),
+            deal_id: 7,
+            deal_type: DealType::VolumeDiscount,
+            total_volume: 10_000,
+            total_value: 10_000_000,
+            discount_percentage: 0,
+            volume_tiers: vec![
+                VolumeTier { min_quantity: 1, max_quantity: 1_000, discount_percentage: 0 },
+                VolumeTier { min_quantity: 1_001, max_quantity: 5_000, discount_percentage: 1_000 },
+                VolumeTier { min_quantity: 5_001, max_quantity: 10_000, discount_percentage: 2_000 },
+            ],
+            sla_terms: "99.9% of tasks within 2s".to_string(),
+            contract_duration: 30 * 24 * 60 * 60,
+            start_date: 0,
+            end_date: 1_000,
+            is_active: true,
+            created_at: 0,
+            payment_mint: Pubkey::new_unique(),
+            payment_mode: BulkPaymentMode::Prepaid,
+            sla_credit_bps: 2_500,
+            accepted_at: Some(0),
+            consumed_volume: 0,
+            drawdown_count: 0,
+            charged_amount: 0,
+            credited_amount: 0,
+            settled_at: None,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_bulk_deal_drawdown_pricing() {
+        let mut deal = tiered_deal();
+        BulkDeal::validate_volume_tiers(&deal.volume_tiers).unwrap();
+
+        // Units inside the first tier pay the base unit price
+        assert_eq!(deal.drawdown_price(1_000).unwrap().amount, 1_000_000);
+
+        // A drawdown straddling a tier boundary is priced partly at each rate
+        deal.consumed_volume = 500;
+        assert_eq!(deal.drawdown_price(1_000).unwrap().amount, 500_000 + 450_000);
+
+        // Later volume gets the deeper discount, and the deal discount stacks
+        deal.consumed_volume = 9_000;
+        assert_eq!(deal.drawdown_price(1_000).unwrap().amount, 800_000);
+        deal.discount_percentage = 1_000;
+        assert_eq!(deal.drawdown_price(1_000).unwrap().amount, 720_000);
+
+        // Volume beyond the commitment is rejected
+        assert!(deal.drawdown_price(1_001).is_err());
+        assert!(deal.drawdown_price(0).is_err());
+
+        // SLA credits reduce what the provider is owed
+        deal.charged_amount = 2_000_000;
+        deal.credited_amount = deal.sla_credit_for(1_000_000);
+        assert_eq!(deal.credited_amount, 250_000);
+        assert_eq!(deal.outstanding_amount(), 1_750_000);
+
+        // Settlement opens at the end of the term or once volume is used up
+        assert!(!deal.can_settle(500));
+        assert!(deal.can_settle(1_001));
+        deal.consumed_volume = deal.total_volume;
+        assert!(deal.can_settle(500));
+        deal.settled_at = Some(600);
+        assert!(!deal.can_settle(2_000));
+
+        let overlapping = vec![
+            VolumeTier { min_quantity: 1, max_quantity: 100, discount_percentage: 0 },
+            VolumeTier { min_quantity: 100, max_quantity: 200, discount_percentage: 500 },
+        ];
+        assert!(BulkDeal::validate_volume_tiers(&overlapping).is_err());
+
+        let mut buf = Vec::new();
+        deal.try_serialize(&mut buf).unwrap();
+        assert!(buf.len() <= BulkDeal::LEN);
+    }
+
+    #[test]
+    fn test_drawdown_price_at_tier_boundaries() {
+        let mut deal = tiered_deal();
+
+        // Last unit of the first tier and first unit of the second
+        deal.consumed_volume = 999;
+        assert_eq!(deal.drawdown_price(1).unwrap().amount, 1_000);
+        deal.consumed_volume = 1_000;
+        assert_eq!(deal.drawdown_price(1).unwrap().amount, 900);
+
+        // One unit either side of the boundary
+        deal.consumed_volume = 999;
+        assert_eq!(deal.drawdown_price(2).unwrap().amount, 1_000 + 900);
+
+        // A drawdown ending exactly on a boundary stays in the lower tier
+        deal.consumed_volume = 0;
+        assert_eq!(deal.drawdown_price(1_000).unwrap().amount, 1_000_000);
+        deal.consumed_volume = 1_000;
+        assert_eq!(deal.drawdown_price(4_000).unwrap().amount, 3_600_000);
+
+        // Spanning all three tiers
+        deal.consumed_volume = 0;
+        assert_eq!(
+            deal.drawdown_price(5_001).unwrap().amount,
+            1_000_000 + 3_600_000 + 800
+        );
+
+        // The final unit is allowed, one past the commitment is not
+        deal.consumed_volume = 9_999;
+        assert_eq!(deal.drawdown_price(1).unwrap().amount, 800);
+        assert!(deal.drawdown_price(2).is_err());
+
+        // The deal discount is applied in basis points on the tiered price
+        deal.discount_percentage = 250;
+        assert_eq!(deal.drawdown_price(1).unwrap().amount, 780);
+        deal.discount_percentage = MAX_DISCOUNT_PERCENTAGE;
+        assert_eq!(deal.drawdown_price(1).unwrap().amount, 0);
+    }
+
+    #[test]
+    fn test_drawdown_quote_and_apply() {
+        let mut deal = tiered_deal();
+
+        let quote = deal.drawdown_price(600).unwrap();
+        assert_eq!(quote, DrawdownQuote { amount: 600_000, consumed_volume: 600, charged_amount: 600_000 });
+        deal.apply_drawdown(&quote).unwrap();
+        assert_eq!(deal.consumed_volume, 600);
+        assert_eq!(deal.charged_amount, 600_000);
+        assert_eq!(deal.drawdown_count, 1);
+
+        // The next quote continues from the recorded consumption
+        let quote = deal.drawdown_price(600).unwrap();
+        assert_eq!(quote.amount, 400_000 + 180_000);
+        assert_eq!(quote.consumed_volume, 1_200);
+        assert_eq!(quote.charged_amount, 1_180_000);
+        deal.apply_drawdown(&quote).unwrap();
+        assert_eq!(deal.drawdown_count, 2);
+        assert_eq!(deal.outstanding_amount(), 1_180_000);
+
+        // A breach credit on the second drawdown is owed back to the customer
+        deal.credited_amount = deal.sla_credit_for(quote.amount);
+        assert_eq!(deal.credited_amount, 145_000);
+        assert_eq!(deal.outstanding_amount(), 1_035_000);
+
+        // Charged totals are checked rather than wrapped
+        deal.charged_amount = u64::MAX;
+        assert!(deal.drawdown_price(1).is_err());
+    }
+}
@@ -1,18 +1,47 @@
 /*!
  * Channel State
- * 
+ *
  * Account structures for communication channels in the GhostSpeak Protocol.
+ * Each participant holds a membership account of their own, so a channel's
+ * size is not bounded by the channel account.
  */
 
 use anchor_lang::prelude::*;
-use super::ChannelType;
+use super::{ChannelType, PodAIMarketplaceError};
+
+// PDA Seeds
+pub const CHANNEL_MEMBERSHIP_SEED: &[u8] = b"channel_membership";
+
+/// Members of a direct channel: the owner and one counterpart
+pub const DIRECT_CHANNEL_MEMBERS: u32 = 2;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ChannelRole {
+    Member,
+    /// Can invite into public and private channels and remove members
+    Moderator,
+    /// Can also promote and demote members below their own rank
+    Admin,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChannelMembershipStatus {
+    /// Invited but not yet accepted; holds a seat but cannot post
+    Invited,
+    Active,
+}
 
 #[account]
 pub struct Channel {
     pub creator: Pubkey,
-    pub participants: Vec<Pubkey>,
+    /// Holds every permission; starts as the creator and can be transferred
+    pub owner: Pubkey,
     pub channel_type: ChannelType,
     pub is_private: bool,
+    /// Memberships that exist, including pending invites
+    pub member_count: u32,
+    /// Seat limit; zero for unlimited
+    pub max_members: u32,
     pub message_count: u64,
     pub created_at: i64,
     pub last_activity: i64,
@@ -23,12 +52,211 @@ pub struct Channel {
 impl Channel {
     pub const LEN: usize = 8 + // discriminator
         32 + // creator
-        4 + (10 * 32) + // participants (max 10 participants)
+        32 + // owner
         1 + // channel_type
         1 + // is_private
+        4 + // member_count
+        4 + // max_members
         8 + // message_count
         8 + // created_at
         8 + // last_activity
         1 + // is_active
         1; // bump
-}
\ No newline at end of file
+
+    /// Rank of a membership's holder, with the owner above every role
+    pub fn rank_of(&self, membership: &ChannelMembership) -> u8 {
+        if membership.member == self.owner {
+            ChannelRole::Admin as u8 + 1
+        } else {
+            membership.role as u8
+        }
+    }
+
+    /// Whether `inviter` may invite others, given the channel's type
+    ///
+    /// Group channels let any member invite, direct channels only the owner
+    /// while the counterpart's seat is free, and public and private channels
+    /// need a moderator.
+    pub fn can_invite(&self, inviter: &ChannelMembership) -> bool {
+        if !inviter.is_active() {
+            return false;
+        }
+        match self.channel_type {
+            ChannelType::Direct => {
+                inviter.member == self.owner && self.member_count < DIRECT_CHANNEL_MEMBERS
+            }
+            ChannelType::Group => true,
+            ChannelType::Public | ChannelType::Private => {
+                self.rank_of(inviter) >= ChannelRole::Moderator as u8
+            }
+        }
+    }
+
+    /// Whether `actor` may remove `target`: moderators and above, over
+    /// members they outrank
+    pub fn can_remove(&self, actor: &ChannelMembership, target: &ChannelMembership) -> bool {
+        let actor_rank = self.rank_of(actor);
+        actor.is_active()
+            && actor_rank >= ChannelRole::Moderator as u8
+            && actor_rank > self.rank_of(target)
+    }
+
+    /// Whether `actor` may give `target` the role `new_role`: admins and
+    /// above, over members they outrank, up to a role below their own
+    pub fn can_set_role(
+        &self,
+        actor: &ChannelMembership,
+        target: &ChannelMembership,
+        new_role: ChannelRole,
+    ) -> bool {
+        let actor_rank = self.rank_of(actor);
+        actor.is_active()
+            && target.is_active()
+            && actor_rank >= ChannelRole::Admin as u8
+            && actor_rank > self.rank_of(target)
+            && actor_rank > new_role as u8
+    }
+
+    /// Claims a seat for a new membership
+    pub fn add_member(&mut self) -> Result<()> {
+        require!(
+            self.max_members == 0 || self.member_count < self.max_members,
+            PodAIMarketplaceError::ChannelFull
+        );
+        self.member_count = self.member_count
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Frees the seat of a closed membership
+    pub fn remove_member(&mut self) -> Result<()> {
+        self.member_count = self.member_count
+            .checked_sub(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticUnderflow)?;
+        Ok(())
+    }
+}
+
+/// One participant's seat in a channel
+///
+/// Whoever creates the membership pays its rent: the member when joining a
+/// public channel, the inviter otherwise. Leaving or being removed returns
+/// the rent to that payer.
+#[account]
+pub struct ChannelMembership {
+    pub channel: Pubkey,
+    pub member: Pubkey,
+    pub role: ChannelRole,
+    pub status: ChannelMembershipStatus,
+    pub invited_by: Option<Pubkey>,
+    pub payer: Pubkey,
+    pub created_at: i64,
+    pub joined_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl ChannelMembership {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // channel
+        32 + // member
+        1 + // role
+        1 + // status
+        1 + 32 + // invited_by
+        32 + // payer
+        8 + // created_at
+        1 + 8 + // joined_at
+        1; // bump
+
+    pub fn is_active(&self) -> bool {
+        self.status == ChannelMembershipStatus::Active
+    }
+
+    pub fn accept(&mut self, now: i64) -> Result<()> {
+        require!(
+            self.status == ChannelMembershipStatus::Invited,
+            PodAIMarketplaceError::InvalidStatusTransition
+        );
+        self.status = ChannelMembershipStatus::Active;
+        self.joined_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_membership_roles() {
+        let owner = Pubkey::new_unique();
+        let current_time = 1640995200i64;
+        let mut channel = Channel {
+            creator: owner,
+            owner,
+            channel_type: ChannelType::Private,
+            is_private: true,
+            member_count: 1,
+            max_members: 3,
+            message_count: 0,
+            created_at: current_time,
+            last_activity: current_time,
+            is_active: true,
+            bump: 254,
+        };
+        let membership = |member: Pubkey, role: ChannelRole, status: ChannelMembershipStatus| ChannelMembership {
+            channel: Pubkey::new_unique(),
+            member,
+            role,
+            status,
+            invited_by: None,
+            payer: member,
+            created_at: current_time,
+            joined_at: None,
+            bump: 255,
+        };
+
+        let owner_seat = membership(owner, ChannelRole::Admin, ChannelMembershipStatus::Active);
+        let admin = membership(Pubkey::new_unique(), ChannelRole::Admin, ChannelMembershipStatus::Active);
+        let moderator = membership(Pubkey::new_unique(), ChannelRole::Moderator, ChannelMembershipStatus::Active);
+        let member = membership(Pubkey::new_unique(), ChannelRole::Member, ChannelMembershipStatus::Active);
+        let mut invited = membership(Pubkey::new_unique(), ChannelRole::Member, ChannelMembershipStatus::Invited);
+
+        // Private channels need a moderator to invite; groups let anyone
+        assert!(channel.can_invite(&moderator));
+        assert!(!channel.can_invite(&member));
+        assert!(!channel.can_invite(&invited));
+        channel.channel_type = ChannelType::Group;
+        assert!(channel.can_invite(&member));
+        channel.channel_type = ChannelType::Direct;
+        assert!(channel.can_invite(&owner_seat));
+        assert!(!channel.can_invite(&admin));
+        channel.channel_type = ChannelType::Private;
+
+        // Removal needs a moderator who outranks the target; the owner outranks admins
+        assert!(channel.can_remove(&moderator, &member));
+        assert!(!channel.can_remove(&moderator, &moderator));
+        assert!(!channel.can_remove(&member, &invited));
+        assert!(!channel.can_remove(&admin, &owner_seat));
+        assert!(channel.can_remove(&owner_seat, &admin));
+
+        // Only the owner can appoint admins
+        assert!(channel.can_set_role(&admin, &member, ChannelRole::Moderator));
+        assert!(!channel.can_set_role(&admin, &member, ChannelRole::Admin));
+        assert!(channel.can_set_role(&owner_seat, &member, ChannelRole::Admin));
+        assert!(!channel.can_set_role(&moderator, &member, ChannelRole::Member));
+        assert!(!channel.can_set_role(&owner_seat, &invited, ChannelRole::Moderator));
+
+        // Seats include pending invites and are capped by max_members
+        assert!(channel.add_member().is_ok());
+        assert!(channel.add_member().is_ok());
+        assert!(channel.add_member().is_err());
+        assert!(channel.remove_member().is_ok());
+        assert_eq!(channel.member_count, 2);
+
+        assert!(invited.accept(current_time + 10).is_ok());
+        assert!(invited.is_active());
+        assert_eq!(invited.joined_at, Some(current_time + 10));
+        assert!(invited.accept(current_time + 20).is_err());
+    }
+}
@@ -6,6 +6,7 @@
 
 use anchor_lang::prelude::*;
 use crate::{MAX_GENERAL_STRING_LENGTH, Deliverable};
+use super::reputation::ReviewSubject;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ServiceListingData {
@@ -26,6 +27,11 @@ pub struct ServicePurchaseData {
     pub requirements: Vec<String>,
     pub custom_instructions: String,
     pub deadline: i64,
+    /// Distinguishes repeat purchases of the same listing by the same buyer
+    pub nonce: u64,
+    /// Index of the listing tier to buy; required when the listing has tiers
+    pub tier: Option<u8>,    /// Highest unit price the buyer accepts, bounding dynamic and tier pricing
+    pub max_unit_price: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -63,8 +69,8 @@ pub struct JobCompletionData {
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct ReviewData {
+    pub subject: ReviewSubject,
     pub rating: u8,
     pub comment: String,
-    pub review_type: String,
     pub work_reference: String,
 }
\ No newline at end of file
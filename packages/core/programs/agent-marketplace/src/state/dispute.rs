@@ -6,6 +6,17 @@
 
 use anchor_lang::prelude::*;
 use crate::{MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError};
+use super::reputation::{MAX_REVIEW_RATING, MIN_REVIEW_RATING};
+
+// PDA Seeds
+pub const ARBITRATOR_REGISTRY_SEED: &[u8] = b"arbitrator_registry";
+pub const ARBITRATOR_STAKE_SEED: &[u8] = b"arbitrator_stake";
+pub const ARBITRATOR_STAKE_VAULT_SEED: &[u8] = b"arbitrator_stake_vault";
+
+// Constants
+pub const MAX_ARBITRATORS: usize = 32;
+pub const DISPUTE_SPLIT_BASIS_POINTS: u16 = 10_000;
+pub const ARBITRATOR_UNSTAKE_COOLDOWN: i64 = 30 * 24 * 60 * 60; // 30 days
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum DisputeStatus {
@@ -118,6 +129,12 @@ impl DisputeCase {
     }
 
     pub fn resolve(&mut self, resolution: String) -> Result<()> {
+        require!(
+            !matches!(self.status, DisputeStatus::Resolved | DisputeStatus::Closed),
+            PodAIMarketplaceError::DisputeAlreadyResolved
+        );
+        require!(resolution.len() <= MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError::StringTooLong);
+        
         self.status = DisputeStatus::Resolved;
         self.resolution = Some(resolution);
         self.resolved_at = Some(Clock::get()?.unix_timestamp);
@@ -125,14 +142,226 @@ impl DisputeCase {
     }
 
     pub fn assign_moderator(&mut self, moderator: Pubkey) -> Result<()> {
+        require!(
+            matches!(
+                self.status,
+                DisputeStatus::Filed | DisputeStatus::UnderReview | DisputeStatus::EvidenceSubmitted
+            ),
+            PodAIMarketplaceError::InvalidDisputeStatus
+        );
+        // A party to the dispute can never arbitrate it
+        require!(
+            moderator != self.complainant && moderator != self.respondent,
+            PodAIMarketplaceError::ArbitratorNotEligible
+        );
+        
         self.moderator = Some(moderator);
         self.status = DisputeStatus::UnderReview;
         Ok(())
     }
 
     pub fn escalate(&mut self) -> Result<()> {
+        require!(
+            matches!(
+                self.status,
+                DisputeStatus::Filed | DisputeStatus::UnderReview | DisputeStatus::EvidenceSubmitted
+            ),
+            PodAIMarketplaceError::InvalidDisputeStatus
+        );
+        
         self.status = DisputeStatus::Escalated;
         self.human_review = true;
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Returns the key allowed to resolve this dispute: the registry authority
+    /// once escalated to human review, otherwise the assigned moderator
+    pub fn resolver(&self, registry_authority: Pubkey) -> Option<Pubkey> {
+        if self.human_review {
+            Some(registry_authority)
+        } else {
+            self.moderator
+        }
+    }
+
+    /// Splits `amount` between complainant and respondent
+    ///
+    /// `complainant_share_bps` is the complainant's share in basis points; the
+    /// respondent receives the remainder so no dust is left behind.
+    pub fn calculate_split(amount: u64, complainant_share_bps: u16) -> Result<(u64, u64)> {
+        require!(
+            complainant_share_bps <= DISPUTE_SPLIT_BASIS_POINTS,
+            PodAIMarketplaceError::InvalidPercentage
+        );
+        
+        let complainant_amount = (amount as u128)
+            .checked_mul(complainant_share_bps as u128)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?
+            / DISPUTE_SPLIT_BASIS_POINTS as u128;
+        let complainant_amount = complainant_amount as u64;
+        let respondent_amount = amount
+            .checked_sub(complainant_amount)
+            .ok_or(PodAIMarketplaceError::ArithmeticUnderflow)?;
+        
+        Ok((complainant_amount, respondent_amount))
+    }
+
+    /// Maps the provider's share of a ruling onto the review rating scale
+    ///
+    /// A full award counts as a top rating and losing everything as the
+    /// lowest, rounded to the nearest star.
+    pub fn ruling_rating(provider_share_bps: u16) -> u8 {
+        let share = provider_share_bps.min(DISPUTE_SPLIT_BASIS_POINTS) as u32;
+        let range = (MAX_REVIEW_RATING - MIN_REVIEW_RATING) as u32;
+        let half = DISPUTE_SPLIT_BASIS_POINTS as u32 / 2;
+        MIN_REVIEW_RATING + ((share * range + half) / DISPUTE_SPLIT_BASIS_POINTS as u32) as u8
+    }
+}
+
+/// Registry of arbitrators eligible to moderate disputes
+///
+/// Arbitrators qualify either by being allowlisted by the registry authority
+/// or by staking at least `min_stake` of `stake_mint`. The authority also
+/// resolves disputes escalated to human review.
+#[account]
+pub struct ArbitratorRegistry {
+    pub authority: Pubkey,
+    pub arbitrators: Vec<Pubkey>,
+    pub stake_mint: Pubkey,
+    pub min_stake: u64,
+    pub bump: u8,
+}
+
+impl ArbitratorRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        4 + (MAX_ARBITRATORS * 32) + // arbitrators
+        32 + // stake_mint
+        8 + // min_stake
+        1; // bump
+
+    pub fn add_arbitrator(&mut self, arbitrator: Pubkey) -> Result<()> {
+        require!(!self.arbitrators.contains(&arbitrator), PodAIMarketplaceError::AccountAlreadyInitialized);
+        require!(self.arbitrators.len() < MAX_ARBITRATORS, PodAIMarketplaceError::TooManyArbitrators);
+        self.arbitrators.push(arbitrator);
+        Ok(())
+    }
+
+    pub fn remove_arbitrator(&mut self, arbitrator: Pubkey) -> Result<()> {
+        let index = self.arbitrators
+            .iter()
+            .position(|a| *a == arbitrator)
+            .ok_or(PodAIMarketplaceError::ArbitratorNotEligible)?;
+        self.arbitrators.swap_remove(index);
+        Ok(())
+    }
+
+    /// An arbitrator is eligible when allowlisted or sufficiently staked
+    pub fn is_eligible(&self, arbitrator: Pubkey, staked_amount: u64) -> bool {
+        self.arbitrators.contains(&arbitrator)
+            || (self.min_stake > 0 && staked_amount >= self.min_stake)
+    }
+}
+
+/// Tokens an arbitrator has locked to join the staked moderator pool
+#[account]
+pub struct ArbitratorStake {
+    pub arbitrator: Pubkey,
+    pub amount: u64,
+    pub staked_at: i64,
+    pub last_assigned_at: i64,
+    pub bump: u8,
+}
+
+impl ArbitratorStake {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // arbitrator
+        8 + // amount
+        8 + // staked_at
+        8 + // last_assigned_at
+        1; // bump
+
+    /// Stake stays locked until the cooldown after the latest assignment elapses
+    pub fn can_unstake(&self, now: i64) -> bool {
+        now >= self.last_assigned_at.saturating_add(ARBITRATOR_UNSTAKE_COOLDOWN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dispute_escrow_split() {
+        // Complainant share is in basis points, respondent gets the remainder
+        let (complainant, respondent) = DisputeCase::calculate_split(1_000_001, 2_500).unwrap();
+        assert_eq!(complainant, 250_000);
+        assert_eq!(respondent, 750_001);
+        assert_eq!(complainant + respondent, 1_000_001, "Split must not leave dust");
+        
+        // Full awards in either direction
+        assert_eq!(DisputeCase::calculate_split(5_000, 10_000).unwrap(), (5_000, 0));
+        assert_eq!(DisputeCase::calculate_split(5_000, 0).unwrap(), (0, 5_000));
+        
+        // Large amounts do not overflow
+        let (complainant, respondent) = DisputeCase::calculate_split(u64::MAX, 5_000).unwrap();
+        assert_eq!(complainant + respondent, u64::MAX);
+        
+        // Shares above 100% are rejected
+        assert!(DisputeCase::calculate_split(5_000, 10_001).is_err());
+    }
+
+    #[test]
+    fn test_dispute_resolver_and_ruling() {
+        let moderator = Pubkey::new_unique();
+        let registry_authority = Pubkey::new_unique();
+        let mut dispute = DisputeCase {
+            transaction: Pubkey::new_unique(),
+            complainant: Pubkey::new_unique(),
+            respondent: Pubkey::new_unique(),
+            moderator: None,
+            reason: "late delivery".to_string(),
+            status: DisputeStatus::Filed,
+            evidence: Vec::new(),
+            resolution: None,
+            ai_score: 0.0,
+            human_review: false,
+            created_at: 0,
+            resolved_at: None,
+            bump: 255,
+        };
+        
+        // Nobody can resolve until a moderator is assigned or it is escalated
+        assert_eq!(dispute.resolver(registry_authority), None);
+        dispute.moderator = Some(moderator);
+        assert_eq!(dispute.resolver(registry_authority), Some(moderator));
+        dispute.human_review = true;
+        assert_eq!(dispute.resolver(registry_authority), Some(registry_authority));
+        
+        // Rulings map the provider's share onto the review scale
+        assert_eq!(DisputeCase::ruling_rating(0), MIN_REVIEW_RATING);
+        assert_eq!(DisputeCase::ruling_rating(5_000), 3);
+        assert_eq!(DisputeCase::ruling_rating(10_000), MAX_REVIEW_RATING);
+    }
+
+    #[test]
+    fn test_arbitrator_eligibility() {
+        let allowlisted = Pubkey::new_unique();
+        let staker = Pubkey::new_unique();
+        let registry = ArbitratorRegistry {
+            authority: Pubkey::new_unique(),
+            arbitrators: vec![allowlisted],
+            stake_mint: Pubkey::new_unique(),
+            min_stake: 1_000,
+            bump: 255,
+        };
+        
+        assert!(registry.is_eligible(allowlisted, 0), "Allowlisted arbitrators need no stake");
+        assert!(registry.is_eligible(staker, 1_000), "Sufficient stake qualifies");
+        assert!(!registry.is_eligible(staker, 999), "Insufficient stake does not qualify");
+        
+        // With the staked pool disabled only the allowlist counts
+        let allowlist_only = ArbitratorRegistry { min_stake: 0, ..registry };
+        assert!(!allowlist_only.is_eligible(staker, 0));
+    }
+}
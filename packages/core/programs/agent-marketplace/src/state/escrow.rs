@@ -6,10 +6,12 @@
 
 use anchor_lang::prelude::*;
 use super::{MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError};
+use super::protocol_config::FeeTransactionType;
 
 // PDA Seeds
 pub const ESCROW_SEED: &[u8] = b"escrow";
 pub const TASK_ESCROW_SEED: &[u8] = b"task_escrow";
+pub const ESCROW_VAULT_SEED: &[u8] = b"escrow_vault";
 
 // Constants
 pub const MAX_TASK_ID_LENGTH: usize = 64;
@@ -45,7 +47,14 @@ pub struct Escrow {
     pub created_at: i64,
     pub expires_at: i64,
     pub dispute_reason: Option<String>,
+    /// Dispute that locked the escrow; only it can resolve the escrow
+    pub dispute: Option<Pubkey>,
     pub resolution_notes: Option<String>,
+    pub work_order: Pubkey,
+    pub token_mint: Pubkey,
+    /// Protocol fee charged when the escrow is released to the provider
+    pub fee_type: FeeTransactionType,
+    pub bump: u8,
 }
 
 #[account]
@@ -72,18 +81,38 @@ impl Escrow {
         8 + // created_at
         8 + // expires_at
         1 + 4 + MAX_DISPUTE_REASON_LENGTH + // dispute_reason
-        1 + 4 + MAX_RESOLUTION_NOTES_LENGTH; // resolution_notes
+        1 + 32 + // dispute
+        1 + 4 + MAX_RESOLUTION_NOTES_LENGTH + // resolution_notes
+        32 + // work_order
+        32 + // token_mint
+        1 + // fee_type
+        1; // bump
+}
+
+/// Parameters for opening an escrow alongside a work order
+pub struct EscrowInit {
+    pub client: Pubkey,
+    pub agent: Pubkey,
+    pub task_id: String,
+    pub amount: u64,
+    pub expires_at: i64,
+    pub work_order: Pubkey,
+    pub token_mint: Pubkey,
+    pub bump: u8,
 }
 
 impl Escrow {
-    pub fn initialize(
-        &mut self,
-        client: Pubkey,
-        agent: Pubkey,
-        task_id: String,
-        amount: u64,
-        expires_at: i64,
-    ) -> Result<()> {
+    pub fn initialize(&mut self, init: EscrowInit) -> Result<()> {
+        let EscrowInit {
+            client,
+            agent,
+            task_id,
+            amount,
+            expires_at,
+            work_order,
+            token_mint,
+            bump,
+        } = init;
         require!(task_id.len() <= MAX_TASK_ID_LENGTH, PodAIMarketplaceError::TaskIdTooLong);
         require!(amount > 0, PodAIMarketplaceError::InvalidAmount);
         
@@ -98,11 +127,21 @@ impl Escrow {
         self.created_at = clock.unix_timestamp;
         self.expires_at = expires_at;
         self.dispute_reason = None;
+        self.dispute = None;
         self.resolution_notes = None;
+        self.work_order = work_order;
+        self.token_mint = token_mint;
+        self.fee_type = FeeTransactionType::WorkOrder;
+        self.bump = bump;
         
         Ok(())
     }
 
+    /// Returns true once the escrow has passed its expiry without being settled
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.status == EscrowStatus::Active && now > self.expires_at
+    }
+
     pub fn complete(&mut self, resolution_notes: Option<String>) -> Result<()> {
         require!(self.status == EscrowStatus::Active, PodAIMarketplaceError::InvalidEscrowStatus);
         
@@ -116,12 +155,25 @@ impl Escrow {
         Ok(())
     }
 
-    pub fn dispute(&mut self, dispute_reason: String) -> Result<()> {
+    /// Returns true if `complainant` and `respondent` are the escrow's client
+    /// and agent, in either order
+    pub fn is_party_pair(&self, complainant: Pubkey, respondent: Pubkey) -> bool {
+        (complainant == self.client && respondent == self.agent)
+            || (complainant == self.agent && respondent == self.client)
+    }
+
+    /// Returns true if the escrow is locked by `dispute`
+    pub fn is_locked_by(&self, dispute: Pubkey) -> bool {
+        self.status == EscrowStatus::Disputed && self.dispute == Some(dispute)
+    }
+
+    pub fn dispute(&mut self, dispute: Pubkey, dispute_reason: String) -> Result<()> {
         require!(self.status == EscrowStatus::Active, PodAIMarketplaceError::InvalidEscrowStatus);
         require!(dispute_reason.len() <= MAX_DISPUTE_REASON_LENGTH, PodAIMarketplaceError::DisputeReasonTooLong);
         
         self.status = EscrowStatus::Disputed;
         self.dispute_reason = Some(dispute_reason);
+        self.dispute = Some(dispute);
         
         Ok(())
     }
@@ -249,4 +301,64 @@ impl Payment {
         1 + // is_confidential
         8 + // paid_at
         1; // bump
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active_escrow(client: Pubkey, agent: Pubkey) -> Escrow {
+        Escrow {
+            client,
+            agent,
+            task_id: "task".to_string(),
+            amount: 1_000,
+            status: EscrowStatus::Active,
+            created_at: 0,
+            expires_at: 100,
+            dispute_reason: None,
+            dispute: None,
+            resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_escrow_dispute_party_binding() {
+        let client = Pubkey::new_unique();
+        let agent = Pubkey::new_unique();
+        let outsider = Pubkey::new_unique();
+        let escrow = active_escrow(client, agent);
+
+        // Either party may file against the other
+        assert!(escrow.is_party_pair(client, agent));
+        assert!(escrow.is_party_pair(agent, client));
+
+        // Outsiders and self-disputes are rejected
+        assert!(!escrow.is_party_pair(outsider, agent));
+        assert!(!escrow.is_party_pair(client, outsider));
+        assert!(!escrow.is_party_pair(client, client));
+    }
+
+    #[test]
+    fn test_escrow_locked_by_dispute() {
+        let mut escrow = active_escrow(Pubkey::new_unique(), Pubkey::new_unique());
+        let dispute = Pubkey::new_unique();
+        let other_dispute = Pubkey::new_unique();
+
+        assert!(!escrow.is_locked_by(dispute), "Active escrow is not locked");
+        escrow.dispute(dispute, "late delivery".to_string()).unwrap();
+        assert!(escrow.is_locked_by(dispute));
+        assert!(!escrow.is_locked_by(other_dispute), "Only the locking dispute may resolve");
+
+        // A locked escrow cannot be disputed a second time
+        assert!(escrow.dispute(other_dispute, "again".to_string()).is_err());
+        assert_eq!(escrow.dispute, Some(dispute));
+
+        escrow.resolve("split".to_string()).unwrap();
+        assert!(!escrow.is_locked_by(dispute), "Resolved escrow is no longer locked");
+    }
+}
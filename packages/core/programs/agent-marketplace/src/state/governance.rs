@@ -8,6 +8,7 @@
 
 use anchor_lang::prelude::*;
 use std::collections::BTreeMap;
+use crate::PodAIMarketplaceError;
 
 // =====================================================
 // MULTI-SIGNATURE STRUCTURES
@@ -65,12 +66,18 @@ pub struct PendingTransaction {
     /// Transaction data
     pub data: Vec<u8>,
     
+    /// Signer that queued the transaction
+    pub proposer: Pubkey,
+    
     /// Required signatures
     pub required_signatures: u8,
     
     /// Current signatures
     pub signatures: Vec<MultisigSignature>,
     
+    /// Signers voting to cancel an approved transaction
+    pub cancel_votes: Vec<Pubkey>,
+    
     /// Creation timestamp
     pub created_at: i64,
     
@@ -294,6 +301,9 @@ pub struct GovernanceProposal {
     /// Proposer
     pub proposer: Pubkey,
     
+    /// Governance configuration the proposal is voted under
+    pub governance_config: Pubkey,
+    
     /// Proposal title
     pub title: String,
     
@@ -312,6 +322,9 @@ pub struct GovernanceProposal {
     /// Voting end timestamp
     pub voting_ends_at: i64,
     
+    /// Voting power is read from voter checkpoints at this timestamp
+    pub snapshot_at: i64,
+    
     /// Execution timestamp (if approved)
     pub execution_timestamp: Option<i64>,
     
@@ -330,6 +343,9 @@ pub struct GovernanceProposal {
     /// Proposal metadata
     pub metadata: ProposalMetadata,
     
+    /// PDA bump
+    pub bump: u8,
+    
     /// Reserved space
     pub reserved: [u8; 64],
 }
@@ -392,9 +408,6 @@ pub struct VotingResults {
     /// Participation rate
     pub participation_rate: u8,
     
-    /// Individual votes
-    pub individual_votes: Vec<Vote>,
-    
     /// Weighted voting enabled
     pub weighted_voting: bool,
     
@@ -427,6 +440,22 @@ pub struct Vote {
     pub delegation_info: Option<DelegationInfo>,
 }
 
+/// Vote cast on a proposal, one account per voter
+///
+/// Keeping votes out of the proposal account means a proposal's size does not
+/// grow with turnout, and the PDA itself prevents double voting.
+#[account]
+pub struct VoteRecord {
+    /// Proposal voted on
+    pub proposal: Pubkey,
+    
+    /// Vote details
+    pub vote: Vote,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
 /// Vote choices
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub enum VoteChoice {
@@ -598,10 +627,66 @@ pub struct GovernanceConfig {
     /// Emergency governance settings
     pub emergency_governance: EmergencyGovernance,
     
+    /// Sum of all deposited voting power
+    pub total_voting_power: u64,
+    
+    /// Proposals currently open for voting
+    pub active_proposals: u32,
+    
+    /// PDA bump
+    pub bump: u8,
+    
+    /// Bump of the PDA that signs executed proposal instructions
+    pub signer_bump: u8,
+    
     /// Reserved space
     pub reserved: [u8; 128],
 }
 
+/// Governance token deposit and voting power of one voter
+#[account]
+pub struct VoterRecord {
+    /// Governance configuration
+    pub governance_config: Pubkey,
+    
+    /// Voter
+    pub voter: Pubkey,
+    
+    /// Governance tokens held in the vault for this voter
+    pub deposited_amount: u64,
+    
+    /// Voting power derived from the deposit
+    pub own_voting_power: u64,
+    
+    /// Voting power delegated to this voter by others
+    pub delegated_power: u64,
+    
+    /// Deposit cannot be withdrawn before this timestamp
+    pub lockup_expires_at: i64,
+    
+    /// Voter this record's own power is delegated to
+    pub delegate: Option<Pubkey>,
+    
+    /// Details of the active delegation
+    pub delegation: Option<DelegationInfo>,
+    
+    /// Last proposal created by this voter
+    pub last_proposal_at: i64,
+    
+    /// Voting power history, oldest first
+    pub checkpoints: Vec<VotingPowerCheckpoint>,
+    
+    /// PDA bump
+    pub bump: u8,
+}
+
+/// Voting power held from `timestamp` until the next checkpoint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VotingPowerCheckpoint {
+    pub timestamp: i64,
+    pub voting_power: u64,
+}
+
 /// Voting system configuration
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct VotingConfig {
@@ -1051,6 +1136,13 @@ pub const MAX_VOTING_POWER_MULTIPLIER: u32 = 100000; // Basis points
 
 impl Multisig {
     pub const fn space() -> usize {
+        Self::base_space() +
+        (MAX_PENDING_TRANSACTIONS * PendingTransaction::size()) // pending_transactions
+    }
+    
+    /// Space with an empty transaction queue. The account starts at this size
+    /// and is grown as transactions and signatures are added.
+    pub const fn base_space() -> usize {
         8 + // discriminator
         8 + // multisig_id
         1 + // threshold
@@ -1059,7 +1151,7 @@ impl Multisig {
         8 + // created_at
         8 + // updated_at
         8 + // nonce
-        4 + (MAX_PENDING_TRANSACTIONS * PendingTransaction::size()) + // pending_transactions
+        4 + // pending_transactions (empty)
         MultisigConfig::size() + // config
         EmergencyConfig::size() + // emergency_config
         128 // reserved
@@ -1072,8 +1164,10 @@ impl PendingTransaction {
         1 + // transaction_type
         32 + // target
         4 + 1024 + // data (max 1KB)
+        32 + // proposer
         1 + // required_signatures
         4 + (20 * MultisigSignature::size()) + // signatures
+        4 + (20 * 32) + // cancel_votes
         8 + // created_at
         8 + // expires_at
         1 + // priority
@@ -1141,22 +1235,34 @@ impl TimeLock {
 }
 
 impl GovernanceProposal {
-    pub const fn space() -> usize {
+    /// Space for a proposal with the given variable-length content
+    ///
+    /// Sized to the actual content rather than the maximums, which would not
+    /// fit in a single account.
+    pub fn space_for(
+        title: &str,
+        description: &str,
+        execution_params: &ExecutionParams,
+        metadata: &ProposalMetadata,
+    ) -> usize {
         8 + // discriminator
         8 + // proposal_id
         32 + // proposer
-        4 + 256 + // title
-        4 + 2048 + // description
+        32 + // governance_config
+        4 + title.len() + // title
+        4 + description.len() + // description
         1 + // proposal_type
         8 + // created_at
         8 + // voting_starts_at
         8 + // voting_ends_at
+        8 + // snapshot_at
         1 + 8 + // execution_timestamp
         1 + // status
         VotingResults::size() + // voting_results
-        ExecutionParams::size() + // execution_params
+        execution_params.try_to_vec().map_or(ExecutionParams::size(), |data| data.len()) + // execution_params
         QuorumRequirements::size() + // quorum_requirements
-        ProposalMetadata::size() + // metadata
+        metadata.try_to_vec().map_or(ProposalMetadata::size(), |data| data.len()) + // metadata
+        1 + // bump
         64 // reserved
     }
 }
@@ -1168,7 +1274,6 @@ impl VotingResults {
         8 + // votes_abstain
         8 + // total_voting_power
         1 + // participation_rate
-        4 + (1000 * Vote::size()) + // individual_votes
         1 + // weighted_voting
         1 + // quorum_reached
         1 // approval_threshold_met
@@ -1186,11 +1291,20 @@ impl Vote {
     }
 }
 
+impl VoteRecord {
+    pub const fn space() -> usize {
+        8 + // discriminator
+        32 + // proposal
+        Vote::size() + // vote
+        1 // bump
+    }
+}
+
 impl DelegationInfo {
     pub const fn size() -> usize {
         32 + // delegator
         8 + // delegated_at
-        1 + // scope (simplified)
+        2 + // scope (ProposalType variant carries one byte)
         1 + 8 // expires_at
     }
 }
@@ -1246,21 +1360,48 @@ impl ProposalMetadata {
 }
 
 impl GovernanceConfig {
-    pub const fn space() -> usize {
+    /// Space for a configuration with the given variable-length settings
+    pub fn space_for(
+        proposal_config: &ProposalConfig,
+        token_governance: &TokenGovernance,
+        council_governance: &Option<CouncilGovernance>,
+        emergency_governance: &EmergencyGovernance,
+    ) -> usize {
         8 + // discriminator
         32 + // authority
         1 + // version
         8 + // created_at
         8 + // updated_at
         VotingConfig::size() + // voting_config
-        ProposalConfig::size() + // proposal_config
-        TokenGovernance::size() + // token_governance
-        1 + CouncilGovernance::size() + // council_governance
-        EmergencyGovernance::size() + // emergency_governance
+        proposal_config.try_to_vec().map_or(ProposalConfig::size(), |data| data.len()) + // proposal_config
+        token_governance.try_to_vec().map_or(TokenGovernance::size(), |data| data.len()) + // token_governance
+        council_governance.try_to_vec().map_or(1 + CouncilGovernance::size(), |data| data.len()) + // council_governance
+        emergency_governance.try_to_vec().map_or(EmergencyGovernance::size(), |data| data.len()) + // emergency_governance
+        8 + // total_voting_power
+        4 + // active_proposals
+        1 + // bump
+        1 + // signer_bump
         128 // reserved
     }
 }
 
+impl VoterRecord {
+    pub const fn space() -> usize {
+        8 + // discriminator
+        32 + // governance_config
+        32 + // voter
+        8 + // deposited_amount
+        8 + // own_voting_power
+        8 + // delegated_power
+        8 + // lockup_expires_at
+        1 + 32 + // delegate
+        1 + DelegationInfo::size() + // delegation
+        8 + // last_proposal_at
+        4 + (MAX_VOTING_POWER_CHECKPOINTS * (8 + 8)) + // checkpoints
+        1 // bump
+    }
+}
+
 impl VotingConfig {
     pub const fn size() -> usize {
         8 + // voting_period
@@ -1442,4 +1583,764 @@ impl EmergencyCondition {
         1 + // auto_activate
         1 // required_confirmations
     }
-}
\ No newline at end of file
+}
+
+// =====================================================
+// MULTISIG TRANSACTION LOGIC
+// =====================================================
+
+/// Maximum size of a pending transaction's instruction data
+pub const MAX_MULTISIG_TRANSACTION_DATA: usize = 1024;
+
+/// Maximum execution conditions per pending transaction
+pub const MAX_EXECUTION_CONDITIONS: usize = 10;
+
+impl Multisig {
+    /// Drops executed, cancelled, failed and expired transactions from the queue
+    ///
+    /// Returns the number of transactions removed.
+    pub fn prune_transactions(&mut self, now: i64) -> usize {
+        let before = self.pending_transactions.len();
+        self.pending_transactions
+            .retain(|tx| !tx.is_finalized() && !tx.is_expired(now));
+        before - self.pending_transactions.len()
+    }
+    
+    pub fn find_transaction_mut(&mut self, transaction_id: u64) -> Result<&mut PendingTransaction> {
+        self.pending_transactions
+            .iter_mut()
+            .find(|tx| tx.transaction_id == transaction_id)
+            .ok_or_else(|| error!(PodAIMarketplaceError::MultisigTransactionNotFound))
+    }
+    
+    /// Whether the emergency freeze is in force, honouring auto-unfreeze
+    pub fn is_frozen(&self, now: i64) -> bool {
+        let emergency = &self.emergency_config;
+        if !emergency.frozen {
+            return false;
+        }
+        match (emergency.frozen_at, emergency.auto_unfreeze_duration) {
+            (Some(frozen_at), Some(duration)) => now < frozen_at.saturating_add(duration),
+            _ => true,
+        }
+    }
+    
+    /// Whether the multisig config permits a transaction type
+    ///
+    /// An empty allowlist permits every type.
+    pub fn allows(&self, transaction_type: TransactionType) -> bool {
+        self.config.allowed_transaction_types.is_empty()
+            || self.config.allowed_transaction_types.contains(&transaction_type)
+    }
+    
+    /// Applies an executed transaction that changes the multisig itself
+    ///
+    /// `data` is the signer's public key for signer changes, the new threshold
+    /// byte for `ThresholdUpdate` and a serialized `MultisigConfig` for
+    /// `ConfigUpdate`. Types that do not target the multisig are rejected.
+    pub fn apply_transaction(&mut self, transaction_type: TransactionType, data: &[u8], now: i64) -> Result<()> {
+        match transaction_type {
+            TransactionType::SignerAddition => {
+                let signer = Pubkey::try_from(data)
+                    .map_err(|_| error!(PodAIMarketplaceError::InvalidInputFormat))?;
+                require!(!self.signers.contains(&signer), PodAIMarketplaceError::InvalidConfiguration);
+                require!(
+                    self.signers.len() < MAX_MULTISIG_SIGNERS &&
+                        (self.config.max_signers == 0 || self.signers.len() < self.config.max_signers as usize),
+                    PodAIMarketplaceError::InputTooLong
+                );
+                self.signers.push(signer);
+            }
+            TransactionType::SignerRemoval => {
+                let signer = Pubkey::try_from(data)
+                    .map_err(|_| error!(PodAIMarketplaceError::InvalidInputFormat))?;
+                require!(self.signers.contains(&signer), PodAIMarketplaceError::InvalidConfiguration);
+                // The remaining signers must still be able to reach the threshold
+                require!(
+                    self.signers.len() > self.threshold as usize,
+                    PodAIMarketplaceError::InvalidConfiguration
+                );
+                self.signers.retain(|existing| *existing != signer);
+            }
+            TransactionType::ThresholdUpdate => {
+                require!(data.len() == 1, PodAIMarketplaceError::InvalidInputFormat);
+                let new_threshold = data[0];
+                require!(
+                    new_threshold > 0 && new_threshold as usize <= self.signers.len(),
+                    PodAIMarketplaceError::InvalidConfiguration
+                );
+                self.threshold = new_threshold;
+            }
+            TransactionType::ConfigUpdate => {
+                self.config = MultisigConfig::try_from_slice(data)
+                    .map_err(|_| error!(PodAIMarketplaceError::InvalidInputFormat))?;
+            }
+            TransactionType::EmergencyFreeze => {
+                require!(self.emergency_config.freeze_enabled, PodAIMarketplaceError::FeatureNotEnabled);
+                self.emergency_config.frozen = true;
+                self.emergency_config.frozen_at = Some(now);
+            }
+            TransactionType::EmergencyUnfreeze => {
+                self.emergency_config.frozen = false;
+                self.emergency_config.frozen_at = None;
+            }
+            _ => return Err(PodAIMarketplaceError::InvalidConfiguration.into()),
+        }
+        Ok(())
+    }
+}
+
+impl PendingTransaction {
+    /// Executed, cancelled and failed transactions can no longer change
+    pub fn is_finalized(&self) -> bool {
+        matches!(
+            self.status,
+            TransactionStatus::Executed | TransactionStatus::Cancelled | TransactionStatus::Failed
+        )
+    }
+    
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.status == TransactionStatus::Expired || (!self.is_finalized() && now >= self.expires_at)
+    }
+    
+    pub fn has_signed(&self, signer: &Pubkey) -> bool {
+        self.signatures.iter().any(|sig| sig.signer == *signer)
+    }
+    
+    /// Checks that an approved transaction may execute now and marks it executed
+    ///
+    /// Requires the threshold of current signers, an elapsed time lock and all
+    /// execution conditions. A frozen multisig can only execute its own unfreeze.
+    pub fn authorize_execution(
+        &mut self,
+        now: i64,
+        signers: &[Pubkey],
+        threshold: u8,
+        token_balance: Option<u64>,
+        frozen: bool,
+    ) -> Result<()> {
+        require!(
+            self.status == TransactionStatus::FullyApproved,
+            PodAIMarketplaceError::InvalidStatusTransition
+        );
+        require!(!self.is_expired(now), PodAIMarketplaceError::TransactionExpired);
+        
+        // SECURITY: Recount against the current signer set and threshold
+        let signature_count = self.valid_signature_count(signers);
+        require!(
+            signature_count >= threshold.max(self.required_signatures) as usize,
+            PodAIMarketplaceError::InsufficientSignatures
+        );
+        
+        // Early unlock conditions are evaluated alongside the execution conditions
+        let conditions_met = self.evaluate_conditions(now, signature_count, token_balance);
+        require!(self.time_lock_elapsed(now), PodAIMarketplaceError::TimeLockActive);
+        require!(conditions_met, PodAIMarketplaceError::ExecutionConditionNotMet);
+        
+        // SECURITY: A frozen multisig can only execute its own unfreeze
+        require!(
+            !frozen || self.transaction_type == TransactionType::EmergencyUnfreeze,
+            PodAIMarketplaceError::ResourceLocked
+        );
+        
+        self.status = TransactionStatus::Executed;
+        Ok(())
+    }
+    
+    /// Decodes the little-endian `u64` amount of a token transfer transaction
+    pub fn transfer_amount(data: &[u8]) -> Result<u64> {
+        let bytes: [u8; 8] = data
+            .try_into()
+            .map_err(|_| error!(PodAIMarketplaceError::InvalidInputFormat))?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+    
+    /// Signatures from keys that are still multisig signers
+    ///
+    /// Signatures from removed signers stop counting toward the threshold.
+    pub fn valid_signature_count(&self, signers: &[Pubkey]) -> usize {
+        self.signatures
+            .iter()
+            .filter(|sig| signers.contains(&sig.signer))
+            .count()
+    }
+    
+    /// Records `signer`'s vote to cancel an approved transaction
+    ///
+    /// Returns whether the votes of current signers have reached the threshold
+    /// needed to approve it, so cancelling takes as much agreement as executing.
+    pub fn vote_to_cancel(&mut self, signer: Pubkey, signers: &[Pubkey], threshold: u8) -> Result<bool> {
+        require!(!self.cancel_votes.contains(&signer), PodAIMarketplaceError::AlreadySigned);
+        self.cancel_votes.push(signer);
+        let required = threshold.max(self.required_signatures) as usize;
+        let votes = self.cancel_votes.iter().filter(|vote| signers.contains(vote)).count();
+        Ok(votes >= required)
+    }
+    
+    /// Starts the time lock, if any, once the threshold is reached
+    pub fn start_time_lock(&mut self, now: i64) {
+        if let Some(time_lock) = self.time_lock.as_mut() {
+            time_lock.locked_at = now;
+            time_lock.unlocks_at = now.saturating_add(time_lock.duration);
+        }
+    }
+    
+    /// Whether the time lock has elapsed or every early unlock condition is met
+    pub fn time_lock_elapsed(&self, now: i64) -> bool {
+        match &self.time_lock {
+            None => true,
+            Some(time_lock) => {
+                time_lock.locked_at > 0 && (
+                    now >= time_lock.unlocks_at || (
+                        !time_lock.early_unlock_conditions.is_empty()
+                            && time_lock.early_unlock_conditions.iter().all(|c| c.met)
+                    )
+                )
+            }
+        }
+    }
+    
+    /// Re-evaluates execution conditions that can be checked on-chain
+    ///
+    /// `token_balance` is the balance of the source token account supplied at
+    /// execution, if any. Returns whether every condition is met.
+    pub fn evaluate_conditions(&mut self, now: i64, signature_count: usize, token_balance: Option<u64>) -> bool {
+        let created_at = self.created_at;
+        let early_unlock = self.time_lock
+            .as_mut()
+            .map(|time_lock| time_lock.early_unlock_conditions.iter_mut())
+            .into_iter()
+            .flatten();
+        
+        let mut all_met = true;
+        for condition in self.execution_conditions.iter_mut().chain(early_unlock) {
+            condition.current_value = match condition.condition_type {
+                ConditionType::TimeDelay => now.saturating_sub(created_at).max(0) as u64,
+                ConditionType::VoteCount => signature_count as u64,
+                ConditionType::TokenBalance => token_balance.unwrap_or(0),
+                _ => condition.current_value,
+            };
+            condition.met = condition.current_value >= condition.target_value;
+        }
+        for condition in &self.execution_conditions {
+            all_met &= condition.met;
+        }
+        all_met
+    }
+}
+
+impl TransactionType {
+    /// Token transfers out of a multisig-owned token account
+    pub fn is_token_transfer(&self) -> bool {
+        matches!(self, TransactionType::Transfer | TransactionType::Withdrawal | TransactionType::EscrowRelease)
+    }
+    
+    /// Types applied to the multisig account itself rather than another program
+    pub fn targets_multisig(&self) -> bool {
+        matches!(
+            self,
+            TransactionType::SignerAddition
+                | TransactionType::SignerRemoval
+                | TransactionType::ThresholdUpdate
+                | TransactionType::ConfigUpdate
+                | TransactionType::EmergencyFreeze
+                | TransactionType::EmergencyUnfreeze
+        )
+    }
+}
+
+impl ConditionType {
+    /// Condition types the program can evaluate without an external attestation
+    pub fn is_on_chain(&self) -> bool {
+        matches!(self, ConditionType::TimeDelay | ConditionType::VoteCount | ConditionType::TokenBalance)
+    }
+}
+
+// =====================================================
+// GOVERNANCE VOTING LOGIC
+// =====================================================
+
+pub const GOVERNANCE_CONFIG_SEED: &[u8] = b"governance_config";
+pub const GOVERNANCE_VAULT_SEED: &[u8] = b"governance_vault";
+pub const GOVERNANCE_SIGNER_SEED: &[u8] = b"governance_signer";
+pub const VOTER_RECORD_SEED: &[u8] = b"voter_record";
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
+
+/// Voting power checkpoints kept per voter
+///
+/// Once full the oldest is dropped; a snapshot older than the remaining
+/// history reads as zero power.
+pub const MAX_VOTING_POWER_CHECKPOINTS: usize = 32;
+
+/// Approval threshold applied when a proposal requires a super majority
+pub const SUPER_MAJORITY_THRESHOLD: u8 = 67;
+
+/// Maximum accounts per proposal instruction
+pub const MAX_PROPOSAL_INSTRUCTION_ACCOUNTS: usize = 20;
+
+/// Maximum data per proposal instruction
+pub const MAX_PROPOSAL_INSTRUCTION_DATA: usize = 1024;
+
+impl VoterRecord {
+    /// Voting power this voter can cast right now
+    ///
+    /// Own power only counts while it is not delegated away.
+    pub fn current_voting_power(&self) -> u64 {
+        let own = if self.delegate.is_none() { self.own_voting_power } else { 0 };
+        own.saturating_add(self.delegated_power)
+    }
+    
+    /// Voting power held at `timestamp`, from the checkpoint history
+    pub fn voting_power_at(&self, timestamp: i64) -> u64 {
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|checkpoint| checkpoint.timestamp <= timestamp)
+            .map_or(0, |checkpoint| checkpoint.voting_power)
+    }
+    
+    /// Records the current voting power, replacing a checkpoint from the
+    /// same timestamp
+    pub fn record_checkpoint(&mut self, now: i64) {
+        let voting_power = self.current_voting_power();
+        match self.checkpoints.last_mut() {
+            Some(last) if last.timestamp == now => last.voting_power = voting_power,
+            _ => {
+                if self.checkpoints.len() >= MAX_VOTING_POWER_CHECKPOINTS {
+                    self.checkpoints.remove(0);
+                }
+                self.checkpoints.push(VotingPowerCheckpoint { timestamp: now, voting_power });
+            }
+        }
+    }
+    
+    /// Whether a delegation can be revoked by anyone because it has expired
+    pub fn delegation_expired(&self, now: i64) -> bool {
+        self.delegation
+            .as_ref()
+            .and_then(|delegation| delegation.expires_at)
+            .is_some_and(|expires_at| now >= expires_at)
+    }
+}
+
+impl TokenGovernance {
+    /// Voting power for a deposit locked for another `lockup_remaining` seconds
+    ///
+    /// Time-weighted power applies the best lockup multiplier whose duration
+    /// threshold the remaining lockup meets.
+    pub fn voting_power_for(&self, deposited_amount: u64, lockup_remaining: i64) -> Result<u64> {
+        match self.voting_power_method {
+            VotingPowerMethod::LinearBalance | VotingPowerMethod::StakedBalance => Ok(deposited_amount),
+            VotingPowerMethod::SquareRootBalance => Ok(integer_sqrt(deposited_amount)),
+            VotingPowerMethod::TimeWeightedBalance => {
+                let multiplier = self.lockup_requirements
+                    .lockup_multipliers
+                    .iter()
+                    .filter(|tier| lockup_remaining >= tier.duration_threshold)
+                    .map(|tier| tier.multiplier.min(tier.max_multiplier) as u32)
+                    .max()
+                    .unwrap_or(10_000)
+                    .min(MAX_VOTING_POWER_MULTIPLIER);
+                let power = (deposited_amount as u128)
+                    .checked_mul(multiplier as u128)
+                    .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?
+                    / 10_000;
+                u64::try_from(power).map_err(|_| error!(PodAIMarketplaceError::ArithmeticOverflow))
+            }
+            VotingPowerMethod::Custom => Err(PodAIMarketplaceError::OperationNotSupported.into()),
+        }
+    }
+}
+
+impl QuorumRequirements {
+    /// Whether turnout satisfies the quorum method
+    ///
+    /// - `Absolute`: total votes cast reach `minimum_voting_power`
+    /// - `Relative`: votes cast reach `minimum_participation` percent of the
+    ///   voting power at the snapshot
+    /// - `Weighted`: as `Relative`, but only For and Abstain votes count
+    ///   toward quorum, so opposing a proposal cannot help it pass
+    /// - `Dynamic`: as `Relative`, with the required participation raised by
+    ///   the Against share of decisive votes
+    ///
+    /// `minimum_voting_power` is a floor for every method.
+    pub fn is_quorum_reached(&self, results: &VotingResults) -> bool {
+        let votes_for = results.votes_for as u128;
+        let votes_against = results.votes_against as u128;
+        let cast = votes_for + votes_against + results.votes_abstain as u128;
+        if cast < self.minimum_voting_power as u128 {
+            return false;
+        }
+        
+        let (counted, required_percent) = match self.quorum_method {
+            QuorumMethod::Absolute => return cast > 0,
+            QuorumMethod::Relative => (cast, self.minimum_participation as u128),
+            QuorumMethod::Weighted => (cast - votes_against, self.minimum_participation as u128),
+            QuorumMethod::Dynamic => {
+                let decisive = votes_for + votes_against;
+                let against_percent = (votes_against * 100).checked_div(decisive).unwrap_or(0);
+                (cast, (self.minimum_participation as u128 + against_percent).min(100))
+            }
+        };
+        counted > 0 && counted * 100 >= required_percent * results.total_voting_power as u128
+    }
+    
+    /// Whether For votes meet the approval threshold of decisive votes
+    pub fn is_approved(&self, results: &VotingResults) -> bool {
+        let threshold = if self.super_majority_required {
+            self.approval_threshold.max(SUPER_MAJORITY_THRESHOLD)
+        } else {
+            self.approval_threshold
+        };
+        let votes_for = results.votes_for as u128;
+        let decisive = votes_for + results.votes_against as u128;
+        votes_for > 0 && votes_for * 100 >= threshold as u128 * decisive
+    }
+}
+
+impl VotingResults {
+    pub fn record_vote(&mut self, choice: VoteChoice, voting_power: u64) -> Result<()> {
+        let tally = match choice {
+            VoteChoice::For => &mut self.votes_for,
+            VoteChoice::Against => &mut self.votes_against,
+            VoteChoice::Abstain => &mut self.votes_abstain,
+        };
+        *tally = tally.checked_add(voting_power).ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(())
+    }
+    
+    /// Total power cast as a percentage of the snapshot voting power
+    pub fn participation(&self) -> u8 {
+        let cast = self.votes_for as u128 + self.votes_against as u128 + self.votes_abstain as u128;
+        if self.total_voting_power == 0 {
+            return if cast > 0 { 100 } else { 0 };
+        }
+        (cast * 100 / self.total_voting_power as u128).min(100) as u8
+    }
+}
+
+impl GovernanceProposal {
+    /// Closes voting, recording quorum and approval, and returns whether the
+    /// proposal passed
+    pub fn tally(&mut self) -> bool {
+        let results = &mut self.voting_results;
+        results.participation_rate = results.participation();
+        results.quorum_reached = self.quorum_requirements.is_quorum_reached(results);
+        results.approval_threshold_met = self.quorum_requirements.is_approved(results);
+        
+        let passed = results.quorum_reached && results.approval_threshold_met;
+        if passed {
+            self.status = ProposalStatus::Passed;
+            self.execution_timestamp = Some(
+                self.voting_ends_at.saturating_add(self.execution_params.execution_delay),
+            );
+        } else {
+            self.status = ProposalStatus::Failed;
+        }
+        passed
+    }
+    
+    /// Re-evaluates execution conditions and returns whether all are met
+    ///
+    /// `TimeDelay` counts from the end of voting and `VoteCount` is the For
+    /// voting power.
+    pub fn evaluate_conditions(&mut self, now: i64) -> bool {
+        let voting_ends_at = self.voting_ends_at;
+        let votes_for = self.voting_results.votes_for;
+        let mut all_met = true;
+        for condition in self.execution_params.execution_conditions.iter_mut() {
+            condition.current_value = match condition.condition_type {
+                ConditionType::TimeDelay => now.saturating_sub(voting_ends_at).max(0) as u64,
+                ConditionType::VoteCount => votes_for,
+                _ => condition.current_value,
+            };
+            condition.met = condition.current_value >= condition.target_value;
+            all_met &= condition.met;
+        }
+        all_met
+    }
+}
+
+fn integer_sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method from an overestimate converges monotonically
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multisig_transaction_approval() {
+        use crate::state::governance::ConditionType;
+
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let signature = |signer: Pubkey| MultisigSignature {
+            signer,
+            signature: [0u8; 64],
+            signed_at: 100,
+            signature_method: "transaction_signer".to_string(),
+            verification_data: None,
+        };
+        let condition = |condition_type: ConditionType, target_value: u64| ExecutionCondition {
+            condition_type,
+            target_value,
+            current_value: 0,
+            met: false,
+            description: String::new(),
+        };
+
+        let mut transaction = PendingTransaction {
+            transaction_id: 1,
+            transaction_type: TransactionType::Withdrawal,
+            target: Pubkey::new_unique(),
+            data: 500u64.to_le_bytes().to_vec(),
+            proposer: alice,
+            required_signatures: 2,
+            signatures: vec![signature(alice), signature(carol)],
+            cancel_votes: Vec::new(),
+            created_at: 100,
+            expires_at: 10_000,
+            priority: TransactionPriority::Normal,
+            execution_conditions: vec![
+                condition(ConditionType::TimeDelay, 60),
+                condition(ConditionType::TokenBalance, 500),
+            ],
+            status: TransactionStatus::PartiallyApproved,
+            time_lock: Some(TimeLock {
+                duration: 3_600,
+                locked_at: 0,
+                unlocks_at: 0,
+                early_unlock_conditions: Vec::new(),
+                lock_type: TimeLockType::Standard,
+                cancellable: true,
+            }),
+        };
+
+        // Signatures from removed signers stop counting
+        assert!(transaction.has_signed(&carol));
+        assert_eq!(transaction.valid_signature_count(&[alice, bob, carol]), 2);
+        assert_eq!(transaction.valid_signature_count(&[alice, bob]), 1);
+
+        // The time lock only starts once approved
+        assert!(!transaction.time_lock_elapsed(200));
+        transaction.start_time_lock(200);
+        assert!(!transaction.time_lock_elapsed(3_799));
+        assert!(transaction.time_lock_elapsed(3_800));
+
+        // Conditions are evaluated against chain state at execution
+        assert!(!transaction.evaluate_conditions(150, 2, Some(500)), "Time delay not reached");
+        assert!(!transaction.evaluate_conditions(160, 2, Some(499)), "Balance too low");
+        assert!(transaction.evaluate_conditions(160, 2, Some(500)));
+
+        // Cancelling an approved transaction takes the approval threshold
+        assert!(!transaction.vote_to_cancel(alice, &[alice, bob, carol], 2).unwrap());
+        assert!(transaction.vote_to_cancel(alice, &[alice, bob, carol], 2).is_err());
+        assert!(transaction.vote_to_cancel(bob, &[alice, bob, carol], 2).unwrap());
+
+        // Expiry applies to unfinished transactions only
+        assert!(transaction.is_expired(10_000));
+        transaction.status = TransactionStatus::Executed;
+        assert!(transaction.is_finalized());
+        assert!(!transaction.is_expired(10_000));
+
+        assert!(ConditionType::VoteCount.is_on_chain());
+        assert!(!ConditionType::ExternalOracle.is_on_chain());
+    }
+
+    fn approved_transaction(transaction_type: TransactionType, data: Vec<u8>, signers: &[Pubkey]) -> PendingTransaction {
+        PendingTransaction {
+            transaction_id: 1,
+            transaction_type,
+            target: Pubkey::new_unique(),
+            data,
+            proposer: signers[0],
+            required_signatures: 2,
+            signatures: signers
+                .iter()
+                .map(|signer| MultisigSignature {
+                    signer: *signer,
+                    signature: [0u8; 64],
+                    signed_at: 100,
+                    signature_method: "transaction_signer".to_string(),
+                    verification_data: None,
+                })
+                .collect(),
+            cancel_votes: Vec::new(),
+            created_at: 100,
+            expires_at: 10_000,
+            priority: TransactionPriority::Normal,
+            execution_conditions: Vec::new(),
+            status: TransactionStatus::FullyApproved,
+            time_lock: None,
+        }
+    }
+
+    #[test]
+    fn test_multisig_execution_authorization() {
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let signers = [alice, bob, carol];
+
+        // Executing marks the transaction so it cannot run twice
+        let mut transaction = approved_transaction(TransactionType::CustomInstruction, Vec::new(), &[alice, bob]);
+        transaction.authorize_execution(200, &signers, 2, None, false).unwrap();
+        assert_eq!(transaction.status, TransactionStatus::Executed);
+        assert!(transaction.authorize_execution(200, &signers, 2, None, false).is_err());
+
+        // A raised threshold or removed signer invalidates the approval
+        let mut transaction = approved_transaction(TransactionType::CustomInstruction, Vec::new(), &[alice, bob]);
+        assert!(transaction.authorize_execution(200, &signers, 3, None, false).is_err());
+        assert!(transaction.authorize_execution(200, &[alice, carol], 2, None, false).is_err());
+        assert!(transaction.authorize_execution(10_000, &signers, 2, None, false).is_err(), "Expired");
+        assert_eq!(transaction.status, TransactionStatus::FullyApproved);
+
+        // Only the unfreeze runs while frozen
+        assert!(transaction.authorize_execution(200, &signers, 2, None, true).is_err());
+        let mut unfreeze = approved_transaction(TransactionType::EmergencyUnfreeze, Vec::new(), &[alice, bob]);
+        unfreeze.authorize_execution(200, &signers, 2, None, true).unwrap();
+    }
+
+    #[test]
+    fn test_multisig_execute_dispatch() {
+        let (alice, bob, carol) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut multisig = Multisig {
+            multisig_id: 1,
+            threshold: 2,
+            signers: vec![alice, bob],
+            owner: alice,
+            created_at: 0,
+            updated_at: 0,
+            nonce: 0,
+            pending_transactions: Vec::new(),
+            config: MultisigConfig {
+                max_signers: 3,
+                default_timeout: 86_400,
+                allow_emergency_override: false,
+                emergency_threshold: None,
+                auto_execute: false,
+                signer_change_threshold: 2,
+                allowed_transaction_types: Vec::new(),
+                daily_limits: BTreeMap::new(),
+            },
+            emergency_config: EmergencyConfig {
+                emergency_contacts: Vec::new(),
+                emergency_threshold: 0,
+                emergency_timeout: 0,
+                emergency_transaction_types: Vec::new(),
+                freeze_enabled: true,
+                frozen: false,
+                frozen_at: None,
+                auto_unfreeze_duration: None,
+            },
+            reserved: [0u8; 128],
+        };
+
+        // Transfers decode a little-endian amount; anything else is malformed
+        assert!(TransactionType::Withdrawal.is_token_transfer());
+        assert!(!TransactionType::Withdrawal.targets_multisig());
+        assert_eq!(PendingTransaction::transfer_amount(&500u64.to_le_bytes()).unwrap(), 500);
+        assert!(PendingTransaction::transfer_amount(&[1, 2, 3]).is_err());
+
+        // Signer changes respect the signer cap and the threshold
+        multisig.apply_transaction(TransactionType::SignerAddition, carol.as_ref(), 100).unwrap();
+        assert_eq!(multisig.signers, vec![alice, bob, carol]);
+        assert!(multisig.apply_transaction(TransactionType::SignerAddition, carol.as_ref(), 100).is_err());
+        let dave = Pubkey::new_unique();
+        assert!(multisig.apply_transaction(TransactionType::SignerAddition, dave.as_ref(), 100).is_err(), "Over max_signers");
+
+        multisig.apply_transaction(TransactionType::ThresholdUpdate, &[3], 100).unwrap();
+        assert!(multisig.apply_transaction(TransactionType::SignerRemoval, carol.as_ref(), 100).is_err(), "Below threshold");
+        assert!(multisig.apply_transaction(TransactionType::ThresholdUpdate, &[4], 100).is_err());
+        multisig.apply_transaction(TransactionType::ThresholdUpdate, &[2], 100).unwrap();
+        multisig.apply_transaction(TransactionType::SignerRemoval, carol.as_ref(), 100).unwrap();
+        assert_eq!(multisig.signers, vec![alice, bob]);
+
+        // Freezing stamps the time; unfreezing clears it
+        multisig.apply_transaction(TransactionType::EmergencyFreeze, &[], 300).unwrap();
+        assert!(multisig.is_frozen(301));
+        multisig.apply_transaction(TransactionType::EmergencyUnfreeze, &[], 400).unwrap();
+        assert!(!multisig.is_frozen(401));
+
+        // Types executed by CPI are not applied to the multisig
+        assert!(!TransactionType::CustomInstruction.targets_multisig());
+        assert!(multisig.apply_transaction(TransactionType::CustomInstruction, &[], 100).is_err());
+    }
+
+    #[test]
+    fn test_governance_voting_power_and_quorum() {
+        use crate::state::governance::VotingPowerCheckpoint;
+
+        // Snapshot reads the checkpoint in force at the snapshot time
+        let mut voter = VoterRecord {
+            governance_config: Pubkey::new_unique(),
+            voter: Pubkey::new_unique(),
+            deposited_amount: 1_000,
+            own_voting_power: 1_000,
+            delegated_power: 0,
+            lockup_expires_at: 0,
+            delegate: None,
+            delegation: None,
+            last_proposal_at: 0,
+            checkpoints: vec![VotingPowerCheckpoint { timestamp: 100, voting_power: 1_000 }],
+            bump: 255,
+        };
+        voter.own_voting_power = 5_000;
+        voter.record_checkpoint(200);
+        assert_eq!(voter.voting_power_at(99), 0);
+        assert_eq!(voter.voting_power_at(199), 1_000, "Later deposits do not count");
+        assert_eq!(voter.voting_power_at(200), 5_000);
+
+        // Delegated power leaves the delegator
+        voter.delegate = Some(Pubkey::new_unique());
+        voter.record_checkpoint(300);
+        assert_eq!(voter.current_voting_power(), 0);
+        assert_eq!(voter.voting_power_at(250), 5_000);
+
+        let mut results = VotingResults {
+            votes_for: 0,
+            votes_against: 0,
+            votes_abstain: 0,
+            total_voting_power: 1_000,
+            participation_rate: 0,
+            weighted_voting: true,
+            quorum_reached: false,
+            approval_threshold_met: false,
+        };
+        results.record_vote(VoteChoice::For, 200).unwrap();
+        results.record_vote(VoteChoice::Against, 100).unwrap();
+        results.record_vote(VoteChoice::Abstain, 50).unwrap();
+        assert_eq!(results.participation(), 35);
+
+        let quorum = |quorum_method: QuorumMethod, minimum_participation: u8| QuorumRequirements {
+            minimum_participation,
+            approval_threshold: 60,
+            super_majority_required: false,
+            minimum_voting_power: 0,
+            quorum_method,
+        };
+        assert!(quorum(QuorumMethod::Relative, 35).is_quorum_reached(&results));
+        assert!(!quorum(QuorumMethod::Relative, 36).is_quorum_reached(&results));
+        // Against votes do not count toward a weighted quorum
+        assert!(!quorum(QuorumMethod::Weighted, 30).is_quorum_reached(&results));
+        assert!(quorum(QuorumMethod::Weighted, 25).is_quorum_reached(&results));
+        // Opposition (1/3 of decisive votes) raises a dynamic quorum
+        assert!(!quorum(QuorumMethod::Dynamic, 5).is_quorum_reached(&results));
+        assert!(quorum(QuorumMethod::Dynamic, 2).is_quorum_reached(&results));
+        let absolute = QuorumRequirements { minimum_voting_power: 351, ..quorum(QuorumMethod::Absolute, 0) };
+        assert!(!absolute.is_quorum_reached(&results));
+
+        // 200 of 300 decisive votes is 66%
+        assert!(quorum(QuorumMethod::Relative, 0).is_approved(&results));
+        let super_majority = QuorumRequirements { super_majority_required: true, ..quorum(QuorumMethod::Relative, 0) };
+        assert!(!super_majority.is_approved(&results));
+    }
+}
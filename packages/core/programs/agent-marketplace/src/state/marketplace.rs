@@ -6,19 +6,66 @@
  */
 
 use anchor_lang::prelude::*;
-use crate::{ApplicationStatus, ContractStatus, Deliverable};
+use crate::{ApplicationStatus, ContractStatus, Deliverable, PodAIMarketplaceError};
+
+// PDA Seeds
+pub const SERVICE_PURCHASE_SEED: &[u8] = b"service_purchase";
+pub const SERVICE_PURCHASE_VAULT_SEED: &[u8] = b"service_purchase_vault";
+
+/// Time a customer has to confirm a delivery before it can be auto-released (3 days)
+pub const PURCHASE_REVIEW_WINDOW: i64 = 3 * 24 * 60 * 60;
+
+/// Maximum length of a purchase delivery reference
+pub const MAX_DELIVERY_REFERENCE_LENGTH: usize = 256;
+
+pub const JOB_CONTRACT_VAULT_SEED: &[u8] = b"job_contract_vault";
+pub const JOB_COMPLETION_SEED: &[u8] = b"job_completion";
+
+/// Maximum number of milestones on a job contract
+pub const MAX_JOB_MILESTONES: usize = 10;
+pub const MAX_MILESTONE_DESCRIPTION_LENGTH: usize = 128;
+
+/// Revisions an employer can request per milestone before it must be approved
+pub const MAX_MILESTONE_REVISIONS: u8 = 3;
+
+/// Time an employer has to review a milestone submission before it can be auto-released (3 days)
+pub const MILESTONE_REVIEW_WINDOW: i64 = 3 * 24 * 60 * 60;
+
+/// Maximum length of an employer's rejection feedback
+pub const MAX_COMPLETION_FEEDBACK_LENGTH: usize = 500;
 
 // =====================================================
 // MARKETPLACE ENUMS
 // =====================================================
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MilestoneStatus {
+    /// Not yet funded by the employer
+    Pending,
+    /// Escrowed in the contract vault
+    Funded,
+    /// Completion submitted, waiting for the employer
+    Submitted,
+    /// Escrow paid out to the agent
+    Released,
+    /// Not delivered by the contract deadline; escrow returned to the employer
+    Refunded,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum PurchaseStatus {
+    /// Paid into escrow, waiting for the agent to accept
     Pending,
+    /// Accepted by the agent and being worked on
     Confirmed,
+    /// Escrow released to the agent
     Completed,
+    /// Escrow refunded to the customer
     Cancelled,
+    /// Legacy status of unescrowed purchases; escrowed purchases settle as `Completed`
     Paid,
+    /// Delivered by the agent, waiting for confirmation or the review window
+    Delivered,
 }
 
 // =====================================================
@@ -59,7 +106,9 @@ pub struct ServiceListing {
     pub tags: Vec<String>,
     pub is_active: bool,
     pub total_orders: u32,
+    /// Average star rating of reviewed purchases
     pub rating: f64,
+    pub rating_count: u32,
     pub created_at: i64,
     pub updated_at: i64,
     pub bump: u8,
@@ -80,6 +129,15 @@ pub struct ServicePurchase {
     pub status: PurchaseStatus,
     pub purchased_at: i64,
     pub updated_at: i64,
+    /// Customer-chosen nonce that lets the same customer buy a listing repeatedly
+    pub nonce: u64,
+    pub delivery_reference: String,
+    pub accepted_at: Option<i64>,
+    pub delivered_at: Option<i64>,
+    /// Listing tier bought, if the listing is tiered
+    pub tier: Option<u8>,
+    /// Price per unit the current `payment_amount` was charged at
+    pub unit_price: u64,
     pub bump: u8,
 }
 
@@ -122,6 +180,32 @@ pub struct JobApplication {
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct JobMilestoneData {
+    pub description: String,
+    pub amount: u64,
+}
+
+/// One staged payout of a job contract
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct JobMilestone {
+    pub description: String,
+    pub amount: u64,
+    /// Amount that reached the vault after transfer fees
+    pub escrowed: u64,
+    pub status: MilestoneStatus,
+    /// Revisions requested so far; also numbers the next completion
+    pub revisions: u8,
+}
+
+impl JobMilestone {
+    pub const LEN: usize = 4 + MAX_MILESTONE_DESCRIPTION_LENGTH + // description
+        8 + // amount
+        8 + // escrowed
+        1 + // status
+        1; // revisions
+}
+
 #[account]
 pub struct JobContract {
     pub job_posting: Pubkey,
@@ -134,6 +218,9 @@ pub struct JobContract {
     pub status: ContractStatus,
     pub created_at: i64,
     pub updated_at: i64,
+    /// Payout schedule; the amounts sum to `agreed_rate`
+    pub milestones: Vec<JobMilestone>,
+    pub released_amount: u64,
     pub bump: u8,
 }
 
@@ -148,6 +235,11 @@ pub struct JobCompletion {
     pub metadata_uri: String,
     pub completed_at: i64,
     pub is_approved: bool,
+    pub milestone_index: u8,
+    pub revision: u8,
+    /// Employer feedback when a revision was requested
+    pub feedback: String,
+    pub reviewed_at: Option<i64>,
     pub bump: u8,
 }
 
@@ -221,9 +313,17 @@ impl ServiceListing {
         1 + // is_active
         4 + // total_orders
         8 + // rating
+        4 + // rating_count
         8 + // created_at
         8 + // updated_at
         1; // bump
+
+    /// Folds a purchase review into the listing's average rating
+    pub fn record_rating(&mut self, rating: u8) {
+        let count = self.rating_count as f64;
+        self.rating = (self.rating * count + rating as f64) / (count + 1.0);
+        self.rating_count = self.rating_count.saturating_add(1);
+    }
 }
 
 impl ServicePurchase {
@@ -241,7 +341,72 @@ impl ServicePurchase {
         1 + // status
         8 + // purchased_at
         8 + // updated_at
+        8 + // nonce
+        4 + MAX_DELIVERY_REFERENCE_LENGTH + // delivery_reference
+        1 + 8 + // accepted_at (Option<i64>)
+        1 + 8 + // delivered_at (Option<i64>)
+        1 + 1 + // tier (Option<u8>)
+        8 + // unit_price
         1; // bump
+
+    pub fn accept(&mut self, now: i64) -> Result<()> {
+        require!(self.status == PurchaseStatus::Pending, PodAIMarketplaceError::InvalidStatusTransition);
+        require!(now < self.deadline, PodAIMarketplaceError::DeadlinePassed);
+
+        self.status = PurchaseStatus::Confirmed;
+        self.accepted_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn deliver(&mut self, delivery_reference: String, now: i64) -> Result<()> {
+        require!(self.status == PurchaseStatus::Confirmed, PodAIMarketplaceError::InvalidStatusTransition);
+        require!(
+            delivery_reference.len() <= MAX_DELIVERY_REFERENCE_LENGTH,
+            PodAIMarketplaceError::InputTooLong
+        );
+
+        self.status = PurchaseStatus::Delivered;
+        self.delivery_reference = delivery_reference;
+        self.delivered_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Whether the review window after delivery has elapsed
+    pub fn review_window_elapsed(&self, now: i64) -> bool {
+        match (self.status, self.delivered_at) {
+            (PurchaseStatus::Delivered, Some(delivered_at)) => {
+                now >= delivered_at.saturating_add(PURCHASE_REVIEW_WINDOW)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn complete(&mut self, now: i64) -> Result<()> {
+        require!(self.status == PurchaseStatus::Delivered, PodAIMarketplaceError::InvalidStatusTransition);
+
+        self.status = PurchaseStatus::Completed;
+        self.updated_at = now;
+        Ok(())
+    }
+
+    /// Cancels an undelivered purchase
+    ///
+    /// The agent may decline until it delivers. The customer may withdraw
+    /// before acceptance, or afterwards once the deadline has passed.
+    pub fn cancel(&mut self, by_customer: bool, now: i64) -> Result<()> {
+        let allowed = match self.status {
+            PurchaseStatus::Pending => true,
+            PurchaseStatus::Confirmed => !by_customer || now >= self.deadline,
+            _ => false,
+        };
+        require!(allowed, PodAIMarketplaceError::InvalidStatusTransition);
+
+        self.status = PurchaseStatus::Cancelled;
+        self.updated_at = now;
+        Ok(())
+    }
 }
 
 impl JobPosting {
@@ -291,7 +456,138 @@ impl JobContract {
         1 + // status
         8 + // created_at
         8 + // updated_at
+        4 + (MAX_JOB_MILESTONES * JobMilestone::LEN) + // milestones
+        8 + // released_amount
         1; // bump
+
+    /// Builds the milestone schedule, defaulting to a single payout of `agreed_rate`
+    pub fn set_milestones(&mut self, milestones: Vec<JobMilestoneData>) -> Result<()> {
+        require!(milestones.len() <= MAX_JOB_MILESTONES, PodAIMarketplaceError::TooManyMilestones);
+
+        if milestones.is_empty() {
+            self.milestones = vec![JobMilestone {
+                description: String::new(),
+                amount: self.agreed_rate,
+                escrowed: 0,
+                status: MilestoneStatus::Pending,
+                revisions: 0,
+            }];
+            return Ok(());
+        }
+
+        let mut total: u64 = 0;
+        self.milestones = Vec::with_capacity(milestones.len());
+        for milestone in milestones {
+            require!(
+                milestone.description.len() <= MAX_MILESTONE_DESCRIPTION_LENGTH,
+                PodAIMarketplaceError::InputTooLong
+            );
+            require!(milestone.amount > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+            total = total
+                .checked_add(milestone.amount)
+                .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+            self.milestones.push(JobMilestone {
+                description: milestone.description,
+                amount: milestone.amount,
+                escrowed: 0,
+                status: MilestoneStatus::Pending,
+                revisions: 0,
+            });
+        }
+        require!(total == self.agreed_rate, PodAIMarketplaceError::InvalidPaymentAmount);
+        Ok(())
+    }
+
+    pub fn milestone_mut(&mut self, index: u8) -> Result<&mut JobMilestone> {
+        self.milestones
+            .get_mut(index as usize)
+            .ok_or_else(|| error!(PodAIMarketplaceError::MilestoneNotFound))
+    }
+
+    pub fn fund_milestone(&mut self, index: u8, escrowed: u64) -> Result<()> {
+        require!(self.status == ContractStatus::Active, PodAIMarketplaceError::InvalidContractStatus);
+        let milestone = self.milestone_mut(index)?;
+        require!(milestone.status == MilestoneStatus::Pending, PodAIMarketplaceError::InvalidStatusTransition);
+
+        milestone.status = MilestoneStatus::Funded;
+        milestone.escrowed = escrowed;
+        Ok(())
+    }
+
+    /// Marks a funded milestone as submitted and returns the completion's revision number
+    pub fn submit_milestone(&mut self, index: u8) -> Result<u8> {
+        require!(self.status == ContractStatus::Active, PodAIMarketplaceError::InvalidContractStatus);
+        let milestone = self.milestone_mut(index)?;
+        require!(milestone.status == MilestoneStatus::Funded, PodAIMarketplaceError::InvalidStatusTransition);
+
+        milestone.status = MilestoneStatus::Submitted;
+        Ok(milestone.revisions)
+    }
+
+    /// Sends a submitted milestone back to the agent
+    pub fn request_revision(&mut self, index: u8) -> Result<()> {
+        let milestone = self.milestone_mut(index)?;
+        require!(milestone.status == MilestoneStatus::Submitted, PodAIMarketplaceError::InvalidStatusTransition);
+        require!(milestone.revisions < MAX_MILESTONE_REVISIONS, PodAIMarketplaceError::TooManyRevisions);
+
+        milestone.status = MilestoneStatus::Funded;
+        milestone.revisions += 1;
+        Ok(())
+    }
+
+    /// Releases a submitted milestone and returns the escrowed amount to pay out
+    ///
+    /// The contract completes once every milestone is released.
+    pub fn release_milestone(&mut self, index: u8) -> Result<u64> {
+        let milestone = self.milestone_mut(index)?;
+        require!(milestone.status == MilestoneStatus::Submitted, PodAIMarketplaceError::InvalidStatusTransition);
+
+        milestone.status = MilestoneStatus::Released;
+        let escrowed = milestone.escrowed;
+        self.released_amount = self.released_amount
+            .checked_add(escrowed)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+        self.settle_if_finished();
+        Ok(escrowed)
+    }
+
+    /// Withdraws an undelivered milestone once the contract deadline has
+    /// passed and returns the escrowed amount to refund
+    ///
+    /// Unfunded milestones are withdrawn with nothing to refund. Submitted
+    /// milestones are left to the employer's review or the auto-release.
+    pub fn refund_milestone(&mut self, index: u8, now: i64) -> Result<u64> {
+        require!(self.status == ContractStatus::Active, PodAIMarketplaceError::InvalidContractStatus);
+        require!(now > self.deadline, PodAIMarketplaceError::InvalidDeadline);
+        let milestone = self.milestone_mut(index)?;
+        require!(
+            matches!(milestone.status, MilestoneStatus::Pending | MilestoneStatus::Funded),
+            PodAIMarketplaceError::InvalidStatusTransition
+        );
+
+        milestone.status = MilestoneStatus::Refunded;
+        let escrowed = milestone.escrowed;
+        milestone.escrowed = 0;
+
+        self.settle_if_finished();
+        Ok(escrowed)
+    }
+
+    /// Closes the contract once every milestone is released or refunded:
+    /// completed if any work was paid for, cancelled otherwise
+    fn settle_if_finished(&mut self) {
+        let finished = self.milestones
+            .iter()
+            .all(|m| matches!(m.status, MilestoneStatus::Released | MilestoneStatus::Refunded));
+        if finished {
+            self.status = if self.milestones.iter().any(|m| m.status == MilestoneStatus::Released) {
+                ContractStatus::Completed
+            } else {
+                ContractStatus::Cancelled
+            };
+        }
+    }
 }
 
 impl JobCompletion {
@@ -305,5 +601,186 @@ impl JobCompletion {
         4 + 200 + // metadata_uri (max 200 chars)
         8 + // completed_at
         1 + // is_approved
+        1 + // milestone_index
+        1 + // revision
+        4 + MAX_COMPLETION_FEEDBACK_LENGTH + // feedback
+        1 + 8 + // reviewed_at (Option<i64>)
         1; // bump
-}
\ No newline at end of file
+
+    /// Whether the employer's review window for this submission has elapsed
+    pub fn review_window_elapsed(&self, now: i64) -> bool {
+        self.reviewed_at.is_none() && now >= self.completed_at.saturating_add(MILESTONE_REVIEW_WINDOW)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_purchase_lifecycle() {
+        let deadline = 10_000;
+        let new_purchase = || ServicePurchase {
+            customer: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            listing: Pubkey::new_unique(),
+            listing_id: 1,
+            quantity: 1,
+            requirements: Vec::new(),
+            custom_instructions: String::new(),
+            deadline,
+            payment_amount: 1_000_000,
+            payment_token: Pubkey::new_unique(),
+            status: PurchaseStatus::Pending,
+            purchased_at: 0,
+            updated_at: 0,
+            nonce: 7,
+            delivery_reference: String::new(),
+            accepted_at: None,
+            delivered_at: None,
+            tier: None,
+            unit_price: 1_000_000,
+            bump: 255,
+        };
+
+        // Delivery requires acceptance, which requires an open deadline
+        let mut purchase = new_purchase();
+        assert!(purchase.deliver("ipfs://result".to_string(), 100).is_err());
+        assert!(purchase.accept(deadline).is_err());
+        purchase.accept(100).unwrap();
+        assert!(purchase.status == PurchaseStatus::Confirmed);
+        assert!(purchase.deliver("x".repeat(MAX_DELIVERY_REFERENCE_LENGTH + 1), 200).is_err());
+        purchase.deliver("ipfs://result".to_string(), 200).unwrap();
+        assert_eq!(purchase.delivered_at, Some(200));
+
+        // Delivered purchases can no longer be cancelled by either side
+        assert!(purchase.clone().cancel(true, deadline + 1).is_err());
+        assert!(purchase.clone().cancel(false, 300).is_err());
+
+        // Auto-release opens once the review window has passed
+        assert!(!purchase.review_window_elapsed(200 + PURCHASE_REVIEW_WINDOW - 1));
+        assert!(purchase.review_window_elapsed(200 + PURCHASE_REVIEW_WINDOW));
+        purchase.complete(200 + PURCHASE_REVIEW_WINDOW).unwrap();
+        assert!(purchase.status == PurchaseStatus::Completed);
+        assert!(purchase.complete(200 + PURCHASE_REVIEW_WINDOW).is_err(), "Escrow is released once");
+        assert!(!purchase.review_window_elapsed(i64::MAX));
+
+        // The customer can withdraw before acceptance or after a missed deadline
+        let mut pending = new_purchase();
+        pending.cancel(true, 100).unwrap();
+        assert!(pending.status == PurchaseStatus::Cancelled);
+
+        let mut accepted = new_purchase();
+        accepted.accept(100).unwrap();
+        assert!(accepted.cancel(true, deadline - 1).is_err());
+        accepted.cancel(true, deadline).unwrap();
+
+        // The agent can decline until it delivers
+        let mut declined = new_purchase();
+        declined.accept(100).unwrap();
+        declined.cancel(false, 150).unwrap();
+        assert!(declined.status == PurchaseStatus::Cancelled);
+    }
+
+    #[test]
+    fn test_job_contract_milestones() {
+        let milestone = |amount: u64| JobMilestoneData {
+            description: "Stage".to_string(),
+            amount,
+        };
+        let mut contract = JobContract {
+            job_posting: Pubkey::new_unique(),
+            application: Pubkey::new_unique(),
+            employer: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            agreed_rate: 3_000,
+            deadline: 10_000,
+            payment_token: Pubkey::new_unique(),
+            status: ContractStatus::Active,
+            created_at: 0,
+            updated_at: 0,
+            milestones: Vec::new(),
+            released_amount: 0,
+            bump: 255,
+        };
+
+        // The schedule must add up to the agreed rate
+        assert!(contract.set_milestones(vec![milestone(1_000), milestone(1_000)]).is_err());
+        assert!(contract.set_milestones(vec![milestone(1_000); MAX_JOB_MILESTONES + 1]).is_err());
+        contract.set_milestones(Vec::new()).unwrap();
+        assert_eq!(contract.milestones.len(), 1);
+        assert_eq!(contract.milestones[0].amount, 3_000);
+        contract.set_milestones(vec![milestone(1_000), milestone(2_000)]).unwrap();
+
+        // Work can only be submitted against a funded milestone
+        assert!(contract.submit_milestone(0).is_err());
+        assert!(contract.fund_milestone(2, 1_000).is_err());
+        contract.fund_milestone(0, 1_000).unwrap();
+        assert!(contract.fund_milestone(0, 1_000).is_err(), "Milestones are funded once");
+        assert_eq!(contract.submit_milestone(0).unwrap(), 0);
+
+        // Revisions are bounded, after which the milestone can only be approved
+        for revision in 1..=MAX_MILESTONE_REVISIONS {
+            contract.request_revision(0).unwrap();
+            assert_eq!(contract.milestones[0].status, MilestoneStatus::Funded);
+            assert_eq!(contract.submit_milestone(0).unwrap(), revision);
+        }
+        assert!(contract.request_revision(0).is_err());
+        assert_eq!(contract.release_milestone(0).unwrap(), 1_000);
+        assert!(contract.release_milestone(0).is_err(), "Escrow is released once");
+        assert!(contract.status == ContractStatus::Active);
+
+        // Releasing the last milestone completes the contract
+        contract.fund_milestone(1, 1_990).unwrap();
+        contract.submit_milestone(1).unwrap();
+        assert_eq!(contract.release_milestone(1).unwrap(), 1_990);
+        assert_eq!(contract.released_amount, 2_990);
+        assert!(contract.status == ContractStatus::Completed);
+        assert!(contract.fund_milestone(1, 1_990).is_err());
+
+        // Undelivered milestones are refunded once the deadline passes
+        let mut overdue = contract.clone();
+        overdue.status = ContractStatus::Active;
+        overdue.set_milestones(vec![milestone(1_000), milestone(1_000), milestone(1_000)]).unwrap();
+        overdue.fund_milestone(0, 1_000).unwrap();
+        overdue.fund_milestone(1, 1_000).unwrap();
+        overdue.submit_milestone(1).unwrap();
+        assert!(overdue.refund_milestone(0, 10_000).is_err(), "Not before the deadline");
+        assert_eq!(overdue.refund_milestone(0, 10_001).unwrap(), 1_000);
+        assert!(overdue.refund_milestone(0, 10_001).is_err(), "Escrow is refunded once");
+        assert!(overdue.refund_milestone(1, 10_001).is_err(), "Submitted work awaits review");
+        assert_eq!(overdue.refund_milestone(2, 10_001).unwrap(), 0);
+        assert!(overdue.status == ContractStatus::Active);
+        overdue.release_milestone(1).unwrap();
+        assert!(overdue.status == ContractStatus::Completed);
+
+        // A contract with nothing delivered is cancelled
+        let mut abandoned = overdue.clone();
+        abandoned.status = ContractStatus::Active;
+        abandoned.set_milestones(Vec::new()).unwrap();
+        abandoned.refund_milestone(0, 10_001).unwrap();
+        assert!(abandoned.status == ContractStatus::Cancelled);
+
+        // Unreviewed submissions can be released after the review window
+        let mut completion = JobCompletion {
+            contract: Pubkey::new_unique(),
+            agent: contract.agent,
+            employer: contract.employer,
+            deliverables: Vec::new(),
+            work_summary: String::new(),
+            ipfs_hash: String::new(),
+            metadata_uri: String::new(),
+            completed_at: 1_000,
+            is_approved: false,
+            milestone_index: 0,
+            revision: 0,
+            feedback: String::new(),
+            reviewed_at: None,
+            bump: 255,
+        };
+        assert!(!completion.review_window_elapsed(1_000 + MILESTONE_REVIEW_WINDOW - 1));
+        assert!(completion.review_window_elapsed(1_000 + MILESTONE_REVIEW_WINDOW));
+        completion.reviewed_at = Some(2_000);
+        assert!(!completion.review_window_elapsed(1_000 + MILESTONE_REVIEW_WINDOW));
+    }
+}
@@ -0,0 +1,248 @@
+/*!
+ * Metering State Module
+ *
+ * Contains usage meters for per-unit billing between a client and an agent,
+ * and the signed usage reports settled against them.
+ */
+
+use anchor_lang::prelude::*;
+use super::dispute::DisputeCase;
+use super::PodAIMarketplaceError;
+
+// PDA Seeds
+pub const USAGE_METER_SEED: &[u8] = b"usage_meter";
+pub const USAGE_METER_VAULT_SEED: &[u8] = b"usage_meter_vault";
+pub const USAGE_REPORT_SEED: &[u8] = b"usage_report";
+
+// Constants
+pub const MIN_USAGE_DISPUTE_WINDOW: i64 = 60 * 60; // 1 hour
+pub const MAX_USAGE_DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60; // 7 days
+
+/// Prepaid per-unit billing between one client and one agent
+///
+/// The client funds the meter's vault. The agent's reporter key signs usage
+/// increments off-chain; each submitted report can be disputed by the client
+/// until `dispute_window` has passed, after which it is charged at
+/// `units * rate_per_unit` from the vault. Disputed reports are held until an
+/// arbitrator rules how much of them is charged.
+#[account]
+pub struct UsageMeter {
+    pub agent: Pubkey,
+    pub client: Pubkey,
+    /// Client-chosen id, so a client can hold several meters with one agent
+    pub meter_id: u64,
+    /// Key that signs usage reports; the agent owner until delegated
+    pub reporter: Pubkey,
+    pub payment_mint: Pubkey,
+    pub rate_per_unit: u64,
+    pub dispute_window: i64,
+    /// Sequence number of the next report, also the count submitted so far
+    pub next_sequence: u64,
+    /// Reports submitted but not yet settled
+    pub open_reports: u32,
+    pub units_reported: u64,
+    pub units_settled: u64,
+    pub units_disputed: u64,
+    pub total_charged: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl UsageMeter {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        32 + // client
+        8 + // meter_id
+        32 + // reporter
+        32 + // payment_mint
+        8 + // rate_per_unit
+        8 + // dispute_window
+        8 + // next_sequence
+        4 + // open_reports
+        8 + // units_reported
+        8 + // units_settled
+        8 + // units_disputed
+        8 + // total_charged
+        8 + // created_at
+        1; // bump
+
+    /// Message the reporter signs for a usage increment
+    ///
+    /// Binds the meter and the report's sequence number so a signature can't
+    /// be replayed on another meter or reused for another report, and the
+    /// rate so the agent explicitly agrees to the client's terms. The meter's
+    /// creation time ties it to one instance of the meter, since sequence
+    /// numbers restart when a closed meter's address is opened again.
+    pub fn report_message(
+        meter: &Pubkey,
+        created_at: i64,
+        sequence: u64,
+        units: u64,
+        rate_per_unit: u64,
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 + 8 + 8 + 8 + 8);
+        message.extend_from_slice(meter.as_ref());
+        message.extend_from_slice(&created_at.to_le_bytes());
+        message.extend_from_slice(&sequence.to_le_bytes());
+        message.extend_from_slice(&units.to_le_bytes());
+        message.extend_from_slice(&rate_per_unit.to_le_bytes());
+        message
+    }
+
+    pub fn charge_for(&self, units: u64) -> Result<u64> {
+        units
+            .checked_mul(self.rate_per_unit)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow.into())
+    }
+}
+
+#[account]
+pub struct UsageReport {
+    pub meter: Pubkey,
+    pub sequence: u64,
+    pub units: u64,
+    /// Charge locked in at submission, or the arbitrator's award once a
+    /// dispute is resolved
+    pub amount: u64,
+    /// Pays the report's rent and receives it back at settlement
+    pub submitter: Pubkey,
+    pub reported_at: i64,
+    pub disputed: bool,
+    /// Set once an arbitrator has ruled on a disputed report
+    pub resolved: bool,
+    pub bump: u8,
+}
+
+impl UsageReport {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // meter
+        8 + // sequence
+        8 + // units
+        8 + // amount
+        32 + // submitter
+        8 + // reported_at
+        1 + // disputed
+        1 + // resolved
+        1; // bump
+
+    pub fn dispute_window_open(&self, dispute_window: i64, now: i64) -> bool {
+        now < self.reported_at.saturating_add(dispute_window)
+    }
+
+    /// Whether a disputed report is still waiting for an arbitrator's ruling
+    pub fn awaiting_ruling(&self) -> bool {
+        self.disputed && !self.resolved
+    }
+
+    /// Records an arbitrator's ruling on a disputed report
+    ///
+    /// `charge_bps` is the share of the reported charge the client pays, in
+    /// basis points. Returns the amount now charged at settlement.
+    pub fn resolve_dispute(&mut self, charge_bps: u16) -> Result<u64> {
+        require!(self.awaiting_ruling(), PodAIMarketplaceError::InvalidDisputeStatus);
+        let (charged, _) = DisputeCase::calculate_split(self.amount, charge_bps)?;
+        self.amount = charged;
+        self.resolved = true;
+        Ok(charged)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_report_signature_message() {
+        let meter_key = Pubkey::new_unique();
+        let reporter = Pubkey::new_unique();
+        let message = UsageMeter::report_message(&meter_key, 1_000, 7, 1_500, 20);
+        assert_eq!(message.len(), 64);
+        assert_ne!(message, UsageMeter::report_message(&meter_key, 1_000, 8, 1_500, 20));
+        // A meter reopened at the same address doesn't accept old signatures
+        assert_ne!(message, UsageMeter::report_message(&meter_key, 2_000, 7, 1_500, 20));
+
+        // Ed25519 program layout: count, padding, one offsets entry, then
+        // public key, signature and message
+        let pubkey_offset: u16 = 16;
+        let signature_offset: u16 = pubkey_offset + 32;
+        let message_offset: u16 = signature_offset + 64;
+        let build = |instruction_index: u16| {
+            let mut data = vec![1u8, 0];
+            for value in [
+                signature_offset, instruction_index,
+                pubkey_offset, instruction_index,
+                message_offset, message.len() as u16, instruction_index,
+            ] {
+                data.extend_from_slice(&value.to_le_bytes());
+            }
+            data.extend_from_slice(reporter.as_ref());
+            data.extend_from_slice(&[0u8; 64]);
+            data.extend_from_slice(&message);
+            data
+        };
+
+        let data = build(u16::MAX);
+        let (signer, signed) = crate::security::parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(signer, reporter);
+        assert_eq!(signed, &message[..]);
+
+        // Data borrowed from another instruction, multiple signatures and
+        // truncated data are rejected
+        assert!(crate::security::parse_ed25519_instruction(&build(0)).is_err());
+        let mut multi = data.clone();
+        multi[0] = 2;
+        assert!(crate::security::parse_ed25519_instruction(&multi).is_err());
+        assert!(crate::security::parse_ed25519_instruction(&data[..data.len() - 1]).is_err());
+
+        let meter = UsageMeter {
+            agent: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            meter_id: 0,
+            reporter,
+            payment_mint: Pubkey::new_unique(),
+            rate_per_unit: 20,
+            dispute_window: MIN_USAGE_DISPUTE_WINDOW,
+            next_sequence: 0,
+            open_reports: 0,
+            units_reported: 0,
+            units_settled: 0,
+            units_disputed: 0,
+            total_charged: 0,
+            created_at: 0,
+            bump: 255,
+        };
+        assert_eq!(meter.charge_for(1_500).unwrap(), 30_000);
+        assert!(meter.charge_for(u64::MAX).is_err());
+
+        let mut report = UsageReport {
+            meter: meter_key,
+            sequence: 7,
+            units: 1_500,
+            amount: 30_000,
+            submitter: Pubkey::new_unique(),
+            reported_at: 1_000,
+            disputed: false,
+            resolved: false,
+            bump: 255,
+        };
+        assert!(report.dispute_window_open(meter.dispute_window, 1_000 + MIN_USAGE_DISPUTE_WINDOW - 1));
+        assert!(!report.dispute_window_open(meter.dispute_window, 1_000 + MIN_USAGE_DISPUTE_WINDOW));
+
+        // Only disputed reports can be ruled on, and only once
+        assert!(report.resolve_dispute(5_000).is_err());
+        report.disputed = true;
+        assert!(report.awaiting_ruling());
+        assert!(report.resolve_dispute(10_001).is_err());
+        assert_eq!(report.resolve_dispute(2_500).unwrap(), 7_500);
+        assert_eq!(report.amount, 7_500);
+        assert!(!report.awaiting_ruling());
+        assert!(report.resolve_dispute(10_000).is_err());
+
+        let mut buf = Vec::new();
+        meter.try_serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), UsageMeter::LEN);
+        buf.clear();
+        report.try_serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), UsageReport::LEN);
+    }
+}
@@ -9,6 +9,7 @@
 pub mod agent;
 
 // Additional modules
+pub mod a2a_protocol;
 pub mod analytics;
 pub mod auction;
 pub mod audit;
@@ -23,19 +24,23 @@ pub mod governance;
 pub mod incentives;
 pub mod marketplace;
 pub mod message;
+pub mod metering;
 pub mod negotiation;
 pub mod pricing;
+pub mod protocol_config;
 pub mod replication;
 pub mod reputation;
 pub mod risk_management;
 pub mod royalty;
 pub mod security_governance;
 pub mod stubs;
+pub mod subscription;
 pub mod work_order;
 pub mod user_registry;
 
 // Re-export all types
 pub use agent::*;
+pub use a2a_protocol::*;
 pub use analytics::*;
 pub use auction::*;
 pub use audit::*;
@@ -50,14 +55,17 @@ pub use governance::*;
 pub use incentives::*;
 pub use marketplace::*;
 pub use message::*;
+pub use metering::*;
 pub use negotiation::*;
 pub use pricing::*;
+pub use protocol_config::*;
 pub use replication::*;
 pub use reputation::*;
 pub use risk_management::*;
 pub use royalty::*;
 pub use security_governance::*;
 pub use stubs::*;
+pub use subscription::*;
 pub use work_order::*;
 pub use user_registry::*;
 
@@ -2,6 +2,10 @@
  * Negotiation State Module
  * 
  * Contains negotiation-related state structures.
+ *
+ * Either the client (buyer) or the provider can initiate a negotiation; the
+ * client side is recorded at initiation. Accepting a negotiation converts it
+ * into a work order funded by the client.
  */
 
 use anchor_lang::prelude::*;
@@ -19,6 +23,8 @@ pub struct NegotiationMessage {
 pub struct NegotiationChatbot {
     pub initiator: Pubkey,
     pub counterparty: Pubkey,
+    /// Chosen by the initiator so a pair can hold more than one negotiation
+    pub negotiation_id: u64,
     pub initial_offer: u64,
     pub current_offer: u64,
     pub status: super::auction::NegotiationStatus,
@@ -28,6 +34,10 @@ pub struct NegotiationChatbot {
     pub auto_accept_threshold: u64,
     pub created_at: i64,
     pub last_activity: i64,
+    pub payment_token: Pubkey,       // Mint the agreed price is paid in
+    pub last_offer_by: Pubkey,       // Party that made `current_offer`
+    pub work_order: Option<Pubkey>,  // Work order created on acceptance
+    pub client: Pubkey,              // Party that funds the work order
     pub bump: u8,
 }
 
@@ -35,6 +45,7 @@ impl NegotiationChatbot {
     pub const LEN: usize = 8 + // discriminator
         32 + // initiator
         32 + // counterparty
+        8 + // negotiation_id
         8 + // initial_offer
         8 + // current_offer
         1 + // status
@@ -44,16 +55,22 @@ impl NegotiationChatbot {
         8 + // auto_accept_threshold
         8 + // created_at
         8 + // last_activity
+        32 + // payment_token
+        32 + // last_offer_by
+        1 + 32 + // work_order (Option<Pubkey>)
+        32 + // client
         1; // bump
 
     pub fn initialize(
         &mut self,
         initiator: Pubkey,
         counterparty: Pubkey,
+        negotiation_id: u64,
         initial_offer: u64,
         auto_accept_threshold: u64,
         negotiation_deadline: i64,
         terms: Vec<String>,
+        payment_token: Pubkey,
         bump: u8,
     ) -> Result<()> {
         let clock = Clock::get()?;
@@ -67,6 +84,7 @@ impl NegotiationChatbot {
         
         self.initiator = initiator;
         self.counterparty = counterparty;
+        self.negotiation_id = negotiation_id;
         self.initial_offer = initial_offer;
         self.current_offer = initial_offer;
         self.status = super::auction::NegotiationStatus::InitialOffer;
@@ -76,12 +94,16 @@ impl NegotiationChatbot {
         self.auto_accept_threshold = auto_accept_threshold;
         self.created_at = clock.unix_timestamp;
         self.last_activity = clock.unix_timestamp;
+        self.payment_token = payment_token;
+        self.last_offer_by = initiator;
+        self.work_order = None;
+        self.client = initiator;
         self.bump = bump;
         
         Ok(())
     }
 
-    pub fn make_counter_offer(&mut self, offer: u64) -> Result<()> {
+    pub fn make_counter_offer(&mut self, sender: Pubkey, offer: u64) -> Result<()> {
         let clock = Clock::get()?;
         
         require!(clock.unix_timestamp < self.negotiation_deadline, PodAIMarketplaceError::NegotiationExpired);
@@ -89,48 +111,219 @@ impl NegotiationChatbot {
             matches!(self.status, super::auction::NegotiationStatus::InitialOffer | super::auction::NegotiationStatus::CounterOffer),
             PodAIMarketplaceError::InvalidNegotiationStatus
         );
+        require!(self.is_participant(&sender), PodAIMarketplaceError::UnauthorizedAccess);
+        // Parties alternate; nobody counters their own standing offer
+        require!(sender != self.last_offer_by, PodAIMarketplaceError::UnauthorizedAccess);
         require!(self.counter_offers.len() < super::auction::MAX_COUNTER_OFFERS, PodAIMarketplaceError::TooManyCounterOffers);
         
-        self.counter_offers.push(offer);
+        self.counter_offers.push(self.current_offer);
         self.current_offer = offer;
+        self.last_offer_by = sender;
         self.status = super::auction::NegotiationStatus::CounterOffer;
         self.last_activity = clock.unix_timestamp;
         
         Ok(())
     }
 
-    pub fn accept_offer(&mut self) -> Result<()> {
-        let clock = Clock::get()?;
-        
-        require!(clock.unix_timestamp < self.negotiation_deadline, PodAIMarketplaceError::NegotiationExpired);
-        require!(
-            matches!(self.status, super::auction::NegotiationStatus::InitialOffer | super::auction::NegotiationStatus::CounterOffer),
-            PodAIMarketplaceError::InvalidNegotiationStatus
-        );
+    /// Accepts the standing offer on behalf of `acceptor`
+    ///
+    /// Open offers can only be accepted by the party that did not make them,
+    /// before the deadline. Auto-accepted offers are already agreed and just
+    /// need funding, so either party may convert them at any time.
+    pub fn accept_offer(&mut self, acceptor: Pubkey, now: i64) -> Result<()> {
+        require!(self.is_participant(&acceptor), PodAIMarketplaceError::UnauthorizedAccess);
+        if self.status != super::auction::NegotiationStatus::AutoAccepted {
+            require!(self.is_open(), PodAIMarketplaceError::InvalidNegotiationStatus);
+            require!(now < self.negotiation_deadline, PodAIMarketplaceError::NegotiationExpired);
+            require!(acceptor != self.last_offer_by, PodAIMarketplaceError::UnauthorizedAccess);
+        }
         
         self.status = super::auction::NegotiationStatus::Accepted;
-        self.last_activity = clock.unix_timestamp;
+        self.last_activity = now;
         
         Ok(())
     }
 
-    pub fn reject_offer(&mut self) -> Result<()> {
-        let clock = Clock::get()?;
+    pub fn reject_offer(&mut self, rejector: Pubkey, now: i64) -> Result<()> {
+        require!(self.is_open(), PodAIMarketplaceError::InvalidNegotiationStatus);
+        require!(self.is_participant(&rejector), PodAIMarketplaceError::UnauthorizedAccess);
         
         self.status = super::auction::NegotiationStatus::Rejected;
-        self.last_activity = clock.unix_timestamp;
+        self.last_activity = now;
         
         Ok(())
     }
 
-    pub fn check_expiry(&mut self) -> Result<()> {
-        let clock = Clock::get()?;
-        
-        if clock.unix_timestamp >= self.negotiation_deadline && 
-           matches!(self.status, super::auction::NegotiationStatus::InitialOffer | super::auction::NegotiationStatus::CounterOffer) {
+    pub fn check_expiry(&mut self, now: i64) {
+        if now >= self.negotiation_deadline && self.is_open() {
             self.status = super::auction::NegotiationStatus::Expired;
         }
-        
-        Ok(())
     }
-}
\ No newline at end of file
+
+    /// Whether the negotiation can still be countered, rejected or expired
+    ///
+    /// Auto-accepted negotiations are agreed and can only be accepted.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            super::auction::NegotiationStatus::InitialOffer
+                | super::auction::NegotiationStatus::CounterOffer
+        )
+    }
+
+    pub fn is_participant(&self, party: &Pubkey) -> bool {
+        *party == self.initiator || *party == self.counterparty
+    }
+
+    /// The party delivering the work; whichever side is not the client
+    pub fn provider(&self) -> Pubkey {
+        if self.client == self.initiator {
+            self.counterparty
+        } else {
+            self.initiator
+        }
+    }
+
+    /// Whether a counter-offer of `offer` by `sender` meets the initiator's
+    /// auto-accept threshold
+    ///
+    /// Only the counterparty's offers are checked. A client initiator accepts
+    /// prices at or below the threshold, a provider initiator prices at or above it.
+    pub fn meets_auto_accept(&self, sender: Pubkey, offer: u64) -> bool {
+        if sender != self.counterparty {
+            return false;
+        }
+        if self.client == self.initiator {
+            offer <= self.auto_accept_threshold
+        } else {
+            offer >= self.auto_accept_threshold
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::NegotiationStatus;
+
+    #[test]
+    fn test_negotiation_open_states() {
+        let client = Pubkey::new_unique();
+        let provider = Pubkey::new_unique();
+        let mut negotiation = NegotiationChatbot {
+            initiator: client,
+            counterparty: provider,
+            negotiation_id: 1,
+            initial_offer: 5_000,
+            current_offer: 5_000,
+            status: NegotiationStatus::InitialOffer,
+            negotiation_deadline: 1641081600,
+            terms: vec!["Deliver in 3 days".to_string()],
+            counter_offers: Vec::new(),
+            auto_accept_threshold: 4_000,
+            created_at: 1640995200,
+            last_activity: 1640995200,
+            payment_token: Pubkey::new_unique(),
+            last_offer_by: client,
+            work_order: None,
+            client,
+            bump: 255,
+        };
+
+        assert!(negotiation.is_participant(&client));
+        assert!(negotiation.is_participant(&provider));
+        assert!(!negotiation.is_participant(&Pubkey::new_unique()));
+
+        for status in [NegotiationStatus::InitialOffer, NegotiationStatus::CounterOffer] {
+            negotiation.status = status;
+            assert!(negotiation.is_open());
+        }
+        for status in [
+            NegotiationStatus::AutoAccepted,
+            NegotiationStatus::Accepted,
+            NegotiationStatus::Rejected,
+            NegotiationStatus::Expired,
+        ] {
+            negotiation.status = status;
+            assert!(!negotiation.is_open());
+        }
+    }
+
+    fn provider_initiated(provider: Pubkey, client: Pubkey) -> NegotiationChatbot {
+        NegotiationChatbot {
+            initiator: provider,
+            counterparty: client,
+            negotiation_id: 1,
+            initial_offer: 8_000,
+            current_offer: 8_000,
+            status: NegotiationStatus::InitialOffer,
+            negotiation_deadline: 1_000,
+            terms: Vec::new(),
+            counter_offers: Vec::new(),
+            auto_accept_threshold: 6_000,
+            created_at: 0,
+            last_activity: 0,
+            payment_token: Pubkey::new_unique(),
+            last_offer_by: provider,
+            work_order: None,
+            client,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_provider_initiated_negotiation_sides() {
+        let provider = Pubkey::new_unique();
+        let client = Pubkey::new_unique();
+        let mut negotiation = provider_initiated(provider, client);
+
+        // The recorded client funds the work order regardless of who initiated
+        assert_eq!(negotiation.provider(), provider);
+        negotiation.client = provider;
+        assert_eq!(negotiation.provider(), client);
+        negotiation.client = client;
+
+        // A provider initiator auto-accepts client offers at or above the threshold
+        assert!(negotiation.meets_auto_accept(client, 6_000));
+        assert!(!negotiation.meets_auto_accept(client, 5_999));
+        assert!(!negotiation.meets_auto_accept(provider, 9_000), "Own offers never auto-accept");
+
+        // A client initiator auto-accepts provider offers at or below it
+        negotiation.client = provider;
+        assert!(negotiation.meets_auto_accept(client, 6_000));
+        assert!(!negotiation.meets_auto_accept(client, 6_001));
+    }
+
+    #[test]
+    fn test_auto_accepted_negotiation_can_only_be_accepted() {
+        let provider = Pubkey::new_unique();
+        let client = Pubkey::new_unique();
+        let mut negotiation = provider_initiated(provider, client);
+        negotiation.status = NegotiationStatus::AutoAccepted;
+        negotiation.last_offer_by = client;
+
+        // Rejecting or expiring an agreed deal is not possible
+        assert!(negotiation.reject_offer(client, 500).is_err());
+        negotiation.check_expiry(5_000);
+        assert_eq!(negotiation.status, NegotiationStatus::AutoAccepted);
+
+        // Either party may convert it, even after the deadline
+        assert!(negotiation.accept_offer(Pubkey::new_unique(), 500).is_err());
+        negotiation.accept_offer(client, 5_000).unwrap();
+        assert_eq!(negotiation.status, NegotiationStatus::Accepted);
+        assert!(negotiation.accept_offer(client, 500).is_err(), "Accepted only once");
+    }
+
+    #[test]
+    fn test_open_offer_acceptance() {
+        let provider = Pubkey::new_unique();
+        let client = Pubkey::new_unique();
+        let mut negotiation = provider_initiated(provider, client);
+
+        // Nobody accepts their own offer, and not past the deadline
+        assert!(negotiation.accept_offer(provider, 500).is_err());
+        assert!(negotiation.accept_offer(client, 1_000).is_err());
+        negotiation.accept_offer(client, 999).unwrap();
+        assert_eq!(negotiation.status, NegotiationStatus::Accepted);
+    }
+}
@@ -5,6 +5,21 @@
  */
 
 use anchor_lang::prelude::*;
+use super::PodAIMarketplaceError;
+
+// PDA Seeds
+pub const DYNAMIC_PRICING_SEED: &[u8] = b"dynamic_pricing";
+pub const LISTING_TIERS_SEED: &[u8] = b"listing_tiers";
+
+// Constants
+/// Fixed-point unit for pricing multipliers, elasticities and scores
+pub const PRICING_BPS: u64 = 10_000;
+/// Upper bound for elasticities, multipliers and oracle model output (10x)
+pub const MAX_PRICING_MULTIPLIER: u32 = 100_000;
+/// Demand and load ratios are capped at 10x their baseline
+const MAX_DEMAND_RATIO: i128 = 10 * PRICING_BPS as i128;
+/// Hour-of-day demand profile is halved once it holds this many units
+const SEASONAL_DECAY_THRESHOLD: u64 = 1_000_000;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum PricingModel {
@@ -83,6 +98,25 @@ pub struct SupplyMetrics {
     pub last_updated: i64,
 }
 
+/// Market inputs posted by the engine's oracle; `None` leaves a value unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PricingObservation {
+    /// Units demanded this window, replacing the program's own order count
+    pub demand: Option<u64>,
+    /// Units the agent can serve per update window; zero if unknown
+    pub supply: Option<u64>,
+    pub market_average_price: Option<u64>,
+    /// Multiplier produced by an off-chain model, for `MLOptimized`
+    pub model_multiplier: Option<u32>,
+    /// Off-chain service performance score (0-10000), for `PerformanceBased`
+    pub performance_score: Option<u32>,
+}
+
+/// Per-agent pricing engine quoted by the agent's service listings
+///
+/// Demand is counted from the agent's own purchases each update window,
+/// unless the oracle reports it. All prices are computed in integer basis
+/// points so every validator derives the same quote.
 #[account]
 pub struct DynamicPricingEngine {
     pub engine_id: u64,
@@ -92,6 +126,18 @@ pub struct DynamicPricingEngine {
     pub current_price: u64,
     pub last_updated: i64,
     pub is_active: bool,
+    /// Key allowed to post observations besides the agent owner; default if none
+    pub oracle: Pubkey,
+    /// Units ordered since the last update
+    pub window_demand: u64,
+    /// Moving average of demand per window, in basis points of a unit
+    pub average_demand: u64,
+    pub supply: u64,
+    pub market_average_price: u64,
+    pub model_multiplier: u32,
+    pub performance_score: u32,
+    /// Units ordered in each UTC hour of the day, for `Seasonal`
+    pub hourly_demand: [u64; 24],
     pub bump: u8,
 }
 
@@ -217,10 +263,18 @@ impl DynamicPricingEngine {
         8 + // engine_id
         32 + // agent
         1 + // algorithm (enum)
-        1 + 8 + 8 + 8 + (8 + 8) + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + // config
+        1 + 8 + 8 + 8 + (8 + 8) + 8 + 8 + 4 + 4 + 4 + 4 + 4 + 4 + 8 + 1 + // config
         8 + // current_price
         8 + // last_updated
         1 + // is_active
+        32 + // oracle
+        8 + // window_demand
+        8 + // average_demand
+        8 + // supply
+        8 + // market_average_price
+        4 + // model_multiplier
+        4 + // performance_score
+        8 * 24 + // hourly_demand
         1; // bump
 
     pub fn initialize(
@@ -238,49 +292,201 @@ impl DynamicPricingEngine {
         self.current_price = self.config.base_price;
         self.last_updated = current_time;
         self.is_active = true;
+        self.oracle = Pubkey::default();
+        self.window_demand = 0;
+        self.average_demand = 0;
+        self.supply = 0;
+        self.market_average_price = 0;
+        self.model_multiplier = PRICING_BPS as u32;
+        self.performance_score = (PRICING_BPS / 2) as u32;
+        self.hourly_demand = [0; 24];
         self.bump = bump;
     }
 
+    /// Whether listings of the agent are priced by this engine
+    pub fn is_quoting(&self) -> bool {
+        self.is_active && self.config.enabled
+    }
+
     pub fn update_price(&mut self, new_price: u64, current_time: i64) {
         self.current_price = new_price.max(self.config.min_price).min(self.config.max_price);
         self.last_updated = current_time;
     }
 
-    pub fn calculate_dynamic_price(&self, demand: u64, supply: u64) -> u64 {
-        let base_price = self.config.base_price;
-        
-        // Calculate demand/supply ratio in basis points
-        let demand_ratio = if supply > 0 { 
-            (demand * 10000) / supply
-        } else { 
-            10000 // 100% in basis points
+    /// Counts a purchase towards this window's demand and the hourly profile
+    pub fn record_order(&mut self, quantity: u64, current_time: i64) {
+        self.window_demand = self.window_demand.saturating_add(quantity);
+        let hour = current_time.rem_euclid(86_400) as usize / 3600;
+        self.hourly_demand[hour] = self.hourly_demand[hour].saturating_add(quantity);
+        if self.hourly_demand.iter().sum::<u64>() >= SEASONAL_DECAY_THRESHOLD {
+            for bucket in self.hourly_demand.iter_mut() {
+                *bucket /= 2;
+            }
+        }
+    }
+
+    pub fn apply_observation(&mut self, observation: &PricingObservation) -> Result<()> {
+        if let Some(multiplier) = observation.model_multiplier {
+            require!(
+                multiplier <= MAX_PRICING_MULTIPLIER,
+                PodAIMarketplaceError::InvalidPriceConfiguration
+            );
+            self.model_multiplier = multiplier;
+        }
+        if let Some(score) = observation.performance_score {
+            require!(score as u64 <= PRICING_BPS, PodAIMarketplaceError::InvalidPercentage);
+            self.performance_score = score;
+        }
+        if let Some(demand) = observation.demand {
+            self.window_demand = demand;
+        }
+        if let Some(supply) = observation.supply {
+            self.supply = supply;
+        }
+        if let Some(price) = observation.market_average_price {
+            self.market_average_price = price;
+        }
+        Ok(())
+    }
+
+    /// Demand this window scaled to one `update_frequency`, so a late update
+    /// does not read as a demand spike
+    fn demand_rate(&self, current_time: i64) -> u64 {
+        let window = self.config.update_frequency.max(1) as u128;
+        let elapsed = (current_time.saturating_sub(self.last_updated).max(0) as u128).max(window);
+        (self.window_demand as u128 * window / elapsed) as u64
+    }
+
+    /// Demand relative to its moving average, in basis points
+    fn demand_ratio(&self, demand: u64) -> i128 {
+        if self.average_demand == 0 {
+            return PRICING_BPS as i128;
+        }
+        (demand as i128 * (PRICING_BPS * PRICING_BPS) as i128 / self.average_demand as i128)
+            .min(MAX_DEMAND_RATIO)
+    }
+
+    /// Demand relative to declared supply, or to its moving average when
+    /// supply is unknown, in basis points
+    fn load_ratio(&self, demand: u64) -> i128 {
+        if self.supply == 0 {
+            return self.demand_ratio(demand);
+        }
+        (demand as i128 * PRICING_BPS as i128 / self.supply as i128).min(MAX_DEMAND_RATIO)
+    }
+
+    /// Elasticity-weighted price pressure in basis points; zero is neutral
+    fn market_pressure(&self, demand: u64) -> i128 {
+        let bps = PRICING_BPS as i128;
+        let demand_pressure =
+            (self.demand_ratio(demand) - bps) * self.config.demand_elasticity as i128 / bps;
+        let supply_pressure = if self.supply > 0 {
+            (self.load_ratio(demand) - bps) * self.config.supply_elasticity as i128 / bps
+        } else {
+            0
         };
-        
-        let price_adjustment = match self.algorithm {
-            PricingAlgorithm::DemandBased => {
-                let ratio_diff = if demand_ratio > 10000 {
-                    demand_ratio - 10000
+        demand_pressure + supply_pressure
+    }
+
+    /// Share of hour-of-day demand falling in the current hour, relative to
+    /// an even spread, in basis points; zero is neutral
+    fn seasonal_deviation(&self, current_time: i64) -> i128 {
+        let total: u64 = self.hourly_demand.iter().sum();
+        if total == 0 {
+            return 0;
+        }
+        let hour = current_time.rem_euclid(86_400) as usize / 3600;
+        self.hourly_demand[hour] as i128 * 24 * PRICING_BPS as i128 / total as i128
+            - PRICING_BPS as i128
+    }
+
+    /// Price the configured algorithm targets, before volatility limits
+    pub fn target_price(&self, reputation_score: u32, current_time: i64) -> u64 {
+        let bps = PRICING_BPS as i128;
+        let base = self.config.base_price as i128;
+        let demand = self.demand_rate(current_time);
+        let pressure = self.market_pressure(demand);
+        let neutral = bps / 2;
+
+        let target = match self.algorithm {
+            PricingAlgorithm::Linear => base * (bps + pressure) / bps,
+            PricingAlgorithm::Exponential => base * exp_bps(pressure) / bps,
+            PricingAlgorithm::Logarithmic => base * (bps + ln_bps((bps + pressure).max(1))) / bps,
+            PricingAlgorithm::Sigmoid => {
+                // Logistic curve through the base price, saturating at the bounds
+                let sigmoid = bps * bps / (bps + exp_bps(-pressure));
+                let swing = 2 * sigmoid - bps;
+                if swing >= 0 {
+                    base + (self.config.max_price as i128 - base) * swing / bps
                 } else {
-                    0
-                };
-                10000 + (ratio_diff * self.config.demand_multiplier as u64) / 10000
-            },
+                    base + (base - self.config.min_price as i128) * swing / bps
+                }
+            }
+            PricingAlgorithm::MarketBased => {
+                base * (bps + (self.load_ratio(demand) - bps) * self.config.supply_elasticity as i128 / bps) / bps
+            }
+            PricingAlgorithm::MLOptimized => base * self.model_multiplier as i128 / bps,
+            PricingAlgorithm::DemandBased => {
+                let excess = (self.demand_ratio(demand) - bps).max(0);
+                base * (bps + excess * self.config.demand_multiplier as i128 / bps) / bps
+            }
+            PricingAlgorithm::ReputationBased => {
+                let deviation = reputation_score as i128 - neutral;
+                base * (bps + deviation * self.config.reputation_multiplier as i128 / bps) / bps
+            }
             PricingAlgorithm::SurgePricing => {
-                if demand_ratio > 15000 { // 150% in basis points
-                    let ratio_diff = demand_ratio - 10000;
-                    10000 + (ratio_diff * self.config.surge_multiplier as u64) / 10000
+                let load = self.load_ratio(demand);
+                if load > bps * 3 / 2 {
+                    base * (bps + (load - bps) * self.config.surge_multiplier as i128 / bps) / bps
                 } else {
-                    10000
+                    base
                 }
-            },
-            PricingAlgorithm::MarketBased => {
-                demand_ratio
-            },
-            _ => 10000 // 100% in basis points
+            }
+            PricingAlgorithm::MarketAverage => {
+                if self.market_average_price > 0 {
+                    self.market_average_price as i128
+                } else {
+                    self.current_price as i128
+                }
+            }
+            PricingAlgorithm::PerformanceBased => {
+                let deviation = self.performance_score as i128 - neutral;
+                base * (bps + deviation * self.config.reputation_multiplier as i128 / bps) / bps
+            }
+            PricingAlgorithm::Seasonal => {
+                let deviation = self.seasonal_deviation(current_time);
+                base * (bps + deviation * self.config.demand_elasticity as i128 / bps) / bps
+            }
         };
 
-        let new_price = (base_price * price_adjustment) / 10000;
-        new_price.max(self.config.min_price).min(self.config.max_price)
+        target.clamp(0, u64::MAX as i128) as u64
+    }
+
+    /// Moves the price towards the algorithm's target and starts a new window
+    ///
+    /// A single update moves at most `volatility_threshold` of the current
+    /// price (unlimited when zero), and the result always stays within
+    /// `min_price..=max_price`. Returns the demand rate the update used.
+    pub fn reprice(&mut self, reputation_score: u32, current_time: i64) -> u64 {
+        let demand = self.demand_rate(current_time);
+        let mut new_price = self.target_price(reputation_score, current_time);
+
+        if self.config.volatility_threshold > 0 {
+            let max_step = (self.current_price as u128 * self.config.volatility_threshold as u128
+                / PRICING_BPS as u128) as u64;
+            new_price = new_price
+                .max(self.current_price.saturating_sub(max_step))
+                .min(self.current_price.saturating_add(max_step));
+        }
+
+        self.average_demand = if self.average_demand == 0 {
+            demand.saturating_mul(PRICING_BPS)
+        } else {
+            ((self.average_demand as u128 * 9 + demand as u128 * PRICING_BPS as u128) / 10) as u64
+        };
+        self.window_demand = 0;
+        self.update_price(new_price, current_time);
+        demand
     }
 
     pub fn should_update(&self, current_time: i64) -> bool {
@@ -289,12 +495,90 @@ impl DynamicPricingEngine {
     }
 }
 
+const FIXED_ONE: i128 = 1_000_000_000_000;
+const FIXED_LN2: i128 = 693_147_180_560;
+
+/// e^(x / 10000) in basis points, for exponents within ±4
+pub fn exp_bps(x: i128) -> i128 {
+    let x = x.clamp(-4 * PRICING_BPS as i128, 4 * PRICING_BPS as i128) * (FIXED_ONE / PRICING_BPS as i128);
+    // Taylor series on x / 64, then square back up six times
+    let y = x / 64;
+    let mut sum = FIXED_ONE;
+    let mut term = FIXED_ONE;
+    for n in 1..=8 {
+        term = term * y / FIXED_ONE / n;
+        sum += term;
+    }
+    for _ in 0..6 {
+        sum = sum * sum / FIXED_ONE;
+    }
+    sum / (FIXED_ONE / PRICING_BPS as i128)
+}
+
+/// ln(x / 10000) in basis points, for positive `x`
+pub fn ln_bps(x: i128) -> i128 {
+    let mut m = x.max(1) * (FIXED_ONE / PRICING_BPS as i128);
+    let mut k: i128 = 0;
+    while m >= 2 * FIXED_ONE {
+        m /= 2;
+        k += 1;
+    }
+    while m < FIXED_ONE {
+        m *= 2;
+        k -= 1;
+    }
+    // ln(m) = 2 * atanh((m - 1) / (m + 1)), with m in [1, 2)
+    let z = (m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE);
+    let z2 = z * z / FIXED_ONE;
+    let mut power = z;
+    let mut series = 0;
+    for n in 0..12 {
+        series += power / (2 * n + 1);
+        power = power * z2 / FIXED_ONE;
+    }
+    (k * FIXED_LN2 + 2 * series) / (FIXED_ONE / PRICING_BPS as i128)
+}
+
 impl PricingTier {
     pub const MAX_NAME_LENGTH: usize = 32;
     pub const MAX_FEATURES: usize = 10;
     pub const MAX_FEATURE_LENGTH: usize = 64;
     pub const MAX_LIMITS: usize = 10;
     pub const MAX_LIMIT_NAME_LENGTH: usize = 32;
+
+    pub const LEN: usize = 4 + Self::MAX_NAME_LENGTH + // name
+        8 + // price
+        4 + (4 + Self::MAX_FEATURE_LENGTH) * Self::MAX_FEATURES + // features
+        4 + (4 + Self::MAX_LIMIT_NAME_LENGTH + 8) * Self::MAX_LIMITS + // limits
+        4; // discount_percentage
+
+    /// Unit price after the tier's discount
+    pub fn unit_price(&self) -> u64 {
+        let discount = self.price as u128 * self.discount_percentage as u128 / PRICING_BPS as u128;
+        self.price - discount as u64
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty() && self.name.len() <= Self::MAX_NAME_LENGTH,
+            PodAIMarketplaceError::NameTooLong
+        );
+        require!(
+            self.features.len() <= Self::MAX_FEATURES
+                && self.features.iter().all(|f| f.len() <= Self::MAX_FEATURE_LENGTH),
+            PodAIMarketplaceError::InputTooLong
+        );
+        require!(
+            self.limits.len() <= Self::MAX_LIMITS
+                && self.limits.iter().all(|(name, _)| !name.is_empty() && name.len() <= Self::MAX_LIMIT_NAME_LENGTH),
+            PodAIMarketplaceError::InputTooLong
+        );
+        require!(
+            self.discount_percentage as u64 <= PRICING_BPS,
+            PodAIMarketplaceError::InvalidPercentage
+        );
+        Ok(())
+    }
 }
 
 impl TieredPricingData {
@@ -302,6 +586,70 @@ impl TieredPricingData {
     pub const MAX_TIER_BENEFITS: usize = 10;
     pub const MAX_TIER_REQUIREMENTS: usize = 10;
     pub const MAX_STRING_LENGTH: usize = 128;
+
+    /// Checks tier contents and that tiers are ordered cheapest first, so a
+    /// higher index is always an upgrade
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.tiers.is_empty() && self.tiers.len() <= Self::MAX_TIERS,
+            PodAIMarketplaceError::InvalidPricingTier
+        );
+        for tier in &self.tiers {
+            tier.validate()?;
+        }
+        require!(
+            self.tiers.windows(2).all(|pair| pair[0].unit_price() < pair[1].unit_price()),
+            PodAIMarketplaceError::InvalidPriceConfiguration
+        );
+        Ok(())
+    }
+}
+
+/// Priced packages offered by a service listing
+///
+/// When a listing has tiers, every purchase selects one and pays its
+/// discounted unit price instead of the listing price.
+#[account]
+pub struct ListingTiers {
+    pub listing: Pubkey,
+    /// Ordered cheapest first
+    pub tiers: Vec<PricingTier>,
+    /// Flat fee charged on top of the price difference when upgrading
+    pub upgrade_cost: u64,
+    /// Kept from the refund when downgrading
+    pub downgrade_penalty: u64,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+impl ListingTiers {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // listing
+        4 + PricingTier::LEN * TieredPricingData::MAX_TIERS + // tiers
+        8 + // upgrade_cost
+        8 + // downgrade_penalty
+        8 + // updated_at
+        1; // bump
+
+    pub fn tier(&self, index: u8) -> Result<&PricingTier> {
+        self.tiers
+            .get(index as usize)
+            .ok_or(PodAIMarketplaceError::InvalidPricingTier.into())
+    }
+
+    /// Amount moving between customer and escrow when a purchase of
+    /// `quantity` units switches from `from_unit_price` to tier `to`
+    ///
+    /// Positive when the customer pays more, negative for a refund.
+    pub fn tier_change_amount(&self, from_unit_price: u64, to: u8, quantity: u32) -> Result<i128> {
+        let to_price = self.tier(to)?.unit_price() as i128;
+        let difference = (to_price - from_unit_price as i128) * quantity as i128;
+        Ok(if difference > 0 {
+            difference + self.upgrade_cost as i128
+        } else {
+            (difference + self.downgrade_penalty as i128).min(0)
+        })
+    }
 }
 
 impl Default for DynamicPricingConfig {
@@ -363,4 +711,170 @@ impl Default for DynamicPricingData {
             algorithm: PricingAlgorithm::MarketBased,
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_pricing_algorithms() {
+        assert_eq!(exp_bps(0), 10_000);
+        assert!((exp_bps(10_000) - 27_183).abs() <= 1);
+        assert!((exp_bps(-10_000) - 3_679).abs() <= 1);
+        assert_eq!(ln_bps(10_000), 0);
+        assert!((ln_bps(27_183) - 10_000).abs() <= 1);
+        assert!((ln_bps(5_000) + 6_931).abs() <= 1);
+
+        let engine_for = |algorithm: PricingAlgorithm| {
+            let config = DynamicPricingConfig {
+                algorithm,
+                ..DynamicPricingConfig::default()
+            };
+            let mut engine = DynamicPricingEngine {
+                engine_id: 0,
+                agent: Pubkey::new_unique(),
+                algorithm,
+                config: config.clone(),
+                current_price: 0,
+                last_updated: 0,
+                is_active: false,
+                oracle: Pubkey::default(),
+                window_demand: 0,
+                average_demand: 0,
+                supply: 0,
+                market_average_price: 0,
+                model_multiplier: 0,
+                performance_score: 0,
+                hourly_demand: [0; 24],
+                bump: 255,
+            };
+            engine.initialize(1, engine.agent, config, 0, 255);
+            engine
+        };
+
+        // The first window only seeds the demand average
+        let mut linear = engine_for(PricingAlgorithm::Linear);
+        assert!(linear.is_quoting());
+        linear.record_order(10, 100);
+        assert!(!linear.should_update(3_599));
+        assert_eq!(linear.reprice(5_000, 3_600), 10);
+        assert_eq!(linear.current_price, 1_000_000);
+        assert_eq!(linear.average_demand, 100_000);
+
+        // Doubled demand targets 2x but moves at most 20% per update
+        linear.record_order(20, 4_000);
+        assert_eq!(linear.target_price(5_000, 7_200), 2_000_000);
+        linear.reprice(5_000, 7_200);
+        assert_eq!(linear.current_price, 1_200_000);
+        assert_eq!(linear.window_demand, 0);
+
+        // A late update spreads demand over the elapsed windows
+        linear.record_order(22, 8_000);
+        assert_eq!(linear.reprice(5_000, 14_400), 11);
+
+        let mut sigmoid = engine_for(PricingAlgorithm::Sigmoid);
+        sigmoid.average_demand = 100_000;
+        sigmoid.window_demand = 10;
+        assert_eq!(sigmoid.target_price(5_000, 3_600), 1_000_000);
+        sigmoid.window_demand = 100;
+        let high = sigmoid.target_price(5_000, 3_600);
+        assert!(high > 9_500_000 && high <= 10_000_000);
+        sigmoid.window_demand = 0;
+        let low = sigmoid.target_price(5_000, 3_600);
+        assert!((500_000..700_000).contains(&low));
+
+        let reputation = engine_for(PricingAlgorithm::ReputationBased);
+        assert_eq!(reputation.target_price(10_000, 3_600), 1_500_000);
+        assert_eq!(reputation.target_price(0, 3_600), 500_000);
+
+        let mut market = engine_for(PricingAlgorithm::MarketBased);
+        market.apply_observation(&PricingObservation {
+            supply: Some(10),
+            demand: Some(15),
+            ..PricingObservation::default()
+        }).unwrap();
+        assert_eq!(market.target_price(5_000, 3_600), 1_500_000);
+
+        let mut model = engine_for(PricingAlgorithm::MLOptimized);
+        model.apply_observation(&PricingObservation {
+            model_multiplier: Some(12_000),
+            ..PricingObservation::default()
+        }).unwrap();
+        assert_eq!(model.target_price(5_000, 3_600), 1_200_000);
+        assert!(model.apply_observation(&PricingObservation {
+            model_multiplier: Some(MAX_PRICING_MULTIPLIER + 1),
+            ..PricingObservation::default()
+        }).is_err());
+        assert!(model.apply_observation(&PricingObservation {
+            performance_score: Some(10_001),
+            ..PricingObservation::default()
+        }).is_err());
+
+        // Seasonal pricing follows the share of demand in the current hour
+        let mut seasonal = engine_for(PricingAlgorithm::Seasonal);
+        seasonal.record_order(12, 9 * 3_600);
+        seasonal.record_order(12, 21 * 3_600);
+        assert_eq!(seasonal.target_price(5_000, 9 * 3_600 + 60), 10_000_000 + 2_000_000);
+        assert_eq!(seasonal.target_price(5_000, 3 * 3_600), 0);
+
+        let mut surge = engine_for(PricingAlgorithm::SurgePricing);
+        surge.supply = 10;
+        surge.window_demand = 15;
+        assert_eq!(surge.target_price(5_000, 3_600), 1_000_000);
+        surge.window_demand = 20;
+        assert_eq!(surge.target_price(5_000, 3_600), 2_500_000);
+    }
+
+    #[test]
+    fn test_listing_tier_changes() {
+        let tier = |name: &str, price: u64, discount_percentage: u32| PricingTier {
+            name: name.to_string(),
+            price,
+            features: vec!["code review".to_string()],
+            limits: vec![("files".to_string(), price / 1_000)],
+            discount_percentage,
+        };
+        let mut data = TieredPricingData {
+            tiers: vec![
+                tier("basic", 1_000_000, 0),
+                tier("standard", 2_000_000, 1_000),
+                tier("premium", 5_000_000, 0),
+            ],
+            current_tier: 0,
+            tier_benefits: Vec::new(),
+            tier_requirements: Vec::new(),
+            upgrade_cost: 50_000,
+            downgrade_penalty: 100_000,
+        };
+        data.validate().unwrap();
+        assert_eq!(data.tiers[1].unit_price(), 1_800_000);
+
+        let tiers = ListingTiers {
+            listing: Pubkey::new_unique(),
+            tiers: data.tiers.clone(),
+            upgrade_cost: data.upgrade_cost,
+            downgrade_penalty: data.downgrade_penalty,
+            updated_at: 0,
+            bump: 255,
+        };
+        assert!(tiers.tier(3).is_err());
+
+        // Upgrades pay the difference per unit plus the upgrade cost
+        assert_eq!(tiers.tier_change_amount(1_000_000, 1, 2).unwrap(), 1_600_000 + 50_000);
+        // Downgrades refund the difference less the penalty, never charging
+        assert_eq!(tiers.tier_change_amount(5_000_000, 0, 1).unwrap(), -3_900_000);
+        assert_eq!(tiers.tier_change_amount(1_050_000, 0, 1).unwrap(), 0);
+
+        // Tiers must be listed cheapest first after discounts
+        data.tiers[1].discount_percentage = 5_000;
+        assert!(data.validate().is_err());
+        data.tiers[1].discount_percentage = 10_001;
+        assert!(data.validate().is_err());
+        data.tiers[1].discount_percentage = 1_000;
+        data.tiers.clear();
+        assert!(data.validate().is_err());
+        data.tiers = vec![tier("t", 1_000_000, 0); TieredPricingData::MAX_TIERS + 1];
+        assert!(data.validate().is_err());
+    }
+}
@@ -0,0 +1,263 @@
+/*!
+ * Protocol Config Module
+ *
+ * Singleton configuration account holding the protocol-wide emergency pause
+ * state and the protocol fee schedule. Every state-changing marketplace,
+ * payment, auction and messaging instruction loads it and refuses to run
+ * while its subsystem is paused; settlements also read their fee from it.
+ */
+
+use anchor_lang::prelude::*;
+
+use crate::security::{EmergencyControls, ProtocolSubsystem};
+use crate::PodAIMarketplaceError;
+
+// PDA Seeds
+pub const PROTOCOL_CONFIG_SEED: &[u8] = b"protocol_config";
+pub const TREASURY_VAULT_SEED: &[u8] = b"treasury_vault";
+
+/// Fees are expressed in basis points of the settled amount
+pub const FEE_BASIS_POINTS: u16 = 10_000;
+
+/// Highest fee the schedule may charge on any settlement (10%)
+pub const MAX_PROTOCOL_FEE_BPS: u16 = 1_000;
+
+/// Maximum number of per-mint fee overrides
+pub const MAX_FEE_MINT_OVERRIDES: usize = 8;
+
+/// Kind of settlement a protocol fee is charged on
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FeeTransactionType {
+    Payment,
+    WorkOrder,
+    ServicePurchase,
+    JobContract,
+    Auction,
+    Replication,
+}
+
+/// Fee terms that replace the defaults for one payment mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MintFeeOverride {
+    pub mint: Pubkey,
+    /// Rate applied to every transaction type, or the per-type rates if `None`
+    pub fee_bps: Option<u16>,
+    /// Largest fee taken from a single settlement in base units; zero for no cap
+    pub max_fee: u64,
+}
+
+impl MintFeeOverride {
+    pub const LEN: usize = 32 + // mint
+        1 + 2 + // fee_bps (Option<u16>)
+        8; // max_fee
+}
+
+/// Protocol fee rates per transaction type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub payment_bps: u16,
+    pub work_order_bps: u16,
+    pub service_purchase_bps: u16,
+    pub job_contract_bps: u16,
+    pub auction_bps: u16,
+    pub replication_bps: u16,
+    pub mint_overrides: Vec<MintFeeOverride>,
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 2 * 6 + // per-type rates
+        4 + (MAX_FEE_MINT_OVERRIDES * MintFeeOverride::LEN); // mint_overrides
+
+    /// Checks every rate against `MAX_PROTOCOL_FEE_BPS` and rejects duplicate overrides
+    pub fn validate(&self) -> Result<()> {
+        let rates = [
+            self.payment_bps,
+            self.work_order_bps,
+            self.service_purchase_bps,
+            self.job_contract_bps,
+            self.auction_bps,
+            self.replication_bps,
+        ];
+        require!(
+            rates.iter().all(|bps| *bps <= MAX_PROTOCOL_FEE_BPS),
+            PodAIMarketplaceError::InvalidPercentage
+        );
+
+        require!(
+            self.mint_overrides.len() <= MAX_FEE_MINT_OVERRIDES,
+            PodAIMarketplaceError::InvalidConfiguration
+        );
+        for (i, fee_override) in self.mint_overrides.iter().enumerate() {
+            require!(
+                fee_override.fee_bps.unwrap_or(0) <= MAX_PROTOCOL_FEE_BPS,
+                PodAIMarketplaceError::InvalidPercentage
+            );
+            require!(
+                !self.mint_overrides[..i].iter().any(|other| other.mint == fee_override.mint),
+                PodAIMarketplaceError::InvalidConfiguration
+            );
+        }
+        Ok(())
+    }
+
+    pub fn rate_for(&self, transaction_type: FeeTransactionType) -> u16 {
+        match transaction_type {
+            FeeTransactionType::Payment => self.payment_bps,
+            FeeTransactionType::WorkOrder => self.work_order_bps,
+            FeeTransactionType::ServicePurchase => self.service_purchase_bps,
+            FeeTransactionType::JobContract => self.job_contract_bps,
+            FeeTransactionType::Auction => self.auction_bps,
+            FeeTransactionType::Replication => self.replication_bps,
+        }
+    }
+
+    /// Protocol fee owed on settling `amount` of `mint`
+    pub fn fee_for(&self, transaction_type: FeeTransactionType, mint: &Pubkey, amount: u64) -> u64 {
+        let fee_override = self.mint_overrides.iter().find(|o| o.mint == *mint);
+        let bps = fee_override
+            .and_then(|o| o.fee_bps)
+            .unwrap_or_else(|| self.rate_for(transaction_type))
+            .min(MAX_PROTOCOL_FEE_BPS);
+
+        // Cannot overflow: bps is at most a tenth of the denominator
+        let fee = (amount as u128 * bps as u128 / FEE_BASIS_POINTS as u128) as u64;
+        match fee_override {
+            Some(o) if o.max_fee > 0 => fee.min(o.max_fee),
+            _ => fee,
+        }
+    }
+}
+
+/// Protocol-wide configuration and kill switch
+///
+/// `authority` administers the config and may rotate both keys. Pausing is
+/// reserved for `emergency.pause_authority`, which can be a hot guardian key
+/// or a multisig/governance PDA that signs the toggle through CPI. The
+/// compliance, governance and multisig instructions never check the pause,
+/// so a paused protocol can still be unpaused through them.
+///
+/// Protocol fees are collected into one treasury vault per mint, owned by
+/// this account. Only `treasury_multisig` can move funds out of them.
+#[account]
+pub struct ProtocolConfig {
+    pub authority: Pubkey,
+    pub emergency: EmergencyControls,
+    pub fee_schedule: FeeSchedule,
+    /// Multisig PDA allowed to withdraw from the treasury; default disables withdrawals
+    pub treasury_multisig: Pubkey,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        EmergencyControls::LEN + // emergency
+        FeeSchedule::LEN + // fee_schedule
+        32 + // treasury_multisig
+        1; // bump
+
+    /// Fails with `ProtocolPaused` if `subsystem` or the whole protocol is paused
+    pub fn check_not_paused(&self, subsystem: ProtocolSubsystem) -> Result<()> {
+        self.emergency.check_not_paused(subsystem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_pause_controls() {
+        use crate::security::{EmergencyControls, ProtocolSubsystem};
+
+        let guardian = Pubkey::new_unique();
+        let mut config = ProtocolConfig {
+            authority: Pubkey::new_unique(),
+            emergency: EmergencyControls::new(guardian),
+            fee_schedule: FeeSchedule::default(),
+            treasury_multisig: Pubkey::default(),
+            bump: 255,
+        };
+        assert!(config.check_not_paused(ProtocolSubsystem::Payments).is_ok());
+
+        // Only the pause authority can flip the switch
+        let outsider = Pubkey::new_unique();
+        assert!(config.emergency.pause(outsider, ProtocolSubsystem::Global, String::new(), 1).is_err());
+
+        // Subsystem pauses are independent of each other
+        config.emergency.pause(guardian, ProtocolSubsystem::Auctions, "bid exploit".to_string(), 10).unwrap();
+        assert!(config.check_not_paused(ProtocolSubsystem::Auctions).is_err());
+        assert!(config.check_not_paused(ProtocolSubsystem::Payments).is_ok());
+        assert!(config.check_not_paused(ProtocolSubsystem::Global).is_ok());
+
+        // The global switch pauses everything
+        config.emergency.pause(guardian, ProtocolSubsystem::Global, "incident".to_string(), 20).unwrap();
+        assert!(config.check_not_paused(ProtocolSubsystem::Messaging).is_err());
+        assert!(config.check_not_paused(ProtocolSubsystem::Global).is_err());
+
+        // Lifting the global switch keeps the auction pause in place
+        config.emergency.unpause(guardian, ProtocolSubsystem::Global).unwrap();
+        assert!(config.check_not_paused(ProtocolSubsystem::Messaging).is_ok());
+        assert!(config.check_not_paused(ProtocolSubsystem::Auctions).is_err());
+        assert_eq!(config.emergency.paused_at, Some(20));
+
+        config.emergency.unpause(guardian, ProtocolSubsystem::Auctions).unwrap();
+        assert!(!config.emergency.any_paused());
+        assert!(config.emergency.paused_at.is_none());
+        assert!(config.emergency.pause_reason.is_empty());
+
+        // Reasons and fee overrides are bounded so the config never outgrows its allocation
+        let max_reason = "x".repeat(crate::security::MAX_PAUSE_REASON_LENGTH);
+        assert!(config.emergency.pause(guardian, ProtocolSubsystem::Global, max_reason.clone() + "x", 30).is_err());
+        config.emergency.pause(guardian, ProtocolSubsystem::Global, max_reason, 30).unwrap();
+        config.fee_schedule.mint_overrides = (0..MAX_FEE_MINT_OVERRIDES)
+            .map(|_| MintFeeOverride { mint: Pubkey::new_unique(), fee_bps: Some(0), max_fee: 0 })
+            .collect();
+        config.fee_schedule.validate().unwrap();
+        assert_eq!(config.try_to_vec().unwrap().len() + 8, ProtocolConfig::LEN);
+    }
+
+    #[test]
+    fn test_protocol_fee_schedule() {
+        let usdc = Pubkey::new_unique();
+        let capped = Pubkey::new_unique();
+        let mut schedule = FeeSchedule {
+            payment_bps: 250,
+            work_order_bps: 100,
+            service_purchase_bps: 500,
+            job_contract_bps: 0,
+            auction_bps: 300,
+            replication_bps: 1_000,
+            mint_overrides: Vec::new(),
+        };
+        schedule.validate().unwrap();
+
+        // Rates are per transaction type and round down
+        assert_eq!(schedule.fee_for(FeeTransactionType::Payment, &usdc, 1_000_000), 25_000);
+        assert_eq!(schedule.fee_for(FeeTransactionType::ServicePurchase, &usdc, 1_000_000), 50_000);
+        assert_eq!(schedule.fee_for(FeeTransactionType::JobContract, &usdc, 1_000_000), 0);
+        assert_eq!(schedule.fee_for(FeeTransactionType::WorkOrder, &usdc, 99), 0);
+        assert_eq!(schedule.fee_for(FeeTransactionType::Replication, &usdc, u64::MAX), u64::MAX / 10);
+
+        // Overrides replace the rate and cap the fee for one mint only
+        schedule.mint_overrides = vec![
+            MintFeeOverride { mint: usdc, fee_bps: Some(50), max_fee: 0 },
+            MintFeeOverride { mint: capped, fee_bps: None, max_fee: 1_000 },
+        ];
+        schedule.validate().unwrap();
+        assert_eq!(schedule.fee_for(FeeTransactionType::ServicePurchase, &usdc, 1_000_000), 5_000);
+        assert_eq!(schedule.fee_for(FeeTransactionType::Auction, &capped, 10_000), 300);
+        assert_eq!(schedule.fee_for(FeeTransactionType::Auction, &capped, 1_000_000), 1_000);
+        assert_eq!(schedule.fee_for(FeeTransactionType::Payment, &Pubkey::new_unique(), 1_000_000), 25_000);
+
+        // Rates above the protocol cap and duplicate overrides are rejected
+        schedule.mint_overrides.push(MintFeeOverride { mint: usdc, fee_bps: None, max_fee: 0 });
+        assert!(schedule.validate().is_err());
+        schedule.mint_overrides.pop();
+        schedule.auction_bps = MAX_PROTOCOL_FEE_BPS + 1;
+        assert!(schedule.validate().is_err());
+        schedule.auction_bps = MAX_PROTOCOL_FEE_BPS;
+        schedule.mint_overrides[1].fee_bps = Some(MAX_PROTOCOL_FEE_BPS + 1);
+        assert!(schedule.validate().is_err());
+    }
+}
@@ -5,7 +5,8 @@
  */
 
 use anchor_lang::prelude::*;
-use crate::{MAX_GENERAL_STRING_LENGTH, MAX_NAME_LENGTH};
+use crate::MAX_GENERAL_STRING_LENGTH;
+use super::royalty::RoyaltyConfig;
 
 #[account]
 pub struct ReplicationTemplate {
@@ -18,6 +19,10 @@ pub struct ReplicationTemplate {
     pub current_replications: u32,
     pub is_active: bool,
     pub created_at: i64,
+    /// Mint the replication fee is paid in
+    pub payment_mint: Pubkey,
+    /// Royalty terms of the stream opened for every replica
+    pub royalty: RoyaltyConfig,
     pub bump: u8,
 }
 
@@ -61,6 +66,8 @@ impl ReplicationTemplate {
         4 + // current_replications
         1 + // is_active
         8 + // created_at
+        32 + // payment_mint
+        RoyaltyConfig::LEN + // royalty
         1; // bump
 }
 
@@ -1,10 +1,253 @@
 /*!
  * Reputation State Module
- * 
- * Contains reputation related state structures.
+ *
+ * Review-backed agent reputation. Every review is bound to a completed job
+ * and weighted by the job's payment volume in whole tokens, up to a cap;
+ * older reviews lose weight with a half-life so the score tracks recent
+ * behaviour. `Agent.reputation_score`
+ * is only ever written from `AgentReputation::score`.
  */
 
 use anchor_lang::prelude::*;
+use crate::{MAX_GENERAL_STRING_LENGTH, PodAIMarketplaceError};
 
-// Reputation related state structures are defined in lib.rs
-// This file is for organizing reputation state as the codebase grows
\ No newline at end of file
+// PDA Seeds
+pub const AGENT_REPUTATION_SEED: &[u8] = b"agent_reputation";
+pub const REVIEW_SEED: &[u8] = b"review";
+
+/// Lowest and highest star rating a review can carry
+pub const MIN_REVIEW_RATING: u8 = 1;
+pub const MAX_REVIEW_RATING: u8 = 5;
+
+/// Reputation scores are reported in basis points (0-10000)
+pub const REPUTATION_SCORE_SCALE: u32 = 10_000;
+
+/// Time after which a review counts half as much as a new one (90 days)
+pub const REPUTATION_HALF_LIFE: i64 = 90 * 24 * 60 * 60;
+
+/// Fixed-point scale applied to review weights so decay keeps precision
+pub const REPUTATION_WEIGHT_SCALE: u128 = 1_000_000;
+
+/// Largest volume, in whole tokens, a single review is weighted by
+pub const MAX_REVIEW_WEIGHT_TOKENS: u128 = 10_000;
+
+/// Kind of completed job a review is bound to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReviewSubject {
+    WorkOrder,
+    ServicePurchase,
+    JobContract,
+}
+
+/// Aggregated, decaying review history of one agent
+#[account]
+pub struct AgentReputation {
+    pub agent: Pubkey,
+    /// Decayed sum of `rating * weight`
+    pub weighted_rating_sum: u128,
+    /// Decayed sum of review weights
+    pub total_weight: u128,
+    pub total_reviews: u32,
+    /// Undecayed payment volume of all reviewed jobs
+    pub total_volume: u64,
+    /// Current score in basis points (0-10000)
+    pub score: u32,
+    pub last_updated: i64,
+    pub bump: u8,
+}
+
+impl AgentReputation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        16 + // weighted_rating_sum
+        16 + // total_weight
+        4 + // total_reviews
+        8 + // total_volume
+        4 + // score
+        8 + // last_updated
+        1; // bump
+
+    pub fn initialize(&mut self, agent: Pubkey, now: i64, bump: u8) {
+        self.agent = agent;
+        self.weighted_rating_sum = 0;
+        self.total_weight = 0;
+        self.total_reviews = 0;
+        self.total_volume = 0;
+        self.score = 0;
+        self.last_updated = now;
+        self.bump = bump;
+    }
+
+    /// Decays `value` by `elapsed` seconds of half-life
+    ///
+    /// Whole half-lives are applied exactly; the remainder is interpolated
+    /// linearly between 1 and 1/2 so the result is deterministic.
+    pub fn decay(value: u128, elapsed: i64) -> u128 {
+        if elapsed <= 0 {
+            return value;
+        }
+        let halvings = elapsed / REPUTATION_HALF_LIFE;
+        if halvings >= 128 {
+            return 0;
+        }
+        let halved = value >> halvings;
+        let remainder = (elapsed % REPUTATION_HALF_LIFE) as u128;
+        let span = 2 * REPUTATION_HALF_LIFE as u128;
+        halved / span * (span - remainder) + halved % span * (span - remainder) / span
+    }
+
+    /// Weight of a review for a job paying `volume` base units of a mint with `decimals`
+    ///
+    /// Volume is converted to whole tokens so mints with different decimals
+    /// weigh alike, and capped so no single job, e.g. one paid in a cheap
+    /// mint with a huge supply, can outweigh the rest of the history.
+    pub fn review_weight(volume: u64, decimals: u8) -> Result<u128> {
+        let unit = 10u128
+            .checked_pow(decimals as u32)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        let weight = (volume as u128).saturating_mul(REPUTATION_WEIGHT_SCALE) / unit;
+        // Every job counts for something
+        Ok(weight.clamp(1, MAX_REVIEW_WEIGHT_TOKENS * REPUTATION_WEIGHT_SCALE))
+    }
+
+    /// Folds a rating weighted by `volume` into the history and returns the new score
+    pub fn record(&mut self, rating: u8, volume: u64, decimals: u8, now: i64) -> Result<u32> {
+        require!(
+            (MIN_REVIEW_RATING..=MAX_REVIEW_RATING).contains(&rating),
+            PodAIMarketplaceError::InvalidRating
+        );
+
+        let elapsed = now.saturating_sub(self.last_updated);
+        self.weighted_rating_sum = Self::decay(self.weighted_rating_sum, elapsed);
+        self.total_weight = Self::decay(self.total_weight, elapsed);
+
+        let weight = Self::review_weight(volume, decimals)?;
+        let weighted_rating = weight
+            .checked_mul(rating as u128)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.weighted_rating_sum = self.weighted_rating_sum
+            .checked_add(weighted_rating)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.total_weight = self.total_weight
+            .checked_add(weight)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+
+        self.total_reviews = self.total_reviews.saturating_add(1);
+        self.total_volume = self.total_volume.saturating_add(volume);
+        self.last_updated = now;
+        self.score = self.current_score();
+        Ok(self.score)
+    }
+
+    /// Weighted average rating scaled to basis points; zero without reviews
+    pub fn current_score(&self) -> u32 {
+        let denominator = self.total_weight.saturating_mul(MAX_REVIEW_RATING as u128);
+        if denominator == 0 {
+            return 0;
+        }
+        let score = self.weighted_rating_sum
+            .saturating_mul(REPUTATION_SCORE_SCALE as u128)
+            / denominator;
+        score.min(REPUTATION_SCORE_SCALE as u128) as u32
+    }
+}
+
+/// A single review left by the paying counterparty of a completed job
+///
+/// The PDA is derived from the job and reviewer, so each counterparty can
+/// review each job exactly once.
+#[account]
+pub struct Review {
+    pub job: Pubkey,
+    pub subject: ReviewSubject,
+    pub reviewer: Pubkey,
+    pub agent: Pubkey,
+    pub rating: u8,
+    pub comment: String,
+    pub work_reference: String,
+    /// Payment volume of the job, used as the review weight
+    pub volume: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Review {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // job
+        1 + // subject
+        32 + // reviewer
+        32 + // agent
+        1 + // rating
+        4 + MAX_GENERAL_STRING_LENGTH + // comment
+        4 + MAX_GENERAL_STRING_LENGTH + // work_reference
+        8 + // volume
+        8 + // created_at
+        1; // bump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::DisputeCase;
+
+    #[test]
+    fn test_review_backed_reputation() {
+        let mut reputation = AgentReputation {
+            agent: Pubkey::new_unique(),
+            weighted_rating_sum: 0,
+            total_weight: 0,
+            total_reviews: 0,
+            total_volume: 0,
+            score: 0,
+            last_updated: 0,
+            bump: 255,
+        };
+        const UNIT: u64 = 1_000_000;
+        assert_eq!(reputation.current_score(), 0);
+        assert!(reputation.record(0, 1_000 * UNIT, 6, 0).is_err());
+        assert!(reputation.record(6, 1_000 * UNIT, 6, 0).is_err());
+        let fresh = reputation.clone();
+
+        // A single five-star review is a perfect score
+        assert_eq!(reputation.record(5, 1_000 * UNIT, 6, 0).unwrap(), 10_000);
+
+        // Weighting follows volume: a 1-star job nine times larger dominates
+        assert_eq!(reputation.record(1, 9_000 * UNIT, 6, 0).unwrap(), 2_800);
+        assert_eq!(reputation.total_reviews, 2);
+        assert_eq!(reputation.total_volume, 10_000 * UNIT);
+
+        // Volume is weighed in whole tokens, whatever the mint's decimals
+        let mut other_mint = fresh.clone();
+        other_mint.record(5, 1_000 * 1_000_000_000, 9, 0).unwrap();
+        assert_eq!(other_mint.record(1, 9_000, 0, 0).unwrap(), 2_800);
+
+        // Decay halves weight per half-life and interpolates within one
+        assert_eq!(AgentReputation::decay(1_000, 0), 1_000);
+        assert_eq!(AgentReputation::decay(1_000, REPUTATION_HALF_LIFE), 500);
+        assert_eq!(AgentReputation::decay(1_000, REPUTATION_HALF_LIFE / 2), 750);
+        assert_eq!(AgentReputation::decay(u128::MAX, REPUTATION_HALF_LIFE * 200), 0);
+
+        // After two half-lives old reviews weigh a quarter of a new one
+        let mut recent = reputation.clone();
+        recent.record(5, 10_000 * UNIT, 6, 2 * REPUTATION_HALF_LIFE).unwrap();
+        let mut same_day = reputation.clone();
+        same_day.record(5, 10_000 * UNIT, 6, 0).unwrap();
+        assert!(recent.score > same_day.score);
+        assert_eq!(recent.score, 8_560);
+
+        // No single review weighs more than the cap, however large the job
+        let mut capped = reputation.clone();
+        capped.record(5, u64::MAX, 0, 0).unwrap();
+        assert_eq!(capped.score, same_day.score);
+        assert_eq!(
+            AgentReputation::review_weight(u64::MAX, 0).unwrap(),
+            MAX_REVIEW_WEIGHT_TOKENS * REPUTATION_WEIGHT_SCALE
+        );
+        assert_eq!(AgentReputation::review_weight(0, 6).unwrap(), 1);
+
+        // Dispute rulings map the provider's share onto the star scale
+        assert_eq!(DisputeCase::ruling_rating(0), MIN_REVIEW_RATING);
+        assert_eq!(DisputeCase::ruling_rating(5_000), 3);
+        assert_eq!(DisputeCase::ruling_rating(10_000), MAX_REVIEW_RATING);
+    }
+}
@@ -5,15 +5,56 @@
  */
 
 use anchor_lang::prelude::*;
-use crate::MAX_GENERAL_STRING_LENGTH;
+use crate::PodAIMarketplaceError;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+// PDA Seeds
+pub const ROYALTY_STREAM_SEED: &[u8] = b"royalty_stream";
+pub const RESALE_SEED: &[u8] = b"resale";
+
+/// Highest royalty a stream may take from an agent's earnings (10%)
+pub const MAX_ROYALTY_PERCENTAGE: u32 = 1_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
 pub struct RoyaltyConfig {
     pub percentage: u32,    // Basis points (0-10000 for 0-100%)
+    /// Smallest royalty taken from a payment, unless the payment is smaller
     pub min_amount: u64,
+    /// Largest royalty taken from a payment; zero for no cap
     pub max_amount: u64,
 }
 
+impl RoyaltyConfig {
+    pub const LEN: usize = 4 + // percentage
+        8 + // min_amount
+        8; // max_amount
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.percentage <= MAX_ROYALTY_PERCENTAGE,
+            PodAIMarketplaceError::InvalidRoyaltyPercentage
+        );
+        require!(
+            self.max_amount == 0 || self.min_amount <= self.max_amount,
+            PodAIMarketplaceError::RoyaltyConfigurationInvalid
+        );
+        Ok(())
+    }
+
+    /// Royalty owed on `earnings`, bounded by `min_amount`/`max_amount` and
+    /// never more than the earnings themselves
+    pub fn royalty_for(&self, earnings: u64) -> u64 {
+        if self.percentage == 0 {
+            return 0;
+        }
+        let mut royalty = (earnings as u128 * self.percentage as u128 / 10_000) as u64;
+        royalty = royalty.max(self.min_amount);
+        if self.max_amount > 0 {
+            royalty = royalty.min(self.max_amount);
+        }
+        royalty.min(earnings)
+    }
+}
+
 #[account]
 pub struct RoyaltyStream {
     pub agent: Pubkey,
@@ -31,6 +72,10 @@ pub struct ResaleMarket {
     pub agent: Pubkey,
     pub seller: Pubkey,
     pub price: u64,
+    /// Mint the sale price is paid in
+    pub payment_mint: Pubkey,
+    /// Share of the sale price owed to the agent's royalty stream creator,
+    /// snapshotted from the stream when listed
     pub royalty_percentage: u32,    // Basis points (0-10000 for 0-100%)
     pub is_listed: bool,
     pub created_at: i64,
@@ -43,7 +88,7 @@ impl RoyaltyStream {
     pub const LEN: usize = 8 + // discriminator
         32 + // agent
         32 + // original_creator
-        16 + // config (u32 + u64 + u64)
+        RoyaltyConfig::LEN + // config
         8 + // total_paid
         8 + // last_payment
         1 + // is_active
@@ -56,10 +101,112 @@ impl ResaleMarket {
         32 + // agent
         32 + // seller
         8 + // price
+        32 + // payment_mint
         4 + // royalty_percentage (u32)
         1 + // is_listed
         8 + // created_at
         1 + 8 + // sold_at (Option<i64>)
         1 + 32 + // buyer (Option<Pubkey>)
         1; // bump
-}
\ No newline at end of file
+
+    /// Creator's royalty out of the sale price
+    pub fn royalty_amount(&self) -> u64 {
+        (self.price as u128 * self.royalty_percentage as u128 / 10_000) as u64
+    }
+
+    /// Records the sale and takes the listing off the market
+    pub fn mark_sold(&mut self, buyer: Pubkey, now: i64) -> Result<()> {
+        require!(self.is_listed, PodAIMarketplaceError::ListingNotActive);
+        self.is_listed = false;
+        self.sold_at = Some(now);
+        self.buyer = Some(buyer);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::UserRegistry;
+
+    #[test]
+    fn test_royalty_config_bounds() {
+        let config = RoyaltyConfig { percentage: 500, min_amount: 100, max_amount: 10_000 };
+        config.validate().unwrap();
+
+        // Percentage share, floored at min_amount and capped at max_amount
+        assert_eq!(config.royalty_for(100_000), 5_000);
+        assert_eq!(config.royalty_for(1_000), 100);
+        assert_eq!(config.royalty_for(1_000_000), 10_000);
+        // Never more than the earnings themselves
+        assert_eq!(config.royalty_for(50), 50);
+
+        let uncapped = RoyaltyConfig { percentage: MAX_ROYALTY_PERCENTAGE, min_amount: 0, max_amount: 0 };
+        uncapped.validate().unwrap();
+        assert_eq!(uncapped.royalty_for(u64::MAX), u64::MAX / 10);
+        assert_eq!(RoyaltyConfig::default().royalty_for(1_000_000), 0);
+
+        // Rates above the cap and inverted bounds are rejected
+        let too_high = RoyaltyConfig { percentage: MAX_ROYALTY_PERCENTAGE + 1, ..uncapped.clone() };
+        assert!(too_high.validate().is_err());
+        let inverted = RoyaltyConfig { percentage: 500, min_amount: 10, max_amount: 5 };
+        assert!(inverted.validate().is_err());
+
+        assert_eq!(RoyaltyStream::LEN, 8 + 32 + 32 + RoyaltyConfig::LEN + 8 + 8 + 1 + 8 + 1);
+        let mut buf = Vec::new();
+        config.serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), RoyaltyConfig::LEN);
+    }
+
+    #[test]
+    fn test_agent_resale_settlement() {
+        let seller = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let mut resale = ResaleMarket {
+            agent: Pubkey::new_unique(),
+            seller,
+            price: 1_000_000,
+            payment_mint: Pubkey::new_unique(),
+            royalty_percentage: 500,
+            is_listed: true,
+            created_at: 0,
+            sold_at: None,
+            buyer: None,
+            bump: 255,
+        };
+
+        // Creator royalty comes out of the sale price
+        assert_eq!(resale.royalty_amount(), 50_000);
+        resale.royalty_percentage = 0;
+        assert_eq!(resale.royalty_amount(), 0);
+
+        // A listing can only be sold once
+        resale.mark_sold(buyer, 1_000).unwrap();
+        assert!(!resale.is_listed);
+        assert_eq!(resale.sold_at, Some(1_000));
+        assert_eq!(resale.buyer, Some(buyer));
+        assert!(resale.mark_sold(Pubkey::new_unique(), 2_000).is_err());
+        assert_eq!(resale.buyer, Some(buyer));
+
+        let mut registry = UserRegistry {
+            user: seller,
+            agent_count: 1,
+            listing_count: 0,
+            work_order_count: 0,
+            channel_count: 0,
+            total_volume_traded: 0,
+            last_activity: 0,
+            created_at: 0,
+            is_rate_limited: false,
+            rate_limit_expiry: 0,
+            bump: 255,
+        };
+        registry.decrement_agents();
+        registry.decrement_agents();
+        assert_eq!(registry.agent_count, 0);
+
+        let mut buf = Vec::new();
+        resale.try_serialize(&mut buf).unwrap();
+        assert!(buf.len() <= ResaleMarket::LEN);
+    }
+}
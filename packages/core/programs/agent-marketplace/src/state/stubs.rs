@@ -10,70 +10,7 @@ use super::MAX_GENERAL_STRING_LENGTH;
 
 // Analytics stubs moved to analytics.rs to avoid ambiguity
 
-// A2A Protocol stubs  
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct A2ASessionData {
-    pub session_id: u64,
-    pub initiator: Pubkey,
-    pub responder: Pubkey,
-    pub session_type: String,
-    pub metadata: String,
-    pub expires_at: i64,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct A2AMessageData {
-    pub message_id: u64,
-    pub session_id: u64,
-    pub sender: Pubkey,
-    pub content: String,
-    pub message_type: String,
-    pub timestamp: i64,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct A2AStatusData {
-    pub status_id: u64,
-    pub agent: Pubkey,
-    pub status: String,
-    pub capabilities: Vec<String>,
-    pub availability: bool,
-    pub last_updated: i64,
-}
-
-#[account]
-pub struct A2ASession {
-    pub session_id: u64,
-    pub initiator: Pubkey,
-    pub responder: Pubkey,
-    pub session_type: String,
-    pub metadata: String,
-    pub is_active: bool,
-    pub created_at: i64,
-    pub expires_at: i64,
-    pub bump: u8,
-}
-
-#[account]
-pub struct A2AMessage {
-    pub message_id: u64,
-    pub session: Pubkey,
-    pub sender: Pubkey,
-    pub content: String,
-    pub message_type: String,
-    pub sent_at: i64,
-    pub bump: u8,
-}
-
-#[account]
-pub struct A2AStatus {
-    pub agent: Pubkey,
-    pub status: String,
-    pub capabilities: Vec<String>,
-    pub availability: bool,
-    pub last_updated: i64,
-    pub bump: u8,
-}
+// A2A Protocol structures moved to a2a_protocol.rs
 
 // A2A Event definitions moved to lib.rs with proper #[event] attribute
 
@@ -124,40 +61,6 @@ pub struct AgentIncentives {
 
 // LEN implementations for all account structs
 
-impl A2ASession {
-    pub const LEN: usize = 8 + // discriminator
-        8 + // session_id
-        32 + // initiator
-        32 + // responder
-        4 + MAX_GENERAL_STRING_LENGTH + // session_type
-        4 + MAX_GENERAL_STRING_LENGTH + // metadata
-        1 + // is_active
-        8 + // created_at
-        8 + // expires_at
-        1; // bump
-}
-
-impl A2AMessage {
-    pub const LEN: usize = 8 + // discriminator
-        8 + // message_id
-        32 + // session
-        32 + // sender
-        4 + MAX_GENERAL_STRING_LENGTH + // content
-        4 + MAX_GENERAL_STRING_LENGTH + // message_type
-        8 + // sent_at
-        1; // bump
-}
-
-impl A2AStatus {
-    pub const LEN: usize = 8 + // discriminator
-        32 + // agent
-        4 + MAX_GENERAL_STRING_LENGTH + // status
-        4 + (10 * (4 + MAX_GENERAL_STRING_LENGTH)) + // capabilities (max 10)
-        1 + // availability
-        8 + // last_updated
-        1; // bump
-}
-
 // Extension LEN implementation moved to extensions.rs
 
 impl IncentiveProgram {
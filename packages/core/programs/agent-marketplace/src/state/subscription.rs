@@ -0,0 +1,283 @@
+/*!
+ * Subscription State Module
+ *
+ * Contains recurring subscription plans offered by agents and the
+ * subscriptions billed against them each period.
+ */
+
+use anchor_lang::prelude::*;
+use super::{MAX_NAME_LENGTH, PodAIMarketplaceError};
+
+// PDA Seeds
+pub const SUBSCRIPTION_PLAN_SEED: &[u8] = b"subscription_plan";
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+
+// Constants
+pub const MIN_SUBSCRIPTION_PERIOD: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_SUBSCRIPTION_PERIOD: i64 = 365 * 24 * 60 * 60; // 1 year
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubscriptionStatus {
+    /// Current period is paid
+    Active,
+    /// A billing attempt failed; the subscription is cancelled if the grace
+    /// period runs out before a payment succeeds
+    PastDue,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubscriptionPlanData {
+    pub name: String,
+    pub price: u64,
+    pub period: i64,
+    pub grace_period: i64,
+    /// Usage units allowed per period; zero for unlimited
+    pub usage_cap: u64,
+}
+
+impl SubscriptionPlanData {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty() && self.name.len() <= MAX_NAME_LENGTH,
+            PodAIMarketplaceError::NameTooLong
+        );
+        require!(self.price > 0, PodAIMarketplaceError::InvalidPaymentAmount);
+        require!(
+            (MIN_SUBSCRIPTION_PERIOD..=MAX_SUBSCRIPTION_PERIOD).contains(&self.period),
+            PodAIMarketplaceError::InvalidDuration
+        );
+        require!(
+            self.grace_period >= 0 && self.grace_period <= self.period,
+            PodAIMarketplaceError::InvalidDuration
+        );
+        Ok(())
+    }
+}
+
+#[account]
+pub struct SubscriptionPlan {
+    pub agent: Pubkey,
+    pub plan_id: u64,
+    pub name: String,
+    pub price: u64,
+    pub period: i64,
+    pub grace_period: i64,
+    pub usage_cap: u64,
+    pub payment_mint: Pubkey,
+    pub total_subscriptions: u32,
+    pub is_active: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl SubscriptionPlan {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // agent
+        8 + // plan_id
+        4 + MAX_NAME_LENGTH + // name
+        8 + // price
+        8 + // period
+        8 + // grace_period
+        8 + // usage_cap
+        32 + // payment_mint
+        4 + // total_subscriptions
+        1 + // is_active
+        8 + // created_at
+        1; // bump
+}
+
+/// A subscriber's recurring agreement, priced from the plan at signup
+///
+/// Periods after the first are pulled by a permissionless crank using a
+/// token delegate approval the subscriber grants to this account.
+#[account]
+pub struct Subscription {
+    pub plan: Pubkey,
+    pub agent: Pubkey,
+    pub subscriber: Pubkey,
+    pub payment_mint: Pubkey,
+    pub price: u64,
+    pub period: i64,
+    pub grace_period: i64,
+    pub usage_cap: u64,
+    pub usage_this_period: u64,
+    pub current_period_start: i64,
+    pub next_billing: i64,
+    pub past_due_since: Option<i64>,
+    pub status: SubscriptionStatus,
+    pub periods_billed: u32,
+    pub total_paid: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Subscription {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // plan
+        32 + // agent
+        32 + // subscriber
+        32 + // payment_mint
+        8 + // price
+        8 + // period
+        8 + // grace_period
+        8 + // usage_cap
+        8 + // usage_this_period
+        8 + // current_period_start
+        8 + // next_billing
+        1 + 8 + // past_due_since
+        1 + // status
+        4 + // periods_billed
+        8 + // total_paid
+        8 + // created_at
+        1; // bump
+
+    pub fn is_due(&self, now: i64) -> bool {
+        self.status != SubscriptionStatus::Cancelled && now >= self.next_billing
+    }
+
+    /// Starts the next period after a successful payment
+    ///
+    /// The period runs from the previous billing date, so a payment made
+    /// during the grace period does not extend the subscription. Once a
+    /// whole period has lapsed unpaid the new period starts at `now`
+    /// instead, so missed periods cannot be billed back one after another.
+    pub fn record_payment(&mut self, amount: u64, now: i64) -> Result<()> {
+        let lapsed_at = self.next_billing
+            .checked_add(self.period)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.current_period_start = if lapsed_at <= now { now } else { self.next_billing };
+        self.next_billing = self.current_period_start
+            .checked_add(self.period)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.usage_this_period = 0;
+        self.status = SubscriptionStatus::Active;
+        self.past_due_since = None;
+        self.periods_billed = self.periods_billed
+            .checked_add(1)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        self.total_paid = self.total_paid
+            .checked_add(amount)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records a failed billing attempt, cancelling once the grace period
+    /// has run out
+    pub fn record_failed_payment(&mut self, now: i64) {
+        match self.status {
+            SubscriptionStatus::Active => {
+                self.status = SubscriptionStatus::PastDue;
+                self.past_due_since = Some(now);
+            }
+            SubscriptionStatus::PastDue => {
+                let since = self.past_due_since.unwrap_or(now);
+                if now >= since.saturating_add(self.grace_period) {
+                    self.status = SubscriptionStatus::Cancelled;
+                }
+            }
+            SubscriptionStatus::Cancelled => {}
+        }
+    }
+
+    /// Consumes `units` of the current period's usage cap
+    pub fn record_usage(&mut self, units: u64, now: i64) -> Result<()> {
+        require!(
+            self.status == SubscriptionStatus::Active && now < self.next_billing,
+            PodAIMarketplaceError::SubscriptionNotActive
+        );
+        let used = self.usage_this_period
+            .checked_add(units)
+            .ok_or(PodAIMarketplaceError::ArithmeticOverflow)?;
+        require!(
+            self.usage_cap == 0 || used <= self.usage_cap,
+            PodAIMarketplaceError::UsageCapExceeded
+        );
+        self.usage_this_period = used;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscription_billing_cycle() {
+        let day = 24 * 60 * 60;
+        let plan = SubscriptionPlanData {
+            name: "Monthly".to_string(),
+            price: 50_000_000,
+            period: 30 * day,
+            grace_period: 3 * day,
+            usage_cap: 10_000,
+        };
+        plan.validate().unwrap();
+        assert!(SubscriptionPlanData { period: day - 1, ..plan.clone() }.validate().is_err());
+        assert!(SubscriptionPlanData { grace_period: 31 * day, ..plan.clone() }.validate().is_err());
+
+        let mut subscription = Subscription {
+            plan: Pubkey::new_unique(),
+            agent: Pubkey::new_unique(),
+            subscriber: Pubkey::new_unique(),
+            payment_mint: Pubkey::new_unique(),
+            price: plan.price,
+            period: plan.period,
+            grace_period: plan.grace_period,
+            usage_cap: plan.usage_cap,
+            usage_this_period: 0,
+            current_period_start: 0,
+            next_billing: 0,
+            past_due_since: None,
+            status: SubscriptionStatus::Active,
+            periods_billed: 0,
+            total_paid: 0,
+            created_at: 0,
+            bump: 255,
+        };
+
+        // Signup pays the first period
+        subscription.record_payment(plan.price, 0).unwrap();
+        assert_eq!(subscription.next_billing, 30 * day);
+        assert!(!subscription.is_due(29 * day));
+
+        // Usage is capped per period
+        subscription.record_usage(9_000, day).unwrap();
+        assert!(subscription.record_usage(1_001, day).is_err());
+        subscription.record_usage(1_000, day).unwrap();
+
+        // A failed pull starts the grace period; paying within it keeps the
+        // original billing schedule and resets usage
+        assert!(subscription.is_due(30 * day));
+        subscription.record_failed_payment(30 * day);
+        assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+        assert!(subscription.record_usage(1, 31 * day).is_err());
+        subscription.record_failed_payment(32 * day);
+        assert_eq!(subscription.status, SubscriptionStatus::PastDue);
+        subscription.record_payment(plan.price, 32 * day).unwrap();
+        assert_eq!(subscription.status, SubscriptionStatus::Active);
+        assert_eq!(subscription.current_period_start, 30 * day);
+        assert_eq!(subscription.next_billing, 60 * day);
+        assert_eq!(subscription.usage_this_period, 0);
+        assert_eq!(subscription.periods_billed, 2);
+        assert_eq!(subscription.total_paid, 2 * plan.price);
+
+        // A payment after a whole lapsed period restarts the schedule at the
+        // payment, so the missed periods are not billed back to back
+        let mut lapsed = subscription.clone();
+        lapsed.record_payment(plan.price, 125 * day).unwrap();
+        assert_eq!(lapsed.current_period_start, 125 * day);
+        assert_eq!(lapsed.next_billing, 155 * day);
+        assert!(!lapsed.is_due(125 * day));
+
+        // Unpaid past the grace period cancels the subscription
+        subscription.record_failed_payment(60 * day);
+        subscription.record_failed_payment(63 * day);
+        assert_eq!(subscription.status, SubscriptionStatus::Cancelled);
+        assert!(!subscription.is_due(90 * day));
+
+        let mut buf = Vec::new();
+        subscription.try_serialize(&mut buf).unwrap();
+        assert_eq!(buf.len(), Subscription::LEN);
+    }
+}
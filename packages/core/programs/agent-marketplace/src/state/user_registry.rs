@@ -53,6 +53,11 @@ impl UserRegistry {
         Ok(())
     }
 
+    /// Called when an agent leaves this user, e.g. on resale
+    pub fn decrement_agents(&mut self) {
+        self.agent_count = self.agent_count.saturating_sub(1);
+    }
+
     pub fn increment_listings(&mut self) -> Result<()> {
         self.listing_count = self.listing_count
             .checked_add(1)
@@ -58,6 +58,8 @@ pub struct WorkDeliveryData {
 pub struct WorkOrder {
     pub client: Pubkey,
     pub provider: Pubkey,
+    /// Agent doing the work, owned by `provider` when the order was created
+    pub provider_agent: Pubkey,
     pub title: String,
     pub description: String,
     pub requirements: Vec<String>,
@@ -86,6 +88,7 @@ impl WorkOrder {
     pub const LEN: usize = 8 + // discriminator
         32 + // client
         32 + // provider
+        32 + // provider_agent
         4 + MAX_TITLE_LENGTH + // title
         4 + MAX_DESCRIPTION_LENGTH + // description
         4 + (MAX_REQUIREMENTS_ITEMS * (4 + MAX_GENERAL_STRING_LENGTH)) + // requirements
@@ -102,6 +105,7 @@ impl WorkOrder {
         &mut self,
         client: Pubkey,
         provider: Pubkey,
+        provider_agent: Pubkey,
         title: String,
         description: String,
         requirements: Vec<String>,
@@ -124,6 +128,7 @@ impl WorkOrder {
         
         self.client = client;
         self.provider = provider;
+        self.provider_agent = provider_agent;
         self.title = title;
         self.description = description;
         self.requirements = requirements;
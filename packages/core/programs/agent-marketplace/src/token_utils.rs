@@ -0,0 +1,148 @@
+/*!
+ * Token Transfer Utilities
+ *
+ * Shared helpers for moving SPL Token and Token-2022 balances through the
+ * token interface, including transfer-fee aware amount calculation.
+ */
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self,
+    spl_token_2022::{
+        self,
+        extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    },
+    CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::instructions::protocol_config::ProtocolFeeCollectedEvent;
+use crate::state::protocol_config::{FeeTransactionType, ProtocolConfig};
+use crate::PodAIMarketplaceError;
+
+// =====================================================
+// TRANSFER FEE CALCULATION
+// =====================================================
+
+/// Returns the fee a Token-2022 mint withholds when `amount` is transferred
+/// in the current epoch.
+///
+/// Legacy SPL Token mints and Token-2022 mints without the transfer fee
+/// extension always return zero.
+pub fn calculate_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(0);
+    }
+
+    let mint_data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => {
+            let epoch = Clock::get()?.epoch;
+            fee_config
+                .calculate_epoch_fee(epoch, amount)
+                .ok_or_else(|| error!(PodAIMarketplaceError::ArithmeticOverflow))
+        }
+        Err(_) => Ok(0),
+    }
+}
+
+/// Returns the amount the recipient actually receives after transfer fees
+pub fn net_transfer_amount(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let fee = calculate_transfer_fee(mint, amount)?;
+    amount
+        .checked_sub(fee)
+        .ok_or_else(|| error!(PodAIMarketplaceError::ArithmeticUnderflow))
+}
+
+// =====================================================
+// TRANSFER CPI HELPERS
+// =====================================================
+
+/// Transfers tokens with `transfer_checked` through the token interface
+///
+/// `signer_seeds` is empty for wallet-signed transfers and carries the PDA
+/// seeds when a program-owned account is the authority.
+pub fn transfer_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let cpi_accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    token_interface::transfer_checked(cpi_ctx, amount, mint.decimals)
+}
+
+/// Closes an empty program-owned token account, returning its rent to `destination`
+pub fn close_token_account<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let cpi_accounts = CloseAccount {
+        account: account.to_account_info(),
+        destination: destination.clone(),
+        authority: authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    );
+
+    token_interface::close_account(cpi_ctx)
+}
+
+// =====================================================
+// PROTOCOL FEES
+// =====================================================
+
+/// Moves the protocol fee on `amount` from `from` into the treasury vault
+///
+/// Returns what is left of `amount` for the counterparty. The fee follows the
+/// config's fee schedule for `transaction_type` and the mint.
+#[allow(clippy::too_many_arguments)]
+pub fn collect_protocol_fee<'info>(
+    config: &ProtocolConfig,
+    transaction_type: FeeTransactionType,
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    treasury_vault: &InterfaceAccount<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let fee = config.fee_schedule.fee_for(transaction_type, &mint.key(), amount);
+    if fee > 0 {
+        transfer_tokens(token_program, from, mint, treasury_vault, authority, fee, signer_seeds)?;
+        emit!(ProtocolFeeCollectedEvent {
+            transaction_type,
+            mint: mint.key(),
+            amount: fee,
+        });
+    }
+
+    Ok(amount - fee)
+}
@@ -126,7 +126,12 @@ mod comprehensive_unit_tests {
             created_at: current_time,
             expires_at: current_time + 86400, // 24 hours
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
 
         // Test creation
@@ -245,6 +250,7 @@ mod comprehensive_unit_tests {
         let work_order = WorkOrder {
             client,
             provider,
+            provider_agent: Pubkey::new_unique(),
             title: "AI Model Training".to_string(),
             description: "Train a custom ML model for image recognition".to_string(),
             requirements: vec![
@@ -302,9 +308,11 @@ mod comprehensive_unit_tests {
         for channel_type in channel_types {
             let channel = Channel {
                 creator,
-                participants: vec![creator],
+                owner: creator,
                 channel_type,
                 is_private: false,
+                member_count: 1,
+                max_members: 0,
                 message_count: 0,
                 created_at: current_time,
                 last_activity: current_time,
@@ -314,7 +322,7 @@ mod comprehensive_unit_tests {
 
             assert_eq!(channel.channel_type, channel_type);
             assert!(channel.is_active);
-            assert_eq!(channel.participants.len(), 1);
+            assert_eq!(channel.member_count, 1);
         }
     }
 
@@ -503,7 +511,12 @@ mod comprehensive_unit_tests {
             created_at: current_time,
             expires_at: future_time,
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
 
         // Escrow should not be expired at current time
@@ -512,4 +525,4 @@ mod comprehensive_unit_tests {
         // Escrow should be expired after expiration time
         assert!(escrow.expires_at < future_time + 1);
     }
-}
\ No newline at end of file
+}
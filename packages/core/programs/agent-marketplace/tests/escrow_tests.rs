@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use podai::state::{Escrow, EscrowStatus, TaskEscrow, TaskStatus};
+use podai::state::{Escrow, EscrowStatus, TaskEscrow, TaskStatus, FeeTransactionType};
 
 #[cfg(test)]
 mod escrow_tests {
@@ -23,7 +23,12 @@ mod escrow_tests {
             created_at: current_time,
             expires_at: current_time + 86400, // 24 hours later
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
         
         // Validate escrow fields
@@ -46,7 +51,12 @@ mod escrow_tests {
             created_at: 1234567890,
             expires_at: 1234567890 + 86400,
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
         
         // Active -> Completed
@@ -86,7 +96,12 @@ mod escrow_tests {
             created_at: current_time - 86400,
             expires_at: expired_time,
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
         
         let valid_escrow = Escrow {
@@ -98,12 +113,19 @@ mod escrow_tests {
             created_at: current_time,
             expires_at: future_time,
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
         
         // Check expiration
         assert!(expired_escrow.expires_at < current_time, "Escrow should be expired");
         assert!(valid_escrow.expires_at > current_time, "Escrow should be valid");
+        assert!(expired_escrow.is_expired(current_time));
+        assert!(!valid_escrow.is_expired(current_time));
     }
     
     #[test]
@@ -246,7 +268,12 @@ mod escrow_tests {
             created_at: 1234567890,
             expires_at: 1234567890 + 86400,
             dispute_reason: None,
+            dispute: None,
             resolution_notes: None,
+            work_order: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            fee_type: FeeTransactionType::WorkOrder,
+            bump: 255,
         };
         
         // Active status - funds locked
@@ -269,4 +296,4 @@ mod escrow_tests {
         let can_release_disputed = disputed_escrow.status == EscrowStatus::Completed;
         assert!(!can_release_disputed, "Should not release funds when disputed");
     }
-}
\ No newline at end of file
+}
@@ -57,6 +57,16 @@ export {
   type ITransactionResult,
   type ITransactionInstruction,
 } from './utils/transaction-helpers';
+export {
+  AuditAction,
+  computeAuditEntryHash,
+  verifyAuditChain,
+  type IAuditEntry,
+  type IAuditContext,
+  type IComplianceFlags,
+  type IAuditChainOptions,
+  type AuditChainVerification,
+} from './utils/audit-chain';
 
 // ===== CONSTANTS =====
 export const PODAI_PROGRAM_ID = '4ufTpHynyoWzSL3d2EL4PU1hSra1tKvQrQiBwJ82x385';
@@ -0,0 +1,192 @@
+/**
+ * Audit trail hash chain verification
+ *
+ * Recomputes the SHA-256 hash chain of on-chain audit trail entries so
+ * compliance reviewers can check a trail without trusting the RPC node.
+ * Mirrors `AuditEntry::compute_hash` and `AuditTrail::verify_chain` in the
+ * program.
+ */
+
+import { getAddressEncoder, type Address } from '@solana/addresses';
+import {
+  addEncoderSizePrefix,
+  getArrayEncoder,
+  getBooleanEncoder,
+  getI64Encoder,
+  getOptionEncoder,
+  getStructEncoder,
+  getTupleEncoder,
+  getU32Encoder,
+  getU64Encoder,
+  getU8Encoder,
+  getUtf8Encoder,
+  type Encoder,
+} from '@solana/codecs';
+
+/**
+ * Audited actions, in program enum order
+ */
+export enum AuditAction {
+  AgentRegistered,
+  AgentUpdated,
+  AgentDeactivated,
+  AgentVerified,
+  PaymentProcessed,
+  EscrowCreated,
+  EscrowReleased,
+  FundsWithdrawn,
+  ProposalCreated,
+  VoteCast,
+  ProposalExecuted,
+  GovernanceUpdated,
+  AccessGranted,
+  AccessRevoked,
+  SecurityPolicyUpdated,
+  SuspiciousActivity,
+  ComplianceReportGenerated,
+  RegulatorySubmission,
+  AuditRequested,
+  ViolationDetected,
+  SystemConfigUpdated,
+  EmergencyAction,
+  MaintenancePerformed,
+  WorkOrderCreated,
+  WorkOrderCompleted,
+  DisputeRaised,
+  DisputeResolved,
+  MultisigCreated,
+  MultisigSigned,
+  MultisigExecuted,
+  RiskAssessmentPerformed,
+  RiskThresholdExceeded,
+  RiskMitigationApplied,
+}
+
+export interface IAuditContext {
+  transactionSignature: string | null;
+  amount: bigint | null;
+  token: Address | null;
+  metadata: Array<[string, string]>;
+  riskScore: number | null;
+  location: string | null;
+  clientInfo: string | null;
+}
+
+export interface IComplianceFlags {
+  requiresReporting: boolean;
+  highRisk: boolean;
+  sensitiveData: boolean;
+  crossBorder: boolean;
+  largeAmount: boolean;
+  suspicious: boolean;
+  manualReview: boolean;
+  jurisdiction: string | null;
+}
+
+export interface IAuditEntry {
+  entryId: bigint;
+  timestamp: bigint;
+  action: AuditAction;
+  actor: Address;
+  target: Address | null;
+  context: IAuditContext;
+  complianceFlags: IComplianceFlags;
+  previousHash: Uint8Array;
+  entryHash: Uint8Array;
+}
+
+export interface IAuditChainOptions {
+  /** Chain head the first entry links to; zeros for a trail's first account */
+  genesisHash?: Uint8Array;
+  /** ID of the first entry; non-zero for trails created by rollover */
+  firstEntryId?: bigint;
+}
+
+export type AuditChainVerification =
+  | { valid: true; chainHead: Uint8Array }
+  | { valid: false; failedEntryId: bigint; reason: string };
+
+const getStringEncoder = (): Encoder<string> =>
+  addEncoderSizePrefix(getUtf8Encoder(), getU32Encoder());
+
+const getAuditContextEncoder = (): Encoder<IAuditContext> =>
+  getStructEncoder([
+    ['transactionSignature', getOptionEncoder(getStringEncoder())],
+    ['amount', getOptionEncoder(getU64Encoder())],
+    ['token', getOptionEncoder(getAddressEncoder())],
+    ['metadata', getArrayEncoder(getTupleEncoder([getStringEncoder(), getStringEncoder()]))],
+    ['riskScore', getOptionEncoder(getU32Encoder())],
+    ['location', getOptionEncoder(getStringEncoder())],
+    ['clientInfo', getOptionEncoder(getStringEncoder())],
+  ]);
+
+const getComplianceFlagsEncoder = (): Encoder<IComplianceFlags> =>
+  getStructEncoder([
+    ['requiresReporting', getBooleanEncoder()],
+    ['highRisk', getBooleanEncoder()],
+    ['sensitiveData', getBooleanEncoder()],
+    ['crossBorder', getBooleanEncoder()],
+    ['largeAmount', getBooleanEncoder()],
+    ['suspicious', getBooleanEncoder()],
+    ['manualReview', getBooleanEncoder()],
+    ['jurisdiction', getOptionEncoder(getStringEncoder())],
+  ]);
+
+/** Borsh encoding of the hashed entry fields */
+const getHashedFieldsEncoder = () =>
+  getStructEncoder([
+    ['entryId', getU64Encoder()],
+    ['timestamp', getI64Encoder()],
+    ['action', getU8Encoder()],
+    ['actor', getAddressEncoder()],
+    ['target', getOptionEncoder(getAddressEncoder())],
+    ['context', getAuditContextEncoder()],
+    ['complianceFlags', getComplianceFlagsEncoder()],
+  ]);
+
+const bytesEqual = (a: Uint8Array, b: Uint8Array): boolean =>
+  a.length === b.length && a.every((byte, i) => byte === b[i]);
+
+/**
+ * SHA-256 over the previous chain head followed by the Borsh-encoded
+ * entry fields; `entryHash` and the signature are not hashed
+ */
+export async function computeAuditEntryHash(entry: IAuditEntry): Promise<Uint8Array> {
+  const fields = getHashedFieldsEncoder().encode(entry);
+  const preimage = new Uint8Array(entry.previousHash.length + fields.length);
+  preimage.set(entry.previousHash, 0);
+  preimage.set(fields, entry.previousHash.length);
+
+  return new Uint8Array(await crypto.subtle.digest('SHA-256', preimage));
+}
+
+/**
+ * Recomputes every entry hash and link in an audit trail account
+ *
+ * To verify across rollovers, pass the previous trail's chain head and
+ * next entry ID as the options for the following trail.
+ */
+export async function verifyAuditChain(
+  entries: readonly IAuditEntry[],
+  options: IAuditChainOptions = {}
+): Promise<AuditChainVerification> {
+  let head = options.genesisHash ?? new Uint8Array(32);
+  let expectedId = options.firstEntryId ?? 0n;
+
+  for (const entry of entries) {
+    if (entry.entryId !== expectedId) {
+      return { valid: false, failedEntryId: entry.entryId, reason: `expected entry ${expectedId}` };
+    }
+    if (!bytesEqual(entry.previousHash, head)) {
+      return { valid: false, failedEntryId: entry.entryId, reason: 'broken link to previous entry' };
+    }
+    const hash = await computeAuditEntryHash(entry);
+    if (!bytesEqual(hash, entry.entryHash)) {
+      return { valid: false, failedEntryId: entry.entryId, reason: 'entry hash mismatch' };
+    }
+    head = hash;
+    expectedId += 1n;
+  }
+
+  return { valid: true, chainHead: head };
+}